@@ -0,0 +1,16 @@
+-- Daily usage counters per designation, standard and language.
+-- Replaces the running totals of analytics.popular_threads, which is kept as is:
+-- the search ranking still adds them, the per-day reports do not.
+CREATE SCHEMA IF NOT EXISTS analytics;
+
+CREATE TABLE IF NOT EXISTS analytics.thread_usage (
+    designation TEXT NOT NULL,
+    standard TEXT NOT NULL,
+    language TEXT NOT NULL,
+    day DATE NOT NULL,
+    usage_count BIGINT NOT NULL DEFAULT 1,
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (designation, standard, language, day)
+);
+
+CREATE INDEX IF NOT EXISTS thread_usage_day_idx ON analytics.thread_usage (day);
@@ -0,0 +1,4 @@
+-- Triage state of client error reports: NULL while the report is open.
+ALTER TABLE analytics.error_reports ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS error_reports_timestamp_idx ON analytics.error_reports (timestamp);
@@ -0,0 +1,23 @@
+-- API keys of partners. Only the SHA-256 of a key is stored; scopes are a
+-- comma-separated list of "read", "svg" and "admin".
+CREATE SCHEMA IF NOT EXISTS auth;
+
+CREATE TABLE IF NOT EXISTS auth.api_keys (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    daily_quota BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    rotated_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- Requests per key and UTC day, checked against daily_quota
+CREATE TABLE IF NOT EXISTS auth.api_key_usage (
+    key_id BIGINT NOT NULL REFERENCES auth.api_keys (id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (key_id, day)
+);
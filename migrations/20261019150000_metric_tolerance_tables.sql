@@ -0,0 +1,919 @@
+-- ISO 965-1 tables the metric limits are computed from by the SQLite and memory
+-- backends; exported from here with `thread-api-admin export`. Values in µm:
+-- tolerances by kind (Td, TD1, Td2, TD2), grade and pitch, for Td2/TD2 also by the
+-- nominal diameter range (diameter_min, diameter_max], 0 for the other kinds.
+CREATE SCHEMA IF NOT EXISTS metric;
+
+CREATE TABLE IF NOT EXISTS metric.tolerance_grades (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    grade INTEGER NOT NULL,
+    pitch DOUBLE PRECISION NOT NULL,
+    diameter_min DOUBLE PRECISION NOT NULL DEFAULT 0,
+    diameter_max DOUBLE PRECISION NOT NULL DEFAULT 0,
+    value INTEGER NOT NULL,
+    UNIQUE (kind, grade, pitch, diameter_min)
+);
+
+-- Fundamental deviations signed as in the standard: es of e, f, g negative, EI of G positive
+CREATE TABLE IF NOT EXISTS metric.fundamental_deviations (
+    id INTEGER PRIMARY KEY,
+    position TEXT NOT NULL,
+    pitch DOUBLE PRECISION NOT NULL,
+    value INTEGER NOT NULL,
+    UNIQUE (position, pitch)
+);
+
+INSERT INTO metric.tolerance_grades (id, kind, grade, pitch, diameter_min, diameter_max, value) VALUES
+    (1, 'Td', 4, 0.2, 0, 0, 36),
+    (2, 'Td', 6, 0.2, 0, 0, 56),
+    (3, 'Td', 4, 0.25, 0, 0, 42),
+    (4, 'Td', 6, 0.25, 0, 0, 67),
+    (5, 'Td', 4, 0.3, 0, 0, 48),
+    (6, 'Td', 6, 0.3, 0, 0, 75),
+    (7, 'Td', 4, 0.35, 0, 0, 53),
+    (8, 'Td', 6, 0.35, 0, 0, 85),
+    (9, 'Td', 4, 0.4, 0, 0, 60),
+    (10, 'Td', 6, 0.4, 0, 0, 95),
+    (11, 'Td', 4, 0.45, 0, 0, 63),
+    (12, 'Td', 6, 0.45, 0, 0, 100),
+    (13, 'Td', 4, 0.5, 0, 0, 67),
+    (14, 'Td', 6, 0.5, 0, 0, 106),
+    (15, 'Td', 4, 0.6, 0, 0, 80),
+    (16, 'Td', 6, 0.6, 0, 0, 125),
+    (17, 'Td', 4, 0.7, 0, 0, 90),
+    (18, 'Td', 6, 0.7, 0, 0, 140),
+    (19, 'Td', 4, 0.75, 0, 0, 90),
+    (20, 'Td', 6, 0.75, 0, 0, 140),
+    (21, 'Td', 4, 0.8, 0, 0, 95),
+    (22, 'Td', 6, 0.8, 0, 0, 150),
+    (23, 'Td', 8, 0.8, 0, 0, 236),
+    (24, 'Td', 4, 1, 0, 0, 112),
+    (25, 'Td', 6, 1, 0, 0, 180),
+    (26, 'Td', 8, 1, 0, 0, 280),
+    (27, 'Td', 4, 1.25, 0, 0, 132),
+    (28, 'Td', 6, 1.25, 0, 0, 212),
+    (29, 'Td', 8, 1.25, 0, 0, 335),
+    (30, 'Td', 4, 1.5, 0, 0, 150),
+    (31, 'Td', 6, 1.5, 0, 0, 236),
+    (32, 'Td', 8, 1.5, 0, 0, 375),
+    (33, 'Td', 4, 1.75, 0, 0, 170),
+    (34, 'Td', 6, 1.75, 0, 0, 265),
+    (35, 'Td', 8, 1.75, 0, 0, 425),
+    (36, 'Td', 4, 2, 0, 0, 180),
+    (37, 'Td', 6, 2, 0, 0, 280),
+    (38, 'Td', 8, 2, 0, 0, 450),
+    (39, 'Td', 4, 2.5, 0, 0, 212),
+    (40, 'Td', 6, 2.5, 0, 0, 335),
+    (41, 'Td', 8, 2.5, 0, 0, 530),
+    (42, 'Td', 4, 3, 0, 0, 236),
+    (43, 'Td', 6, 3, 0, 0, 375),
+    (44, 'Td', 8, 3, 0, 0, 600),
+    (45, 'Td', 4, 3.5, 0, 0, 265),
+    (46, 'Td', 6, 3.5, 0, 0, 425),
+    (47, 'Td', 8, 3.5, 0, 0, 670),
+    (48, 'Td', 4, 4, 0, 0, 300),
+    (49, 'Td', 6, 4, 0, 0, 475),
+    (50, 'Td', 8, 4, 0, 0, 750),
+    (51, 'Td', 4, 4.5, 0, 0, 315),
+    (52, 'Td', 6, 4.5, 0, 0, 500),
+    (53, 'Td', 8, 4.5, 0, 0, 800),
+    (54, 'Td', 4, 5, 0, 0, 335),
+    (55, 'Td', 6, 5, 0, 0, 530),
+    (56, 'Td', 8, 5, 0, 0, 850),
+    (57, 'Td', 4, 5.5, 0, 0, 355),
+    (58, 'Td', 6, 5.5, 0, 0, 560),
+    (59, 'Td', 8, 5.5, 0, 0, 900),
+    (60, 'Td', 4, 6, 0, 0, 375),
+    (61, 'Td', 6, 6, 0, 0, 600),
+    (62, 'Td', 8, 6, 0, 0, 950),
+    (63, 'Td', 4, 8, 0, 0, 450),
+    (64, 'Td', 6, 8, 0, 0, 710),
+    (65, 'Td', 8, 8, 0, 0, 1180),
+    (66, 'TD1', 4, 0.2, 0, 0, 38),
+    (67, 'TD1', 4, 0.25, 0, 0, 45),
+    (68, 'TD1', 5, 0.25, 0, 0, 56),
+    (69, 'TD1', 4, 0.3, 0, 0, 53),
+    (70, 'TD1', 5, 0.3, 0, 0, 67),
+    (71, 'TD1', 6, 0.3, 0, 0, 85),
+    (72, 'TD1', 4, 0.35, 0, 0, 63),
+    (73, 'TD1', 5, 0.35, 0, 0, 80),
+    (74, 'TD1', 6, 0.35, 0, 0, 100),
+    (75, 'TD1', 4, 0.4, 0, 0, 71),
+    (76, 'TD1', 5, 0.4, 0, 0, 90),
+    (77, 'TD1', 6, 0.4, 0, 0, 112),
+    (78, 'TD1', 4, 0.45, 0, 0, 80),
+    (79, 'TD1', 5, 0.45, 0, 0, 100),
+    (80, 'TD1', 6, 0.45, 0, 0, 125),
+    (81, 'TD1', 4, 0.5, 0, 0, 90),
+    (82, 'TD1', 5, 0.5, 0, 0, 112),
+    (83, 'TD1', 6, 0.5, 0, 0, 140),
+    (84, 'TD1', 7, 0.5, 0, 0, 180),
+    (85, 'TD1', 4, 0.6, 0, 0, 100),
+    (86, 'TD1', 5, 0.6, 0, 0, 125),
+    (87, 'TD1', 6, 0.6, 0, 0, 160),
+    (88, 'TD1', 7, 0.6, 0, 0, 200),
+    (89, 'TD1', 4, 0.7, 0, 0, 112),
+    (90, 'TD1', 5, 0.7, 0, 0, 140),
+    (91, 'TD1', 6, 0.7, 0, 0, 180),
+    (92, 'TD1', 7, 0.7, 0, 0, 224),
+    (93, 'TD1', 4, 0.75, 0, 0, 118),
+    (94, 'TD1', 5, 0.75, 0, 0, 150),
+    (95, 'TD1', 6, 0.75, 0, 0, 190),
+    (96, 'TD1', 7, 0.75, 0, 0, 236),
+    (97, 'TD1', 4, 0.8, 0, 0, 125),
+    (98, 'TD1', 5, 0.8, 0, 0, 160),
+    (99, 'TD1', 6, 0.8, 0, 0, 200),
+    (100, 'TD1', 7, 0.8, 0, 0, 250),
+    (101, 'TD1', 8, 0.8, 0, 0, 315),
+    (102, 'TD1', 4, 1, 0, 0, 150),
+    (103, 'TD1', 5, 1, 0, 0, 190),
+    (104, 'TD1', 6, 1, 0, 0, 236),
+    (105, 'TD1', 7, 1, 0, 0, 300),
+    (106, 'TD1', 8, 1, 0, 0, 375),
+    (107, 'TD1', 4, 1.25, 0, 0, 170),
+    (108, 'TD1', 5, 1.25, 0, 0, 212),
+    (109, 'TD1', 6, 1.25, 0, 0, 265),
+    (110, 'TD1', 7, 1.25, 0, 0, 335),
+    (111, 'TD1', 8, 1.25, 0, 0, 425),
+    (112, 'TD1', 4, 1.5, 0, 0, 190),
+    (113, 'TD1', 5, 1.5, 0, 0, 236),
+    (114, 'TD1', 6, 1.5, 0, 0, 300),
+    (115, 'TD1', 7, 1.5, 0, 0, 375),
+    (116, 'TD1', 8, 1.5, 0, 0, 475),
+    (117, 'TD1', 4, 1.75, 0, 0, 212),
+    (118, 'TD1', 5, 1.75, 0, 0, 265),
+    (119, 'TD1', 6, 1.75, 0, 0, 335),
+    (120, 'TD1', 7, 1.75, 0, 0, 425),
+    (121, 'TD1', 8, 1.75, 0, 0, 530),
+    (122, 'TD1', 4, 2, 0, 0, 236),
+    (123, 'TD1', 5, 2, 0, 0, 300),
+    (124, 'TD1', 6, 2, 0, 0, 375),
+    (125, 'TD1', 7, 2, 0, 0, 475),
+    (126, 'TD1', 8, 2, 0, 0, 600),
+    (127, 'TD1', 4, 2.5, 0, 0, 280),
+    (128, 'TD1', 5, 2.5, 0, 0, 355),
+    (129, 'TD1', 6, 2.5, 0, 0, 450),
+    (130, 'TD1', 7, 2.5, 0, 0, 560),
+    (131, 'TD1', 8, 2.5, 0, 0, 710),
+    (132, 'TD1', 4, 3, 0, 0, 315),
+    (133, 'TD1', 5, 3, 0, 0, 400),
+    (134, 'TD1', 6, 3, 0, 0, 500),
+    (135, 'TD1', 7, 3, 0, 0, 630),
+    (136, 'TD1', 8, 3, 0, 0, 800),
+    (137, 'TD1', 4, 3.5, 0, 0, 355),
+    (138, 'TD1', 5, 3.5, 0, 0, 450),
+    (139, 'TD1', 6, 3.5, 0, 0, 560),
+    (140, 'TD1', 7, 3.5, 0, 0, 710),
+    (141, 'TD1', 8, 3.5, 0, 0, 900),
+    (142, 'TD1', 4, 4, 0, 0, 375),
+    (143, 'TD1', 5, 4, 0, 0, 475),
+    (144, 'TD1', 6, 4, 0, 0, 600),
+    (145, 'TD1', 7, 4, 0, 0, 750),
+    (146, 'TD1', 8, 4, 0, 0, 950),
+    (147, 'TD1', 4, 4.5, 0, 0, 425),
+    (148, 'TD1', 5, 4.5, 0, 0, 530),
+    (149, 'TD1', 6, 4.5, 0, 0, 670),
+    (150, 'TD1', 7, 4.5, 0, 0, 850),
+    (151, 'TD1', 8, 4.5, 0, 0, 1060),
+    (152, 'TD1', 4, 5, 0, 0, 450),
+    (153, 'TD1', 5, 5, 0, 0, 560),
+    (154, 'TD1', 6, 5, 0, 0, 710),
+    (155, 'TD1', 7, 5, 0, 0, 900),
+    (156, 'TD1', 8, 5, 0, 0, 1120),
+    (157, 'TD1', 4, 5.5, 0, 0, 475),
+    (158, 'TD1', 5, 5.5, 0, 0, 600),
+    (159, 'TD1', 6, 5.5, 0, 0, 750),
+    (160, 'TD1', 7, 5.5, 0, 0, 950),
+    (161, 'TD1', 8, 5.5, 0, 0, 1180),
+    (162, 'TD1', 4, 6, 0, 0, 500),
+    (163, 'TD1', 5, 6, 0, 0, 630),
+    (164, 'TD1', 6, 6, 0, 0, 800),
+    (165, 'TD1', 7, 6, 0, 0, 1000),
+    (166, 'TD1', 8, 6, 0, 0, 1250),
+    (167, 'TD1', 4, 8, 0, 0, 630),
+    (168, 'TD1', 5, 8, 0, 0, 800),
+    (169, 'TD1', 6, 8, 0, 0, 1000),
+    (170, 'TD1', 7, 8, 0, 0, 1250),
+    (171, 'TD1', 8, 8, 0, 0, 1600),
+    (172, 'Td2', 3, 0.2, 0.99, 1.4, 24),
+    (173, 'Td2', 4, 0.2, 0.99, 1.4, 30),
+    (174, 'Td2', 5, 0.2, 0.99, 1.4, 38),
+    (175, 'Td2', 6, 0.2, 0.99, 1.4, 48),
+    (176, 'Td2', 7, 0.2, 0.99, 1.4, 60),
+    (177, 'Td2', 8, 0.2, 0.99, 1.4, 75),
+    (178, 'Td2', 9, 0.2, 0.99, 1.4, 95),
+    (179, 'Td2', 3, 0.25, 0.99, 1.4, 26),
+    (180, 'Td2', 4, 0.25, 0.99, 1.4, 34),
+    (181, 'Td2', 5, 0.25, 0.99, 1.4, 42),
+    (182, 'Td2', 6, 0.25, 0.99, 1.4, 53),
+    (183, 'Td2', 7, 0.25, 0.99, 1.4, 67),
+    (184, 'Td2', 8, 0.25, 0.99, 1.4, 85),
+    (185, 'Td2', 9, 0.25, 0.99, 1.4, 106),
+    (186, 'Td2', 3, 0.3, 0.99, 1.4, 28),
+    (187, 'Td2', 4, 0.3, 0.99, 1.4, 36),
+    (188, 'Td2', 5, 0.3, 0.99, 1.4, 45),
+    (189, 'Td2', 6, 0.3, 0.99, 1.4, 56),
+    (190, 'Td2', 7, 0.3, 0.99, 1.4, 71),
+    (191, 'Td2', 8, 0.3, 0.99, 1.4, 90),
+    (192, 'Td2', 9, 0.3, 0.99, 1.4, 112),
+    (193, 'Td2', 3, 0.2, 1.4, 2.8, 25),
+    (194, 'Td2', 4, 0.2, 1.4, 2.8, 32),
+    (195, 'Td2', 5, 0.2, 1.4, 2.8, 40),
+    (196, 'Td2', 6, 0.2, 1.4, 2.8, 50),
+    (197, 'Td2', 7, 0.2, 1.4, 2.8, 63),
+    (198, 'Td2', 8, 0.2, 1.4, 2.8, 80),
+    (199, 'Td2', 9, 0.2, 1.4, 2.8, 100),
+    (200, 'Td2', 3, 0.25, 1.4, 2.8, 28),
+    (201, 'Td2', 4, 0.25, 1.4, 2.8, 36),
+    (202, 'Td2', 5, 0.25, 1.4, 2.8, 45),
+    (203, 'Td2', 6, 0.25, 1.4, 2.8, 56),
+    (204, 'Td2', 7, 0.25, 1.4, 2.8, 71),
+    (205, 'Td2', 8, 0.25, 1.4, 2.8, 90),
+    (206, 'Td2', 9, 0.25, 1.4, 2.8, 112),
+    (207, 'Td2', 3, 0.35, 1.4, 2.8, 32),
+    (208, 'Td2', 4, 0.35, 1.4, 2.8, 40),
+    (209, 'Td2', 5, 0.35, 1.4, 2.8, 50),
+    (210, 'Td2', 6, 0.35, 1.4, 2.8, 63),
+    (211, 'Td2', 7, 0.35, 1.4, 2.8, 80),
+    (212, 'Td2', 8, 0.35, 1.4, 2.8, 100),
+    (213, 'Td2', 9, 0.35, 1.4, 2.8, 125),
+    (214, 'Td2', 3, 0.4, 1.4, 2.8, 34),
+    (215, 'Td2', 4, 0.4, 1.4, 2.8, 42),
+    (216, 'Td2', 5, 0.4, 1.4, 2.8, 53),
+    (217, 'Td2', 6, 0.4, 1.4, 2.8, 67),
+    (218, 'Td2', 7, 0.4, 1.4, 2.8, 85),
+    (219, 'Td2', 8, 0.4, 1.4, 2.8, 106),
+    (220, 'Td2', 9, 0.4, 1.4, 2.8, 132),
+    (221, 'Td2', 3, 0.45, 1.4, 2.8, 36),
+    (222, 'Td2', 4, 0.45, 1.4, 2.8, 45),
+    (223, 'Td2', 5, 0.45, 1.4, 2.8, 56),
+    (224, 'Td2', 6, 0.45, 1.4, 2.8, 71),
+    (225, 'Td2', 7, 0.45, 1.4, 2.8, 90),
+    (226, 'Td2', 8, 0.45, 1.4, 2.8, 112),
+    (227, 'Td2', 9, 0.45, 1.4, 2.8, 140),
+    (228, 'Td2', 3, 0.35, 2.8, 5.6, 34),
+    (229, 'Td2', 4, 0.35, 2.8, 5.6, 42),
+    (230, 'Td2', 5, 0.35, 2.8, 5.6, 53),
+    (231, 'Td2', 6, 0.35, 2.8, 5.6, 67),
+    (232, 'Td2', 7, 0.35, 2.8, 5.6, 85),
+    (233, 'Td2', 8, 0.35, 2.8, 5.6, 106),
+    (234, 'Td2', 9, 0.35, 2.8, 5.6, 132),
+    (235, 'Td2', 3, 0.5, 2.8, 5.6, 38),
+    (236, 'Td2', 4, 0.5, 2.8, 5.6, 48),
+    (237, 'Td2', 5, 0.5, 2.8, 5.6, 60),
+    (238, 'Td2', 6, 0.5, 2.8, 5.6, 75),
+    (239, 'Td2', 7, 0.5, 2.8, 5.6, 95),
+    (240, 'Td2', 8, 0.5, 2.8, 5.6, 118),
+    (241, 'Td2', 9, 0.5, 2.8, 5.6, 150),
+    (242, 'Td2', 3, 0.6, 2.8, 5.6, 42),
+    (243, 'Td2', 4, 0.6, 2.8, 5.6, 53),
+    (244, 'Td2', 5, 0.6, 2.8, 5.6, 67),
+    (245, 'Td2', 6, 0.6, 2.8, 5.6, 85),
+    (246, 'Td2', 7, 0.6, 2.8, 5.6, 106),
+    (247, 'Td2', 8, 0.6, 2.8, 5.6, 132),
+    (248, 'Td2', 9, 0.6, 2.8, 5.6, 170),
+    (249, 'Td2', 3, 0.7, 2.8, 5.6, 45),
+    (250, 'Td2', 4, 0.7, 2.8, 5.6, 56),
+    (251, 'Td2', 5, 0.7, 2.8, 5.6, 71),
+    (252, 'Td2', 6, 0.7, 2.8, 5.6, 90),
+    (253, 'Td2', 7, 0.7, 2.8, 5.6, 112),
+    (254, 'Td2', 8, 0.7, 2.8, 5.6, 140),
+    (255, 'Td2', 9, 0.7, 2.8, 5.6, 180),
+    (256, 'Td2', 3, 0.75, 2.8, 5.6, 48),
+    (257, 'Td2', 4, 0.75, 2.8, 5.6, 60),
+    (258, 'Td2', 5, 0.75, 2.8, 5.6, 75),
+    (259, 'Td2', 6, 0.75, 2.8, 5.6, 95),
+    (260, 'Td2', 7, 0.75, 2.8, 5.6, 118),
+    (261, 'Td2', 8, 0.75, 2.8, 5.6, 150),
+    (262, 'Td2', 9, 0.75, 2.8, 5.6, 190),
+    (263, 'Td2', 3, 0.8, 2.8, 5.6, 48),
+    (264, 'Td2', 4, 0.8, 2.8, 5.6, 60),
+    (265, 'Td2', 5, 0.8, 2.8, 5.6, 75),
+    (266, 'Td2', 6, 0.8, 2.8, 5.6, 95),
+    (267, 'Td2', 7, 0.8, 2.8, 5.6, 118),
+    (268, 'Td2', 8, 0.8, 2.8, 5.6, 150),
+    (269, 'Td2', 9, 0.8, 2.8, 5.6, 190),
+    (270, 'Td2', 3, 0.75, 5.6, 11.2, 50),
+    (271, 'Td2', 4, 0.75, 5.6, 11.2, 63),
+    (272, 'Td2', 5, 0.75, 5.6, 11.2, 80),
+    (273, 'Td2', 6, 0.75, 5.6, 11.2, 100),
+    (274, 'Td2', 7, 0.75, 5.6, 11.2, 125),
+    (275, 'Td2', 8, 0.75, 5.6, 11.2, 160),
+    (276, 'Td2', 9, 0.75, 5.6, 11.2, 200),
+    (277, 'Td2', 3, 1, 5.6, 11.2, 56),
+    (278, 'Td2', 4, 1, 5.6, 11.2, 71),
+    (279, 'Td2', 5, 1, 5.6, 11.2, 90),
+    (280, 'Td2', 6, 1, 5.6, 11.2, 112),
+    (281, 'Td2', 7, 1, 5.6, 11.2, 140),
+    (282, 'Td2', 8, 1, 5.6, 11.2, 180),
+    (283, 'Td2', 9, 1, 5.6, 11.2, 224),
+    (284, 'Td2', 3, 1.25, 5.6, 11.2, 60),
+    (285, 'Td2', 4, 1.25, 5.6, 11.2, 75),
+    (286, 'Td2', 5, 1.25, 5.6, 11.2, 95),
+    (287, 'Td2', 6, 1.25, 5.6, 11.2, 118),
+    (288, 'Td2', 7, 1.25, 5.6, 11.2, 150),
+    (289, 'Td2', 8, 1.25, 5.6, 11.2, 190),
+    (290, 'Td2', 9, 1.25, 5.6, 11.2, 236),
+    (291, 'Td2', 3, 1.5, 5.6, 11.2, 67),
+    (292, 'Td2', 4, 1.5, 5.6, 11.2, 85),
+    (293, 'Td2', 5, 1.5, 5.6, 11.2, 106),
+    (294, 'Td2', 6, 1.5, 5.6, 11.2, 132),
+    (295, 'Td2', 7, 1.5, 5.6, 11.2, 170),
+    (296, 'Td2', 8, 1.5, 5.6, 11.2, 212),
+    (297, 'Td2', 9, 1.5, 5.6, 11.2, 265),
+    (298, 'Td2', 3, 1, 11.2, 22.4, 60),
+    (299, 'Td2', 4, 1, 11.2, 22.4, 75),
+    (300, 'Td2', 5, 1, 11.2, 22.4, 95),
+    (301, 'Td2', 6, 1, 11.2, 22.4, 118),
+    (302, 'Td2', 7, 1, 11.2, 22.4, 150),
+    (303, 'Td2', 8, 1, 11.2, 22.4, 190),
+    (304, 'Td2', 9, 1, 11.2, 22.4, 236),
+    (305, 'Td2', 3, 1.25, 11.2, 22.4, 67),
+    (306, 'Td2', 4, 1.25, 11.2, 22.4, 85),
+    (307, 'Td2', 5, 1.25, 11.2, 22.4, 106),
+    (308, 'Td2', 6, 1.25, 11.2, 22.4, 132),
+    (309, 'Td2', 7, 1.25, 11.2, 22.4, 170),
+    (310, 'Td2', 8, 1.25, 11.2, 22.4, 212),
+    (311, 'Td2', 9, 1.25, 11.2, 22.4, 265),
+    (312, 'Td2', 3, 1.5, 11.2, 22.4, 71),
+    (313, 'Td2', 4, 1.5, 11.2, 22.4, 90),
+    (314, 'Td2', 5, 1.5, 11.2, 22.4, 112),
+    (315, 'Td2', 6, 1.5, 11.2, 22.4, 140),
+    (316, 'Td2', 7, 1.5, 11.2, 22.4, 180),
+    (317, 'Td2', 8, 1.5, 11.2, 22.4, 224),
+    (318, 'Td2', 9, 1.5, 11.2, 22.4, 280),
+    (319, 'Td2', 3, 1.75, 11.2, 22.4, 75),
+    (320, 'Td2', 4, 1.75, 11.2, 22.4, 95),
+    (321, 'Td2', 5, 1.75, 11.2, 22.4, 118),
+    (322, 'Td2', 6, 1.75, 11.2, 22.4, 150),
+    (323, 'Td2', 7, 1.75, 11.2, 22.4, 190),
+    (324, 'Td2', 8, 1.75, 11.2, 22.4, 236),
+    (325, 'Td2', 9, 1.75, 11.2, 22.4, 300),
+    (326, 'Td2', 3, 2, 11.2, 22.4, 80),
+    (327, 'Td2', 4, 2, 11.2, 22.4, 100),
+    (328, 'Td2', 5, 2, 11.2, 22.4, 125),
+    (329, 'Td2', 6, 2, 11.2, 22.4, 160),
+    (330, 'Td2', 7, 2, 11.2, 22.4, 200),
+    (331, 'Td2', 8, 2, 11.2, 22.4, 250),
+    (332, 'Td2', 9, 2, 11.2, 22.4, 315),
+    (333, 'Td2', 3, 2.5, 11.2, 22.4, 85),
+    (334, 'Td2', 4, 2.5, 11.2, 22.4, 106),
+    (335, 'Td2', 5, 2.5, 11.2, 22.4, 132),
+    (336, 'Td2', 6, 2.5, 11.2, 22.4, 170),
+    (337, 'Td2', 7, 2.5, 11.2, 22.4, 212),
+    (338, 'Td2', 8, 2.5, 11.2, 22.4, 265),
+    (339, 'Td2', 9, 2.5, 11.2, 22.4, 335),
+    (340, 'Td2', 3, 1, 22.4, 45, 63),
+    (341, 'Td2', 4, 1, 22.4, 45, 80),
+    (342, 'Td2', 5, 1, 22.4, 45, 100),
+    (343, 'Td2', 6, 1, 22.4, 45, 125),
+    (344, 'Td2', 7, 1, 22.4, 45, 160),
+    (345, 'Td2', 8, 1, 22.4, 45, 200),
+    (346, 'Td2', 9, 1, 22.4, 45, 250),
+    (347, 'Td2', 3, 1.5, 22.4, 45, 75),
+    (348, 'Td2', 4, 1.5, 22.4, 45, 95),
+    (349, 'Td2', 5, 1.5, 22.4, 45, 118),
+    (350, 'Td2', 6, 1.5, 22.4, 45, 150),
+    (351, 'Td2', 7, 1.5, 22.4, 45, 190),
+    (352, 'Td2', 8, 1.5, 22.4, 45, 236),
+    (353, 'Td2', 9, 1.5, 22.4, 45, 300),
+    (354, 'Td2', 3, 2, 22.4, 45, 85),
+    (355, 'Td2', 4, 2, 22.4, 45, 106),
+    (356, 'Td2', 5, 2, 22.4, 45, 132),
+    (357, 'Td2', 6, 2, 22.4, 45, 170),
+    (358, 'Td2', 7, 2, 22.4, 45, 212),
+    (359, 'Td2', 8, 2, 22.4, 45, 265),
+    (360, 'Td2', 9, 2, 22.4, 45, 335),
+    (361, 'Td2', 3, 3, 22.4, 45, 100),
+    (362, 'Td2', 4, 3, 22.4, 45, 125),
+    (363, 'Td2', 5, 3, 22.4, 45, 160),
+    (364, 'Td2', 6, 3, 22.4, 45, 200),
+    (365, 'Td2', 7, 3, 22.4, 45, 250),
+    (366, 'Td2', 8, 3, 22.4, 45, 315),
+    (367, 'Td2', 9, 3, 22.4, 45, 400),
+    (368, 'Td2', 3, 3.5, 22.4, 45, 106),
+    (369, 'Td2', 4, 3.5, 22.4, 45, 132),
+    (370, 'Td2', 5, 3.5, 22.4, 45, 170),
+    (371, 'Td2', 6, 3.5, 22.4, 45, 212),
+    (372, 'Td2', 7, 3.5, 22.4, 45, 265),
+    (373, 'Td2', 8, 3.5, 22.4, 45, 335),
+    (374, 'Td2', 9, 3.5, 22.4, 45, 425),
+    (375, 'Td2', 3, 4, 22.4, 45, 112),
+    (376, 'Td2', 4, 4, 22.4, 45, 140),
+    (377, 'Td2', 5, 4, 22.4, 45, 180),
+    (378, 'Td2', 6, 4, 22.4, 45, 224),
+    (379, 'Td2', 7, 4, 22.4, 45, 280),
+    (380, 'Td2', 8, 4, 22.4, 45, 355),
+    (381, 'Td2', 9, 4, 22.4, 45, 450),
+    (382, 'Td2', 3, 4.5, 22.4, 45, 118),
+    (383, 'Td2', 4, 4.5, 22.4, 45, 150),
+    (384, 'Td2', 5, 4.5, 22.4, 45, 190),
+    (385, 'Td2', 6, 4.5, 22.4, 45, 236),
+    (386, 'Td2', 7, 4.5, 22.4, 45, 300),
+    (387, 'Td2', 8, 4.5, 22.4, 45, 375),
+    (388, 'Td2', 9, 4.5, 22.4, 45, 475),
+    (389, 'Td2', 3, 1.5, 45, 90, 80),
+    (390, 'Td2', 4, 1.5, 45, 90, 100),
+    (391, 'Td2', 5, 1.5, 45, 90, 125),
+    (392, 'Td2', 6, 1.5, 45, 90, 160),
+    (393, 'Td2', 7, 1.5, 45, 90, 200),
+    (394, 'Td2', 8, 1.5, 45, 90, 250),
+    (395, 'Td2', 9, 1.5, 45, 90, 315),
+    (396, 'Td2', 3, 2, 45, 90, 90),
+    (397, 'Td2', 4, 2, 45, 90, 112),
+    (398, 'Td2', 5, 2, 45, 90, 140),
+    (399, 'Td2', 6, 2, 45, 90, 180),
+    (400, 'Td2', 7, 2, 45, 90, 224),
+    (401, 'Td2', 8, 2, 45, 90, 280),
+    (402, 'Td2', 9, 2, 45, 90, 355),
+    (403, 'Td2', 3, 3, 45, 90, 106),
+    (404, 'Td2', 4, 3, 45, 90, 132),
+    (405, 'Td2', 5, 3, 45, 90, 170),
+    (406, 'Td2', 6, 3, 45, 90, 212),
+    (407, 'Td2', 7, 3, 45, 90, 265),
+    (408, 'Td2', 8, 3, 45, 90, 335),
+    (409, 'Td2', 9, 3, 45, 90, 425),
+    (410, 'Td2', 3, 4, 45, 90, 118),
+    (411, 'Td2', 4, 4, 45, 90, 150),
+    (412, 'Td2', 5, 4, 45, 90, 190),
+    (413, 'Td2', 6, 4, 45, 90, 236),
+    (414, 'Td2', 7, 4, 45, 90, 300),
+    (415, 'Td2', 8, 4, 45, 90, 375),
+    (416, 'Td2', 9, 4, 45, 90, 475),
+    (417, 'Td2', 3, 5, 45, 90, 125),
+    (418, 'Td2', 4, 5, 45, 90, 160),
+    (419, 'Td2', 5, 5, 45, 90, 200),
+    (420, 'Td2', 6, 5, 45, 90, 250),
+    (421, 'Td2', 7, 5, 45, 90, 315),
+    (422, 'Td2', 8, 5, 45, 90, 400),
+    (423, 'Td2', 9, 5, 45, 90, 500),
+    (424, 'Td2', 3, 5.5, 45, 90, 132),
+    (425, 'Td2', 4, 5.5, 45, 90, 170),
+    (426, 'Td2', 5, 5.5, 45, 90, 212),
+    (427, 'Td2', 6, 5.5, 45, 90, 265),
+    (428, 'Td2', 7, 5.5, 45, 90, 335),
+    (429, 'Td2', 8, 5.5, 45, 90, 425),
+    (430, 'Td2', 9, 5.5, 45, 90, 530),
+    (431, 'Td2', 3, 6, 45, 90, 140),
+    (432, 'Td2', 4, 6, 45, 90, 180),
+    (433, 'Td2', 5, 6, 45, 90, 224),
+    (434, 'Td2', 6, 6, 45, 90, 280),
+    (435, 'Td2', 7, 6, 45, 90, 355),
+    (436, 'Td2', 8, 6, 45, 90, 450),
+    (437, 'Td2', 9, 6, 45, 90, 560),
+    (438, 'Td2', 3, 2, 90, 180, 95),
+    (439, 'Td2', 4, 2, 90, 180, 118),
+    (440, 'Td2', 5, 2, 90, 180, 150),
+    (441, 'Td2', 6, 2, 90, 180, 190),
+    (442, 'Td2', 7, 2, 90, 180, 236),
+    (443, 'Td2', 8, 2, 90, 180, 300),
+    (444, 'Td2', 9, 2, 90, 180, 375),
+    (445, 'Td2', 3, 3, 90, 180, 112),
+    (446, 'Td2', 4, 3, 90, 180, 140),
+    (447, 'Td2', 5, 3, 90, 180, 180),
+    (448, 'Td2', 6, 3, 90, 180, 224),
+    (449, 'Td2', 7, 3, 90, 180, 280),
+    (450, 'Td2', 8, 3, 90, 180, 355),
+    (451, 'Td2', 9, 3, 90, 180, 450),
+    (452, 'Td2', 3, 4, 90, 180, 125),
+    (453, 'Td2', 4, 4, 90, 180, 160),
+    (454, 'Td2', 5, 4, 90, 180, 200),
+    (455, 'Td2', 6, 4, 90, 180, 250),
+    (456, 'Td2', 7, 4, 90, 180, 315),
+    (457, 'Td2', 8, 4, 90, 180, 400),
+    (458, 'Td2', 9, 4, 90, 180, 500),
+    (459, 'Td2', 3, 6, 90, 180, 150),
+    (460, 'Td2', 4, 6, 90, 180, 190),
+    (461, 'Td2', 5, 6, 90, 180, 236),
+    (462, 'Td2', 6, 6, 90, 180, 300),
+    (463, 'Td2', 7, 6, 90, 180, 375),
+    (464, 'Td2', 8, 6, 90, 180, 475),
+    (465, 'Td2', 9, 6, 90, 180, 600),
+    (466, 'Td2', 3, 8, 90, 180, 170),
+    (467, 'Td2', 4, 8, 90, 180, 212),
+    (468, 'Td2', 5, 8, 90, 180, 265),
+    (469, 'Td2', 6, 8, 90, 180, 335),
+    (470, 'Td2', 7, 8, 90, 180, 425),
+    (471, 'Td2', 8, 8, 90, 180, 530),
+    (472, 'Td2', 9, 8, 90, 180, 670),
+    (473, 'Td2', 3, 3, 180, 355, 118),
+    (474, 'Td2', 4, 3, 180, 355, 150),
+    (475, 'Td2', 5, 3, 180, 355, 190),
+    (476, 'Td2', 6, 3, 180, 355, 236),
+    (477, 'Td2', 7, 3, 180, 355, 300),
+    (478, 'Td2', 8, 3, 180, 355, 375),
+    (479, 'Td2', 9, 3, 180, 355, 475),
+    (480, 'Td2', 3, 4, 180, 355, 132),
+    (481, 'Td2', 4, 4, 180, 355, 170),
+    (482, 'Td2', 5, 4, 180, 355, 212),
+    (483, 'Td2', 6, 4, 180, 355, 265),
+    (484, 'Td2', 7, 4, 180, 355, 335),
+    (485, 'Td2', 8, 4, 180, 355, 425),
+    (486, 'Td2', 9, 4, 180, 355, 530),
+    (487, 'Td2', 3, 6, 180, 355, 160),
+    (488, 'Td2', 4, 6, 180, 355, 200),
+    (489, 'Td2', 5, 6, 180, 355, 250),
+    (490, 'Td2', 6, 6, 180, 355, 315),
+    (491, 'Td2', 7, 6, 180, 355, 400),
+    (492, 'Td2', 8, 6, 180, 355, 500),
+    (493, 'Td2', 9, 6, 180, 355, 630),
+    (494, 'Td2', 3, 8, 180, 355, 180),
+    (495, 'Td2', 4, 8, 180, 355, 224),
+    (496, 'Td2', 5, 8, 180, 355, 280),
+    (497, 'Td2', 6, 8, 180, 355, 355),
+    (498, 'Td2', 7, 8, 180, 355, 450),
+    (499, 'Td2', 8, 8, 180, 355, 560),
+    (500, 'Td2', 9, 8, 180, 355, 710),
+    (501, 'Td2', 3, 6, 355, 600, 170),
+    (502, 'Td2', 4, 6, 355, 600, 212),
+    (503, 'Td2', 5, 6, 355, 600, 265),
+    (504, 'Td2', 6, 6, 355, 600, 335),
+    (505, 'Td2', 7, 6, 355, 600, 425),
+    (506, 'Td2', 8, 6, 355, 600, 530),
+    (507, 'Td2', 9, 6, 355, 600, 670),
+    (508, 'TD2', 4, 0.2, 0.99, 1.4, 40),
+    (509, 'TD2', 5, 0.2, 0.99, 1.4, 50),
+    (510, 'TD2', 6, 0.2, 0.99, 1.4, 63),
+    (511, 'TD2', 7, 0.2, 0.99, 1.4, 80),
+    (512, 'TD2', 8, 0.2, 0.99, 1.4, 100),
+    (513, 'TD2', 4, 0.25, 0.99, 1.4, 45),
+    (514, 'TD2', 5, 0.25, 0.99, 1.4, 56),
+    (515, 'TD2', 6, 0.25, 0.99, 1.4, 71),
+    (516, 'TD2', 7, 0.25, 0.99, 1.4, 90),
+    (517, 'TD2', 8, 0.25, 0.99, 1.4, 112),
+    (518, 'TD2', 4, 0.3, 0.99, 1.4, 48),
+    (519, 'TD2', 5, 0.3, 0.99, 1.4, 60),
+    (520, 'TD2', 6, 0.3, 0.99, 1.4, 75),
+    (521, 'TD2', 7, 0.3, 0.99, 1.4, 95),
+    (522, 'TD2', 8, 0.3, 0.99, 1.4, 118),
+    (523, 'TD2', 4, 0.2, 1.4, 2.8, 42),
+    (524, 'TD2', 5, 0.2, 1.4, 2.8, 53),
+    (525, 'TD2', 6, 0.2, 1.4, 2.8, 67),
+    (526, 'TD2', 7, 0.2, 1.4, 2.8, 85),
+    (527, 'TD2', 8, 0.2, 1.4, 2.8, 106),
+    (528, 'TD2', 4, 0.25, 1.4, 2.8, 48),
+    (529, 'TD2', 5, 0.25, 1.4, 2.8, 60),
+    (530, 'TD2', 6, 0.25, 1.4, 2.8, 75),
+    (531, 'TD2', 7, 0.25, 1.4, 2.8, 95),
+    (532, 'TD2', 8, 0.25, 1.4, 2.8, 118),
+    (533, 'TD2', 4, 0.35, 1.4, 2.8, 53),
+    (534, 'TD2', 5, 0.35, 1.4, 2.8, 67),
+    (535, 'TD2', 6, 0.35, 1.4, 2.8, 85),
+    (536, 'TD2', 7, 0.35, 1.4, 2.8, 106),
+    (537, 'TD2', 8, 0.35, 1.4, 2.8, 132),
+    (538, 'TD2', 4, 0.4, 1.4, 2.8, 56),
+    (539, 'TD2', 5, 0.4, 1.4, 2.8, 71),
+    (540, 'TD2', 6, 0.4, 1.4, 2.8, 90),
+    (541, 'TD2', 7, 0.4, 1.4, 2.8, 112),
+    (542, 'TD2', 8, 0.4, 1.4, 2.8, 140),
+    (543, 'TD2', 4, 0.45, 1.4, 2.8, 60),
+    (544, 'TD2', 5, 0.45, 1.4, 2.8, 75),
+    (545, 'TD2', 6, 0.45, 1.4, 2.8, 95),
+    (546, 'TD2', 7, 0.45, 1.4, 2.8, 118),
+    (547, 'TD2', 8, 0.45, 1.4, 2.8, 150),
+    (548, 'TD2', 4, 0.35, 2.8, 5.6, 56),
+    (549, 'TD2', 5, 0.35, 2.8, 5.6, 71),
+    (550, 'TD2', 6, 0.35, 2.8, 5.6, 90),
+    (551, 'TD2', 7, 0.35, 2.8, 5.6, 112),
+    (552, 'TD2', 8, 0.35, 2.8, 5.6, 140),
+    (553, 'TD2', 4, 0.5, 2.8, 5.6, 63),
+    (554, 'TD2', 5, 0.5, 2.8, 5.6, 80),
+    (555, 'TD2', 6, 0.5, 2.8, 5.6, 100),
+    (556, 'TD2', 7, 0.5, 2.8, 5.6, 125),
+    (557, 'TD2', 8, 0.5, 2.8, 5.6, 160),
+    (558, 'TD2', 4, 0.6, 2.8, 5.6, 71),
+    (559, 'TD2', 5, 0.6, 2.8, 5.6, 90),
+    (560, 'TD2', 6, 0.6, 2.8, 5.6, 112),
+    (561, 'TD2', 7, 0.6, 2.8, 5.6, 140),
+    (562, 'TD2', 8, 0.6, 2.8, 5.6, 180),
+    (563, 'TD2', 4, 0.7, 2.8, 5.6, 75),
+    (564, 'TD2', 5, 0.7, 2.8, 5.6, 95),
+    (565, 'TD2', 6, 0.7, 2.8, 5.6, 118),
+    (566, 'TD2', 7, 0.7, 2.8, 5.6, 150),
+    (567, 'TD2', 8, 0.7, 2.8, 5.6, 190),
+    (568, 'TD2', 4, 0.75, 2.8, 5.6, 80),
+    (569, 'TD2', 5, 0.75, 2.8, 5.6, 100),
+    (570, 'TD2', 6, 0.75, 2.8, 5.6, 125),
+    (571, 'TD2', 7, 0.75, 2.8, 5.6, 160),
+    (572, 'TD2', 8, 0.75, 2.8, 5.6, 200),
+    (573, 'TD2', 4, 0.8, 2.8, 5.6, 80),
+    (574, 'TD2', 5, 0.8, 2.8, 5.6, 100),
+    (575, 'TD2', 6, 0.8, 2.8, 5.6, 125),
+    (576, 'TD2', 7, 0.8, 2.8, 5.6, 160),
+    (577, 'TD2', 8, 0.8, 2.8, 5.6, 200),
+    (578, 'TD2', 4, 0.75, 5.6, 11.2, 85),
+    (579, 'TD2', 5, 0.75, 5.6, 11.2, 106),
+    (580, 'TD2', 6, 0.75, 5.6, 11.2, 132),
+    (581, 'TD2', 7, 0.75, 5.6, 11.2, 170),
+    (582, 'TD2', 8, 0.75, 5.6, 11.2, 212),
+    (583, 'TD2', 4, 1, 5.6, 11.2, 95),
+    (584, 'TD2', 5, 1, 5.6, 11.2, 118),
+    (585, 'TD2', 6, 1, 5.6, 11.2, 150),
+    (586, 'TD2', 7, 1, 5.6, 11.2, 190),
+    (587, 'TD2', 8, 1, 5.6, 11.2, 236),
+    (588, 'TD2', 4, 1.25, 5.6, 11.2, 100),
+    (589, 'TD2', 5, 1.25, 5.6, 11.2, 125),
+    (590, 'TD2', 6, 1.25, 5.6, 11.2, 160),
+    (591, 'TD2', 7, 1.25, 5.6, 11.2, 200),
+    (592, 'TD2', 8, 1.25, 5.6, 11.2, 250),
+    (593, 'TD2', 4, 1.5, 5.6, 11.2, 112),
+    (594, 'TD2', 5, 1.5, 5.6, 11.2, 140),
+    (595, 'TD2', 6, 1.5, 5.6, 11.2, 180),
+    (596, 'TD2', 7, 1.5, 5.6, 11.2, 224),
+    (597, 'TD2', 8, 1.5, 5.6, 11.2, 280),
+    (598, 'TD2', 4, 1, 11.2, 22.4, 100),
+    (599, 'TD2', 5, 1, 11.2, 22.4, 125),
+    (600, 'TD2', 6, 1, 11.2, 22.4, 160),
+    (601, 'TD2', 7, 1, 11.2, 22.4, 200),
+    (602, 'TD2', 8, 1, 11.2, 22.4, 250),
+    (603, 'TD2', 4, 1.25, 11.2, 22.4, 112),
+    (604, 'TD2', 5, 1.25, 11.2, 22.4, 140),
+    (605, 'TD2', 6, 1.25, 11.2, 22.4, 180),
+    (606, 'TD2', 7, 1.25, 11.2, 22.4, 224),
+    (607, 'TD2', 8, 1.25, 11.2, 22.4, 280),
+    (608, 'TD2', 4, 1.5, 11.2, 22.4, 118),
+    (609, 'TD2', 5, 1.5, 11.2, 22.4, 150),
+    (610, 'TD2', 6, 1.5, 11.2, 22.4, 190),
+    (611, 'TD2', 7, 1.5, 11.2, 22.4, 236),
+    (612, 'TD2', 8, 1.5, 11.2, 22.4, 300),
+    (613, 'TD2', 4, 1.75, 11.2, 22.4, 125),
+    (614, 'TD2', 5, 1.75, 11.2, 22.4, 160),
+    (615, 'TD2', 6, 1.75, 11.2, 22.4, 200),
+    (616, 'TD2', 7, 1.75, 11.2, 22.4, 250),
+    (617, 'TD2', 8, 1.75, 11.2, 22.4, 315),
+    (618, 'TD2', 4, 2, 11.2, 22.4, 132),
+    (619, 'TD2', 5, 2, 11.2, 22.4, 170),
+    (620, 'TD2', 6, 2, 11.2, 22.4, 212),
+    (621, 'TD2', 7, 2, 11.2, 22.4, 265),
+    (622, 'TD2', 8, 2, 11.2, 22.4, 335),
+    (623, 'TD2', 4, 2.5, 11.2, 22.4, 140),
+    (624, 'TD2', 5, 2.5, 11.2, 22.4, 180),
+    (625, 'TD2', 6, 2.5, 11.2, 22.4, 224),
+    (626, 'TD2', 7, 2.5, 11.2, 22.4, 280),
+    (627, 'TD2', 8, 2.5, 11.2, 22.4, 355),
+    (628, 'TD2', 4, 1, 22.4, 45, 106),
+    (629, 'TD2', 5, 1, 22.4, 45, 132),
+    (630, 'TD2', 6, 1, 22.4, 45, 170),
+    (631, 'TD2', 7, 1, 22.4, 45, 212),
+    (632, 'TD2', 8, 1, 22.4, 45, 265),
+    (633, 'TD2', 4, 1.5, 22.4, 45, 125),
+    (634, 'TD2', 5, 1.5, 22.4, 45, 160),
+    (635, 'TD2', 6, 1.5, 22.4, 45, 200),
+    (636, 'TD2', 7, 1.5, 22.4, 45, 250),
+    (637, 'TD2', 8, 1.5, 22.4, 45, 315),
+    (638, 'TD2', 4, 2, 22.4, 45, 140),
+    (639, 'TD2', 5, 2, 22.4, 45, 180),
+    (640, 'TD2', 6, 2, 22.4, 45, 224),
+    (641, 'TD2', 7, 2, 22.4, 45, 280),
+    (642, 'TD2', 8, 2, 22.4, 45, 355),
+    (643, 'TD2', 4, 3, 22.4, 45, 170),
+    (644, 'TD2', 5, 3, 22.4, 45, 212),
+    (645, 'TD2', 6, 3, 22.4, 45, 265),
+    (646, 'TD2', 7, 3, 22.4, 45, 335),
+    (647, 'TD2', 8, 3, 22.4, 45, 425),
+    (648, 'TD2', 4, 3.5, 22.4, 45, 180),
+    (649, 'TD2', 5, 3.5, 22.4, 45, 224),
+    (650, 'TD2', 6, 3.5, 22.4, 45, 280),
+    (651, 'TD2', 7, 3.5, 22.4, 45, 355),
+    (652, 'TD2', 8, 3.5, 22.4, 45, 450),
+    (653, 'TD2', 4, 4, 22.4, 45, 190),
+    (654, 'TD2', 5, 4, 22.4, 45, 236),
+    (655, 'TD2', 6, 4, 22.4, 45, 300),
+    (656, 'TD2', 7, 4, 22.4, 45, 375),
+    (657, 'TD2', 8, 4, 22.4, 45, 475),
+    (658, 'TD2', 4, 4.5, 22.4, 45, 200),
+    (659, 'TD2', 5, 4.5, 22.4, 45, 250),
+    (660, 'TD2', 6, 4.5, 22.4, 45, 315),
+    (661, 'TD2', 7, 4.5, 22.4, 45, 400),
+    (662, 'TD2', 8, 4.5, 22.4, 45, 500),
+    (663, 'TD2', 4, 1.5, 45, 90, 132),
+    (664, 'TD2', 5, 1.5, 45, 90, 170),
+    (665, 'TD2', 6, 1.5, 45, 90, 212),
+    (666, 'TD2', 7, 1.5, 45, 90, 265),
+    (667, 'TD2', 8, 1.5, 45, 90, 335),
+    (668, 'TD2', 4, 2, 45, 90, 150),
+    (669, 'TD2', 5, 2, 45, 90, 190),
+    (670, 'TD2', 6, 2, 45, 90, 236),
+    (671, 'TD2', 7, 2, 45, 90, 300),
+    (672, 'TD2', 8, 2, 45, 90, 375),
+    (673, 'TD2', 4, 3, 45, 90, 180),
+    (674, 'TD2', 5, 3, 45, 90, 224),
+    (675, 'TD2', 6, 3, 45, 90, 280),
+    (676, 'TD2', 7, 3, 45, 90, 355),
+    (677, 'TD2', 8, 3, 45, 90, 450),
+    (678, 'TD2', 4, 4, 45, 90, 200),
+    (679, 'TD2', 5, 4, 45, 90, 250),
+    (680, 'TD2', 6, 4, 45, 90, 315),
+    (681, 'TD2', 7, 4, 45, 90, 400),
+    (682, 'TD2', 8, 4, 45, 90, 500),
+    (683, 'TD2', 4, 5, 45, 90, 212),
+    (684, 'TD2', 5, 5, 45, 90, 265),
+    (685, 'TD2', 6, 5, 45, 90, 335),
+    (686, 'TD2', 7, 5, 45, 90, 425),
+    (687, 'TD2', 8, 5, 45, 90, 530),
+    (688, 'TD2', 4, 5.5, 45, 90, 224),
+    (689, 'TD2', 5, 5.5, 45, 90, 280),
+    (690, 'TD2', 6, 5.5, 45, 90, 355),
+    (691, 'TD2', 7, 5.5, 45, 90, 450),
+    (692, 'TD2', 8, 5.5, 45, 90, 560),
+    (693, 'TD2', 4, 6, 45, 90, 236),
+    (694, 'TD2', 5, 6, 45, 90, 300),
+    (695, 'TD2', 6, 6, 45, 90, 375),
+    (696, 'TD2', 7, 6, 45, 90, 475),
+    (697, 'TD2', 8, 6, 45, 90, 600),
+    (698, 'TD2', 4, 2, 90, 180, 160),
+    (699, 'TD2', 5, 2, 90, 180, 200),
+    (700, 'TD2', 6, 2, 90, 180, 250),
+    (701, 'TD2', 7, 2, 90, 180, 315),
+    (702, 'TD2', 8, 2, 90, 180, 400),
+    (703, 'TD2', 4, 3, 90, 180, 190),
+    (704, 'TD2', 5, 3, 90, 180, 236),
+    (705, 'TD2', 6, 3, 90, 180, 300),
+    (706, 'TD2', 7, 3, 90, 180, 375),
+    (707, 'TD2', 8, 3, 90, 180, 475),
+    (708, 'TD2', 4, 4, 90, 180, 212),
+    (709, 'TD2', 5, 4, 90, 180, 265),
+    (710, 'TD2', 6, 4, 90, 180, 335),
+    (711, 'TD2', 7, 4, 90, 180, 425),
+    (712, 'TD2', 8, 4, 90, 180, 530),
+    (713, 'TD2', 4, 6, 90, 180, 250),
+    (714, 'TD2', 5, 6, 90, 180, 315),
+    (715, 'TD2', 6, 6, 90, 180, 400),
+    (716, 'TD2', 7, 6, 90, 180, 500),
+    (717, 'TD2', 8, 6, 90, 180, 630),
+    (718, 'TD2', 4, 8, 90, 180, 280),
+    (719, 'TD2', 5, 8, 90, 180, 355),
+    (720, 'TD2', 6, 8, 90, 180, 450),
+    (721, 'TD2', 7, 8, 90, 180, 560),
+    (722, 'TD2', 8, 8, 90, 180, 710),
+    (723, 'TD2', 4, 3, 180, 355, 200),
+    (724, 'TD2', 5, 3, 180, 355, 250),
+    (725, 'TD2', 6, 3, 180, 355, 315),
+    (726, 'TD2', 7, 3, 180, 355, 400),
+    (727, 'TD2', 8, 3, 180, 355, 500),
+    (728, 'TD2', 4, 4, 180, 355, 224),
+    (729, 'TD2', 5, 4, 180, 355, 280),
+    (730, 'TD2', 6, 4, 180, 355, 355),
+    (731, 'TD2', 7, 4, 180, 355, 450),
+    (732, 'TD2', 8, 4, 180, 355, 560),
+    (733, 'TD2', 4, 6, 180, 355, 265),
+    (734, 'TD2', 5, 6, 180, 355, 335),
+    (735, 'TD2', 6, 6, 180, 355, 425),
+    (736, 'TD2', 7, 6, 180, 355, 530),
+    (737, 'TD2', 8, 6, 180, 355, 670),
+    (738, 'TD2', 4, 8, 180, 355, 300),
+    (739, 'TD2', 5, 8, 180, 355, 375),
+    (740, 'TD2', 6, 8, 180, 355, 475),
+    (741, 'TD2', 7, 8, 180, 355, 600),
+    (742, 'TD2', 8, 8, 180, 355, 750),
+    (743, 'TD2', 4, 6, 355, 600, 280),
+    (744, 'TD2', 5, 6, 355, 600, 355),
+    (745, 'TD2', 6, 6, 355, 600, 450),
+    (746, 'TD2', 7, 6, 355, 600, 560),
+    (747, 'TD2', 8, 6, 355, 600, 710)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO metric.fundamental_deviations (id, position, pitch, value) VALUES
+    (1, 'G', 0.2, 17),
+    (2, 'H', 0.2, 0),
+    (3, 'g', 0.2, -17),
+    (4, 'h', 0.2, 0),
+    (5, 'G', 0.25, 18),
+    (6, 'H', 0.25, 0),
+    (7, 'g', 0.25, -18),
+    (8, 'h', 0.25, 0),
+    (9, 'G', 0.3, 18),
+    (10, 'H', 0.3, 0),
+    (11, 'g', 0.3, -18),
+    (12, 'h', 0.3, 0),
+    (13, 'G', 0.35, 19),
+    (14, 'H', 0.35, 0),
+    (15, 'f', 0.35, -34),
+    (16, 'g', 0.35, -19),
+    (17, 'h', 0.35, 0),
+    (18, 'G', 0.4, 19),
+    (19, 'H', 0.4, 0),
+    (20, 'f', 0.4, -34),
+    (21, 'g', 0.4, -19),
+    (22, 'h', 0.4, 0),
+    (23, 'G', 0.45, 20),
+    (24, 'H', 0.45, 0),
+    (25, 'f', 0.45, -35),
+    (26, 'g', 0.45, -20),
+    (27, 'h', 0.45, 0),
+    (28, 'G', 0.5, 20),
+    (29, 'H', 0.5, 0),
+    (30, 'e', 0.5, -50),
+    (31, 'f', 0.5, -36),
+    (32, 'g', 0.5, -20),
+    (33, 'h', 0.5, 0),
+    (34, 'G', 0.6, 21),
+    (35, 'H', 0.6, 0),
+    (36, 'e', 0.6, -53),
+    (37, 'f', 0.6, -36),
+    (38, 'g', 0.6, -21),
+    (39, 'h', 0.6, 0),
+    (40, 'G', 0.7, 22),
+    (41, 'H', 0.7, 0),
+    (42, 'e', 0.7, -56),
+    (43, 'f', 0.7, -38),
+    (44, 'g', 0.7, -22),
+    (45, 'h', 0.7, 0),
+    (46, 'G', 0.75, 22),
+    (47, 'H', 0.75, 0),
+    (48, 'e', 0.75, -56),
+    (49, 'f', 0.75, -38),
+    (50, 'g', 0.75, -22),
+    (51, 'h', 0.75, 0),
+    (52, 'G', 0.8, 24),
+    (53, 'H', 0.8, 0),
+    (54, 'e', 0.8, -60),
+    (55, 'f', 0.8, -38),
+    (56, 'g', 0.8, -24),
+    (57, 'h', 0.8, 0),
+    (58, 'G', 1, 26),
+    (59, 'H', 1, 0),
+    (60, 'e', 1, -60),
+    (61, 'f', 1, -40),
+    (62, 'g', 1, -26),
+    (63, 'h', 1, 0),
+    (64, 'G', 1.25, 28),
+    (65, 'H', 1.25, 0),
+    (66, 'e', 1.25, -63),
+    (67, 'f', 1.25, -42),
+    (68, 'g', 1.25, -28),
+    (69, 'h', 1.25, 0),
+    (70, 'G', 1.5, 32),
+    (71, 'H', 1.5, 0),
+    (72, 'e', 1.5, -67),
+    (73, 'f', 1.5, -45),
+    (74, 'g', 1.5, -32),
+    (75, 'h', 1.5, 0),
+    (76, 'G', 1.75, 34),
+    (77, 'H', 1.75, 0),
+    (78, 'e', 1.75, -71),
+    (79, 'f', 1.75, -48),
+    (80, 'g', 1.75, -34),
+    (81, 'h', 1.75, 0),
+    (82, 'G', 2, 38),
+    (83, 'H', 2, 0),
+    (84, 'e', 2, -71),
+    (85, 'f', 2, -52),
+    (86, 'g', 2, -38),
+    (87, 'h', 2, 0),
+    (88, 'G', 2.5, 42),
+    (89, 'H', 2.5, 0),
+    (90, 'e', 2.5, -80),
+    (91, 'f', 2.5, -58),
+    (92, 'g', 2.5, -42),
+    (93, 'h', 2.5, 0),
+    (94, 'G', 3, 48),
+    (95, 'H', 3, 0),
+    (96, 'e', 3, -85),
+    (97, 'f', 3, -63),
+    (98, 'g', 3, -48),
+    (99, 'h', 3, 0),
+    (100, 'G', 3.5, 53),
+    (101, 'H', 3.5, 0),
+    (102, 'e', 3.5, -90),
+    (103, 'f', 3.5, -70),
+    (104, 'g', 3.5, -53),
+    (105, 'h', 3.5, 0),
+    (106, 'G', 4, 60),
+    (107, 'H', 4, 0),
+    (108, 'e', 4, -95),
+    (109, 'f', 4, -75),
+    (110, 'g', 4, -60),
+    (111, 'h', 4, 0),
+    (112, 'G', 4.5, 63),
+    (113, 'H', 4.5, 0),
+    (114, 'e', 4.5, -100),
+    (115, 'f', 4.5, -80),
+    (116, 'g', 4.5, -63),
+    (117, 'h', 4.5, 0),
+    (118, 'G', 5, 71),
+    (119, 'H', 5, 0),
+    (120, 'e', 5, -106),
+    (121, 'f', 5, -85),
+    (122, 'g', 5, -71),
+    (123, 'h', 5, 0),
+    (124, 'G', 5.5, 75),
+    (125, 'H', 5.5, 0),
+    (126, 'e', 5.5, -112),
+    (127, 'f', 5.5, -90),
+    (128, 'g', 5.5, -75),
+    (129, 'h', 5.5, 0),
+    (130, 'G', 6, 80),
+    (131, 'H', 6, 0),
+    (132, 'e', 6, -118),
+    (133, 'f', 6, -95),
+    (134, 'g', 6, -80),
+    (135, 'h', 6, 0),
+    (136, 'G', 8, 100),
+    (137, 'H', 8, 0),
+    (138, 'e', 8, -140),
+    (139, 'f', 8, -118),
+    (140, 'g', 8, -100),
+    (141, 'h', 8, 0)
+ON CONFLICT DO NOTHING;
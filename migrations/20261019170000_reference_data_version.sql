@@ -0,0 +1,36 @@
+-- Version of the reference tables: statement triggers bump it on every change,
+-- so the server notices imports by reading one row instead of every table.
+-- Reference tables created after this migration need the trigger added by hand.
+CREATE TABLE IF NOT EXISTS public.reference_data_version (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+INSERT INTO public.reference_data_version (id) VALUES (1) ON CONFLICT (id) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION public.bump_reference_data_version() RETURNS trigger AS $$
+BEGIN
+    UPDATE public.reference_data_version SET version = version + 1, updated_at = now() WHERE id = 1;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    reference_table TEXT;
+BEGIN
+    FOREACH reference_table IN ARRAY ARRAY[
+        'metric.main', 'metric.tolerance_grades', 'metric.fundamental_deviations',
+        'imperial.main', 'trapezoidal.main', 'trapezoidal.basic_dimensions', 'pipe.main'
+    ] LOOP
+        IF to_regclass(reference_table) IS NOT NULL THEN
+            EXECUTE format('DROP TRIGGER IF EXISTS reference_data_version ON %s', reference_table);
+            EXECUTE format(
+                'CREATE TRIGGER reference_data_version AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON %s '
+                'FOR EACH STATEMENT EXECUTE FUNCTION public.bump_reference_data_version()',
+                reference_table
+            );
+        END IF;
+    END LOOP;
+END $$;
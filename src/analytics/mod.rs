@@ -1 +1,2 @@
-pub mod db;
+pub mod pipeline;
+pub mod report;
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    Notify,
+};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use utoipa::ToSchema;
+
+use crate::shared::database::repositories::{ThreadUsage, TraitAnalyticsRepository};
+use crate::shared::setting::models::app_config::AnalyticsConfig;
+use crate::{log_error, log_info, log_warn};
+
+/// Shortest flush interval, a zero interval would spin
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Counters of the analytics pipeline since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AnalyticsStats {
+    /// Lookups accepted into the queue
+    pub recorded: u64,
+    /// Lookups dropped because the queue was full or the worker had stopped
+    pub dropped: u64,
+    /// Lookups written to the repository
+    pub flushed: u64,
+    /// Lookups lost with batches the repository rejected
+    pub failed: u64,
+    /// Batches written
+    pub batches: u64,
+    /// Lookups waiting in the queue
+    pub queued: usize,
+}
+
+#[derive(Default)]
+struct Counters {
+    recorded: AtomicU64,
+    dropped: AtomicU64,
+    flushed: AtomicU64,
+    failed: AtomicU64,
+    batches: AtomicU64,
+}
+
+/// Handle of the handlers: queues a lookup without waiting for the database
+#[derive(Clone)]
+pub struct AnalyticsRecorder {
+    sender: mpsc::Sender<ThreadUsage>,
+    counters: Arc<Counters>,
+}
+
+impl AnalyticsRecorder {
+    /// Queues the lookup; when the queue is full the lookup is dropped and counted
+    pub fn record(&self, usage: ThreadUsage) {
+        match self.sender.try_send(usage) {
+            Ok(()) => self.counters.recorded.fetch_add(1, Ordering::Relaxed),
+            Err(TrySendError::Full(_) | TrySendError::Closed(_)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed)
+            }
+        };
+    }
+
+    pub fn stats(&self) -> AnalyticsStats {
+        let counter = |value: &AtomicU64| value.load(Ordering::Relaxed);
+        AnalyticsStats {
+            recorded: counter(&self.counters.recorded),
+            dropped: counter(&self.counters.dropped),
+            flushed: counter(&self.counters.flushed),
+            failed: counter(&self.counters.failed),
+            batches: counter(&self.counters.batches),
+            queued: self.sender.max_capacity() - self.sender.capacity(),
+        }
+    }
+}
+
+/// Background worker that sums queued lookups per designation, standard,
+/// language and day and writes them in batches
+pub struct AnalyticsPipeline {
+    shutdown: Arc<Notify>,
+    worker: JoinHandle<()>,
+}
+
+impl AnalyticsPipeline {
+    pub fn start(
+        repository: Arc<dyn TraitAnalyticsRepository + Send + Sync>,
+        config: &AnalyticsConfig,
+    ) -> (AnalyticsRecorder, AnalyticsPipeline) {
+        let (sender, receiver) = mpsc::channel(config.channel_capacity.max(1));
+        let counters = Arc::new(Counters::default());
+        let shutdown = Arc::new(Notify::new());
+
+        let worker = Worker {
+            repository,
+            counters: counters.clone(),
+            batch_size: config.batch_size.max(1),
+            pending: HashMap::new(),
+            reported_drops: 0,
+        };
+        let interval = Duration::from_millis(config.flush_interval_ms).max(MIN_FLUSH_INTERVAL);
+        let worker = tokio::spawn(worker.run(receiver, shutdown.clone(), interval));
+        log_info!(
+            "Analytics pipeline started: queue {}, batch {}, flush every {:?}",
+            config.channel_capacity,
+            config.batch_size,
+            interval
+        );
+
+        (
+            AnalyticsRecorder { sender, counters },
+            AnalyticsPipeline { shutdown, worker },
+        )
+    }
+
+    /// Writes everything still queued and stops the worker
+    pub async fn shutdown(self) {
+        self.shutdown.notify_one();
+        if let Err(e) = self.worker.await {
+            log_error!("Analytics worker failed: {}", e);
+        }
+    }
+}
+
+struct Worker {
+    repository: Arc<dyn TraitAnalyticsRepository + Send + Sync>,
+    counters: Arc<Counters>,
+    batch_size: usize,
+    pending: HashMap<ThreadUsage, i64>,
+    reported_drops: u64,
+}
+
+impl Worker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<ThreadUsage>, shutdown: Arc<Notify>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                received = receiver.recv() => match received {
+                    Some(usage) => {
+                        *self.pending.entry(usage).or_default() += 1;
+                        if self.pending.len() >= self.batch_size {
+                            self.flush().await;
+                        }
+                    }
+                    None => break,
+                },
+                _ = ticker.tick() => self.flush().await,
+                _ = shutdown.notified() => {
+                    // New lookups are dropped from now on, the queued ones are still written
+                    receiver.close();
+                    while let Some(usage) = receiver.recv().await {
+                        *self.pending.entry(usage).or_default() += 1;
+                    }
+                    break;
+                }
+            }
+        }
+
+        self.flush().await;
+        let counter = |value: &AtomicU64| value.load(Ordering::Relaxed);
+        log_info!(
+            "Analytics pipeline stopped: {} lookups written, {} dropped, {} failed",
+            counter(&self.counters.flushed),
+            counter(&self.counters.dropped),
+            counter(&self.counters.failed)
+        );
+    }
+
+    async fn flush(&mut self) {
+        let dropped = self.counters.dropped.load(Ordering::Relaxed);
+        if dropped > self.reported_drops {
+            log_warn!(
+                "Analytics queue full, {} lookups dropped since the last flush",
+                dropped - self.reported_drops
+            );
+            self.reported_drops = dropped;
+        }
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let batch: Vec<(ThreadUsage, i64)> = self.pending.drain().collect();
+        let lookups: i64 = batch.iter().map(|(_, count)| count).sum();
+        match self.repository.add_usage(&batch).await {
+            Ok(()) => {
+                self.counters.flushed.fetch_add(lookups as u64, Ordering::Relaxed);
+                self.counters.batches.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                // Not retried: while the database is down the counters would only pile up
+                self.counters.failed.fetch_add(lookups as u64, Ordering::Relaxed);
+                log_error!("Failed to write {} analytics counters: {}", batch.len(), e);
+            }
+        }
+    }
+}
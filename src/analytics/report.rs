@@ -0,0 +1,135 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+
+use crate::shared::database::repositories::DailyUsage;
+use crate::shared::enums::ThreadStandard;
+
+/// Length of a report interval
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    /// Week starting on Monday
+    Week,
+    /// Calendar month
+    Month,
+}
+
+impl Period {
+    /// First day of the interval `day` falls into
+    pub fn start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Day => day,
+            Period::Week => day - Days::new(day.weekday().num_days_from_monday() as u64),
+            Period::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    /// First day of the next interval
+    pub fn next(self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Period::Day => start.checked_add_days(Days::new(1)),
+            Period::Week => start.checked_add_days(Days::new(7)),
+            Period::Month => start.checked_add_months(Months::new(1)),
+        }
+    }
+
+    /// Days of the ranking ending `today`: one, 7 or 30 days
+    pub fn trailing(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let days = match self {
+            Period::Day => 0,
+            Period::Week => 6,
+            Period::Month => 29,
+        };
+        (today - Days::new(days), today)
+    }
+
+    /// Default start of a series: 30 days, 12 weeks or 12 months up to `to` inclusive
+    pub fn default_from(self, to: NaiveDate) -> NaiveDate {
+        let start = self.start(to);
+        match self {
+            Period::Day => start - Days::new(29),
+            Period::Week => start - Days::new(7 * 11),
+            Period::Month => start.checked_sub_months(Months::new(11)).unwrap_or(start),
+        }
+    }
+}
+
+/// Thread designation with its request count
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PopularThread {
+    pub designation: String,
+    pub standard: ThreadStandard,
+    pub count: i64,
+}
+
+/// Request count of the interval starting at `start`
+#[derive(Debug, Serialize, ToSchema, PartialEq)]
+pub struct UsagePoint {
+    pub start: NaiveDate,
+    pub count: i64,
+}
+
+/// The `limit` most requested designations; ties are sorted alphabetically
+pub fn popular(rows: &[DailyUsage], limit: usize) -> Vec<PopularThread> {
+    let mut totals: HashMap<(&str, ThreadStandard), i64> = HashMap::new();
+    for row in rows {
+        *totals.entry((row.designation.as_str(), row.standard)).or_default() += row.count;
+    }
+
+    let mut threads: Vec<PopularThread> = totals
+        .into_iter()
+        .map(|((designation, standard), count)| PopularThread {
+            designation: designation.to_string(),
+            standard,
+            count,
+        })
+        .collect();
+    threads.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.designation.cmp(&b.designation)));
+    threads.truncate(limit);
+    threads
+}
+
+/// Number of intervals overlapping the days `from..=to`, without building the list
+pub fn bucket_count(period: Period, from: NaiveDate, to: NaiveDate) -> u64 {
+    if from > to {
+        return 0;
+    }
+    let (first, last) = (period.start(from), period.start(to));
+    let count = match period {
+        Period::Day => (last - first).num_days(),
+        Period::Week => (last - first).num_days() / 7,
+        Period::Month => {
+            let months = |day: NaiveDate| day.year() as i64 * 12 + day.month0() as i64;
+            months(last) - months(first)
+        }
+    };
+    count as u64 + 1
+}
+
+/// Intervals overlapping the days `from..=to`
+pub fn buckets(period: Period, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let mut starts = Vec::new();
+    let mut start = Some(period.start(from));
+    while let Some(current) = start.filter(|current| *current <= to) {
+        starts.push(current);
+        start = period.next(current);
+    }
+    starts
+}
+
+/// Request totals per interval, including intervals without requests
+pub fn timeseries(rows: &[DailyUsage], period: Period, from: NaiveDate, to: NaiveDate) -> Vec<UsagePoint> {
+    let mut counts: BTreeMap<NaiveDate, i64> = buckets(period, from, to).into_iter().map(|start| (start, 0)).collect();
+    for row in rows {
+        if let Some(count) = counts.get_mut(&period.start(row.day)) {
+            *count += row.count;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(start, count)| UsagePoint { start, count })
+        .collect()
+}
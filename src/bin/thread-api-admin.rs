@@ -0,0 +1,284 @@
+//! Maintenance of the reference tables: export to CSV/JSON, validation and
+//! transactional import of edited files, sqlx migrations from the changes.
+//!
+//! Uses the environment and `config/<env>.toml` of the server.
+
+use clap::{Args, Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use thread_api::services::reference_data::{
+    files::{read_rows, write_rows},
+    integrity::check_store,
+    migration::{render_migration, write_migration},
+    validate_rows, FileFormat, ReferenceStore, ReferenceTable, TableDiff,
+};
+use thread_api::shared::{
+    database::{connection::PostgresConnection, sqlite::SqliteConnection},
+    error::AppError,
+    logging::{enums::LogLevel, init::init_logging, structs::LogConfig},
+    setting::models::{
+        app_config::{AppConfig, DatabaseBackend},
+        app_env::AppEnv,
+        app_setting::AppSettings,
+    },
+    utils::db::JsonRow,
+};
+
+#[derive(Parser)]
+#[command(name = "thread-api-admin", version, about = "Reference data tooling for thread-api")]
+struct Cli {
+    /// Log database activity
+    #[arg(long, global = true)]
+    verbose: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write tables to `<output>/<schema>/<table>.<format>`
+    Export {
+        /// Table to export (`imperial.main`, ...); all tables when omitted
+        #[arg(long)]
+        table: Vec<ReferenceTable>,
+        #[arg(long, default_value = "csv")]
+        format: FileFormat,
+        #[arg(long, default_value = "./export")]
+        output: PathBuf,
+    },
+    /// Check an edited file against the table columns
+    Validate(FileArgs),
+    /// Check the stored tables for inverted limits, NULL tolerances, duplicates, ...
+    Check {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the changes of an edited file and apply them in one transaction
+    Import {
+        #[command(flatten)]
+        file: FileArgs,
+        /// Only print the diff
+        #[arg(long)]
+        dry_run: bool,
+        /// Also write the changes as a migration
+        #[arg(long)]
+        migration: bool,
+        #[arg(long, default_value = "./migrations")]
+        migrations_dir: PathBuf,
+    },
+    /// Write the changes of an edited file as an sqlx migration without applying them
+    Migration {
+        #[command(flatten)]
+        file: FileArgs,
+        /// Migration description; `update_<schema>_<table>` by default
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long, default_value = "./migrations")]
+        migrations_dir: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct FileArgs {
+    /// Edited CSV or JSON file
+    path: PathBuf,
+    /// Target table; taken from `<schema>/<table>.<ext>` when omitted
+    #[arg(long)]
+    table: Option<ReferenceTable>,
+}
+
+impl FileArgs {
+    fn table(&self) -> Result<ReferenceTable, AppError> {
+        self.table
+            .or_else(|| ReferenceTable::from_path(&self.path))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "cannot tell the table of {}, pass --table",
+                    self.path.display()
+                ))
+            })
+    }
+
+    fn format(&self) -> Result<FileFormat, AppError> {
+        FileFormat::from_path(&self.path)
+            .ok_or_else(|| AppError::BadRequest(format!("{} is neither .csv nor .json", self.path.display())))
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let level = if cli.verbose { LogLevel::Debug } else { LogLevel::Warn };
+    init_logging(LogConfig {
+        level,
+        ..LogConfig::default()
+    })
+    .expect("Failed to initialize logger");
+
+    match run(cli.command).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command) -> Result<ExitCode, AppError> {
+    let store = open_store().await?;
+
+    match command {
+        Command::Export { table, format, output } => {
+            let tables = if table.is_empty() {
+                ReferenceTable::ALL.to_vec()
+            } else {
+                table
+            };
+            for table in tables {
+                let columns = store.columns(table).await?;
+                let rows = store.rows(table).await?;
+                let path = table.file_path(&output, format);
+                write_rows(&path, format, &columns, &rows)?;
+                println!("{}: {} rows -> {}", table, rows.len(), path.display());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Validate(file) => {
+            let table = file.table()?;
+            match load_edited(&store, table, &file).await? {
+                Some(rows) => {
+                    println!("{}: {} rows are valid", file.path.display(), rows.len());
+                    Ok(ExitCode::SUCCESS)
+                }
+                None => Ok(ExitCode::FAILURE),
+            }
+        }
+        Command::Check { json } => {
+            let report = check_store(&store).await?;
+            if json {
+                let content = serde_json::to_string_pretty(&report)
+                    .map_err(|e| AppError::InternalError { message: e.to_string() })?;
+                println!("{}", content);
+            } else {
+                for finding in &report.findings {
+                    println!(
+                        "{:?}\t{}\t{}\t{}\t{}",
+                        finding.severity,
+                        finding.table,
+                        finding.row,
+                        finding.column.as_deref().unwrap_or("-"),
+                        finding.message
+                    );
+                }
+                println!("{} error(s), {} warning(s)", report.errors, report.warnings);
+            }
+            Ok(if report.is_ok() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Command::Import {
+            file,
+            dry_run,
+            migration,
+            migrations_dir,
+        } => {
+            let table = file.table()?;
+            let Some(edited) = load_edited(&store, table, &file).await? else {
+                return Ok(ExitCode::FAILURE);
+            };
+            let diff = TableDiff::between(table, &store.rows(table).await?, &edited);
+            print!("{}", diff.preview(table));
+            if diff.is_empty() || dry_run {
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            let columns = store.columns(table).await?;
+            store.apply(table, &columns, &diff).await?;
+            println!("Imported {} into {}", file.path.display(), table);
+            if migration {
+                let name = format!("update_{}_{}", table.schema(), table.table());
+                save_migration(table, &file.path, &diff, &store, &migrations_dir, &name).await?;
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Migration {
+            file,
+            name,
+            migrations_dir,
+        } => {
+            let table = file.table()?;
+            let Some(edited) = load_edited(&store, table, &file).await? else {
+                return Ok(ExitCode::FAILURE);
+            };
+            let diff = TableDiff::between(table, &store.rows(table).await?, &edited);
+            print!("{}", diff.preview(table));
+            if diff.is_empty() {
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            let name = name.unwrap_or_else(|| format!("update_{}_{}", table.schema(), table.table()));
+            save_migration(table, &file.path, &diff, &store, &migrations_dir, &name).await?;
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Reads and validates an edited file; prints the findings and returns `None` when there are any
+async fn load_edited(
+    store: &ReferenceStore,
+    table: ReferenceTable,
+    file: &FileArgs,
+) -> Result<Option<Vec<JsonRow>>, AppError> {
+    let columns = store.columns(table).await?;
+    let raw = read_rows(&file.path, file.format()?)?;
+    let (rows, findings) = validate_rows(table, &columns, raw);
+    if findings.is_empty() {
+        return Ok(Some(rows));
+    }
+
+    for finding in &findings {
+        eprintln!("{}: {}", file.path.display(), finding);
+    }
+    eprintln!("{} problem(s) found, nothing imported", findings.len());
+    Ok(None)
+}
+
+async fn save_migration(
+    table: ReferenceTable,
+    source: &Path,
+    diff: &TableDiff,
+    store: &ReferenceStore,
+    dir: &Path,
+    name: &str,
+) -> Result<(), AppError> {
+    let columns = store.columns(table).await?;
+    let sql = render_migration(table, &columns, diff, &source.display().to_string());
+    let path = write_migration(dir, name, &sql)?;
+    println!("Migration written to {}", path.display());
+    Ok(())
+}
+
+/// Connects to the database configured for the server
+async fn open_store() -> Result<ReferenceStore, AppError> {
+    let env = AppEnv::new();
+    let config = AppConfig::new(&env.env);
+    let settings = Arc::new(AppSettings { config, env });
+
+    match settings.config.database.backend {
+        DatabaseBackend::Postgres => {
+            let connection = PostgresConnection::new(settings.clone()).await?;
+            Ok(ReferenceStore::Postgres(connection.pool().clone()))
+        }
+        DatabaseBackend::Sqlite => {
+            let connection = SqliteConnection::open(&settings.config.database.sqlite_path).await?;
+            Ok(ReferenceStore::Sqlite(connection.pool().clone()))
+        }
+        // Read-only: exports and checks work, imports ask to edit the files
+        DatabaseBackend::Memory => Ok(ReferenceStore::Files(settings.config.database.data_dir.clone())),
+    }
+}
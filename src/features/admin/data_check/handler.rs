@@ -0,0 +1,43 @@
+use crate::{log_info, log_warn};
+use axum::{extract::Extension, response::IntoResponse, Json};
+use std::sync::Arc;
+
+use crate::services::reference_data::{
+    integrity::{check_store, IntegrityReport},
+    ReferenceStore,
+};
+use crate::shared::setting::models::app_state::AppState;
+
+/// GET /admin/data-check - integrity check of the reference tables, with findings per row
+#[utoipa::path(
+    get,
+    path = "/admin/data-check",
+    tag = "system",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Integrity report of the reference tables", body = IntegrityReport),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Extension(app_state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    log_info!("Running reference data integrity check");
+
+    let store = ReferenceStore::for_database(
+        &app_state.postgres_service,
+        &app_state.settings.config.database.data_dir,
+    );
+    match check_store(&store).await {
+        Ok(report) => {
+            if !report.is_ok() {
+                log_warn!(
+                    "Reference data check: {} error(s), {} warning(s)",
+                    report.errors,
+                    report.warnings
+                );
+            }
+            Json(report).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
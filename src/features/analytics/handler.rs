@@ -0,0 +1,100 @@
+use axum::{extract::Extension, Json};
+use std::sync::Arc;
+
+use crate::analytics::report::{self, Period};
+use crate::features::analytics::models::{PopularParams, PopularResponse, TimeseriesParams, TimeseriesResponse};
+use crate::shared::database::repositories::UsageFilter;
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+/// A year of daily points; longer ranges have to use weeks or months
+const MAX_POINTS: usize = 366;
+
+/// Most requested designations over the last day, week or month
+#[utoipa::path(
+    get,
+    path = "/v1/analytics/popular",
+    tag = "analytics",
+    params(PopularParams),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Designations by number of lookups", body = PopularResponse),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn popular(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<PopularParams>,
+) -> Result<Json<PopularResponse>, AppError> {
+    let period = params.period.unwrap_or(Period::Week);
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let (from, to) = period.trailing(chrono::Utc::now().date_naive());
+
+    let filter = UsageFilter {
+        from,
+        to,
+        standard: params.standard,
+        language: params.language,
+        designation: None,
+    };
+    let rows = database.repository_analytics.daily_usage(&filter).await?;
+
+    Ok(Json(PopularResponse {
+        period,
+        from,
+        to,
+        items: report::popular(&rows, limit),
+    }))
+}
+
+/// Lookups per day, week or month, with zeros for intervals without lookups
+#[utoipa::path(
+    get,
+    path = "/v1/analytics/timeseries",
+    tag = "analytics",
+    params(TimeseriesParams),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Lookups per interval in date order", body = TimeseriesResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn timeseries(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<TimeseriesParams>,
+) -> Result<Json<TimeseriesResponse>, AppError> {
+    let period = params.period.unwrap_or(Period::Day);
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = params.from.unwrap_or_else(|| period.default_from(to));
+    if from > to {
+        return Err(AppError::BadRequest(format!("'from' {} is after 'to' {}", from, to)));
+    }
+    if report::bucket_count(period, from, to) > MAX_POINTS as u64 {
+        return Err(AppError::BadRequest(format!(
+            "Range {}..{} has more than {} points, use a longer period",
+            from, to, MAX_POINTS
+        )));
+    }
+
+    let filter = UsageFilter {
+        from,
+        to,
+        standard: params.standard,
+        language: params.language,
+        designation: params.designation,
+    };
+    let rows = database.repository_analytics.daily_usage(&filter).await?;
+
+    Ok(Json(TimeseriesResponse {
+        period,
+        from,
+        to,
+        points: report::timeseries(&rows, period, from, to),
+    }))
+}
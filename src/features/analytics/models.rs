@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::analytics::report::{Period, PopularThread, UsagePoint};
+use crate::shared::enums::{Language, ThreadStandard};
+
+/// Parameters of the designation ranking
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PopularParams {
+    /// The last day, 7 or 30 days, `week` by default
+    pub period: Option<Period>,
+    /// Only threads of one standard
+    pub standard: Option<ThreadStandard>,
+    /// Only requests in one language
+    pub language: Option<Language>,
+    /// Number of designations, 10 by default, at most 100
+    pub limit: Option<usize>,
+}
+
+/// Parameters of the request series
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeseriesParams {
+    /// Interval of a point of the series, `day` by default
+    pub period: Option<Period>,
+    /// First day, by default 30 days, 12 weeks or 12 months before `to`
+    pub from: Option<NaiveDate>,
+    /// Last day inclusive, today (UTC) by default
+    pub to: Option<NaiveDate>,
+    pub standard: Option<ThreadStandard>,
+    pub language: Option<Language>,
+    /// Only one designation, e.g. "M10×1.25-6g"
+    pub designation: Option<String>,
+}
+
+/// The most requested designations of the period
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PopularResponse {
+    pub period: Period,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub items: Vec<PopularThread>,
+}
+
+/// Request counts per interval
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TimeseriesResponse {
+    pub period: Period,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub points: Vec<UsagePoint>,
+}
@@ -0,0 +1,206 @@
+use crate::log_info;
+use axum::{extract::Extension, http::StatusCode, Json as JsonResponse};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::analytics::report::{self, Period, UsagePoint};
+use crate::features::api_keys::models::{ApiKeyItem, IssueRequest, IssuedKey, KeyUsageParams, KeyUsageResponse};
+use crate::shared::database::repositories::{ApiKey, NewApiKey};
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::extract::{Json, Path, Query};
+use crate::shared::middleware::{api_key_prefix, generate_api_key, hash_api_key};
+
+/// A year of days, as in `/v1/analytics/timeseries`
+const MAX_DAYS: usize = 366;
+
+fn key_not_found(id: i64) -> AppError {
+    AppError::NotFound(format!("active API key {}", id))
+}
+
+/// Issues a key; the secret is in the response only
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    tag = "api_keys",
+    request_body = IssueRequest,
+    security(("admin_token" = [])),
+    responses(
+        (status = 201, description = "Issued key with its secret", body = IssuedKey),
+        (status = 400, description = "Invalid name, scopes or quota"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn issue(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Json(request): Json<IssueRequest>,
+) -> Result<(StatusCode, JsonResponse<IssuedKey>), AppError> {
+    request.validate()?;
+
+    let mut scopes = request.scopes;
+    scopes.sort_by_key(|scope| *scope as u8);
+    scopes.dedup();
+    let secret = generate_api_key();
+    let key = database
+        .repository_api_keys
+        .create_api_key(&NewApiKey {
+            name: request.name.trim().to_string(),
+            key_hash: hash_api_key(&secret),
+            prefix: api_key_prefix(&secret),
+            scopes,
+            daily_quota: request.daily_quota,
+        })
+        .await?;
+
+    log_info!("Issued API key {} ({}) with scopes {:?}", key.id, key.name, key.scopes);
+    Ok((StatusCode::CREATED, JsonResponse(IssuedKey { key, secret })))
+}
+
+/// Every key, revoked ones included, with today's requests
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    tag = "api_keys",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Keys without their secrets, oldest first", body = [ApiKeyItem]),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn list(
+    Extension(database): Extension<Arc<PostgresService>>,
+) -> Result<JsonResponse<Vec<ApiKeyItem>>, AppError> {
+    let repository = &database.repository_api_keys;
+    let today = chrono::Utc::now().date_naive();
+    let requests: HashMap<i64, i64> = repository
+        .api_key_usage(today, today)
+        .await?
+        .into_iter()
+        .map(|usage| (usage.key_id, usage.requests))
+        .collect();
+
+    let keys = repository.api_keys().await?;
+    Ok(JsonResponse(
+        keys.into_iter()
+            .map(|key| ApiKeyItem {
+                requests_today: requests.get(&key.id).copied().unwrap_or_default(),
+                key,
+            })
+            .collect(),
+    ))
+}
+
+/// Replaces the secret of a key; the old secret stops working at once
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{id}/rotate",
+    tag = "api_keys",
+    params(("id" = i64, Path, description = "Key id")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Key with its new secret", body = IssuedKey),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "No such key or the key is revoked"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn rotate(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Path(id): Path<i64>,
+) -> Result<JsonResponse<IssuedKey>, AppError> {
+    let secret = generate_api_key();
+    let key = database
+        .repository_api_keys
+        .rotate_api_key(id, &hash_api_key(&secret), &api_key_prefix(&secret))
+        .await?
+        .ok_or_else(|| key_not_found(id))?;
+
+    log_info!("Rotated API key {} ({})", key.id, key.name);
+    Ok(JsonResponse(IssuedKey { key, secret }))
+}
+
+/// Revokes a key for good; its usage stays for the reports
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{id}",
+    tag = "api_keys",
+    params(("id" = i64, Path, description = "Key id")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Revoked key", body = ApiKey),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "No such key"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn revoke(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Path(id): Path<i64>,
+) -> Result<JsonResponse<ApiKey>, AppError> {
+    let key = database
+        .repository_api_keys
+        .revoke_api_key(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("API key {}", id)))?;
+
+    log_info!("Revoked API key {} ({})", key.id, key.name);
+    Ok(JsonResponse(key))
+}
+
+/// Requests of a key per day
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys/{id}/usage",
+    tag = "api_keys",
+    params(("id" = i64, Path, description = "Key id"), KeyUsageParams),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Requests per day in date order", body = KeyUsageResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn usage(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Path(id): Path<i64>,
+    Query(params): Query<KeyUsageParams>,
+) -> Result<JsonResponse<KeyUsageResponse>, AppError> {
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = params.from.unwrap_or_else(|| Period::Day.default_from(to));
+    if from > to {
+        return Err(AppError::BadRequest(format!("'from' {} is after 'to' {}", from, to)));
+    }
+    if report::bucket_count(Period::Day, from, to) > MAX_DAYS as u64 {
+        return Err(AppError::BadRequest(format!(
+            "Range {}..{} is longer than {} days",
+            from, to, MAX_DAYS
+        )));
+    }
+
+    let requests: HashMap<_, _> = database
+        .repository_api_keys
+        .api_key_usage(from, to)
+        .await?
+        .into_iter()
+        .filter(|usage| usage.key_id == id)
+        .map(|usage| (usage.day, usage.requests))
+        .collect();
+    let days: Vec<UsagePoint> = report::buckets(Period::Day, from, to)
+        .into_iter()
+        .map(|day| UsagePoint {
+            start: day,
+            count: requests.get(&day).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(JsonResponse(KeyUsageResponse {
+        key_id: id,
+        from,
+        to,
+        total: days.iter().map(|point| point.count).sum(),
+        days,
+    }))
+}
@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::analytics::report::UsagePoint;
+use crate::shared::database::repositories::{ApiKey, ApiScope};
+use crate::shared::error::AppError;
+
+const MAX_NAME_CHARS: usize = 64;
+
+/// A new key
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IssueRequest {
+    /// Partner or application, e.g. "acme-cad"
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// Requests per day (UTC), unlimited when not set
+    pub daily_quota: Option<i64>,
+}
+
+impl IssueRequest {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(AppError::ValidationError(format!(
+                "'name' must have 1 to {} characters",
+                MAX_NAME_CHARS
+            )));
+        }
+        if self.scopes.is_empty() {
+            return Err(AppError::ValidationError("'scopes' must not be empty".to_string()));
+        }
+        if self.daily_quota.is_some_and(|quota| quota < 1) {
+            return Err(AppError::ValidationError("'daily_quota' must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Key with its secret; the secret is only shown on creation and rotation
+#[derive(Serialize, Debug, ToSchema)]
+pub struct IssuedKey {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    /// Value of the `X-API-Key` header
+    pub secret: String,
+}
+
+/// Key with its request count of today
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ApiKeyItem {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    pub requests_today: i64,
+}
+
+/// Days of the key usage report
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct KeyUsageParams {
+    /// First day, 30 days before `to` by default
+    pub from: Option<NaiveDate>,
+    /// Last day inclusive, today (UTC) by default
+    pub to: Option<NaiveDate>,
+}
+
+/// Requests of the key per day, including days without requests
+#[derive(Serialize, Debug, ToSchema)]
+pub struct KeyUsageResponse {
+    pub key_id: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: i64,
+    pub days: Vec<UsagePoint>,
+}
@@ -0,0 +1,162 @@
+use axum::{extract::Extension, Json as JsonResponse};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::Instrument;
+
+use crate::analytics::pipeline::AnalyticsRecorder;
+use crate::features::batch::models::{BatchInfoItem, BatchInfoResult};
+use crate::features::{imperial, metric, pipe, trapezoidal};
+use crate::shared::database::repositories::ThreadUsage;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::{Language, ThreadStandard};
+use crate::shared::error::AppError;
+use crate::shared::extract::Json;
+use crate::shared::middleware::{charge_api_key, ApiClient, RateLimitBucket};
+use crate::{log_error, log_info};
+
+/// Upper bound of rows in one request, a large assembly BOM fits with room to spare
+const MAX_ITEMS: usize = 1000;
+/// Lookups in flight at once, keeps a single batch from taking the whole pool
+const MAX_CONCURRENT_LOOKUPS: usize = 8;
+
+/// Resolves a list of `/info` requests of any standard in one call.
+/// Every row gets its own status; a missing thread does not fail the batch.
+#[utoipa::path(
+    post,
+    path = "/v1/batch/info",
+    tag = "batch",
+    request_body(content = Vec<BatchInfoItem>, description = "Rows tagged with `standard`, each with the query parameters of the standard's `/info` route"),
+    responses(
+        (status = 200, description = "Results in request order", body = Vec<BatchInfoResult>),
+        (status = 400, description = "Body is not a JSON array or has too many rows"),
+        (status = 429, description = "The rows exceed the rate limit or the daily quota of the API key"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
+    client: Option<Extension<ApiClient>>,
+    bucket: Option<Extension<RateLimitBucket>>,
+    Json(items): Json<Vec<Value>>,
+) -> Result<JsonResponse<Vec<BatchInfoResult>>, AppError> {
+    if items.len() > MAX_ITEMS {
+        return Err(AppError::BadRequest(format!(
+            "Batch has {} rows, the limit is {}",
+            items.len(),
+            MAX_ITEMS
+        )));
+    }
+    // Every row is a lookup of the rate limit and of the key's quota; the request
+    // itself was counted by the middleware
+    let extra_rows = items.len().saturating_sub(1);
+    if extra_rows > 0 {
+        if let Some(Extension(bucket)) = &bucket {
+            bucket.charge(extra_rows as u32)?;
+        }
+        if let Some(Extension(client)) = &client {
+            charge_api_key(database.repository_api_keys.as_ref(), &client.key, extra_rows as i64).await?;
+        }
+    }
+    log_info!("Resolving batch of {} rows", items.len());
+
+    let count = items.len();
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_LOOKUPS));
+    let mut tasks = JoinSet::new();
+    for (index, raw) in items.into_iter().enumerate() {
+        let database = database.clone();
+        let analytics = analytics.clone();
+        let permits = permits.clone();
+        // A spawned task does not inherit the request span, the row spans need it as parent
+        tasks.spawn(
+            async move {
+                let _permit = permits.acquire_owned().await;
+                resolve(&database, &analytics, index, raw).await
+            }
+            .instrument(tracing::Span::current()),
+        );
+    }
+
+    // A row whose task panicked keeps the placeholder
+    let mut results: Vec<BatchInfoResult> = (0..count)
+        .map(|index| {
+            let error = AppError::InternalError {
+                message: "lookup failed".to_string(),
+            };
+            BatchInfoResult::failed(index, &error, Language::En)
+        })
+        .collect();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => {
+                let index = result.index;
+                results[index] = result;
+            }
+            Err(e) => log_error!("Batch lookup task failed: {}", e),
+        }
+    }
+
+    Ok(JsonResponse(results))
+}
+
+#[tracing::instrument(name = "batch::row", skip_all, fields(batch.row = index))]
+async fn resolve(
+    database: &PostgresService,
+    analytics: &AnalyticsRecorder,
+    index: usize,
+    raw: Value,
+) -> BatchInfoResult {
+    let language = row_language(&raw);
+    let outcome = match serde_json::from_value::<BatchInfoItem>(raw) {
+        Ok(item) => lookup(database, analytics, item).await,
+        Err(e) => Err(AppError::BadRequest(e.to_string())),
+    };
+
+    match outcome {
+        Ok(data) => BatchInfoResult::found(index, data),
+        Err(e) => BatchInfoResult::failed(index, &e, language),
+    }
+}
+
+/// Same lookup and analytics as the single-row handlers
+async fn lookup(
+    database: &PostgresService,
+    analytics: &AnalyticsRecorder,
+    item: BatchInfoItem,
+) -> Result<Value, AppError> {
+    let (data, usage) = match item {
+        BatchInfoItem::Metric(params) => (metric::v1::info::handler::lookup(database, &params).await?, None),
+        BatchInfoItem::Imperial(request) => {
+            let (response, designation) = imperial::v2::info::handler::lookup(database, &request).await?;
+            let usage = ThreadUsage::new(designation, ThreadStandard::Imperial, request.language);
+            (to_value(response)?, Some(usage))
+        }
+        BatchInfoItem::Trapezoidal(params) => {
+            let (response, designation) = trapezoidal::v1::info::handler::lookup(database, &params).await?;
+            let usage = ThreadUsage::new(designation, ThreadStandard::Trapezoidal, params.language);
+            (to_value(response)?, Some(usage))
+        }
+        BatchInfoItem::Pipe(request) => {
+            let response = pipe::v1::info::handler::lookup(database, &request).await?;
+            let usage = ThreadUsage::new(response.designation1.clone(), ThreadStandard::Pipe, request.language);
+            (to_value(response)?, Some(usage))
+        }
+    };
+
+    if let Some(usage) = usage {
+        analytics.record(usage);
+    }
+    Ok(data)
+}
+
+fn to_value(response: impl serde::Serialize) -> Result<Value, AppError> {
+    serde_json::to_value(response).map_err(|e| AppError::InternalError { message: e.to_string() })
+}
+
+/// Errors of a row follow its own `language`, even when the row itself is malformed
+fn row_language(raw: &Value) -> Language {
+    match raw.get("language").and_then(Value::as_str) {
+        Some(code) if code.eq_ignore_ascii_case("ru") => Language::Ru,
+        _ => Language::En,
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::features::imperial::v2::info::models::request::RequestV2ImperialInfo;
+use crate::features::metric::v1::info::handler::InfoParams;
+use crate::features::pipe::v1::info::models::request_pipe_info::RequestPipeInfo;
+use crate::features::trapezoidal::v1::info::models::request::RequestTrapezoidalInfo;
+use crate::shared::enums::Language;
+use crate::shared::error::{AppError, ErrorResponse};
+
+/// One row of a batch: the `/info` parameters of the chosen standard
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(tag = "standard", rename_all = "lowercase")]
+pub enum BatchInfoItem {
+    /// Parameters of `/v1/metric/info`
+    Metric(InfoParams),
+    /// Parameters of `/v2/imperial/info`
+    Imperial(RequestV2ImperialInfo),
+    /// Parameters of `/v1/trapezoidal/info`
+    Trapezoidal(RequestTrapezoidalInfo),
+    /// Parameters of `/v1/pipe/info`
+    Pipe(RequestPipeInfo),
+}
+
+/// Result of a batch row: `data` with status 200, `error` otherwise
+#[derive(Serialize, Debug, ToSchema)]
+pub struct BatchInfoResult {
+    /// Position of the row in the request
+    pub index: usize,
+    /// HTTP status the single-row request would return
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+impl BatchInfoResult {
+    pub fn found(index: usize, data: Value) -> Self {
+        Self {
+            index,
+            status: 200,
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn failed(index: usize, error: &AppError, language: Language) -> Self {
+        Self {
+            index,
+            status: error.status_code().as_u16(),
+            data: None,
+            error: Some(error.envelope(language)),
+        }
+    }
+}
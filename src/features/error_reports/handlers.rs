@@ -1,47 +1,338 @@
-use crate::{log_error, log_info};
+use crate::{log_error, log_info, log_warn};
 use axum::{
-    extract::{Json, State},
-    http::HeaderMap,
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Extension, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json as JsonResponse,
 };
-use serde_json::Value;
-use sqlx::PgPool;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
-use crate::{features::error_reports::models::ErrorReport, shared::utils::http::get_client_ip_from_headers};
+use crate::features::error_reports::models::{
+    ErrorReport, ErrorReportGroup, ErrorReportItem, ErrorReportPayload, ErrorReportQuery, ExportFormat, ExportParams,
+    ResolveRequest, ResolveResponse,
+};
+use crate::services::rate_limit::KeyedRateLimiter;
+use crate::shared::database::repositories::ErrorReportFilter;
+use crate::shared::error::AppError;
+use crate::shared::middleware::ClientIp;
+use crate::shared::{
+    database::service::PostgresService,
+    extract::{Json, Query},
+};
+
+const DEFAULT_LIMIT: usize = 100;
+/// Also the number of reports searched for a fingerprint
+const MAX_LIMIT: usize = 10_000;
+
+/// State of `POST /v1/error_reports/`
+#[derive(Clone)]
+pub struct ErrorReportsState {
+    pub database: Arc<PostgresService>,
+    /// Submissions per client IP
+    pub limiter: Arc<KeyedRateLimiter>,
+}
 
 #[axum::debug_handler]
-pub async fn create_error_report(
-    State(pool): State<PgPool>,
-    headers: HeaderMap,
-    Json(payload): Json<Value>,
-) -> impl IntoResponse {
-    let client_ip = get_client_ip_from_headers(&headers);
-
-    log_info!("Received error report from {}: {}", client_ip, payload);
-
-    let result = sqlx::query_as!(
-        ErrorReport,
-        r#"
-        INSERT INTO analytics.error_reports (json_data, client_ip)
-        VALUES ($1, $2)
-        RETURNING id, timestamp, json_data, client_ip
-        "#,
-        payload,
-        client_ip
+#[utoipa::path(
+    post,
+    path = "/v1/error_reports/",
+    tag = "system",
+    request_body = ErrorReportPayload,
+    responses(
+        (status = 204, description = "Report stored"),
+        (status = 400, description = "Body is not a valid report"),
+        (status = 413, description = "Body is larger than the configured limit"),
+        (status = 429, description = "Too many reports from this IP, see Retry-After"),
+        (status = 500, description = "Internal server error"),
     )
-    .fetch_one(&pool)
-    .await;
+)]
+pub async fn create_error_report(
+    State(state): State<ErrorReportsState>,
+    client_ip: Option<Extension<ClientIp>>,
+    Json(payload): Json<ErrorReportPayload>,
+) -> Result<StatusCode, AppError> {
+    let client_ip = client_ip.map_or_else(|| "unknown".to_string(), |Extension(ClientIp(client_ip))| client_ip);
+    if let Err(wait) = state.limiter.check(&client_ip) {
+        log_warn!("Error report from {} rejected by the rate limit", client_ip);
+        return Err(AppError::TooManyRequests {
+            retry_after: wait.as_secs_f64().ceil() as u64,
+        });
+    }
+    payload.validate()?;
 
-    match result {
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
-        Err(e) => {
-            log_error!("Failed to insert error report: {}", e);
+    log_info!("Received error report from {}: {}", client_ip, payload.message);
+    let json_data = serde_json::to_value(&payload).map_err(|e| AppError::InternalError { message: e.to_string() })?;
+    state
+        .database
+        .repository_analytics
+        .insert_error_report(json_data, &client_ip)
+        .await
+        .inspect_err(|e| log_error!("Failed to insert error report: {}", e))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reports matching the query, newest first
+async fn find_reports(database: &PostgresService, query: &ErrorReportQuery) -> Result<Vec<ErrorReportItem>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let filter = ErrorReportFilter {
+        from: query.from,
+        to: query.to,
+        app_version: query.app_version.clone(),
+        message: query.message.clone(),
+        resolved: query.resolved,
+        // The fingerprint is not stored, so it is matched among the newest reports
+        limit: if query.fingerprint.is_some() { MAX_LIMIT } else { limit },
+    };
+    let reports = database.repository_analytics.error_reports(&filter).await?;
+
+    Ok(reports
+        .into_iter()
+        .map(|report| ErrorReportItem {
+            fingerprint: report.fingerprint(),
+            report,
+        })
+        .filter(|item| {
+            query
+                .fingerprint
+                .as_deref()
+                .is_none_or(|wanted| item.fingerprint == wanted)
+        })
+        .take(limit)
+        .collect())
+}
+
+/// Groups reports by fingerprint, the most recently seen group first
+pub fn group_reports(items: &[ErrorReportItem]) -> Vec<ErrorReportGroup> {
+    let mut groups: HashMap<&str, (ErrorReportGroup, BTreeSet<String>)> = HashMap::new();
+    for ErrorReportItem { report, fingerprint } in items {
+        let (group, versions) = groups.entry(fingerprint).or_insert_with(|| {
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": "Не удалось сохранить отчет об ошибке"})),
+                ErrorReportGroup {
+                    fingerprint: fingerprint.clone(),
+                    message: report.message().to_string(),
+                    count: 0,
+                    open: 0,
+                    first_seen: report.timestamp,
+                    last_seen: report.timestamp,
+                    app_versions: Vec::new(),
+                    latest_id: report.id,
+                },
+                BTreeSet::new(),
             )
-                .into_response()
+        });
+        group.count += 1;
+        if report.resolved_at.is_none() {
+            group.open += 1;
+        }
+        group.first_seen = group.first_seen.min(report.timestamp).or(report.timestamp);
+        group.last_seen = group.last_seen.max(report.timestamp);
+        if report.id > group.latest_id {
+            group.latest_id = report.id;
+            group.message = report.message().to_string();
         }
+        versions.extend(report.app_version().map(str::to_string));
+    }
+
+    let mut groups: Vec<ErrorReportGroup> = groups
+        .into_values()
+        .map(|(mut group, versions)| {
+            group.app_versions = versions.into_iter().collect();
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.last_seen
+            .cmp(&a.last_seen)
+            .then_with(|| b.count.cmp(&a.count))
+            .then_with(|| a.fingerprint.cmp(&b.fingerprint))
+    });
+    groups
+}
+
+/// Stored error reports, newest first
+#[utoipa::path(
+    get,
+    path = "/admin/error-reports",
+    tag = "error_reports",
+    params(ErrorReportQuery),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Reports with their fingerprints", body = [ErrorReportItem]),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn list(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(query): Query<ErrorReportQuery>,
+) -> Result<JsonResponse<Vec<ErrorReportItem>>, AppError> {
+    Ok(JsonResponse(find_reports(&database, &query).await?))
+}
+
+/// Duplicate reports grouped by fingerprint
+#[utoipa::path(
+    get,
+    path = "/admin/error-reports/groups",
+    tag = "error_reports",
+    params(ErrorReportQuery),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Groups of the matching reports, most recently seen first", body = [ErrorReportGroup]),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn groups(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(query): Query<ErrorReportQuery>,
+) -> Result<JsonResponse<Vec<ErrorReportGroup>>, AppError> {
+    let items = find_reports(&database, &query).await?;
+    Ok(JsonResponse(group_reports(&items)))
+}
+
+/// Marks reports as resolved, or reopens them with `"resolved": false`
+#[utoipa::path(
+    post,
+    path = "/admin/error-reports/resolve",
+    tag = "error_reports",
+    request_body = ResolveRequest,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Number of reports changed", body = ResolveResponse),
+        (status = 400, description = "Neither ids nor a fingerprint given"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn resolve(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Json(request): Json<ResolveRequest>,
+) -> Result<JsonResponse<ResolveResponse>, AppError> {
+    if request.ids.is_empty() && request.fingerprint.is_none() {
+        return Err(AppError::BadRequest("Give 'ids', 'fingerprint' or both".to_string()));
+    }
+
+    let mut ids = request.ids;
+    if let Some(fingerprint) = request.fingerprint {
+        let query = ErrorReportQuery {
+            from: None,
+            to: None,
+            app_version: None,
+            message: None,
+            resolved: Some(!request.resolved),
+            fingerprint: Some(fingerprint),
+            limit: Some(MAX_LIMIT),
+        };
+        ids.extend(find_reports(&database, &query).await?.iter().map(|item| item.report.id));
+    }
+    ids.sort_unstable();
+    ids.dedup();
+
+    let updated = database
+        .repository_analytics
+        .set_error_reports_resolved(&ids, request.resolved)
+        .await?;
+    log_info!(
+        "{} error report(s) marked as {}",
+        updated,
+        if request.resolved { "resolved" } else { "open" }
+    );
+    Ok(JsonResponse(ResolveResponse { updated }))
+}
+
+/// Matching reports as a JSON or CSV file
+#[utoipa::path(
+    get,
+    path = "/admin/error-reports/export",
+    tag = "error_reports",
+    params(ErrorReportQuery, ExportParams),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Reports file", content(
+            ([ErrorReportItem] = "application/json"),
+            (String = "text/csv"),
+        )),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn export(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(query): Query<ErrorReportQuery>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, AppError> {
+    let items = find_reports(&database, &query).await?;
+    let format = params.format.unwrap_or_default();
+    let (content, content_type, extension) = match format {
+        ExportFormat::Json => (
+            serde_json::to_vec_pretty(&items).map_err(|e| AppError::InternalError { message: e.to_string() })?,
+            "application/json",
+            "json",
+        ),
+        ExportFormat::Csv => (to_csv(&items)?, "text/csv; charset=utf-8", "csv"),
+    };
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"error_reports.{}\"", extension),
+            ),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+fn to_csv(items: &[ErrorReportItem]) -> Result<Vec<u8>, AppError> {
+    let csv_error = |e: csv::Error| AppError::InternalError { message: e.to_string() };
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            "id",
+            "timestamp",
+            "client_ip",
+            "fingerprint",
+            "resolved_at",
+            "message",
+            "app_version",
+            "platform",
+            "json_data",
+        ])
+        .map_err(csv_error)?;
+    for ErrorReportItem { report, fingerprint } in items {
+        let ErrorReport {
+            id,
+            timestamp,
+            json_data,
+            client_ip,
+            resolved_at,
+        } = report;
+        writer
+            .write_record([
+                id.to_string(),
+                timestamp.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                client_ip.clone(),
+                fingerprint.clone(),
+                resolved_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                report.message().to_string(),
+                report.app_version().unwrap_or_default().to_string(),
+                json_data
+                    .get("platform")
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                json_data.to_string(),
+            ])
+            .map_err(csv_error)?;
     }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::InternalError { message: e.to_string() })
 }
@@ -1,11 +1,204 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+use crate::shared::error::AppError;
+
+const MAX_MESSAGE_CHARS: usize = 2000;
+const MAX_LABEL_CHARS: usize = 64;
+const MAX_STACK_TRACE_CHARS: usize = 16_000;
+
+/// Stored report
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct ErrorReport {
     pub id: i32,
     pub timestamp: Option<DateTime<Utc>>,
+    /// The submitted `ErrorReportPayload`; older reports may have any shape
+    #[schema(value_type = Object)]
     pub json_data: Value,
     pub client_ip: String,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ErrorReport {
+    pub fn message(&self) -> &str {
+        self.json_data
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    pub fn app_version(&self) -> Option<&str> {
+        self.json_data.get("app_version").and_then(Value::as_str)
+    }
+
+    /// Reports of the same failure share the fingerprint: the message and the top
+    /// stack frame with numbers, case and spacing ignored
+    pub fn fingerprint(&self) -> String {
+        let top_frame = self
+            .json_data
+            .get("stack_trace")
+            .and_then(Value::as_str)
+            .and_then(|trace| trace.lines().map(str::trim).find(|line| !line.is_empty()))
+            .unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        hasher.update(normalize(self.message()).as_bytes());
+        hasher.update([0]);
+        hasher.update(normalize(top_frame).as_bytes());
+        hasher.finalize()[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// "Timeout after 3012 ms" and "timeout after 2870 ms" are the same failure
+fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut previous = ' ';
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let c = if c.is_ascii_digit() {
+            '#'
+        } else if c.is_whitespace() {
+            ' '
+        } else {
+            c
+        };
+        if !(c == previous && (c == '#' || c == ' ')) {
+            normalized.push(c);
+        }
+        previous = c;
+    }
+    normalized.trim().to_string()
+}
+
+/// Body of `POST /v1/error_reports/`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorReportPayload {
+    /// Error text, up to 2000 characters
+    pub message: String,
+    /// Client version, e.g. "2.4.1"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    /// Client platform, e.g. "android", "ios", "web"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_trace: Option<String>,
+    /// Any other client data as a JSON object: screen, request parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub context: Option<Value>,
+}
+
+impl ErrorReportPayload {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.message.trim().is_empty() {
+            return Err(AppError::ValidationError("'message' must not be empty".to_string()));
+        }
+        check_length("message", Some(&self.message), MAX_MESSAGE_CHARS)?;
+        check_length("app_version", self.app_version.as_deref(), MAX_LABEL_CHARS)?;
+        check_length("platform", self.platform.as_deref(), MAX_LABEL_CHARS)?;
+        check_length("stack_trace", self.stack_trace.as_deref(), MAX_STACK_TRACE_CHARS)?;
+        if self.context.as_ref().is_some_and(|context| !context.is_object()) {
+            return Err(AppError::ValidationError("'context' must be a JSON object".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn check_length(field: &str, value: Option<&str>, max: usize) -> Result<(), AppError> {
+    match value {
+        Some(value) if value.chars().count() > max => Err(AppError::ValidationError(format!(
+            "'{}' is longer than {} characters",
+            field, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Filters of the triage routes
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ErrorReportQuery {
+    /// Received at or after, RFC 3339
+    pub from: Option<DateTime<Utc>>,
+    /// Received at or before, RFC 3339
+    pub to: Option<DateTime<Utc>>,
+    /// Exact `app_version` of the report
+    pub app_version: Option<String>,
+    /// Part of the message, case-insensitive
+    pub message: Option<String>,
+    /// Only resolved (`true`) or open (`false`) reports
+    pub resolved: Option<bool>,
+    /// Only reports with this fingerprint
+    pub fingerprint: Option<String>,
+    /// Newest reports taken, by default 100, at most 10000
+    pub limit: Option<usize>,
+}
+
+/// Stored report with its fingerprint
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorReportItem {
+    #[serde(flatten)]
+    pub report: ErrorReport,
+    pub fingerprint: String,
+}
+
+/// Reports sharing a fingerprint
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorReportGroup {
+    pub fingerprint: String,
+    /// Message of the newest report
+    pub message: String,
+    pub count: usize,
+    /// Reports not marked as resolved
+    pub open: usize,
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Distinct `app_version` values, sorted
+    pub app_versions: Vec<String>,
+    pub latest_id: i32,
+}
+
+/// Reports to mark: by id, by fingerprint or both
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveRequest {
+    #[serde(default)]
+    pub ids: Vec<i32>,
+    /// Every stored report with this fingerprint
+    pub fingerprint: Option<String>,
+    /// `false` reopens the reports
+    #[serde(default = "default_resolved")]
+    pub resolved: bool,
+}
+
+fn default_resolved() -> bool {
+    true
+}
+
+/// Number of reports changed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResolveResponse {
+    pub updated: u64,
+}
+
+/// File format of the export
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    /// `json` (default) or `csv`
+    pub format: Option<ExportFormat>,
 }
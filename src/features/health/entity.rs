@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::analytics::pipeline::AnalyticsStats;
+use crate::services::cache::CacheStats;
+use crate::services::rate_limit::RateLimitStats;
 
 /// Статус здоровья системы
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum HealthStatus {
     Healthy,
     Degraded,
@@ -20,22 +25,57 @@ impl std::fmt::Display for HealthStatus {
 }
 
 /// Информация о компоненте системы
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ComponentHealth {
     pub name: String,
     pub status: HealthStatus,
     pub message: Option<String>,
     pub response_time: Option<u64>, // в миллисекундах
+    /// Подробности проверки, например число строк по стандартам
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Object)]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ComponentHealth {
+    pub fn new(name: &str, status: HealthStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: Some(message.into()),
+            response_time: None,
+            details: None,
+        }
+    }
+
+    pub fn with_response_time(mut self, started: std::time::Instant) -> Self {
+        self.response_time = Some(started.elapsed().as_millis() as u64);
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
 }
 
 /// Сущность проверки здоровья
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Health {
     pub status: HealthStatus,
     pub timestamp: DateTime<Utc>,
     pub uptime: u64, // в секундах
     pub version: String,
     pub components: Vec<ComponentHealth>,
+    /// Статистика кэша ответов
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheStats>,
+    /// Счётчики очереди аналитики: принятые, отброшенные и записанные обращения
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analytics: Option<AnalyticsStats>,
+    /// Пропущенные и отклонённые запросы по правилам ограничения частоты
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<Vec<RateLimitStats>>,
 }
 
 impl Health {
@@ -46,6 +86,9 @@ impl Health {
             uptime,
             version,
             components,
+            cache: None,
+            analytics: None,
+            rate_limits: None,
         }
     }
 
@@ -1,15 +1,64 @@
 use axum::{
+    extract::Extension,
     http::StatusCode,
     response::{IntoResponse, Json as JsonResponse, Response},
 };
 use std::sync::Arc;
 
-use super::{entity::HealthStatus, service::HealthService};
+use crate::shared::setting::models::app_state::AppState;
+
+use super::{
+    entity::{Health, HealthStatus},
+    service::HealthService,
+};
+
+/// GET /health - состояние сервиса, базы данных и кэша
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "system",
+    responses(
+        (status = 200, description = "Healthy or degraded", body = Health),
+        (status = 503, description = "Unhealthy", body = Health),
+    )
+)]
+pub async fn health(Extension(app_state): Extension<Arc<AppState>>) -> Response {
+    app_state.health_handler.get_health().await
+}
+
+/// GET /health/live - liveness-проба: процесс отвечает на запросы
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "system",
+    responses(
+        (status = 200, description = "The process is serving requests", body = Health),
+    )
+)]
+pub async fn live(Extension(app_state): Extension<Arc<AppState>>) -> Response {
+    app_state.health_handler.get_liveness().await
+}
+
+/// GET /health/ready - readiness-проба: база, миграции, справочные данные и шаблоны SVG
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "system",
+    responses(
+        (status = 200, description = "Ready for traffic, possibly degraded", body = Health),
+        (status = 503, description = "Not ready: a dependency is unhealthy", body = Health),
+    )
+)]
+pub async fn ready(Extension(app_state): Extension<Arc<AppState>>) -> Response {
+    app_state.health_handler.get_readiness().await
+}
 
 /// Трейт обработчика health проверок
 #[async_trait::async_trait]
 pub trait HealthHandler: Send + Sync {
     async fn get_health(&self) -> Response;
+    async fn get_liveness(&self) -> Response;
+    async fn get_readiness(&self) -> Response;
 }
 
 /// Реализация обработчика health v1
@@ -30,15 +79,25 @@ impl HealthHandlerV1 {
             HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE, // 503
         }
     }
+
+    fn respond(health: Health) -> Response {
+        let status_code = Self::status_to_http_code(&health.status);
+        (status_code, JsonResponse(health)).into_response()
+    }
 }
 
 #[async_trait::async_trait]
 impl HealthHandler for HealthHandlerV1 {
     /// GET /health - полная проверка здоровья
     async fn get_health(&self) -> Response {
-        let health = self.service.get_health().await;
-        let status_code = Self::status_to_http_code(&health.status);
+        Self::respond(self.service.get_health().await)
+    }
 
-        (status_code, JsonResponse(health)).into_response()
+    async fn get_liveness(&self) -> Response {
+        Self::respond(self.service.liveness().await)
+    }
+
+    async fn get_readiness(&self) -> Response {
+        Self::respond(self.service.readiness().await)
     }
 }
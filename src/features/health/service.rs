@@ -1,8 +1,21 @@
+use serde_json::{json, Value};
 use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::time::{timeout, Duration};
 
+use crate::analytics::pipeline::AnalyticsRecorder;
+use crate::services::cache::ResponseCache;
+use crate::services::rate_limit::RateLimits;
+use crate::services::shutdown::Drain;
+use crate::services::svg::{
+    svg_service::SvgService,
+    templates::{self, TEMPLATE_DIR},
+};
+use crate::shared::database::{migrations::check_pending_migrations, sqlite::SqliteConnection};
+use crate::shared::enums::{Theme, ThreadStandard, ThreadType};
 use crate::shared::setting::models::app_setting::AppSettings;
 
 use super::entity::{ComponentHealth, Health, HealthStatus};
@@ -10,32 +23,141 @@ use super::entity::{ComponentHealth, Health, HealthStatus};
 /// Трейт сервиса проверки здоровья
 #[async_trait::async_trait]
 pub trait HealthService: Send + Sync {
+    /// Готовность и статистика сервисов, для людей и мониторинга
     async fn get_health(&self) -> Health;
+    /// Процесс отвечает; зависимости не проверяются, чтобы сбой базы не перезапускал поды
+    async fn liveness(&self) -> Health;
+    /// Можно ли направлять трафик: база, миграции, справочные данные и шаблоны SVG
+    async fn readiness(&self) -> Health;
 }
 
+/// Стандарты и их основные справочные таблицы
+const REFERENCE_TABLES: [(&str, &str); 4] = [
+    ("metric", "metric.main"),
+    ("imperial", "imperial.main"),
+    ("trapezoidal", "trapezoidal.main"),
+    ("pipe", "pipe.main"),
+];
+/// Стандарты, чертежи которых рисуются по шаблонам `static/svg`
+const TEMPLATE_STANDARDS: [ThreadStandard; 3] = [
+    ThreadStandard::Metric,
+    ThreadStandard::Imperial,
+    ThreadStandard::Trapezoidal,
+];
+const CHECK_TIMEOUT: Duration = Duration::from_millis(5000);
+
 /// Реализация сервиса проверки здоровья
 pub struct HealthServiceImpl {
     settings: Arc<AppSettings>,
-    pool: PgPool,
+    pool: Option<PgPool>,
+    sqlite: Option<Arc<SqliteConnection>>,
+    bundled_rows: Option<BTreeMap<String, usize>>,
+    cache: Option<Arc<ResponseCache>>,
+    analytics: Option<AnalyticsRecorder>,
+    rate_limits: Option<Arc<RateLimits>>,
+    template_dir: PathBuf,
+    drain: Drain,
     start_time: Instant,
 }
 
 impl HealthServiceImpl {
-    pub fn new(settings: Arc<AppSettings>, pool: PgPool) -> Self {
+    pub fn new(settings: Arc<AppSettings>, pool: Option<PgPool>) -> Self {
         Self {
             settings,
             pool,
+            sqlite: None,
+            bundled_rows: None,
+            cache: None,
+            analytics: None,
+            rate_limits: None,
+            template_dir: PathBuf::from(TEMPLATE_DIR),
+            drain: Drain::new(),
             start_time: Instant::now(),
         }
     }
 
+    /// Проверять файл SQLite вместо PostgreSQL
+    pub fn with_sqlite(mut self, sqlite: Option<Arc<SqliteConnection>>) -> Self {
+        self.sqlite = sqlite;
+        self
+    }
+
+    /// Строки встроенных таблиц, если сервис работает без базы
+    pub fn with_bundled_rows(mut self, rows: Option<BTreeMap<String, usize>>) -> Self {
+        self.bundled_rows = rows;
+        self
+    }
+
+    /// Добавлять в ответ статистику кэша
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Добавлять в ответ счётчики очереди аналитики
+    pub fn with_analytics(mut self, analytics: AnalyticsRecorder) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
+
+    /// Добавлять в ответ счётчики ограничения частоты, если заданы правила
+    pub fn with_rate_limits(mut self, rate_limits: Arc<RateLimits>) -> Self {
+        self.rate_limits = Some(rate_limits).filter(|rate_limits| !rate_limits.is_empty());
+        self
+    }
+
+    /// Флаг остановки: пока идет дренаж соединений, readiness отвечает 503
+    pub fn with_drain(mut self, drain: Drain) -> Self {
+        self.drain = drain;
+        self
+    }
+
+    /// Искать шаблоны SVG в другом каталоге
+    pub fn with_template_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.template_dir = dir.into();
+        self
+    }
+
+    /// Проверяет файл SQLite
+    async fn check_sqlite(&self, sqlite: &SqliteConnection) -> ComponentHealth {
+        let start = Instant::now();
+        let result = timeout(Duration::from_millis(5000), sqlite.health_check()).await;
+        let response_time = start.elapsed().as_millis() as u64;
+
+        let (status, message) = match result {
+            Ok(Ok(_)) => (HealthStatus::Healthy, "Connected to SQLite".to_string()),
+            Ok(Err(e)) => (HealthStatus::Unhealthy, format!("Database error: {}", e)),
+            Err(_) => (HealthStatus::Unhealthy, "Database connection timeout".to_string()),
+        };
+        ComponentHealth {
+            name: "database".to_string(),
+            status,
+            message: Some(message),
+            response_time: Some(response_time),
+            details: None,
+        }
+    }
+
     /// Проверяет подключение к базе данных
     async fn check_database(&self) -> ComponentHealth {
+        let Some(pool) = &self.pool else {
+            if let Some(sqlite) = &self.sqlite {
+                return self.check_sqlite(sqlite).await;
+            }
+            return ComponentHealth {
+                name: "database".to_string(),
+                status: HealthStatus::Healthy,
+                message: Some("Using bundled in-memory reference data".to_string()),
+                response_time: Some(0),
+                details: None,
+            };
+        };
+
         let start = Instant::now();
 
         let result = timeout(
             Duration::from_millis(5000), // 5 секунд таймаут
-            sqlx::query("SELECT 1").execute(&self.pool),
+            sqlx::query("SELECT 1").execute(pool),
         )
         .await;
 
@@ -51,22 +173,169 @@ impl HealthServiceImpl {
                 },
                 message: Some("Connected to PostgreSQL".to_string()),
                 response_time: Some(response_time),
+                details: None,
             },
             Ok(Err(e)) => ComponentHealth {
                 name: "database".to_string(),
                 status: HealthStatus::Unhealthy,
                 message: Some(format!("Database error: {}", e)),
                 response_time: Some(response_time),
+                details: None,
             },
             Err(_) => ComponentHealth {
                 name: "database".to_string(),
                 status: HealthStatus::Unhealthy,
                 message: Some("Database connection timeout".to_string()),
                 response_time: Some(response_time),
+                details: None,
             },
         }
     }
 
+    /// Миграции из `./migrations`, не применённые к PostgreSQL: код ждёт схему, которой ещё нет
+    async fn check_migrations(&self, pool: &PgPool) -> ComponentHealth {
+        let start = Instant::now();
+        let component = match timeout(CHECK_TIMEOUT, check_pending_migrations(pool)).await {
+            Ok(Ok(pending)) if pending.is_empty() => {
+                ComponentHealth::new("migrations", HealthStatus::Healthy, "All migrations applied")
+            }
+            Ok(Ok(pending)) => ComponentHealth::new(
+                "migrations",
+                HealthStatus::Unhealthy,
+                format!("{} pending migration(s)", pending.len()),
+            )
+            .with_details(json!(pending
+                .iter()
+                .map(|(version, description)| format!("{} {}", version, description))
+                .collect::<Vec<_>>())),
+            Ok(Err(e)) => ComponentHealth::new(
+                "migrations",
+                HealthStatus::Unhealthy,
+                format!("Migration check failed: {}", e),
+            ),
+            Err(_) => ComponentHealth::new("migrations", HealthStatus::Unhealthy, "Migration check timeout"),
+        };
+        component.with_response_time(start)
+    }
+
+    /// Число строк основной таблицы каждого стандарта. Пустой стандарт отвечает 404
+    /// на все запросы, поэтому это деградация, а без данных совсем сервис не готов
+    async fn check_reference_data(&self) -> Option<ComponentHealth> {
+        let start = Instant::now();
+        let mut counts = serde_json::Map::new();
+        for (standard, table) in REFERENCE_TABLES {
+            let count = if let Some(pool) = &self.pool {
+                let query = format!("SELECT COUNT(*) FROM {}", table);
+                timeout(CHECK_TIMEOUT, sqlx::query_scalar::<_, i64>(&query).fetch_one(pool)).await
+            } else if let Some(sqlite) = &self.sqlite {
+                let query = format!("SELECT COUNT(*) FROM {}", table.replace('.', "_"));
+                timeout(
+                    CHECK_TIMEOUT,
+                    sqlx::query_scalar::<_, i64>(&query).fetch_one(sqlite.pool()),
+                )
+                .await
+            } else if let Some(rows) = &self.bundled_rows {
+                Ok(Ok(rows.get(table).copied().unwrap_or_default() as i64))
+            } else {
+                return None;
+            };
+            let count = match count {
+                Ok(Ok(count)) => count,
+                Ok(Err(e)) => {
+                    let message = format!("Failed to count {}: {}", table, e);
+                    let component = ComponentHealth::new("reference_data", HealthStatus::Unhealthy, message);
+                    return Some(component.with_response_time(start));
+                }
+                Err(_) => {
+                    let message = format!("Counting {} timed out", table);
+                    let component = ComponentHealth::new("reference_data", HealthStatus::Unhealthy, message);
+                    return Some(component.with_response_time(start));
+                }
+            };
+            counts.insert(standard.to_string(), count.into());
+        }
+
+        let empty: Vec<&str> = counts
+            .iter()
+            .filter(|(_, count)| count.as_i64() == Some(0))
+            .map(|(standard, _)| standard.as_str())
+            .collect();
+        let (status, message) = if empty.len() == counts.len() {
+            (HealthStatus::Unhealthy, "No reference data loaded".to_string())
+        } else if !empty.is_empty() {
+            (HealthStatus::Degraded, format!("No rows for {}", empty.join(", ")))
+        } else {
+            (HealthStatus::Healthy, "Reference data loaded".to_string())
+        };
+        Some(
+            ComponentHealth::new("reference_data", status, message)
+                .with_details(Value::Object(counts))
+                .with_response_time(start),
+        )
+    }
+
+    /// Шаблоны чертежей всех стандартов, типов резьбы и тем
+    async fn check_templates(&self) -> ComponentHealth {
+        let start = Instant::now();
+        let mut expected = 0;
+        let mut missing = Vec::new();
+        for standard in TEMPLATE_STANDARDS {
+            for thread_type in [ThreadType::Male, ThreadType::Female] {
+                for theme in [Theme::Light, Theme::Dark] {
+                    expected += 1;
+                    let file_name = SvgService::template_file_name(standard, thread_type, theme);
+                    // Загруженный однажды шаблон берётся из памяти
+                    if templates::load(self.template_dir.join(&file_name)).await.is_err() {
+                        missing.push(file_name);
+                    }
+                }
+            }
+        }
+
+        let (status, message) = if missing.len() == expected {
+            (
+                HealthStatus::Unhealthy,
+                format!("No SVG templates in {}", self.template_dir.display()),
+            )
+        } else if !missing.is_empty() {
+            (
+                HealthStatus::Degraded,
+                format!("{} of {} SVG templates missing", missing.len(), expected),
+            )
+        } else {
+            (HealthStatus::Healthy, format!("{} SVG templates available", expected))
+        };
+        let component = ComponentHealth::new("svg_templates", status, message).with_response_time(start);
+        if missing.is_empty() {
+            component
+        } else {
+            component.with_details(json!(missing))
+        }
+    }
+
+    /// Компоненты, от которых зависит обработка запросов
+    async fn check_components(&self) -> Vec<ComponentHealth> {
+        // При остановке новые запросы уже не нужны, проверять остальное незачем
+        if self.drain.is_draining() {
+            return vec![ComponentHealth::new(
+                "shutdown",
+                HealthStatus::Unhealthy,
+                "Shutting down, draining in-flight requests",
+            )];
+        }
+        let mut components = vec![self.check_database().await];
+        // Без базы остальные проверки только повторят её ошибку
+        if components[0].status == HealthStatus::Unhealthy {
+            return components;
+        }
+        if let Some(pool) = &self.pool {
+            components.push(self.check_migrations(pool).await);
+        }
+        components.extend(self.check_reference_data().await);
+        components.push(self.check_templates().await);
+        components
+    }
+
     /// Получает время работы приложения
     fn get_uptime(&self) -> u64 {
         self.start_time.elapsed().as_secs()
@@ -82,19 +351,20 @@ impl HealthServiceImpl {
 impl HealthService for HealthServiceImpl {
     /// Полная проверка здоровья со всеми компонентами
     async fn get_health(&self) -> Health {
-        let mut components = vec![];
-
-        // Проверяем базу данных
-        components.push(self.check_database().await);
+        let mut health = self.readiness().await;
+        health.cache = self.cache.as_ref().map(|cache| cache.stats());
+        health.analytics = self.analytics.as_ref().map(|analytics| analytics.stats());
+        health.rate_limits = self.rate_limits.as_ref().map(|rate_limits| rate_limits.stats());
+        health
+    }
 
-        // Можно добавить другие проверки:
-        // - Redis
-        // - External APIs
-        // - File system
-        // - Memory usage
+    async fn liveness(&self) -> Health {
+        Health::basic(self.get_uptime(), self.get_version())
+    }
 
+    async fn readiness(&self) -> Health {
+        let components = self.check_components().await;
         let mut health = Health::new(HealthStatus::Healthy, self.get_uptime(), self.get_version(), components);
-
         health.calculate_overall_status();
         health
     }
@@ -1,27 +1,22 @@
 use crate::log_error;
 use axum::{extract::Extension, http::StatusCode, response::IntoResponse, response::Response, Json};
 use serde::Serialize;
-use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use utoipa::ToSchema;
 
-/// Модель данных из базы данных
-#[derive(Serialize, FromRow)]
-struct DiameterModelDb {
-    id: i64,
-    diameter: String,
-    diameter_2: f64,
-    tpi: f64,
-    series_designation: String,
-}
+use crate::shared::database::{repositories::imperial::ImperialDiameterRow, service::PostgresService};
 
 /// Вложенная структура для форматированных данных
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = ImperialFormattedValue)]
 struct FormattedSubModel {
     fractional: String,
     decimal: String,
 }
 
 /// Структура для ответа API
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = ImperialDiameter)]
 struct ResponseModel {
     id: i64,
     formatted: FormattedSubModel,
@@ -31,34 +26,36 @@ struct ResponseModel {
 }
 
 /// Обработчик для маршрута `/diameters`
-pub async fn handle(Extension(pool): Extension<PgPool>) -> Response {
-    // Выполнение запроса к базе данных через функцию
-    let db_records = match fetch_diameters(&pool).await {
+#[utoipa::path(
+    get,
+    path = "/v1/imperial/diameters",
+    tag = "imperial",
+    responses(
+        (status = 200, description = "Nominal diameters with series and TPI", body = Vec<ResponseModel>),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Extension(database): Extension<Arc<PostgresService>>) -> Response {
+    // Выполнение запроса к базе данных через репозиторий
+    let db_records = match database.repository_imperial.unique_diameters().await {
         Ok(records) => records,
         Err(e) => {
             log_error!("Ошибка при выполнении запроса к базе данных: {}", e);
-            return internal_server_error();
+            return e.into_response();
         }
     };
 
     // Преобразование записей из базы данных в модель ответа
     let response: Vec<ResponseModel> = db_records.into_iter().map(transform_record).collect();
 
-    // Сортировка уже выполнена в функции базы данных, дополнительная сортировка не нужна
+    // Сортировка уже выполнена в репозитории, дополнительная сортировка не нужна
 
     // Формирование успешного ответа
     (StatusCode::OK, Json(response)).into_response()
 }
 
-/// Функция для вызова PostgreSQL функции и получения данных из базы
-async fn fetch_diameters(pool: &PgPool) -> Result<Vec<DiameterModelDb>, sqlx::Error> {
-    sqlx::query_as::<_, DiameterModelDb>("SELECT * FROM imperial.get_unique_diameters()")
-        .fetch_all(pool)
-        .await
-}
-
 /// Функция для преобразования записи из базы данных в модель ответа
-fn transform_record(record: DiameterModelDb) -> ResponseModel {
+fn transform_record(record: ImperialDiameterRow) -> ResponseModel {
     ResponseModel {
         id: record.id,
         formatted: FormattedSubModel {
@@ -70,12 +67,3 @@ fn transform_record(record: DiameterModelDb) -> ResponseModel {
         tpi: record.tpi.to_string(),
     }
 }
-
-/// Функция для формирования ответа с внутренней ошибкой сервера
-fn internal_server_error() -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({ "error": "Внутренняя ошибка сервера" })),
-    )
-        .into_response()
-}
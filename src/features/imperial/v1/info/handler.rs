@@ -1,41 +1,49 @@
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
-};
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
 
 use crate::log_error;
-use sqlx::PgPool;
+use serde_json::json;
+use std::sync::Arc;
 
 use crate::{
-    analytics::db::handle_thread_analytics,
+    analytics::pipeline::AnalyticsRecorder,
     features::imperial::v1::info::{
         additional_info::additional_thread_info,
         models::{DbModel, ImperialInfoResponse, RequestParams},
     },
-    shared::enums::{Language, ThreadType, Unit},
+    shared::database::{
+        repositories::{required, ThreadUsage},
+        service::PostgresService,
+    },
+    shared::enums::{Language, ThreadStandard, ThreadType, Unit},
+    shared::extract::Query,
     shared::utils::number::NumberFormatter,
 };
 
 /// Обработчик запроса
-pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<RequestParams>) -> impl IntoResponse {
-    let is_male = matches!(params.type_, ThreadType::Male);
-
-    // Build query based on thread type
-    let query = if is_male {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_m = $3"
-    } else {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_f = $3"
-    };
-
-    match sqlx::query_as::<_, DbModel>(query)
-        .bind(&params.diameter)
-        .bind(params.tpi)
-        .bind(&params.series)
-        .fetch_one(&pool)
+#[utoipa::path(
+    get,
+    path = "/v1/imperial/info",
+    tag = "imperial",
+    params(RequestParams),
+    responses(
+        (status = 200, description = "Dimensions and deviations of the thread", body = ImperialInfoResponse),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
+    Query(params): Query<RequestParams>,
+) -> impl IntoResponse {
+    match database
+        .repository_imperial
+        .find_thread(&params.diameter, params.tpi, params.type_, &params.series)
         .await
-    {
+        .and_then(required(
+            || json!({ "diameter": params.diameter, "tpi": params.tpi, "type": params.type_, "series": params.series }),
+        )) {
         Ok(record) => {
             let is_male = matches!(params.type_, ThreadType::Male);
             let record_clone = record.clone();
@@ -48,14 +56,11 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Req
                 record.diameter, record.tpi, record.series_designation, class
             );
 
-            // Clone pool and designation for background task
-            let pool_clone = pool.clone();
-            let designation_clone = designation.clone();
-
-            // Spawn background task for analytics
-            tokio::spawn(async move {
-                handle_thread_analytics(pool_clone, designation_clone).await;
-            });
+            analytics.record(ThreadUsage::new(
+                designation.clone(),
+                ThreadStandard::Imperial,
+                params.language,
+            ));
 
             let tpi = record.tpi;
             let pitch = 1.0 / tpi;
@@ -268,14 +273,7 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Req
         }
         Err(e) => {
             log_error!("Database query error: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": format!("Thread with diameter: {}, TPI: {}, class: {} not found",
-                        params.diameter, params.tpi, params.series)
-                })),
-            )
-                .into_response()
+            e.into_response()
         }
     }
 }
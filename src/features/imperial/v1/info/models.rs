@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::shared::enums::{Language, ThreadType, Unit};
 
+/// Модель данных из базы данных
+pub use crate::shared::database::repositories::imperial::ImperialThreadRow as DbModel;
+
 /// Структура для извлечения параметров запроса
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RequestParams {
     pub diameter: String,
     pub tpi: f64,
@@ -16,34 +20,8 @@ pub struct RequestParams {
     pub precision: Option<usize>,
 }
 
-/// Модель данных из базы данных
-#[derive(Serialize, FromRow, Debug, Clone)]
-pub struct DbModel {
-    pub id: i64,
-    pub diameter: String,
-    pub diameter_2: f64, // Используем как 'd'
-    pub tpi: f64,
-    pub series_designation: String,
-    pub class_m: String,
-    pub class_f: String,
-    pub allowance: f64,
-    pub major_diam_max_m: f64,
-    pub major_diam_min_m: f64,
-    pub major_diam_min2_m: Option<f64>,
-    pub pitch_diameter_max_m: f64,
-    pub pitch_diameter_min_m: f64,
-    pub pitch_diameter_tolerance_m: f64,
-    pub unr_minor_diameter_max_m: f64,
-    pub minor_diameter_min_f: f64,
-    pub minor_diameter_max_f: f64,
-    pub pitch_diameter_min_f: f64,
-    pub pitch_diameter_max_f: f64,
-    pub pitch_diameter_tolerance_f: f64,
-    pub major_diameter_min_f: f64,
-}
-
 /// Структура для представления дополнительной информации о резьбе
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct ThreadInfoItem {
     pub name: String,
     pub value: String,
@@ -52,7 +30,7 @@ pub struct ThreadInfoItem {
 }
 
 /// Модель данных для ответа клиенту
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct ImperialInfoResponse {
     pub id: i64,
     pub fractional_diameter: String,
@@ -1,61 +1,53 @@
 use axum::body::Body;
 
 use crate::log_error;
-use axum::http::{Response, StatusCode};
-use axum::response::IntoResponse;
-use axum::Json;
-use axum::{extract::Query, Extension};
-use sqlx::PgPool;
+use crate::services::svg::export::ExportParams;
+use axum::http::Response;
+use axum::Extension;
+use serde_json::json;
+use std::sync::Arc;
 
 use crate::features::imperial::v1::svg_dimensions::models::RequestSvgDimension;
 use crate::services::svg::enums::{FontFamily, FontWeight, TextAnchor};
+use crate::shared::database::{repositories::required, service::PostgresService};
 use crate::shared::enums::{Language, ThreadStandard, ThreadType, Unit};
 use crate::shared::error::AppError;
+use crate::shared::extract::Query;
 
-use crate::features::imperial::v1::info::models::DbModel;
 use crate::shared::utils::number::NumberFormatter;
 
 use crate::services::svg::{SvgService, SvgText, TextOptionsGenerator};
 
+#[utoipa::path(
+    get,
+    path = "/v1/imperial/svg-dimensions",
+    tag = "drawings",
+    params(RequestSvgDimension, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
 pub async fn handle(
-    Extension(pool): Extension<PgPool>,
+    Extension(database): Extension<Arc<PostgresService>>,
     Query(params): Query<RequestSvgDimension>,
 ) -> Result<Response<Body>, AppError> {
-    let is_male = matches!(params.thread_type, ThreadType::Male);
-
-    // Build query based on thread type
-    let query = if is_male {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_m = $3"
-    } else {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_f = $3"
-    };
-
-    // Fetch thread data from database
-    let thread_data = match sqlx::query_as::<_, DbModel>(query)
-        .bind(&params.diameter)
-        .bind(params.tpi)
-        .bind(&params.tolerance)
-        .fetch_one(&pool)
+    // Fetch thread data from the repository
+    let thread_data = database
+        .repository_imperial
+        .find_thread(&params.diameter, params.tpi, params.thread_type, &params.tolerance)
         .await
-    {
-        Ok(data) => data,
-        Err(e) => {
-            log_error!("Database query error: {}", e);
-            let error_message = if e.to_string().contains("no rows") {
-                "No thread specifications found for the given diameter and TPI"
-            } else {
-                "An error occurred while retrieving thread specifications"
-            };
-            return Ok((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({
-                    "error": error_message,
-                    "details": e.to_string()
-                })),
-            )
-                .into_response());
-        }
-    };
+        .and_then(required(|| {
+            json!({
+                "diameter": params.diameter,
+                "tpi": params.tpi,
+                "thread_type": params.thread_type,
+                "tolerance": params.tolerance,
+            })
+        }))
+        .inspect_err(|e| log_error!("Database query error: {}", e))?;
 
     let svg_service = SvgService::new("./static/svg");
 
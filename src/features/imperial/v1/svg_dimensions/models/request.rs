@@ -1,8 +1,10 @@
 use serde::Deserialize;
+use utoipa::IntoParams;
 
 use crate::shared::enums::{theme::Theme, Language, ThreadType, Unit};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RequestSvgDimension {
     #[serde(rename = "type")]
     pub thread_type: ThreadType,
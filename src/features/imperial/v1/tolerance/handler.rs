@@ -1,41 +1,31 @@
 use crate::log_error;
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-    response::Response,
-    Json,
-};
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, response::Response, Json};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::shared::database::{repositories::imperial::ImperialClassRow, service::PostgresService};
+use crate::shared::extract::Query;
 
 /// Структура для извлечения параметров запроса
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct Params {
     diameter: String,
     tpi: f64,
 }
 
-/// Структура для представления данных из базы данных
-#[derive(FromRow)]
-struct DbModel {
-    id: i64,
-    diameter: String,
-    diameter_2: f64,
-    tpi: f64,
-    class_f: String,
-    class_m: String,
-}
-
 /// Вложенная структура для форматированных данных
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = ImperialFormattedValue)]
 struct FormattedSubModel {
     fractional: String,
     decimal: String,
 }
 
 /// Структура для информации о классе
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = ImperialToleranceInfo)]
 struct ToleranceInfo {
     id: i64,
     series: String,
@@ -43,20 +33,36 @@ struct ToleranceInfo {
 }
 
 /// Структура для ответа API
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = ImperialTolerances)]
 struct ResponseModel {
     female: Vec<ToleranceInfo>,
     male: Vec<ToleranceInfo>,
 }
 
 /// Обработчик для маршрута `/diameters`
-pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Params>) -> Response {
-    // Выполнение запроса к базе данных через функцию
-    let db_models = match fetch_db_models(&pool, &params).await {
+#[utoipa::path(
+    get,
+    path = "/v1/imperial/tolerance",
+    tag = "imperial",
+    params(Params),
+    responses(
+        (status = 200, description = "Classes of the thread by type", body = ResponseModel),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Extension(database): Extension<Arc<PostgresService>>, Query(params): Query<Params>) -> Response {
+    // Выполнение запроса к базе данных через репозиторий
+    let db_models = match database
+        .repository_imperial
+        .diameter_classes(&params.diameter, params.tpi)
+        .await
+    {
         Ok(rows) => rows,
         Err(e) => {
             log_error!("Ошибка при выполнении запроса к базе данных: {}", e);
-            return internal_server_error();
+            return e.into_response();
         }
     };
 
@@ -78,17 +84,8 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Par
     (StatusCode::OK, Json(response)).into_response()
 }
 
-/// Функция для вызова PostgreSQL функции и получения данных из базы
-async fn fetch_db_models(pool: &PgPool, params: &Params) -> Result<Vec<DbModel>, sqlx::Error> {
-    sqlx::query_as::<_, DbModel>("SELECT * FROM imperial.get_diameter_tolerance_data($1, $2)")
-        .bind(&params.diameter)
-        .bind(params.tpi)
-        .fetch_all(pool)
-        .await
-}
-
 /// Вспомогательная функция для создания ToleranceInfo
-fn create_tolerance_info(item: &DbModel, tolerance: &str) -> ToleranceInfo {
+fn create_tolerance_info(item: &ImperialClassRow, tolerance: &str) -> ToleranceInfo {
     let formatted = FormattedSubModel {
         fractional: format!("{} - {} - {}", item.diameter, item.tpi, tolerance),
         decimal: format!("{} - {} - {}", item.diameter_2, item.tpi, tolerance),
@@ -100,12 +97,3 @@ fn create_tolerance_info(item: &DbModel, tolerance: &str) -> ToleranceInfo {
         formatted,
     }
 }
-
-/// Функция для формирования ответа с внутренней ошибкой сервера
-fn internal_server_error() -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({ "error": "Внутренняя ошибка сервера" })),
-    )
-        .into_response()
-}
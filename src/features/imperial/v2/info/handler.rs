@@ -1,72 +1,85 @@
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
-};
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
 
 use crate::log_error;
-use sqlx::PgPool;
+use serde_json::json;
+use std::sync::Arc;
 
 use crate::{
-    analytics::db::handle_thread_analytics, features::imperial::v2::info::mappers::ImperialInfoMapper,
-    shared::enums::ThreadType,
+    analytics::pipeline::AnalyticsRecorder,
+    features::imperial::v2::info::mappers::ImperialInfoMapper,
+    services::cache::{CachedResponse, ResponseCache},
+    shared::database::{
+        repositories::{required, ThreadUsage},
+        service::PostgresService,
+    },
+    shared::enums::ThreadStandard,
+    shared::error::AppError,
+    shared::extract::Query,
 };
 
-use super::models::{db::ModelV2ImperialDB, request::RequestV2ImperialInfo};
-
-const QUERY_IMPERIAL: &str = "
-SELECT * FROM imperial.main WHERE id = $1
-";
+use super::models::{request::RequestV2ImperialInfo, response::ResponseV2ImperialInfo};
 
+#[utoipa::path(
+    get,
+    path = "/v2/imperial/info",
+    tag = "imperial",
+    params(RequestV2ImperialInfo),
+    responses(
+        (status = 200, description = "Dimensions and deviations of the thread", body = ResponseV2ImperialInfo),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
 pub async fn handle(
-    Extension(pool): Extension<PgPool>,
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
     Query(request): Query<RequestV2ImperialInfo>,
 ) -> impl IntoResponse {
-    let is_male = matches!(request.type_, ThreadType::Male);
-
-    // Build query based on thread type
-    let query = if is_male {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_m = $3"
-    } else {
-        "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_f = $3"
-    };
+    let cache_key = request.cache_key();
+    if let Some(cached) = cache.get(&cache_key) {
+        if let Some(designation) = cached.designation.clone() {
+            let usage = ThreadUsage::new(designation, ThreadStandard::Imperial, request.language);
+            analytics.record(usage);
+        }
+        return cached.into_response();
+    }
 
-    let db_model = match sqlx::query_as::<_, ModelV2ImperialDB>(query)
-        .bind(&request.diameter)
-        .bind(request.tpi)
-        .bind(&request.series)
-        .fetch_one(&pool)
-        .await
-    {
-        Ok(record) => record,
+    let (response, designation) = match lookup(&database, &request).await {
+        Ok(found) => found,
         Err(e) => {
             log_error!("Database query error: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": format!("Thread with diameter: {}, TPI: {}, class: {} not found",
-                        request.diameter, request.tpi, request.series)
-                }))
-                .into_response(),
-            );
+            return e.into_response();
         }
     };
 
-    // Generate designation for analytics
-    let designation = ImperialInfoMapper::generate_designation1(&db_model, &request.type_);
-
-    // Clone pool and designation for background task
-    let pool_clone = pool.clone();
-    let designation_clone = designation.clone();
+    let usage = ThreadUsage::new(designation.clone(), ThreadStandard::Imperial, request.language);
+    analytics.record(usage);
 
-    // Spawn background task for analytics
-    tokio::spawn(async move {
-        handle_thread_analytics(pool_clone, designation_clone).await;
-    });
+    match serde_json::to_vec(&response) {
+        Ok(body) => {
+            let cached = CachedResponse::new("application/json", body, Some(designation));
+            cache.insert(cache_key, cached.clone());
+            cached.into_response()
+        }
+        Err(_) => (StatusCode::OK, Json(response)).into_response(),
+    }
+}
 
-    // Map database record to response
-    let response = ImperialInfoMapper::from_data(db_model, &request);
+/// Looks the thread up and maps it to the response, together with the designation for analytics
+pub async fn lookup(
+    database: &PostgresService,
+    request: &RequestV2ImperialInfo,
+) -> Result<(ResponseV2ImperialInfo, String), AppError> {
+    let db_model = database
+        .repository_imperial
+        .find_thread(&request.diameter, request.tpi, request.type_, &request.series)
+        .await
+        .and_then(required(|| {
+            json!({ "diameter": request.diameter, "tpi": request.tpi, "type": request.type_, "series": request.series })
+        }))?;
 
-    (StatusCode::OK, Json(response).into_response())
+    let designation = ImperialInfoMapper::generate_designation1(&db_model, &request.type_);
+    Ok((ImperialInfoMapper::from_data(db_model, request), designation))
 }
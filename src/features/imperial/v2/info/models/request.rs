@@ -1,9 +1,12 @@
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::services::cache::CacheKey;
 use crate::shared::enums::{Language, ThreadType, Unit};
 
 /// Структура для извлечения параметров запроса
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
 pub struct RequestV2ImperialInfo {
     pub diameter: String,
     pub tpi: f64,
@@ -14,3 +17,21 @@ pub struct RequestV2ImperialInfo {
     pub units: Unit,
     pub precision: Option<usize>,
 }
+
+impl RequestV2ImperialInfo {
+    /// Ключ кэша ответа `/v2/imperial/info`
+    pub fn cache_key(&self) -> CacheKey {
+        CacheKey::new(
+            "/v2/imperial/info",
+            [
+                ("diameter", Some(self.diameter.clone())),
+                ("tpi", Some(self.tpi.to_string())),
+                ("series", Some(self.series.clone())),
+                ("type", Some(self.type_.to_string())),
+                ("language", Some(self.language.to_string())),
+                ("units", Some(self.units.to_string())),
+                ("precision", self.precision.map(|p| p.to_string())),
+            ],
+        )
+    }
+}
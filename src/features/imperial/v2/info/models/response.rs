@@ -1,6 +1,7 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ResponseV2ImperialInfo {
     pub designation1: String,
     pub designation2: String,
@@ -11,7 +12,7 @@ pub struct ResponseV2ImperialInfo {
     pub additional_info: Vec<ModelImperialAdditionalInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ModelImperialAdditionalInfo {
     pub name: String,
     pub value: String,
@@ -19,7 +20,7 @@ pub struct ModelImperialAdditionalInfo {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, PartialEq, Clone, ToSchema)]
 pub struct ModelImperialDiameterInfo {
     pub name: String,
     pub max: String,
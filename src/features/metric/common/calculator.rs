@@ -0,0 +1,425 @@
+//! Limits of size of ISO general purpose metric threads (ISO 68-1, ISO 965-1),
+//! the logic behind `metric.get_info` for databases without PL/pgSQL.
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::features::metric::models::ThreadInfo;
+use crate::shared::enums::ThreadType;
+use crate::shared::error::AppError;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Tolerance classes offered for each side of the fit
+pub const EXTERNAL_CLASSES: [&str; 6] = ["4h", "6e", "6f", "6g", "6h", "8g"];
+pub const INTERNAL_CLASSES: [&str; 5] = ["4H", "5H", "6G", "6H", "7H"];
+
+/// Diameter ranges of the pitch diameter tolerances, ISO 965-1 table 5 and 6
+const DIAMETER_RANGES: [(f64, f64); 10] = [
+    (0.99, 1.4),
+    (1.4, 2.8),
+    (2.8, 5.6),
+    (5.6, 11.2),
+    (11.2, 22.4),
+    (22.4, 45.0),
+    (45.0, 90.0),
+    (90.0, 180.0),
+    (180.0, 355.0),
+    (355.0, 600.0),
+];
+
+/// R40 preferred numbers of one decade, used to round computed tolerances
+const R40: [f64; 40] = [
+    100.0, 106.0, 112.0, 118.0, 125.0, 132.0, 140.0, 150.0, 160.0, 170.0, 180.0, 190.0, 200.0, 212.0, 224.0, 236.0,
+    250.0, 265.0, 280.0, 300.0, 315.0, 335.0, 355.0, 375.0, 400.0, 425.0, 450.0, 475.0, 500.0, 530.0, 560.0, 600.0,
+    630.0, 670.0, 710.0, 750.0, 800.0, 850.0, 900.0, 950.0,
+];
+
+/// Tolerance of one diameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToleranceKind {
+    /// Td, major diameter of the external thread
+    #[serde(rename = "Td")]
+    MajorExternal,
+    /// TD1, minor diameter of the internal thread
+    #[serde(rename = "TD1")]
+    MinorInternal,
+    /// Td2, pitch diameter of the external thread
+    #[serde(rename = "Td2")]
+    PitchExternal,
+    /// TD2, pitch diameter of the internal thread
+    #[serde(rename = "TD2")]
+    PitchInternal,
+}
+
+impl ToleranceKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ToleranceKind::MajorExternal => "Td",
+            ToleranceKind::MinorInternal => "TD1",
+            ToleranceKind::PitchExternal => "Td2",
+            ToleranceKind::PitchInternal => "TD2",
+        }
+    }
+
+    fn grades(&self) -> &'static [u8] {
+        match self {
+            ToleranceKind::MajorExternal => &[4, 6, 8],
+            ToleranceKind::MinorInternal | ToleranceKind::PitchInternal => &[4, 5, 6, 7, 8],
+            ToleranceKind::PitchExternal => &[3, 4, 5, 6, 7, 8, 9],
+        }
+    }
+}
+
+/// Tabulated tolerance in micrometres; `diameter_min`/`diameter_max` bound the
+/// nominal diameter range of pitch diameter tolerances and are 0 otherwise
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ToleranceGradeRow {
+    pub kind: String,
+    pub grade: i32,
+    pub pitch: f64,
+    pub diameter_min: f64,
+    pub diameter_max: f64,
+    pub value: i32,
+}
+
+/// Tabulated fundamental deviation in micrometres, signed as in the standard
+/// (es of e, f, g is negative, EI of E, F, G positive)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FundamentalDeviationRow {
+    pub position: String,
+    pub pitch: f64,
+    pub value: i32,
+}
+
+/// Row of the metric thread series (ISO 261)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MetricThread {
+    pub id: i32,
+    pub diameter: f64,
+    pub pitch: f64,
+    /// 0 for the coarse pitch, 1 for fine pitches
+    pub type_pitch: i32,
+}
+
+/// Tolerance class such as `6g`, `6H` or `5g6g` (pitch diameter zone first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToleranceClass {
+    pub pitch_grade: u8,
+    pub pitch_position: char,
+    pub crest_grade: u8,
+    pub crest_position: char,
+}
+
+impl ToleranceClass {
+    pub fn parse(class: &str, thread_type: ThreadType) -> Result<Self, AppError> {
+        let invalid = || AppError::InvalidThreadParams(format!("invalid tolerance class '{}'", class));
+        let chars: Vec<char> = class.trim().chars().collect();
+        let zone = |pair: &[char]| -> Result<(u8, char), AppError> {
+            let grade = pair[0].to_digit(10).ok_or_else(invalid)? as u8;
+            let position = pair[1];
+            let valid = match thread_type {
+                ThreadType::Male => matches!(position, 'e' | 'f' | 'g' | 'h'),
+                ThreadType::Female => matches!(position, 'E' | 'F' | 'G' | 'H'),
+            };
+            if valid {
+                Ok((grade, position))
+            } else {
+                Err(invalid())
+            }
+        };
+
+        let ((pitch_grade, pitch_position), (crest_grade, crest_position)) = match chars.len() {
+            2 => (zone(&chars)?, zone(&chars)?),
+            4 => (zone(&chars[..2])?, zone(&chars[2..])?),
+            _ => return Err(invalid()),
+        };
+
+        let (pitch_kind, crest_kind) = match thread_type {
+            ThreadType::Male => (ToleranceKind::PitchExternal, ToleranceKind::MajorExternal),
+            ThreadType::Female => (ToleranceKind::PitchInternal, ToleranceKind::MinorInternal),
+        };
+        if !pitch_kind.grades().contains(&pitch_grade) || !crest_kind.grades().contains(&crest_grade) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            pitch_grade,
+            pitch_position,
+            crest_grade,
+            crest_position,
+        })
+    }
+}
+
+/// Computes limits of size from tabulated tolerances, falling back to the
+/// ISO 965-1 formulas rounded to R40 where a value is not tabulated. Other grades
+/// are derived from the rounded grade 6 like in the standard; a few printed values
+/// still differ from the formulas by one R40 step, hence the tables.
+pub struct MetricThreadCalculator<'a> {
+    grades: &'a [ToleranceGradeRow],
+    deviations: &'a [FundamentalDeviationRow],
+}
+
+impl<'a> MetricThreadCalculator<'a> {
+    pub fn new(grades: &'a [ToleranceGradeRow], deviations: &'a [FundamentalDeviationRow]) -> Self {
+        Self { grades, deviations }
+    }
+
+    /// Tolerance in micrometres
+    pub fn tolerance(&self, kind: ToleranceKind, grade: u8, pitch: f64, diameter: f64) -> f64 {
+        let tabulated = self.grades.iter().find(|row| {
+            row.kind == kind.code()
+                && row.grade == grade as i32
+                && same(row.pitch, pitch)
+                && (row.diameter_max == 0.0 || (diameter > row.diameter_min && diameter <= row.diameter_max))
+        });
+        if let Some(row) = tabulated {
+            return row.value as f64;
+        }
+
+        let factor = |grade: u8| match grade {
+            3 => 0.5,
+            4 => 0.63,
+            5 => 0.8,
+            7 => 1.25,
+            8 => 1.6,
+            9 => 2.0,
+            _ => 1.0,
+        };
+        match kind {
+            ToleranceKind::MajorExternal => {
+                round_r40(factor(grade) * (180.0 * pitch.powf(2.0 / 3.0) - 3.15 / pitch.sqrt()))
+            }
+            ToleranceKind::MinorInternal => {
+                let grade_6 = if pitch < 1.0 {
+                    433.0 * pitch - 190.0 * pitch.powf(1.22)
+                } else {
+                    230.0 * pitch.powf(0.7)
+                };
+                round_r40(factor(grade) * grade_6)
+            }
+            ToleranceKind::PitchExternal => round_r40(factor(grade) * Self::pitch_tolerance_6(pitch, diameter)),
+            ToleranceKind::PitchInternal => round_r40(factor(grade) * 1.32 * Self::pitch_tolerance_6(pitch, diameter)),
+        }
+    }
+
+    /// Td2 of grade 6 for the geometric mean of the diameter range, rounded to R40
+    fn pitch_tolerance_6(pitch: f64, diameter: f64) -> f64 {
+        let d = DIAMETER_RANGES
+            .iter()
+            .find(|(min, max)| diameter > *min && diameter <= *max)
+            .map(|(min, max)| (min * max).sqrt())
+            .unwrap_or(diameter);
+        round_r40(90.0 * pitch.powf(0.4) * d.powf(0.1))
+    }
+
+    /// Fundamental deviation in micrometres: es of an external, EI of an internal thread
+    pub fn fundamental_deviation(&self, position: char, pitch: f64) -> f64 {
+        let tabulated = self
+            .deviations
+            .iter()
+            .find(|row| row.position == position.to_string() && same(row.pitch, pitch));
+        if let Some(row) = tabulated {
+            return row.value as f64;
+        }
+
+        let magnitude = match position.to_ascii_lowercase() {
+            'e' => 50.0 + 11.0 * pitch,
+            'f' => 30.0 + 11.0 * pitch,
+            'g' => 15.0 + 11.0 * pitch,
+            _ => 0.0,
+        };
+        if position.is_ascii_lowercase() {
+            -magnitude.round()
+        } else {
+            magnitude.round()
+        }
+    }
+
+    /// Row of `metric.get_info` for one thread and class
+    pub fn info(
+        &self,
+        thread: &MetricThread,
+        thread_type: ThreadType,
+        class: &str,
+        language: &str,
+        units: &str,
+    ) -> Result<ThreadInfo, AppError> {
+        let zones = ToleranceClass::parse(class, thread_type)?;
+        let russian = language.eq_ignore_ascii_case("ru");
+        let d = thread.diameter;
+        let p = thread.pitch;
+        let um = |value: f64| value / 1000.0;
+
+        // Basic profile, ISO 68-1
+        let h = 3f64.sqrt() / 2.0 * p;
+        let d2 = d - 0.75 * h;
+        let d1 = d - 1.25 * h;
+        let d3 = d - 17.0 / 12.0 * h;
+
+        let td2_grade = match thread_type {
+            ThreadType::Male => ToleranceKind::PitchExternal,
+            ThreadType::Female => ToleranceKind::PitchInternal,
+        };
+        let pitch_tolerance = um(self.tolerance(td2_grade, zones.pitch_grade, p, d));
+        let pitch_deviation = um(self.fundamental_deviation(zones.pitch_position, p));
+        let crest_deviation = um(self.fundamental_deviation(zones.crest_position, p));
+
+        let limits = match thread_type {
+            ThreadType::Male => {
+                let td = um(self.tolerance(ToleranceKind::MajorExternal, zones.crest_grade, p, d));
+                let d2_es = pitch_deviation;
+                Limits {
+                    d_es: Some(crest_deviation),
+                    d_ei: crest_deviation - td,
+                    d2_es,
+                    d2_ei: d2_es - pitch_tolerance,
+                    // d1 is not toleranced, only the maximum of the root diameter d3
+                    d1: None,
+                    d3_es: Some(d2_es),
+                }
+            }
+            ThreadType::Female => {
+                let td1 = um(self.tolerance(ToleranceKind::MinorInternal, zones.crest_grade, p, d));
+                let d2_ei = pitch_deviation;
+                Limits {
+                    // The maximum major diameter of an internal thread is not specified
+                    d_es: None,
+                    d_ei: crest_deviation,
+                    d2_es: d2_ei + pitch_tolerance,
+                    d2_ei,
+                    d1: Some((crest_deviation + td1, crest_deviation)),
+                    d3_es: None,
+                }
+            }
+        };
+
+        let scale = match units.to_ascii_lowercase().as_str() {
+            "mm" => 1.0,
+            "inch" => 1.0 / MM_PER_INCH,
+            "micron" => 1000.0,
+            _ => return Err(AppError::InvalidThreadParams(format!("unknown units '{}'", units))),
+        };
+        let len = |value: f64| value * scale;
+        let avg = |max: f64, min: f64| len((max + min) / 2.0);
+        let number =
+            |value: f64| crate::shared::utils::number::NumberFormatter::format_number_trim_zeros(value, Some(3));
+
+        let (description, thread_depth) = match (thread_type, russian) {
+            (ThreadType::Male, true) => ("Наружная метрическая резьба", 17.0 / 24.0 * h),
+            (ThreadType::Male, false) => ("External metric thread", 17.0 / 24.0 * h),
+            (ThreadType::Female, true) => ("Внутренняя метрическая резьба", 5.0 / 8.0 * h),
+            (ThreadType::Female, false) => ("Internal metric thread", 5.0 / 8.0 * h),
+        };
+        let coarse = thread.type_pitch == 0;
+        // The coarse pitch is omitted from the designation
+        let designation = if coarse {
+            format!("M{}-{}", number(d), class.trim())
+        } else {
+            format!("M{}×{}-{}", number(d), number(p), class.trim())
+        };
+
+        let major_max = limits.d_es.map(|es| d + es);
+        let major_min = d + limits.d_ei;
+        let pitch = (d2 + limits.d2_es, d2 + limits.d2_ei);
+        let minor = limits.d1.map(|(es, ei)| (d1 + es, d1 + ei));
+        let minor_d3_max = limits.d3_es.map(|es| d3 + es);
+
+        Ok(ThreadInfo {
+            id: thread.id,
+            diameter: d,
+            pitch: p,
+            tolerance: class.trim().to_string(),
+            designation,
+            description: description.to_string(),
+            type_pitch_description: type_pitch_description(thread.type_pitch, language).to_string(),
+            thread_type: thread_type.to_string(),
+            type_pitch: thread.type_pitch,
+            thread_depth: len(thread_depth),
+            major_diam_min: len(major_min),
+            major_diam_avg: major_max.map(|max| avg(max, major_min)),
+            major_diam_max: major_max.map(len),
+            pitch_diam_d2: len(d2),
+            pitch_diam_min: len(pitch.1),
+            pitch_diam_avg: avg(pitch.0, pitch.1),
+            pitch_diam_max: len(pitch.0),
+            minor_diam_min: minor.map(|(_, min)| len(min)),
+            minor_diam_avg: minor.map(|(max, min)| avg(max, min)),
+            minor_diam_max: minor.map(|(max, _)| len(max)),
+            minor_diam_d1: len(d1),
+            minor_diam_d3: len(d3),
+            // Only d3 max is specified, the minimum follows from the root radius
+            minor_diam_min_d3: None,
+            minor_diam_avg_d3: None,
+            minor_diam_max_d3: minor_d3_max.map(len),
+            // Crest and root truncation of the basic profile
+            y_value: len(h / 8.0),
+            z_value: len(h / 4.0),
+            h: len(h),
+            d_ei: len(limits.d_ei),
+            d_es: limits.d_es.map(len),
+            d1_es: limits.d1.map(|(es, _)| len(es)),
+            d1_ei: limits.d1.map(|(_, ei)| len(ei)),
+            d2_es: Some(len(limits.d2_es)),
+            d2_ei: Some(len(limits.d2_ei)),
+            d3_ei: None,
+            d3_es: limits.d3_es.map(len),
+            h_div_4: len(h / 4.0),
+            five_h_div_8: len(5.0 * h / 8.0),
+            three_h_div_8: len(3.0 * h / 8.0),
+            h_div_8: len(h / 8.0),
+            pitch_div_8: len(p / 8.0),
+            pitch_div_4: len(p / 4.0),
+            pitch_div_2: len(p / 2.0),
+            // Root radius of the external thread (ISO 965-1 clause 7) and flat widths
+            rmax: len(h / 6.0),
+            rmin: len(0.125 * p),
+            cmax: len(p / 4.0),
+            cmin: len(p / 8.0),
+        })
+    }
+}
+
+/// Name of the pitch series, `metric.get_pitch` column `type_pitch_description`
+pub fn type_pitch_description(type_pitch: i32, language: &str) -> &'static str {
+    match (type_pitch == 0, language.eq_ignore_ascii_case("ru")) {
+        (true, true) => "Крупный шаг",
+        (true, false) => "Coarse pitch",
+        (false, true) => "Мелкий шаг",
+        (false, false) => "Fine pitch",
+    }
+}
+
+/// Deviations from the basic diameters in millimetres; `None` where ISO 965-1
+/// specifies no limit
+struct Limits {
+    d_es: Option<f64>,
+    d_ei: f64,
+    d2_es: f64,
+    d2_ei: f64,
+    /// es and EI of D1
+    d1: Option<(f64, f64)>,
+    d3_es: Option<f64>,
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Nearest R40 number. The choice is made on the exact series `10^(i/40)`, the
+/// printed numbers (170 for 167.9, 180 for 177.8) would move the midpoints: 174 is
+/// 180 in ISO 965-1. Values below 100 µm are whole micrometres like in the tables.
+fn round_r40(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let decade = 10f64.powf((value / 100.0).log10().floor());
+    let step = (40.0 * (value / decade / 100.0).log10()).round() as usize;
+    let nearest = R40.get(step).copied().unwrap_or(1000.0) * decade;
+    if nearest < 100.0 {
+        nearest.round_ties_even()
+    } else {
+        nearest
+    }
+}
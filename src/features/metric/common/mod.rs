@@ -0,0 +1,6 @@
+pub mod calculator;
+
+pub use self::calculator::{
+    type_pitch_description, FundamentalDeviationRow, MetricThread, MetricThreadCalculator, ToleranceGradeRow,
+    EXTERNAL_CLASSES, INTERNAL_CLASSES,
+};
@@ -1,2 +1,3 @@
+pub mod common;
 pub mod models;
 pub mod v1;
@@ -13,15 +13,17 @@ pub struct ThreadInfo {
     pub type_pitch: i32,
     pub thread_depth: f64,
     pub major_diam_min: f64,
-    pub major_diam_avg: f64,
-    pub major_diam_max: f64,
+    /// Not specified by ISO 965-1 for internal threads
+    pub major_diam_avg: Option<f64>,
+    pub major_diam_max: Option<f64>,
     pub pitch_diam_d2: f64,
     pub pitch_diam_min: f64,
     pub pitch_diam_avg: f64,
     pub pitch_diam_max: f64,
-    pub minor_diam_min: f64,
-    pub minor_diam_avg: f64,
-    pub minor_diam_max: f64,
+    /// Minor diameter D1 of internal threads; external threads are toleranced on d3
+    pub minor_diam_min: Option<f64>,
+    pub minor_diam_avg: Option<f64>,
+    pub minor_diam_max: Option<f64>,
     pub minor_diam_d1: f64,
     pub minor_diam_d3: f64,
     pub minor_diam_min_d3: Option<f64>,
@@ -31,7 +33,7 @@ pub struct ThreadInfo {
     pub z_value: f64,
     pub h: f64,
     pub d_ei: f64,
-    pub d_es: f64,
+    pub d_es: Option<f64>,
     pub d1_es: Option<f64>,
     pub d1_ei: Option<f64>,
     pub d2_es: Option<f64>,
@@ -1,38 +1,51 @@
 use crate::log_info;
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-};
+use axum::{extract::Extension, response::IntoResponse};
 use serde::Deserialize;
-use sqlx::PgPool;
+use std::sync::Arc;
+use utoipa::IntoParams;
 
-use crate::shared::utils::db::{execute_query, ResponseType};
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+use crate::shared::utils::db::{build_json_response, ResponseType};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DiameterParams {
+    /// Sort order, "asc" (default) or "desc"
     order: Option<String>,
 }
 
-pub async fn diameters(Extension(pool): Extension<PgPool>, Query(params): Query<DiameterParams>) -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/v1/metric/diameters",
+    tag = "metric",
+    params(DiameterParams),
+    responses(
+        (status = 200, description = "Nominal diameters", body = Vec<serde_json::Value>),
+        (status = 400, description = "Invalid sort order"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn diameters(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<DiameterParams>,
+) -> impl IntoResponse {
     log_info!("Handling diameters request with parameters: {:?}", params);
     // Clone the order to own the String
     let order = params.order.clone().unwrap_or_else(|| "asc".to_string());
 
     // Валидация параметра order
     if order != "asc" && order != "desc" {
-        return (
-            StatusCode::BAD_REQUEST,
-            "Invalid parameter 'order'. Use 'asc' or 'desc'.",
-        )
-            .into_response();
+        return AppError::BadRequest("Invalid parameter 'order'. Use 'asc' or 'desc'.".to_string()).into_response();
     }
 
-    // Определение SQL-запроса
-    let query = "SELECT * FROM metric.get_diameters($1)";
+    let rows = match database.repository_metric.get_diameters(&order).await {
+        Ok(rows) => rows,
+        Err(err) => return err.into_response(),
+    };
 
-    // Выполнение запроса с привязкой параметра
-    match execute_query(&pool, query, move |q| q.bind(order), ResponseType::Multiple, None).await {
+    match build_json_response(rows, ResponseType::Multiple, None) {
         Ok(json) => json.into_response(),
         Err(err) => err.into_response(),
     }
@@ -1,47 +1,82 @@
 use crate::log_info;
-use axum::{
-    extract::{Extension, Query},
-    response::IntoResponse,
-};
+use axum::{extract::Extension, response::IntoResponse, Json};
 use serde::Deserialize;
-use sqlx::PgPool;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::shared::utils::db::{execute_query, ResponseType};
+use crate::shared::database::{repositories::MetricInfoQuery, service::PostgresService};
+use crate::shared::enums::{Language, ThreadType, Unit};
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+use crate::shared::utils::db::{build_json_response, ResponseType};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+#[schema(as = MetricInfoParams)]
 pub struct InfoParams {
     diameter: f64,
     pitch: f64,
     #[serde(rename = "type")]
+    #[param(value_type = ThreadType)]
+    #[schema(value_type = ThreadType)]
     type_: String,
+    /// Tolerance class ("6g", "6H")
     tolerance: String,
+    #[param(value_type = Language)]
+    #[schema(value_type = Language)]
     language: String,
+    #[param(value_type = Unit)]
+    #[schema(value_type = Unit)]
     units: String,
     precision: Option<usize>,
 }
 
-pub async fn info(Extension(pool): Extension<PgPool>, Query(params): Query<InfoParams>) -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/v1/metric/info",
+    tag = "metric",
+    params(InfoParams),
+    responses(
+        (status = 200, description = "Dimensions and deviations of the thread", body = serde_json::Value),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn info(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<InfoParams>,
+) -> impl IntoResponse {
     log_info!("Processing info request with parameters: {:?}", params);
 
-    let query = "SELECT * FROM metric.get_info($1, $2, $3, $4, $5, $6)";
-
-    match execute_query(
-        &pool,
-        query,
-        move |q| {
-            q.bind(params.diameter)
-                .bind(params.pitch)
-                .bind(params.type_)
-                .bind(params.tolerance)
-                .bind(params.language)
-                .bind(params.units)
-        },
-        ResponseType::Single,
-        params.precision, // Passing optional precision
-    )
-    .await
-    {
-        Ok(json) => json.into_response(),
+    match lookup(&database, &params).await {
+        Ok(info) => Json(info).into_response(),
         Err(err) => err.into_response(),
     }
 }
+
+/// Thread information for the request parameters
+pub async fn lookup(database: &PostgresService, params: &InfoParams) -> Result<Value, AppError> {
+    let query = MetricInfoQuery {
+        diameter: params.diameter,
+        pitch: params.pitch,
+        type_: params.type_.clone(),
+        tolerance: params.tolerance.clone(),
+        language: params.language.clone(),
+        units: params.units.clone(),
+    };
+
+    let rows = database.repository_metric.get_info(&query).await?;
+    if rows.is_empty() {
+        return Err(AppError::thread_not_found(json!({
+            "diameter": query.diameter,
+            "pitch": query.pitch,
+            "type": query.type_,
+            "tolerance": query.tolerance,
+        })));
+    }
+
+    let Json(info) = build_json_response(rows, ResponseType::Single, params.precision)?;
+    Ok(info)
+}
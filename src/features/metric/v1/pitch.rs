@@ -1,38 +1,53 @@
 use crate::log_info;
-use axum::{
-    extract::{Extension, Query},
-    response::IntoResponse,
-};
+use axum::{extract::Extension, response::IntoResponse};
 use serde::Deserialize;
-use sqlx::PgPool;
+use std::sync::Arc;
+use utoipa::IntoParams;
 
-use crate::shared::utils::db::{execute_query, ResponseType};
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::Language;
+use crate::shared::extract::Query;
+use crate::shared::utils::db::{build_json_response, ResponseType};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PitchParams {
     diameter: f64,
+    #[param(value_type = Option<Language>)]
     language: Option<String>,
 }
 
-pub async fn pitch(Extension(pool): Extension<PgPool>, Query(params): Query<PitchParams>) -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/v1/metric/pitch",
+    tag = "metric",
+    params(PitchParams),
+    responses(
+        (status = 200, description = "Pitches of the diameter", body = Vec<serde_json::Value>),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn pitch(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<PitchParams>,
+) -> impl IntoResponse {
     log_info!("Handling pitch request with parameters: {:?}", params);
 
     let diameter = params.diameter;
     // Clone the language to own the String
     let language = params.language.clone().unwrap_or_else(|| "en".to_string());
 
-    // Определение SQL-запроса
-    let query = "SELECT * FROM metric.get_pitch($1, $2)";
+    let rows = match database.repository_metric.get_pitch(diameter, &language).await {
+        Ok(rows) => rows,
+        Err(err) => return err.into_response(),
+    };
 
-    match execute_query(
-        &pool,
-        query,
-        move |q| q.bind(diameter).bind(language),
+    match build_json_response(
+        rows,
         ResponseType::Multiple, // Явно указываем массив
         None,
-    )
-    .await
-    {
+    ) {
         Ok(json) => json.into_response(),
         Err(err) => err.into_response(),
     }
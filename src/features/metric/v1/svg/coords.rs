@@ -1,6 +1,6 @@
 /// Функция для наполнения координат текстовых элементов SVG на основе типа
 pub fn initialize(type_: &str) -> SvgTextCoordinates {
-    let is_female = type_ == "female";
+    let is_female = type_.eq_ignore_ascii_case("female");
 
     SvgTextCoordinates {
         major_diam_x: 1047.0 - 15.0,
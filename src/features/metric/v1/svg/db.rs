@@ -1,29 +1,31 @@
 use crate::features::metric::v1::svg::params::SvgParams;
-use crate::shared::utils::db::{execute_query, ResponseType};
-use axum::http::StatusCode;
+use crate::shared::database::{repositories::MetricInfoQuery, service::PostgresService};
+use crate::shared::error::AppError;
+use crate::shared::utils::db::{build_json_response, ResponseType};
 use axum::Json;
-use serde_json::Value;
-use sqlx::Pool;
-use sqlx::Postgres;
+use serde_json::{json, Value};
 
 pub async fn fetch_thread_info_from_db(
-    pool: &Pool<Postgres>,
+    database: &PostgresService,
     params: &SvgParams,
-) -> Result<Json<Value>, (StatusCode, String)> {
-    let query = "SELECT * FROM metric.get_info($1, $2, $3, $4, $5, $6)";
-    execute_query(
-        pool,
-        query,
-        |q| {
-            q.bind(params.diameter)
-                .bind(params.pitch)
-                .bind(params.type_.clone())
-                .bind(params.tolerance.clone())
-                .bind(params.language.clone())
-                .bind(params.units.clone())
-        },
-        ResponseType::Single,
-        Some(params.precision),
-    )
-    .await
+) -> Result<Json<Value>, AppError> {
+    let query = MetricInfoQuery {
+        diameter: params.diameter,
+        pitch: params.pitch,
+        type_: params.type_.clone(),
+        tolerance: params.tolerance.clone(),
+        language: params.language.clone(),
+        units: params.units.clone(),
+    };
+    let rows = database.repository_metric.get_info(&query).await?;
+    if rows.is_empty() {
+        return Err(AppError::thread_not_found(json!({
+            "diameter": query.diameter,
+            "pitch": query.pitch,
+            "type": query.type_,
+            "tolerance": query.tolerance,
+        })));
+    }
+
+    build_json_response(rows, ResponseType::Single, Some(params.precision))
 }
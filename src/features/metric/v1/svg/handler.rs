@@ -1,27 +1,54 @@
-use crate::analytics::db::handle_thread_analytics;
+use crate::analytics::pipeline::AnalyticsRecorder;
 use crate::features::metric::models::ThreadInfo;
 use crate::features::metric::v1::svg::coords::initialize;
 use crate::features::metric::v1::svg::db::fetch_thread_info_from_db;
 use crate::features::metric::v1::svg::text_generation::generate_svg_texts;
+use crate::services::svg::export::ExportParams;
 
-use axum::{
-    extract::{Extension, Query},
-    http::{
-        header::{HeaderMap, HeaderValue, CONTENT_TYPE},
-        StatusCode,
-    },
-    response::IntoResponse,
-};
+use axum::{extract::Extension, response::IntoResponse};
 
 use crate::features::metric::v1::svg::params::SvgParams;
+use crate::services::cache::{CachedResponse, ResponseCache};
+use crate::services::svg::templates::{self, TEMPLATE_DIR};
+use crate::services::telemetry;
+use crate::shared::database::repositories::ThreadUsage;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::{Language, ThreadStandard};
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
 use crate::{log_error, log_info};
-use sqlx::PgPool;
-use std::path::PathBuf;
-use tokio::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::Instrument;
+
+#[utoipa::path(
+    get,
+    path = "/v1/metric/svg",
+    tag = "drawings",
+    params(SvgParams, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn svg(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
+    Query(params): Query<SvgParams>,
+) -> impl IntoResponse {
+    let cache_key = params.cache_key();
+    if let Some(cached) = cache.get(&cache_key) {
+        if let Some(designation) = cached.designation.clone() {
+            analytics.record(usage(&params, designation));
+        }
+        return cached.into_response();
+    }
 
-pub async fn svg(Extension(pool): Extension<PgPool>, Query(params): Query<SvgParams>) -> impl IntoResponse {
     // Fetching thread information
-    let thread_info_json = match fetch_thread_info_from_db(&pool, &params).await {
+    let thread_info_json = match fetch_thread_info_from_db(&database, &params).await {
         Ok(val) => val.0,
         Err(err) => return err.into_response(),
     };
@@ -34,23 +61,19 @@ pub async fn svg(Extension(pool): Extension<PgPool>, Query(params): Query<SvgPar
                 "Error deserializing JSON: {}",
                 serde_json::to_string(&thread_info_json).unwrap_or_else(|_| "invalid JSON".to_string())
             );
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Error deserializing: {}", err),
-            )
-                .into_response();
+            return AppError::InternalError {
+                message: format!("Error deserializing: {}", err),
+            }
+            .into_response();
         }
     };
 
     log_info!("Thread information received: {:?}", thread_info);
 
-    if params.show_dimensions {
-        // Вызов функции add_or_increment_thread в фоне через модуль analytics
-        let designation_clone = thread_info.designation.clone();
-        let pool_clone = pool.clone();
-        tokio::spawn(async move {
-            handle_thread_analytics(pool_clone, designation_clone).await;
-        });
+    // Only drawings with dimensions are counted in the analytics
+    let designation = params.show_dimensions.then(|| thread_info.designation.clone());
+    if let Some(designation) = &designation {
+        analytics.record(usage(&params, designation.clone()));
     }
 
     // Loading SVG template based on type and theme
@@ -58,50 +81,66 @@ pub async fn svg(Extension(pool): Extension<PgPool>, Query(params): Query<SvgPar
         Ok(template) => template,
         Err(err) => {
             log_error!("Error loading SVG template: {}", err);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to load SVG template.".to_string(),
-            )
-                .into_response();
+            return AppError::SvgTemplateNotFound(err.to_string()).into_response();
         }
     };
     let coords = initialize(&params.type_);
 
     // Generating text elements based on theme and language
-    let svg_texts = generate_svg_texts(
-        &thread_info,
-        &params.type_,
-        &coords,
-        &params.theme,
-        &params.language,
-        params.show_dimensions,
-    );
+    let svg_texts = {
+        let _span = tracing::info_span!(
+            "metric::generate_svg_texts",
+            svg.show_dimensions = params.show_dimensions
+        )
+        .entered();
+        generate_svg_texts(
+            &thread_info,
+            &params.type_,
+            &coords,
+            &params.theme,
+            &params.language,
+            params.show_dimensions,
+        )
+    };
 
     // Inserting text elements into the SVG
     if let Some(index) = result_load_svg_template.rfind("</svg>") {
-        let mut updated_svg = result_load_svg_template.clone();
+        let mut updated_svg = result_load_svg_template.to_string();
         updated_svg.insert_str(index, &svg_texts);
 
-        let content = updated_svg.into_bytes();
-        send_success_response(content)
+        let cached = CachedResponse::new("image/svg+xml", updated_svg, designation);
+        cache.insert(cache_key, cached.clone());
+        cached.into_response()
     } else {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Invalid SVG format: closing </svg> tag not found.".to_string(),
-        )
-            .into_response()
+        AppError::InvalidSvgTemplate("closing </svg> tag not found".to_string()).into_response()
     }
 }
 
-fn send_success_response(content: Vec<u8>) -> axum::http::Response<axum::body::Body> {
-    let mut headers = HeaderMap::new();
-    headers.insert(CONTENT_TYPE, HeaderValue::from_static("image/svg+xml"));
-    (StatusCode::OK, headers, content).into_response()
+/// `language` of a drawing is a string, anything but "ru" is drawn in English
+fn usage(params: &SvgParams, designation: String) -> ThreadUsage {
+    let language = if params.language.eq_ignore_ascii_case("ru") {
+        Language::Ru
+    } else {
+        Language::En
+    };
+    ThreadUsage::new(designation, ThreadStandard::Metric, language)
 }
 
-async fn load_svg_template(type_: &str, theme: &str) -> Result<String, std::io::Error> {
+async fn load_svg_template(type_: &str, theme: &str) -> Result<Arc<str>, std::io::Error> {
     // Forming the file name
     let file_name = format!("metric-thread-{}-{}.svg", type_.to_lowercase(), theme.to_lowercase());
-    let file_path = PathBuf::from("./static/svg").join(file_name);
-    fs::read_to_string(file_path).await
+    let span = tracing::info_span!(
+        "metric::load_svg_template",
+        svg.template = file_name.as_str(),
+        otel.status_code = tracing::field::Empty,
+        otel.status_description = tracing::field::Empty,
+    );
+
+    let template = templates::load(Path::new(TEMPLATE_DIR).join(file_name))
+        .instrument(span.clone())
+        .await;
+    if let Err(e) = &template {
+        telemetry::set_error(&span, e);
+    }
+    template
 }
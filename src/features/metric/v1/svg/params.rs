@@ -1,16 +1,49 @@
 use serde::Deserialize;
+use utoipa::IntoParams;
 
-#[derive(Deserialize)]
+use crate::services::cache::CacheKey;
+use crate::shared::enums::{Language, Theme, ThreadType, Unit};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SvgParams {
     #[serde(rename = "type")]
+    #[param(value_type = ThreadType)]
     pub type_: String,
+    #[param(value_type = Theme)]
     pub theme: String,
+    /// Tolerance class ("6g", "6H")
     pub tolerance: String,
     pub diameter: f64,
     pub pitch: f64,
+    #[param(value_type = Language)]
     pub language: String,
+    #[param(value_type = Unit)]
     pub units: String,
     pub precision: usize,
+    /// Draw the dimension values, counted in the popularity statistics
     #[serde(default)]
     pub show_dimensions: bool,
 }
+
+impl SvgParams {
+    /// Cache key of the `/v1/metric/svg` response. The handler matches type,
+    /// theme, language and units ignoring case, so the key does too
+    pub fn cache_key(&self) -> CacheKey {
+        let lowercase = |value: &str| Some(value.to_ascii_lowercase());
+        CacheKey::new(
+            "/v1/metric/svg",
+            [
+                ("type", lowercase(&self.type_)),
+                ("theme", lowercase(&self.theme)),
+                ("tolerance", Some(self.tolerance.clone())),
+                ("diameter", Some(self.diameter.to_string())),
+                ("pitch", Some(self.pitch.to_string())),
+                ("language", lowercase(&self.language)),
+                ("units", lowercase(&self.units)),
+                ("precision", Some(self.precision.to_string())),
+                ("show_dimensions", Some(self.show_dimensions.to_string())),
+            ],
+        )
+    }
+}
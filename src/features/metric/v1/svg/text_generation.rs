@@ -29,6 +29,11 @@ pub fn generate_svg_texts(
     } else {
         "avg."
     };
+    let (min_label, max_label) = if language.eq_ignore_ascii_case("ru") {
+        ("мин.", "макс.")
+    } else {
+        ("min", "max")
+    };
     let female_thread_label = if language.eq_ignore_ascii_case("ru") {
         "Внутренняя резьба"
     } else {
@@ -44,7 +49,7 @@ pub fn generate_svg_texts(
         coords.major_diam_x,
         coords.major_diam_y,
         &if show_dimensions {
-            format!("ø{}-{}", thread_info.major_diam_min, thread_info.major_diam_max)
+            limits_label(thread_info.major_diam_min, thread_info.major_diam_max, min_label)
         } else {
             major_diam_label.to_string()
         },
@@ -54,18 +59,17 @@ pub fn generate_svg_texts(
         None,
     );
 
-    let svg_text_major_diam_avg = if show_dimensions {
-        generate_svg_text(
+    let svg_text_major_diam_avg = match thread_info.major_diam_avg {
+        Some(value) if show_dimensions => generate_svg_text(
             coords.major_diam_avg_x,
             coords.major_diam_avg_y,
-            &format!("({} ø{})", avg_label, thread_info.major_diam_avg),
+            &format!("({} ø{})", avg_label, value),
             theme,
             10.0,
             -90.0,
             None,
-        )
-    } else {
-        String::new()
+        ),
+        _ => String::new(),
     };
 
     let svg_text_pitch_tolerance = generate_svg_text(
@@ -100,7 +104,11 @@ pub fn generate_svg_texts(
         coords.minor_diam_x,
         coords.minor_diam_y,
         &if show_dimensions {
-            format!("ø{}-{}", thread_info.minor_diam_min, thread_info.minor_diam_max)
+            match (thread_info.minor_diam_min, thread_info.minor_diam_max) {
+                (Some(min), max) => limits_label(min, max, min_label),
+                // External threads are toleranced on d3, d1 is the basic size
+                (None, _) => format!("ø{}", thread_info.minor_diam_d1),
+            }
         } else {
             minor_diam_label.to_string()
         },
@@ -110,18 +118,17 @@ pub fn generate_svg_texts(
         None,
     );
 
-    let svg_text_minor_tolerance_avg = if show_dimensions {
-        generate_svg_text(
+    let svg_text_minor_tolerance_avg = match thread_info.minor_diam_avg {
+        Some(value) if show_dimensions => generate_svg_text(
             coords.minor_diam_avg_x,
             coords.minor_diam_avg_y,
-            &format!("({} ø{})", avg_label, thread_info.minor_diam_avg),
+            &format!("({} ø{})", avg_label, value),
             theme,
             10.0,
             -90.0,
             None,
-        )
-    } else {
-        String::new()
+        ),
+        _ => String::new(),
     };
 
     let svg_text_pitch = generate_svg_text(
@@ -333,11 +340,11 @@ pub fn generate_svg_texts(
             coords.d3_x,
             coords.d3_y,
             &if show_dimensions {
-                format!(
-                    "ø{}-{}",
-                    thread_info.minor_diam_min_d3.unwrap_or(0.0),
-                    thread_info.minor_diam_max_d3.unwrap_or(0.0)
-                )
+                match (thread_info.minor_diam_min_d3, thread_info.minor_diam_max_d3) {
+                    (Some(min), max) => limits_label(min, max, min_label),
+                    (None, Some(max)) => format!("ø{} {}", max, max_label),
+                    (None, None) => String::new(),
+                }
             } else {
                 "d3".to_string()
             },
@@ -347,18 +354,17 @@ pub fn generate_svg_texts(
             None,
         );
 
-        let svg_text_d3_avg = if show_dimensions {
-            generate_svg_text(
+        let svg_text_d3_avg = match thread_info.minor_diam_avg_d3 {
+            Some(value) if show_dimensions => generate_svg_text(
                 coords.d3_x_avg,
                 coords.d3_y_avg,
-                &format!("({} ø{:?})", avg_label, thread_info.minor_diam_avg_d3.unwrap()),
+                &format!("({} ø{})", avg_label, value),
                 theme,
                 10.0,
                 -90.0,
                 None,
-            )
-        } else {
-            String::new()
+            ),
+            _ => String::new(),
         };
 
         svg_texts_vec.push(svg_text_d3);
@@ -367,3 +373,11 @@ pub fn generate_svg_texts(
 
     svg_texts_vec.join("")
 }
+
+/// "ømin-max", or "ømin min" when ISO 965-1 leaves the maximum unspecified
+fn limits_label(min: f64, max: Option<f64>, min_label: &str) -> String {
+    match max {
+        Some(max) => format!("ø{}-{}", min, max),
+        None => format!("ø{} {}", min, min_label),
+    }
+}
@@ -1,39 +1,49 @@
 use crate::log_info;
-use axum::{
-    extract::{Extension, Query},
-    response::IntoResponse,
-};
+use axum::{extract::Extension, response::IntoResponse};
 use serde::Deserialize;
-use sqlx::PgPool;
+use std::sync::Arc;
+use utoipa::IntoParams;
 
-use crate::shared::utils::db::{execute_query, ResponseType};
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::ThreadType;
+use crate::shared::extract::Query;
+use crate::shared::utils::db::{build_json_response, ResponseType};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ToleranceParams {
     id: i32,
     #[serde(rename = "type")]
+    #[param(value_type = Option<ThreadType>)]
     type_: Option<String>,
 }
 
-pub async fn tolerance(Extension(pool): Extension<PgPool>, Query(params): Query<ToleranceParams>) -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/v1/metric/tolerance",
+    tag = "metric",
+    params(ToleranceParams),
+    responses(
+        (status = 200, description = "Tolerance classes of the thread", body = Vec<serde_json::Value>),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn tolerance(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<ToleranceParams>,
+) -> impl IntoResponse {
     log_info!("Handling tolerance request with parameters: {:?}", params);
 
     let id = params.id;
     let type_ = params.type_.clone().unwrap_or_else(|| "f".to_string());
 
-    // Определение SQL-запроса
-    let query = "SELECT * FROM metric.get_tolerance($1, $2)";
+    let rows = match database.repository_metric.get_tolerance(id, &type_).await {
+        Ok(rows) => rows,
+        Err(err) => return err.into_response(),
+    };
 
-    // Выполнение запроса с привязкой параметров
-    match execute_query(
-        &pool,
-        query,
-        move |q| q.bind(id).bind(type_),
-        ResponseType::Multiple,
-        None,
-    )
-    .await
-    {
+    match build_json_response(rows, ResponseType::Multiple, None) {
         Ok(json) => json.into_response(),
         Err(err) => err.into_response(),
     }
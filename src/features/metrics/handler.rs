@@ -0,0 +1,156 @@
+use axum::{
+    extract::Extension,
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::services::metrics::{Labels, Metrics, PrometheusText};
+use crate::shared::setting::models::app_state::AppState;
+
+fn labels(pairs: &[(&'static str, &str)]) -> Labels {
+    pairs.iter().map(|(name, value)| (*name, value.to_string())).collect()
+}
+
+/// Counters kept by the services themselves, read at scrape time
+fn counter(text: &mut PrometheusText, name: &str, help: &str, samples: &[(Labels, u64)]) {
+    text.counter(name, help, samples.iter().map(|(labels, value)| (labels, value)));
+}
+
+/// Connections of the PostgreSQL or SQLite pool; the memory backend has none
+fn pool_gauges(app_state: &AppState, text: &mut PrometheusText) {
+    let database = &app_state.postgres_service;
+    let pool = database
+        .pool()
+        .map(|pool| {
+            (
+                "postgres",
+                pool.size(),
+                pool.num_idle(),
+                pool.options().get_max_connections(),
+            )
+        })
+        .or_else(|| {
+            database.sqlite.as_ref().map(|sqlite| {
+                let pool = sqlite.pool();
+                (
+                    "sqlite",
+                    pool.size(),
+                    pool.num_idle(),
+                    pool.options().get_max_connections(),
+                )
+            })
+        });
+    let Some((backend, size, idle, max)) = pool else {
+        return;
+    };
+
+    let in_use = size.saturating_sub(idle as u32);
+    text.gauge(
+        "db_pool_connections",
+        "Open pool connections by state",
+        &[
+            (labels(&[("backend", backend), ("state", "idle")]), idle as f64),
+            (labels(&[("backend", backend), ("state", "in_use")]), f64::from(in_use)),
+        ],
+    );
+    text.gauge(
+        "db_pool_max_connections",
+        "Configured pool size",
+        &[(labels(&[("backend", backend)]), f64::from(max))],
+    );
+}
+
+fn cache_metrics(app_state: &AppState, text: &mut PrometheusText) {
+    let stats = app_state.response_cache.stats();
+    counter(
+        text,
+        "response_cache_requests_total",
+        "Response cache lookups by result",
+        &[
+            (labels(&[("result", "hit")]), stats.hits),
+            (labels(&[("result", "miss")]), stats.misses),
+        ],
+    );
+    counter(
+        text,
+        "response_cache_evictions_total",
+        "Entries dropped by reason",
+        &[
+            (labels(&[("reason", "capacity")]), stats.evictions),
+            (labels(&[("reason", "ttl")]), stats.expirations),
+        ],
+    );
+    text.gauge(
+        "response_cache_entries",
+        "Responses in the cache",
+        &[(Labels::new(), stats.entries as f64)],
+    );
+}
+
+fn analytics_metrics(app_state: &AppState, text: &mut PrometheusText) {
+    let stats = app_state.analytics.stats();
+    text.gauge(
+        "analytics_queue_depth",
+        "Lookups waiting for the analytics writer",
+        &[(Labels::new(), stats.queued as f64)],
+    );
+    counter(
+        text,
+        "analytics_lookups_total",
+        "Lookups by outcome: recorded, dropped on a full queue, flushed, failed",
+        &[
+            (labels(&[("outcome", "recorded")]), stats.recorded),
+            (labels(&[("outcome", "dropped")]), stats.dropped),
+            (labels(&[("outcome", "flushed")]), stats.flushed),
+            (labels(&[("outcome", "failed")]), stats.failed),
+        ],
+    );
+}
+
+fn rate_limit_metrics(app_state: &AppState, text: &mut PrometheusText) {
+    let stats = app_state.rate_limits.stats();
+    let mut requests = Vec::new();
+    let mut tracked = Vec::new();
+    for policy in &stats {
+        let name = policy.policy.as_str();
+        requests.push((labels(&[("policy", name), ("outcome", "allowed")]), policy.allowed));
+        requests.push((labels(&[("policy", name), ("outcome", "limited")]), policy.limited));
+        tracked.push((labels(&[("policy", name), ("client", "ip")]), policy.tracked_ips as f64));
+        tracked.push((
+            labels(&[("policy", name), ("client", "api_key")]),
+            policy.tracked_keys as f64,
+        ));
+    }
+    counter(
+        text,
+        "rate_limit_requests_total",
+        "Requests under a rate limit policy by outcome",
+        &requests,
+    );
+    text.gauge(
+        "rate_limit_tracked_clients",
+        "Client IPs and API keys with a token bucket",
+        &tracked,
+    );
+}
+
+/// GET /metrics - metrics in the Prometheus text format
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "system",
+    responses(
+        (status = 200, description = "Prometheus text format 0.0.4", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn metrics(Extension(app_state): Extension<Arc<AppState>>) -> Response {
+    let mut text = PrometheusText::new();
+    Metrics::global().render(&mut text);
+    pool_gauges(&app_state, &mut text);
+    cache_metrics(&app_state, &mut text);
+    analytics_metrics(&app_state, &mut text);
+    rate_limit_metrics(&app_state, &mut text);
+
+    ([(CONTENT_TYPE, PrometheusText::CONTENT_TYPE)], text.into_string()).into_response()
+}
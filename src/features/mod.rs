@@ -1,8 +1,18 @@
 // pub mod materials;
+pub mod admin;
+pub mod analytics;
+pub mod api_keys;
+pub mod batch;
 pub mod error_reports;
 pub mod health;
 pub mod imperial;
 pub mod metric;
+pub mod metrics;
+pub mod model;
+pub mod openapi;
 pub mod pipe;
+pub mod profile;
+pub mod search;
 pub mod test;
+pub mod tolerance_zones;
 pub mod trapezoidal;
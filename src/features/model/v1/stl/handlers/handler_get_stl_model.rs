@@ -0,0 +1,128 @@
+use axum::body::Body;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::Extension;
+use std::sync::Arc;
+
+use crate::features::model::v1::stl::models::RequestStlModel;
+use crate::features::profile::common::resolve_thread;
+use crate::services::mesh::{StlEncoding, StlWriter, ThreadMesh, ThreadMeshOptions};
+use crate::services::svg::tolerance_renderer::MaterialCondition;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::ThreadType;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+const MAX_STARTS: usize = 8;
+const MAX_LENGTH_PITCHES: f64 = 200.0;
+/// Facets of one response: 50 MB of binary STL
+const MAX_FACETS_BINARY: usize = 1_000_000;
+/// An ASCII facet takes about 250 bytes, so the same 50 MB
+const MAX_FACETS_ASCII: usize = 200_000;
+const DEFAULT_SEGMENTS: usize = 96;
+const SEGMENTS_RANGE: (usize, usize) = (16, 360);
+
+/// Closed helical mesh of an external thread body or an internal thread cavity for 3D printing
+#[utoipa::path(
+    get,
+    path = "/v1/model/stl",
+    tag = "exports",
+    params(RequestStlModel),
+    responses(
+        (status = 200, description = "Helical thread body or cavity as STL", body = Vec<u8>, content_type = "model/stl"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<RequestStlModel>,
+) -> Result<Response<Body>, AppError> {
+    let starts = params.starts.unwrap_or(1);
+    if !(1..=MAX_STARTS).contains(&starts) {
+        return Err(AppError::InvalidThreadParams(format!(
+            "starts must be between 1 and {}",
+            MAX_STARTS
+        )));
+    }
+    let clearance = params.clearance.unwrap_or(0.0);
+    if !clearance.is_finite() || clearance < 0.0 {
+        return Err(AppError::InvalidThreadParams(
+            "clearance must not be negative".to_string(),
+        ));
+    }
+
+    let thread = resolve_thread(
+        &database,
+        params.standard,
+        params.thread_type,
+        &params.diameter,
+        params.pitch,
+        params.tpi,
+        params.tolerance.as_deref(),
+    )
+    .await?;
+
+    let profile = match &thread.zones {
+        Some(zones) => zones.material_profile(&thread.profile, MaterialCondition::Middle),
+        None => thread.profile,
+    };
+    if !params.length.is_finite() || params.length <= 0.0 || params.length > MAX_LENGTH_PITCHES * profile.pitch {
+        return Err(AppError::InvalidThreadParams(format!(
+            "length must be positive and at most {} pitches",
+            MAX_LENGTH_PITCHES
+        )));
+    }
+
+    // The clearance makes a body smaller and a cavity larger
+    let profile = match params.thread_type {
+        ThreadType::Male => profile.offset_radially(-clearance),
+        ThreadType::Female => profile.offset_radially(clearance),
+    };
+    if profile.root_level <= 0.0 || profile.crest_level <= 0.0 {
+        return Err(AppError::InvalidThreadParams(
+            "clearance is too large for the given thread".to_string(),
+        ));
+    }
+
+    let options = ThreadMeshOptions {
+        hand: params.hand,
+        starts,
+        length: params.length,
+        segments: params
+            .segments
+            .unwrap_or(DEFAULT_SEGMENTS)
+            .clamp(SEGMENTS_RANGE.0, SEGMENTS_RANGE.1),
+    };
+    // Length, pitch, profile points and segments multiply, each bound alone is not enough
+    let max_facets = match params.encoding {
+        StlEncoding::Binary => MAX_FACETS_BINARY,
+        StlEncoding::Ascii => MAX_FACETS_ASCII,
+    };
+    if ThreadMesh::surface_facets(&profile, &options) > max_facets {
+        return Err(AppError::InvalidThreadParams(format!(
+            "the mesh would exceed {} facets, reduce length or segments",
+            max_facets
+        )));
+    }
+    let name = thread.designation.replace('×', "x").replace('ø', "G");
+    let encoding = params.encoding;
+    let content = tokio::task::spawn_blocking(move || {
+        let mesh = ThreadMesh::build(&profile, &options);
+        StlWriter::write(&mesh, &name, encoding)
+    })
+    .await
+    .map_err(|e| AppError::InternalError {
+        message: format!("STL generation failed: {}", e),
+    })?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("model/stl"));
+    headers.insert(
+        CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"thread.stl\""),
+    );
+    Ok((StatusCode::OK, headers, content).into_response())
+}
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::services::mesh::{Hand, StlEncoding};
+use crate::shared::enums::{ThreadStandard, ThreadType};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestStlModel {
+    pub standard: ThreadStandard,
+    /// `male` for the external thread body, `female` for the internal thread cavity
+    #[serde(rename = "type")]
+    pub thread_type: ThreadType,
+    /// Nominal diameter: inches for imperial threads, millimetres otherwise.
+    /// With `tolerance` it must match the standard's tables ("10", "20", "1/4").
+    pub diameter: String,
+    /// Pitch in millimetres (metric, trapezoidal, pipe)
+    pub pitch: Option<f64>,
+    /// Threads per inch (imperial, pipe)
+    pub tpi: Option<f64>,
+    /// Tolerance class ("6g", "7H", "2A"); the model is made to the middle of the zones
+    pub tolerance: Option<String>,
+    #[serde(default)]
+    pub hand: Hand,
+    /// Number of starts, 1 by default
+    pub starts: Option<usize>,
+    /// Threaded length in millimetres
+    pub length: f64,
+    /// Printer clearance in millimetres, removed from a body and added to a cavity
+    pub clearance: Option<f64>,
+    #[serde(default)]
+    pub encoding: StlEncoding,
+    /// Facets per turn, 96 by default
+    pub segments: Option<usize>,
+}
@@ -0,0 +1,118 @@
+use utoipa::openapi::security::{
+    ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::shared::enums::{Language, Theme, ThreadStandard, ThreadType, Unit};
+
+use super::{
+    admin, analytics, api_keys, batch, error_reports, health, imperial, metric, metrics, model, pipe, profile, search,
+    test, tolerance_zones, trapezoidal,
+};
+
+/// OpenAPI 3.1 description of every route registered in `create_application_router`
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Thread API",
+        description = "Reference data and drawings for metric, imperial, trapezoidal and pipe threads"
+    ),
+    paths(
+        metric::v1::diameters::handler::diameters,
+        metric::v1::pitch::pitch,
+        metric::v1::tolerance::tolerance,
+        metric::v1::info::handler::info,
+        metric::v1::svg::handler::svg,
+        imperial::v1::diameters::handler::handle,
+        imperial::v1::tolerance::handler::handle,
+        imperial::v1::info::handler::handle,
+        imperial::v2::info::handler::handle,
+        imperial::v1::svg_annotations::handlers::handler_get_svg_annotations::handle,
+        imperial::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle,
+        trapezoidal::v1::diameters::handler::handle,
+        trapezoidal::v1::tolerance::handler::handle,
+        trapezoidal::v1::info::handler::handle,
+        trapezoidal::v1::svg_annotations::handlers::handler_get_svg_annotations::handle,
+        trapezoidal::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle,
+        pipe::v1::diameters::handler::handle,
+        pipe::v1::info::handler::handle,
+        profile::v1::svg::handlers::handler_get_svg_profile::handle,
+        profile::v1::dxf::handlers::handler_get_dxf_profile::handle,
+        tolerance_zones::v1::svg::handlers::handler_get_svg_tolerance_zones::handle,
+        model::v1::stl::handlers::handler_get_stl_model::handle,
+        batch::handler::handle,
+        search::handler::handle,
+        analytics::handler::popular,
+        analytics::handler::timeseries,
+        error_reports::handlers::create_error_report,
+        error_reports::handlers::list,
+        error_reports::handlers::groups,
+        error_reports::handlers::resolve,
+        error_reports::handlers::export,
+        admin::data_check::handler::handle,
+        api_keys::handler::issue,
+        api_keys::handler::list,
+        api_keys::handler::rotate,
+        api_keys::handler::revoke,
+        api_keys::handler::usage,
+        health::handler::health,
+        health::handler::live,
+        health::handler::ready,
+        metrics::handler::metrics,
+        test::test,
+    ),
+    components(schemas(Language, Unit, Theme, ThreadType, ThreadStandard)),
+    modifiers(&AdminToken, &ApiKeys),
+    tags(
+        (name = "metric", description = "ISO metric threads"),
+        (name = "imperial", description = "Unified inch threads (ASME B1.1)"),
+        (name = "trapezoidal", description = "ISO trapezoidal threads"),
+        (name = "pipe", description = "Pipe threads (ISO 228-1)"),
+        (name = "drawings", description = "Annotated SVG drawings, also as PNG or PDF"),
+        (name = "exports", description = "CAD and 3D printing files"),
+        (name = "batch", description = "Many lookups in one request"),
+        (name = "search", description = "Designation autocomplete"),
+        (name = "analytics", description = "Usage of thread designations, admin token required"),
+        (name = "api_keys", description = "Partner API keys: issue, rotate, revoke, usage; admin token required"),
+        (name = "error_reports", description = "Triage of client error reports, admin token required"),
+        (name = "system", description = "Health, metrics, diagnostics and error reports"),
+    )
+)]
+pub struct ApiDoc;
+
+/// `Authorization: Bearer <ADMIN_TOKEN>` of the admin routes
+struct AdminToken;
+
+impl Modify for AdminToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// `X-API-Key` of partners, optional unless `auth.require_api_key` is set;
+/// admin routes declare their own security
+struct ApiKeys;
+
+impl Modify for ApiKeys {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        openapi.security = Some(vec![
+            SecurityRequirement::new("api_key", Vec::<String>::new()),
+            SecurityRequirement::default(),
+        ]);
+    }
+}
+
+/// Swagger UI at `/docs`, the document itself at `/openapi.json`
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi())
+}
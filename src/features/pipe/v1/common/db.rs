@@ -1,7 +1,9 @@
 // src/routes/v1/pipe/core/db.rs
 
+use crate::services::telemetry;
 use crate::{log_debug, log_error};
 use sqlx::{PgPool, Row};
+use tracing::Instrument;
 
 use crate::{
     features::pipe::v1::common::models::{ModelPipeDiameterBasic, ModelPipeOtherDimensions, ModelPipeTolerance},
@@ -30,6 +32,33 @@ impl ThreadDataService {
         pitch: f64,
         thread_type: ThreadType,
         tolerance: &str,
+    ) -> Result<ThreadData, sqlx::Error> {
+        let span = tracing::info_span!(
+            "ThreadDataService::fetch_thread_data",
+            thread.standard = "pipe",
+            thread.diameter = diameter,
+            thread.pitch = pitch,
+            thread.tolerance = tolerance,
+            otel.status_code = tracing::field::Empty,
+            otel.status_description = tracing::field::Empty,
+        );
+
+        let data = self
+            .query_thread_data(diameter, pitch, thread_type, tolerance)
+            .instrument(span.clone())
+            .await;
+        if let Err(e) = &data {
+            telemetry::set_error(&span, e);
+        }
+        data
+    }
+
+    async fn query_thread_data(
+        &self,
+        diameter: i32,
+        pitch: f64,
+        thread_type: ThreadType,
+        tolerance: &str,
     ) -> Result<ThreadData, sqlx::Error> {
         let main_query = "SELECT * FROM pipe.main WHERE diameter = $1::integer AND pitch = $2::double precision";
         let basic_dim_query = "SELECT * FROM pipe.basic_dimensions WHERE p = $1::double precision";
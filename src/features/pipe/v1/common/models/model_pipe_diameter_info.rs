@@ -1,6 +1,7 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, PartialEq, Clone, ToSchema)]
 pub struct ModelPipeDiameterInfo {
     pub name: String,
     pub max: String,
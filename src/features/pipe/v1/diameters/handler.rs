@@ -1,39 +1,25 @@
 use super::models::ResponsePipeDiameters;
-use crate::features::pipe::v1::common::models::model_pipe_db::ModelPipeDB;
 use crate::log_error;
+use crate::shared::database::service::PostgresService;
 use axum::{extract::Extension, http::StatusCode, response::IntoResponse, response::Response, Json};
-use sqlx::PgPool;
+use std::sync::Arc;
 
-const QUERY_PIPE: &str = "
-SELECT
-    id,
-    designation,
-    designation_2,
-    thread_pitch,
-    thread_per,
-    class_name,
-    ex_major_dia_max,
-    NULL::double precision as ex_major_dia_min,
-    NULL::double precision as ex_pitch_diam_max,
-    NULL::double precision as ex_pitch_diam_min,
-    NULL::double precision as ex_minor_dia_max,
-    NULL::double precision as in_minor_dia_min,
-    NULL::double precision as in_minor_dia_max,
-    NULL::double precision as in_pitch_diam_min,
-    NULL::double precision as in_pitch_diam_max,
-    in_major_dia_min,
-    NULL::double precision as in_tap_drill
-FROM pipe.main
-ORDER BY designation ASC;
-";
-
-pub async fn handle(Extension(pool): Extension<PgPool>) -> Response {
+#[utoipa::path(
+    get,
+    path = "/v1/pipe/diameters",
+    tag = "pipe",
+    responses(
+        (status = 200, description = "Pipe thread sizes by type", body = ResponsePipeDiameters),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Extension(database): Extension<Arc<PostgresService>>) -> Response {
     // Выполнение запроса к базе данных
-    let db_records = match fetch_diameters(&pool).await {
+    let db_records = match database.repository_pipe.list().await {
         Ok(records) => records,
         Err(e) => {
             log_error!("Ошибка при выполнении запроса к базе данных: {}", e);
-            return internal_server_error();
+            return e.into_response();
         }
     };
 
@@ -41,15 +27,3 @@ pub async fn handle(Extension(pool): Extension<PgPool>) -> Response {
 
     (StatusCode::OK, Json(response)).into_response()
 }
-
-async fn fetch_diameters(pool: &PgPool) -> Result<Vec<ModelPipeDB>, sqlx::Error> {
-    sqlx::query_as::<_, ModelPipeDB>(QUERY_PIPE).fetch_all(pool).await
-}
-
-fn internal_server_error() -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({ "error": "Внутренняя ошибка сервера" })),
-    )
-        .into_response()
-}
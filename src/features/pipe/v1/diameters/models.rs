@@ -1,6 +1,7 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ModelPipeDiameter {
     pub id: i32,
     pub fractional: String,
@@ -9,7 +10,7 @@ pub struct ModelPipeDiameter {
     pub tolerance: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ResponsePipeDiameters {
     pub male: Vec<ModelPipeDiameter>,
     pub female: Vec<ModelPipeDiameter>,
@@ -1,68 +1,62 @@
 // src/routes/v1/pipe/info/handler_pipe_info.rs
 use crate::{
-    analytics::db::handle_thread_analytics,
-    features::pipe::v1::{
-        common::models::model_pipe_db::ModelPipeDB,
-        info::models::{request_pipe_info::RequestPipeInfo, response_pipe_info::ResponsePipeInfo},
+    analytics::pipeline::AnalyticsRecorder,
+    features::pipe::v1::info::models::{request_pipe_info::RequestPipeInfo, response_pipe_info::ResponsePipeInfo},
+    shared::database::{
+        repositories::{required, ThreadUsage},
+        service::PostgresService,
     },
+    shared::enums::ThreadStandard,
+    shared::error::AppError,
+    shared::extract::Query,
 };
 use axum::{extract::Extension, http::StatusCode, response::IntoResponse, response::Response, Json};
-use sqlx::query_as;
-use sqlx::PgPool;
+use serde_json::json;
+use std::sync::Arc;
 
 use crate::log_error;
 
-use axum::extract::Query;
-
-const QUERY_PIPE: &str = "
-SELECT id,
-       designation,
-       designation_2,
-       thread_pitch,
-       thread_per,
-       class_name,
-       ex_major_dia_max,
-       ex_major_dia_min,
-       ex_pitch_diam_max,
-       ex_pitch_diam_min,
-       ex_minor_dia_max,
-       in_minor_dia_min,
-       in_minor_dia_max,
-       in_pitch_diam_min,
-       in_pitch_diam_max,
-       in_major_dia_min,
-       in_tap_drill
-FROM pipe.main
-where id = $1;
-";
-
-pub async fn handle(Extension(pool): Extension<PgPool>, Query(request): Query<RequestPipeInfo>) -> Response {
-    // Выполнение запроса к базе данных
-    let db_records = query_as::<_, ModelPipeDB>(QUERY_PIPE)
-        .bind(request.id)
-        .fetch_one(&pool)
-        .await;
-
-    let db_records = match db_records {
-        Ok(records) => records,
+#[utoipa::path(
+    get,
+    path = "/v1/pipe/info",
+    tag = "pipe",
+    params(RequestPipeInfo),
+    responses(
+        (status = 200, description = "Dimensions and deviations of the thread", body = ResponsePipeInfo),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No pipe thread with this id"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
+    Query(request): Query<RequestPipeInfo>,
+) -> Response {
+    let response = match lookup(&database, &request).await {
+        Ok(response) => response,
         Err(e) => {
             log_error!("Ошибка при выполнении запроса к базе данных: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": "Внутренняя ошибка сервера" })),
-            )
-                .into_response();
+            return e.into_response();
         }
     };
-    let response = ResponsePipeInfo::from_data(db_records, &request);
 
-    // Clone pool and designation for background task
-    let pool_clone = pool.clone();
-    let designation_clone = response.designation1.clone();
-    // Spawn background task for analytics
-    tokio::spawn(async move {
-        handle_thread_analytics(pool_clone, designation_clone).await;
-    });
+    analytics.record(ThreadUsage::new(
+        response.designation1.clone(),
+        ThreadStandard::Pipe,
+        request.language,
+    ));
 
     (StatusCode::OK, Json(response)).into_response()
 }
+
+/// Finds the thread by id and builds the response
+pub async fn lookup(database: &PostgresService, request: &RequestPipeInfo) -> Result<ResponsePipeInfo, AppError> {
+    let db_records = database
+        .repository_pipe
+        .find_by_id(request.id)
+        .await
+        .and_then(required(|| json!({ "id": request.id })))?;
+
+    Ok(ResponsePipeInfo::from_data(db_records, request))
+}
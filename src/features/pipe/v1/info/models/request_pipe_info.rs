@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::shared::enums::{Language, Unit};
 
 // Request models
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
 pub struct RequestPipeInfo {
     pub id: i32,
     pub language: Language,
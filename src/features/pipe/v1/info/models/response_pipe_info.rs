@@ -1,10 +1,11 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::features::pipe::v1::common::models::ModelPipeAdditionalInfo;
 use crate::features::pipe::v1::common::models::ModelPipeDiameterInfo;
 
 // Response models
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ResponsePipeInfo {
     pub designation1: String,
     pub designation2: String,
@@ -0,0 +1,110 @@
+use crate::features::tolerance_zones::common::db::ToleranceZoneService;
+use crate::services::geometry::ThreadProfile;
+use crate::services::svg::tolerance_renderer::ToleranceZones;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::{ThreadStandard, ThreadType};
+use crate::shared::error::AppError;
+use crate::shared::utils::number::NumberFormatter;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Thread selected by the export endpoints
+#[derive(Debug, Clone)]
+pub struct ResolvedThread {
+    pub profile: ThreadProfile,
+    /// Tolerance zones of the requested class
+    pub zones: Option<ToleranceZones>,
+    /// Designation with the tolerance class, e.g. "M10×1.5-6g"
+    pub designation: String,
+}
+
+/// Resolves a thread from the reference tables when a tolerance class is given,
+/// otherwise builds the basic profile from `diameter` as a number.
+pub async fn resolve_thread(
+    database: &PostgresService,
+    standard: ThreadStandard,
+    thread_type: ThreadType,
+    diameter: &str,
+    pitch: Option<f64>,
+    tpi: Option<f64>,
+    tolerance: Option<&str>,
+) -> Result<ResolvedThread, AppError> {
+    match tolerance.map(str::trim) {
+        Some(class) if !class.is_empty() => {
+            let tolerance = ToleranceZoneService::new(database)
+                .fetch(standard, thread_type, diameter, pitch, tpi, class)
+                .await?;
+            Ok(ResolvedThread {
+                profile: tolerance.profile,
+                zones: Some(tolerance.zones),
+                designation: format!("{}-{}", tolerance.designation, class),
+            })
+        }
+        _ => {
+            let nominal = diameter
+                .parse::<f64>()
+                .map_err(|_| AppError::InvalidThreadParams(format!("invalid diameter '{}'", diameter)))?;
+            let profile = basic_profile(standard, thread_type, nominal, pitch, tpi)?;
+            // Imperial profiles carry the pitch in millimetres, designations show it in inches
+            let pitch = match standard {
+                ThreadStandard::Imperial => profile.pitch / MM_PER_INCH,
+                _ => profile.pitch,
+            };
+            Ok(ResolvedThread {
+                profile,
+                zones: None,
+                designation: designation(standard, nominal, pitch),
+            })
+        }
+    }
+}
+
+/// Basic profile from the request parameters shared by the profile endpoints.
+/// `diameter` is in inches for imperial threads, `tpi` is accepted for imperial and pipe threads.
+pub fn basic_profile(
+    standard: ThreadStandard,
+    thread_type: ThreadType,
+    diameter: f64,
+    pitch: Option<f64>,
+    tpi: Option<f64>,
+) -> Result<ThreadProfile, AppError> {
+    if !diameter.is_finite() || diameter <= 0.0 {
+        return Err(AppError::InvalidThreadParams("diameter must be positive".to_string()));
+    }
+
+    let pitch = match (standard, pitch, tpi) {
+        // Imperial profiles are built from inches, pitch = 1/TPI
+        (ThreadStandard::Imperial, _, Some(tpi)) if tpi > 0.0 => 1.0 / tpi,
+        (ThreadStandard::Imperial, Some(pitch), None) if pitch > 0.0 => pitch / MM_PER_INCH,
+        (ThreadStandard::Pipe, None, Some(tpi)) if tpi > 0.0 => MM_PER_INCH / tpi,
+        (ThreadStandard::Metric | ThreadStandard::Trapezoidal | ThreadStandard::Pipe, Some(pitch), _)
+            if pitch > 0.0 =>
+        {
+            pitch
+        }
+        _ => {
+            return Err(AppError::InvalidThreadParams(
+                "a positive pitch (or tpi for imperial and pipe threads) is required".to_string(),
+            ))
+        }
+    };
+
+    let profile = ThreadProfile::for_standard(standard, thread_type, diameter, pitch);
+    if profile.root_level <= 0.0 || profile.crest_level <= 0.0 {
+        return Err(AppError::InvalidThreadParams(
+            "pitch is too large for the given diameter".to_string(),
+        ));
+    }
+    Ok(profile)
+}
+
+/// Designation of a thread, e.g. "M10×1.5"; imperial pitch in inches
+pub fn designation(standard: ThreadStandard, diameter: f64, pitch: f64) -> String {
+    let number = |value: f64| NumberFormatter::format_number_trim_zeros(value, Some(4));
+    match standard {
+        ThreadStandard::Metric => format!("M{}×{}", number(diameter), number(pitch)),
+        ThreadStandard::Trapezoidal => format!("Tr{}×{}", number(diameter), number(pitch)),
+        ThreadStandard::Imperial => format!("{}\"-{}", number(diameter), number(1.0 / pitch)),
+        ThreadStandard::Pipe => format!("ø{}×{}", number(diameter), number(pitch)),
+    }
+}
@@ -0,0 +1,61 @@
+use axum::body::Body;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::Extension;
+use std::sync::Arc;
+
+use crate::features::profile::common::resolve_thread;
+use crate::features::profile::v1::dxf::models::RequestDxfProfile;
+use crate::services::dxf::profile_exporter::{ProfileDxfExporter, ProfileDxfOptions};
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+const MAX_PITCHES: usize = 8;
+
+/// Axial section of the thread as an R12 DXF for CAD import.
+/// With a tolerance class the profiles at maximum and minimum material are added on their own layers.
+#[utoipa::path(
+    get,
+    path = "/v1/profile/dxf",
+    tag = "exports",
+    params(RequestDxfProfile),
+    responses(
+        (status = 200, description = "Thread profile as a DXF drawing", body = String, content_type = "application/dxf"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<RequestDxfProfile>,
+) -> Result<Response<Body>, AppError> {
+    let thread = resolve_thread(
+        &database,
+        params.standard,
+        params.thread_type,
+        &params.diameter,
+        params.pitch,
+        params.tpi,
+        params.tolerance.as_deref(),
+    )
+    .await?;
+
+    let options = ProfileDxfOptions {
+        // R12 text is single-byte, use the AutoCAD control codes instead
+        title: thread.designation.replace('×', "x").replace('ø', "%%c"),
+        precision: params.precision.unwrap_or(3),
+        pitches: params.pitches.unwrap_or(3).clamp(1, MAX_PITCHES),
+    };
+    let content = ProfileDxfExporter::export(&thread.profile, thread.zones.as_ref(), &options);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/dxf"));
+    headers.insert(
+        CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"thread-profile.dxf\""),
+    );
+    Ok((StatusCode::OK, headers, content.into_bytes()).into_response())
+}
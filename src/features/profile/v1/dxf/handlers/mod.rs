@@ -0,0 +1 @@
+pub mod handler_get_dxf_profile;
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::shared::enums::{ThreadStandard, ThreadType};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestDxfProfile {
+    pub standard: ThreadStandard,
+    #[serde(rename = "type")]
+    pub thread_type: ThreadType,
+    /// Nominal diameter: inches for imperial threads, millimetres otherwise.
+    /// With `tolerance` it must match the standard's tables ("10", "20", "1/4").
+    pub diameter: String,
+    /// Pitch in millimetres (metric, trapezoidal, pipe)
+    pub pitch: Option<f64>,
+    /// Threads per inch (imperial, pipe)
+    pub tpi: Option<f64>,
+    /// Tolerance class ("6g", "7H", "2A"); adds the maximum and minimum material profiles
+    pub tolerance: Option<String>,
+    pub precision: Option<usize>,
+    /// Number of pitches drawn, 3 by default
+    pub pitches: Option<usize>,
+}
@@ -0,0 +1,2 @@
+pub mod dxf;
+pub mod svg;
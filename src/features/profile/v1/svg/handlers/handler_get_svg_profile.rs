@@ -0,0 +1,67 @@
+use axum::body::Body;
+use axum::http::Response;
+
+use crate::features::profile::common::{basic_profile, designation};
+use crate::features::profile::v1::svg::models::RequestSvgProfile;
+use crate::services::svg::export::ExportParams;
+use crate::services::svg::profile_renderer::{ProfileDrawingOptions, ProfileRenderer};
+use crate::services::svg::SvgService;
+use crate::shared::enums::{Language, ThreadStandard, ThreadType};
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+const MM_PER_INCH: f64 = 25.4;
+const MAX_PITCHES: usize = 8;
+
+/// Basic thread profile drawn to scale from the standard's formulas
+#[utoipa::path(
+    get,
+    path = "/v1/profile/svg",
+    tag = "drawings",
+    params(RequestSvgProfile, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Query(params): Query<RequestSvgProfile>) -> Result<Response<Body>, AppError> {
+    let profile = basic_profile(
+        params.standard,
+        params.thread_type,
+        params.diameter,
+        params.pitch,
+        params.tpi,
+    )?;
+    // Imperial profiles carry the pitch in millimetres, the title shows it in inches
+    let pitch = match params.standard {
+        ThreadStandard::Imperial => profile.pitch / MM_PER_INCH,
+        _ => profile.pitch,
+    };
+
+    let options = ProfileDrawingOptions {
+        title: title(&params, pitch),
+        theme: params.theme,
+        language: params.language,
+        units: params.units,
+        precision: Some(params.precision.unwrap_or(3)),
+        pitches: params.pitches.unwrap_or(3).clamp(3, MAX_PITCHES),
+    };
+
+    let svg_service = SvgService::new("./static/svg");
+    let svg_content = ProfileRenderer::new(&svg_service).render(&profile, &options);
+
+    Ok(svg_service.create_svg_response(svg_content))
+}
+
+/// Designation of the drawn thread, e.g. "M10×1.5 — external thread"
+fn title(params: &RequestSvgProfile, pitch: f64) -> String {
+    let designation = designation(params.standard, params.diameter, pitch);
+    let kind = match (params.language, params.thread_type) {
+        (Language::Ru, ThreadType::Male) => "наружная резьба",
+        (Language::Ru, ThreadType::Female) => "внутренняя резьба",
+        (Language::En, ThreadType::Male) => "external thread",
+        (Language::En, ThreadType::Female) => "internal thread",
+    };
+    format!("{} — {}", designation, kind)
+}
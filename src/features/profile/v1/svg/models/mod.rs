@@ -0,0 +1,3 @@
+pub mod request;
+
+pub use request::RequestSvgProfile;
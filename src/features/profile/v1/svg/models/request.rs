@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::shared::enums::{Language, Theme, ThreadStandard, ThreadType, Unit};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestSvgProfile {
+    pub standard: ThreadStandard,
+    #[serde(rename = "type")]
+    pub thread_type: ThreadType,
+    /// Nominal diameter: inches for imperial threads, millimetres otherwise
+    pub diameter: f64,
+    /// Pitch in millimetres (metric, trapezoidal, pipe)
+    pub pitch: Option<f64>,
+    /// Threads per inch (imperial, pipe)
+    pub tpi: Option<f64>,
+    pub theme: Theme,
+    pub language: Language,
+    pub units: Unit,
+    pub precision: Option<usize>,
+    /// Number of pitches shown, 3 by default
+    pub pitches: Option<usize>,
+}
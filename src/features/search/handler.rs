@@ -0,0 +1,40 @@
+use axum::{extract::Extension, Json};
+use std::sync::Arc;
+
+use crate::features::search::models::SearchParams;
+use crate::services::search::SearchHit;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+use crate::shared::setting::models::app_state::AppState;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+/// Designation suggestions across all standards, the most used sizes first
+#[utoipa::path(
+    get,
+    path = "/v1/search",
+    tag = "search",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Matching sizes, best matches first", body = Vec<SearchHit>),
+        (status = 400, description = "Empty query"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<SearchHit>>, AppError> {
+    let query = params.q.trim();
+    if query.is_empty() {
+        return Err(AppError::BadRequest("Parameter 'q' must not be empty".to_string()));
+    }
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let hits = app_state
+        .search_index
+        .search(&app_state.postgres_service, query, params.standard, limit)
+        .await?;
+    Ok(Json(hits))
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::shared::enums::ThreadStandard;
+
+/// Parameters of the designation search
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    /// Start or part of a designation: "M1", "1/4", "Tr 2", "G 1/2"
+    pub q: String,
+    /// Only threads of one standard
+    pub standard: Option<ThreadStandard>,
+    /// Number of suggestions, 10 by default, at most 50
+    pub limit: Option<usize>,
+}
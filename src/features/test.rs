@@ -1,5 +1,6 @@
 use axum::{http::StatusCode, response::IntoResponse};
 
+#[utoipa::path(get, path = "/test", tag = "system", responses((status = 200, description = "Server is up")))]
 pub async fn test() -> impl IntoResponse {
     // info!("Handling test request");
     StatusCode::OK
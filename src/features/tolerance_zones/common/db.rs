@@ -0,0 +1,281 @@
+use crate::features::trapezoidal::common::db::ThreadDataService;
+use crate::log_error;
+use crate::services::geometry::ThreadProfile;
+use crate::services::svg::tolerance_renderer::{DiameterZone, ToleranceZones};
+use crate::shared::database::repositories::MetricInfoQuery;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::{ThreadStandard, ThreadType};
+use crate::shared::error::AppError;
+use crate::shared::utils::db::JsonRow;
+use crate::shared::utils::number::NumberFormatter;
+use serde_json::json;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Tolerance zones of one thread together with its basic profile
+#[derive(Debug, Clone)]
+pub struct ThreadTolerance {
+    pub designation: String,
+    pub profile: ThreadProfile,
+    pub zones: ToleranceZones,
+}
+
+/// Collects limits of size from the reference tables of each standard
+pub struct ToleranceZoneService<'a> {
+    database: &'a PostgresService,
+}
+
+impl<'a> ToleranceZoneService<'a> {
+    pub fn new(database: &'a PostgresService) -> Self {
+        Self { database }
+    }
+
+    pub async fn fetch(
+        &self,
+        standard: ThreadStandard,
+        thread_type: ThreadType,
+        diameter: &str,
+        pitch: Option<f64>,
+        tpi: Option<f64>,
+        class: &str,
+    ) -> Result<ThreadTolerance, AppError> {
+        match standard {
+            ThreadStandard::Metric => {
+                let diameter = Self::parse_diameter(diameter)?;
+                self.fetch_metric(thread_type, diameter, Self::require(pitch, "pitch")?, class)
+                    .await
+            }
+            ThreadStandard::Trapezoidal => {
+                let diameter = diameter
+                    .parse::<i32>()
+                    .map_err(|_| AppError::InvalidThreadParams(format!("invalid diameter '{}'", diameter)))?;
+                self.fetch_trapezoidal(thread_type, diameter, Self::require(pitch, "pitch")?, class)
+                    .await
+            }
+            ThreadStandard::Imperial => {
+                self.fetch_imperial(thread_type, diameter, Self::require(tpi, "tpi")?, class)
+                    .await
+            }
+            ThreadStandard::Pipe => Err(AppError::InvalidThreadParams(
+                "tolerance zones are not available for pipe threads".to_string(),
+            )),
+        }
+    }
+
+    fn parse_diameter(diameter: &str) -> Result<f64, AppError> {
+        diameter
+            .parse::<f64>()
+            .map_err(|_| AppError::InvalidThreadParams(format!("invalid diameter '{}'", diameter)))
+    }
+
+    fn require(value: Option<f64>, name: &str) -> Result<f64, AppError> {
+        value
+            .filter(|v| *v > 0.0)
+            .ok_or_else(|| AppError::InvalidThreadParams(format!("a positive {} is required", name)))
+    }
+
+    /// ISO 965 limits from `metric.get_info`, requested in millimetres
+    async fn fetch_metric(
+        &self,
+        thread_type: ThreadType,
+        diameter: f64,
+        pitch: f64,
+        class: &str,
+    ) -> Result<ThreadTolerance, AppError> {
+        let query = MetricInfoQuery {
+            diameter,
+            pitch,
+            type_: thread_type.to_string(),
+            tolerance: class.to_string(),
+            language: "en".to_string(),
+            units: "mm".to_string(),
+        };
+        let row = self
+            .database
+            .repository_metric
+            .get_info(&query)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::thread_not_found(json!({ "diameter": diameter, "pitch": pitch, "tolerance": class }))
+            })?;
+
+        let major_max = Self::get_optional_f64(&row, "major_diam_max");
+        let major_min = Self::get_f64(&row, "major_diam_min")?;
+        let pitch_zone = DiameterZone::from_limits(
+            Self::get_f64(&row, "pitch_diam_d2")?,
+            Some(Self::get_f64(&row, "pitch_diam_max")?),
+            Some(Self::get_f64(&row, "pitch_diam_min")?),
+        );
+        let minor_d1 = DiameterZone::from_limits(
+            Self::get_f64(&row, "minor_diam_d1")?,
+            Self::get_optional_f64(&row, "minor_diam_max"),
+            Self::get_optional_f64(&row, "minor_diam_min"),
+        );
+
+        let zones = match thread_type {
+            ThreadType::Male => {
+                let minor_d3_max = Self::get_optional_f64(&row, "minor_diam_max_d3");
+                let minor = match minor_d3_max {
+                    Some(max) => DiameterZone::from_limits(
+                        Self::get_f64(&row, "minor_diam_d3")?,
+                        Some(max),
+                        Self::get_optional_f64(&row, "minor_diam_min_d3"),
+                    ),
+                    None => minor_d1,
+                };
+                ToleranceZones {
+                    thread_type,
+                    class: class.to_string(),
+                    major: DiameterZone::from_limits(diameter, major_max, Some(major_min)),
+                    pitch: pitch_zone,
+                    minor,
+                }
+            }
+            // The maximum major diameter of an internal thread is not specified
+            ThreadType::Female => ToleranceZones {
+                thread_type,
+                class: class.to_string(),
+                major: DiameterZone::from_limits(diameter, None, Some(major_min)),
+                pitch: pitch_zone,
+                minor: minor_d1,
+            },
+        };
+
+        let number = |value: f64| NumberFormatter::format_number_trim_zeros(value, Some(3));
+        Ok(ThreadTolerance {
+            designation: format!("M{}×{}", number(diameter), number(pitch)),
+            profile: ThreadProfile::metric(thread_type, diameter, pitch),
+            zones,
+        })
+    }
+
+    /// ISO 2903 deviations from the trapezoidal tables
+    async fn fetch_trapezoidal(
+        &self,
+        thread_type: ThreadType,
+        diameter: i32,
+        pitch: f64,
+        class: &str,
+    ) -> Result<ThreadTolerance, AppError> {
+        let data = ThreadDataService::new(self.database.repository_trapezoidal.clone())
+            .fetch_thread_data(diameter, pitch, thread_type, class)
+            .await?;
+        let basic = &data.basic_diameters;
+        let tolerances = &data.tolerances;
+
+        let zones = match thread_type {
+            ThreadType::Male => ToleranceZones {
+                thread_type,
+                class: class.to_string(),
+                major: DiameterZone {
+                    basic: basic.d,
+                    es: Some(tolerances.es_d),
+                    ei: Some(tolerances.ei_d),
+                },
+                pitch: DiameterZone {
+                    basic: basic.d2,
+                    es: Some(tolerances.es_d2),
+                    ei: Some(tolerances.ei_d2),
+                },
+                minor: DiameterZone {
+                    basic: basic.d3,
+                    es: Some(tolerances.es_d3),
+                    ei: Some(tolerances.ei_d3),
+                },
+            },
+            // Only the minimum of D4 is specified
+            ThreadType::Female => ToleranceZones {
+                thread_type,
+                class: class.to_string(),
+                major: DiameterZone {
+                    basic: basic.d4,
+                    es: None,
+                    ei: Some(0.0),
+                },
+                pitch: DiameterZone {
+                    basic: basic.d2,
+                    es: Some(tolerances.es_d2),
+                    ei: Some(tolerances.ei_d2),
+                },
+                minor: DiameterZone {
+                    basic: basic.d1,
+                    es: Some(tolerances.es_d1),
+                    ei: Some(tolerances.ei_d1),
+                },
+            },
+        };
+
+        Ok(ThreadTolerance {
+            designation: format!(
+                "Tr{}×{}",
+                diameter,
+                NumberFormatter::format_number_trim_zeros(pitch, Some(3))
+            ),
+            profile: ThreadProfile::trapezoidal(thread_type, diameter as f64, pitch),
+            zones,
+        })
+    }
+
+    /// ASME B1.1 limits from `imperial.main`, converted to millimetres
+    async fn fetch_imperial(
+        &self,
+        thread_type: ThreadType,
+        diameter: &str,
+        tpi: f64,
+        class: &str,
+    ) -> Result<ThreadTolerance, AppError> {
+        let record = self
+            .database
+            .repository_imperial
+            .find_thread(diameter, tpi, thread_type, class)
+            .await?
+            .ok_or_else(|| AppError::thread_not_found(json!({ "diameter": diameter, "tpi": tpi, "series": class })))?;
+
+        let mm = |inch: f64| inch * MM_PER_INCH;
+        let p = 1.0 / tpi;
+        let h = 3f64.sqrt() / 2.0 * p;
+        let d = record.diameter_2;
+        let d2 = d - 0.75 * h;
+        let d1 = d - 1.25 * h;
+        let zone = |basic: f64, max: Option<f64>, min: Option<f64>| {
+            DiameterZone::from_limits(mm(basic), max.map(mm), min.map(mm))
+        };
+
+        let zones = match thread_type {
+            ThreadType::Male => ToleranceZones {
+                thread_type,
+                class: record.class_m.clone(),
+                major: zone(d, Some(record.major_diam_max_m), Some(record.major_diam_min_m)),
+                pitch: zone(d2, Some(record.pitch_diameter_max_m), Some(record.pitch_diameter_min_m)),
+                // UNR minor diameter, limited only from above
+                minor: zone(d1, Some(record.unr_minor_diameter_max_m), None),
+            },
+            ThreadType::Female => ToleranceZones {
+                thread_type,
+                class: record.class_f.clone(),
+                major: zone(d, None, Some(record.major_diameter_min_f)),
+                pitch: zone(d2, Some(record.pitch_diameter_max_f), Some(record.pitch_diameter_min_f)),
+                minor: zone(d1, Some(record.minor_diameter_max_f), Some(record.minor_diameter_min_f)),
+            },
+        };
+
+        Ok(ThreadTolerance {
+            designation: format!("{}-{} {}", record.diameter, record.tpi, record.series_designation),
+            profile: ThreadProfile::unified(thread_type, d, tpi),
+            zones,
+        })
+    }
+
+    fn get_f64(row: &JsonRow, name: &str) -> Result<f64, AppError> {
+        row.get(name).and_then(|v| v.as_f64()).ok_or_else(|| {
+            log_error!("Error getting value for {}", name);
+            AppError::DatabaseError(format!("column '{}' is missing or not a number", name))
+        })
+    }
+
+    fn get_optional_f64(row: &JsonRow, name: &str) -> Option<f64> {
+        row.get(name).and_then(|v| v.as_f64())
+    }
+}
@@ -0,0 +1,79 @@
+use axum::body::Body;
+use axum::http::Response;
+use axum::Extension;
+use std::sync::Arc;
+
+use crate::features::tolerance_zones::common::db::ToleranceZoneService;
+use crate::features::tolerance_zones::v1::svg::models::RequestSvgToleranceZones;
+use crate::services::svg::export::ExportParams;
+use crate::services::svg::tolerance_renderer::{ToleranceDrawingOptions, ToleranceRenderer};
+use crate::services::svg::SvgService;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::ThreadType;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+/// Tolerance zones of a thread class, or of both threads of a fit, against the basic profile
+#[utoipa::path(
+    get,
+    path = "/v1/tolerance-zones/svg",
+    tag = "drawings",
+    params(RequestSvgToleranceZones, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<RequestSvgToleranceZones>,
+) -> Result<Response<Body>, AppError> {
+    let service = ToleranceZoneService::new(&database);
+
+    let mut tolerances = Vec::new();
+    for (thread_type, class) in requested_classes(&params)? {
+        let tolerance = service
+            .fetch(
+                params.standard,
+                thread_type,
+                &params.diameter,
+                params.pitch,
+                params.tpi,
+                class,
+            )
+            .await?;
+        tolerances.push(tolerance);
+    }
+
+    let options = ToleranceDrawingOptions {
+        title: format!("{}-{}", tolerances[0].designation, params.tolerance.trim()),
+        theme: params.theme,
+        language: params.language,
+        units: params.units,
+        precision: Some(params.precision.unwrap_or(3)),
+    };
+    let profile = tolerances[0].profile;
+    let zones: Vec<_> = tolerances.into_iter().map(|tolerance| tolerance.zones).collect();
+
+    let svg_service = SvgService::new("./static/svg");
+    let svg_content = ToleranceRenderer::new(&svg_service).render(&profile, &zones, &options);
+
+    Ok(svg_service.create_svg_response(svg_content))
+}
+
+/// "6H/6g" selects the female and male classes of a fit, a single class needs `type`
+fn requested_classes(params: &RequestSvgToleranceZones) -> Result<Vec<(ThreadType, &str)>, AppError> {
+    let tolerance = params.tolerance.trim();
+    match (tolerance.split_once('/'), params.thread_type) {
+        (Some((female, male)), _) if !female.trim().is_empty() && !male.trim().is_empty() => Ok(vec![
+            (ThreadType::Female, female.trim()),
+            (ThreadType::Male, male.trim()),
+        ]),
+        (None, Some(thread_type)) if !tolerance.is_empty() => Ok(vec![(thread_type, tolerance)]),
+        _ => Err(AppError::InvalidThreadParams(
+            "expected a tolerance class with `type`, or a female/male pair such as 6H/6g".to_string(),
+        )),
+    }
+}
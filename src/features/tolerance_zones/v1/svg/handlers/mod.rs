@@ -0,0 +1 @@
+pub mod handler_get_svg_tolerance_zones;
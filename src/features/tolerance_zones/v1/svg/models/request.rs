@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::shared::enums::{Language, Theme, ThreadStandard, ThreadType, Unit};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestSvgToleranceZones {
+    pub standard: ThreadStandard,
+    /// Omitted when `tolerance` describes a male/female pair
+    #[serde(rename = "type")]
+    pub thread_type: Option<ThreadType>,
+    /// Nominal diameter as used by the standard's endpoints ("10", "20", "1/4")
+    pub diameter: String,
+    /// Pitch in millimetres (metric, trapezoidal)
+    pub pitch: Option<f64>,
+    /// Threads per inch (imperial)
+    pub tpi: Option<f64>,
+    /// Tolerance class ("6g", "7e", "2A") or a fit "female/male" ("6H/6g", "2B/2A")
+    pub tolerance: String,
+    pub theme: Theme,
+    pub language: Language,
+    pub units: Unit,
+    pub precision: Option<usize>,
+}
@@ -1,17 +1,23 @@
 // src/routes/v1/trapezoidal/core/db.rs
 
+use serde_json::json;
+
+use crate::services::telemetry;
 use crate::{log_debug, log_error};
-use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use tracing::Instrument;
 
 use crate::{
     features::trapezoidal::common::models::{
         ModelTrapezoidalDiameterBasic, ModelTrapezoidalOtherDimensions, ModelTrapezoidalTolerance,
     },
-    shared::enums::ThreadType,
+    shared::{
+        database::repositories::TraitTrapezoidalRepository, enums::ThreadType, error::AppError, utils::db::JsonRow,
+    },
 };
 
 pub struct ThreadDataService {
-    pool: PgPool,
+    repository: Arc<dyn TraitTrapezoidalRepository + Send + Sync>,
 }
 
 #[derive(Debug)]
@@ -22,8 +28,8 @@ pub struct ThreadData {
 }
 
 impl ThreadDataService {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(repository: Arc<dyn TraitTrapezoidalRepository + Send + Sync>) -> Self {
+        Self { repository }
     }
 
     pub async fn fetch_thread_data(
@@ -32,19 +38,51 @@ impl ThreadDataService {
         pitch: f64,
         thread_type: ThreadType,
         tolerance: &str,
-    ) -> Result<ThreadData, sqlx::Error> {
-        let main_query = "SELECT * FROM trapezoidal.main WHERE diameter = $1::integer AND pitch = $2::double precision";
-        let basic_dim_query = "SELECT * FROM trapezoidal.basic_dimensions WHERE p = $1::double precision";
-
-        log_debug!("Executing queries with params: diameter={}, pitch={}", diameter, pitch);
-
-        let main_row = sqlx::query(main_query)
-            .bind(diameter)
-            .bind(pitch)
-            .fetch_one(&self.pool)
-            .await?;
+    ) -> Result<ThreadData, AppError> {
+        let span = tracing::info_span!(
+            "ThreadDataService::fetch_thread_data",
+            thread.standard = "trapezoidal",
+            thread.diameter = diameter,
+            thread.pitch = pitch,
+            thread.tolerance = tolerance,
+            otel.status_code = tracing::field::Empty,
+            otel.status_description = tracing::field::Empty,
+        );
+
+        let data = self
+            .query_thread_data(diameter, pitch, thread_type, tolerance)
+            .instrument(span.clone())
+            .await;
+        if let Err(e) = &data {
+            telemetry::set_error(&span, e);
+        }
+        data
+    }
 
-        let basic_dim_row = sqlx::query(basic_dim_query).bind(pitch).fetch_one(&self.pool).await?;
+    async fn query_thread_data(
+        &self,
+        diameter: i32,
+        pitch: f64,
+        thread_type: ThreadType,
+        tolerance: &str,
+    ) -> Result<ThreadData, AppError> {
+        log_debug!(
+            "Fetching thread data with params: diameter={}, pitch={}",
+            diameter,
+            pitch
+        );
+
+        let not_found = || AppError::thread_not_found(json!({ "diameter": diameter, "pitch": pitch }));
+        let main_row = self
+            .repository
+            .find_thread(diameter, pitch)
+            .await?
+            .ok_or_else(not_found)?;
+        let basic_dim_row = self
+            .repository
+            .find_basic_dimensions(pitch)
+            .await?
+            .ok_or_else(not_found)?;
 
         let type_suffix = match thread_type {
             ThreadType::Male => "_m",
@@ -59,11 +97,7 @@ impl ThreadDataService {
         })
     }
 
-    fn extract_basic_diameters(
-        &self,
-        row: &sqlx::postgres::PgRow,
-        diameter: i32,
-    ) -> Result<ModelTrapezoidalDiameterBasic, sqlx::Error> {
+    fn extract_basic_diameters(&self, row: &JsonRow, diameter: i32) -> Result<ModelTrapezoidalDiameterBasic, AppError> {
         Ok(ModelTrapezoidalDiameterBasic {
             d: diameter as f64,
             d1: self.get_f64(row, "d1")?,
@@ -75,10 +109,10 @@ impl ThreadDataService {
 
     fn extract_tolerances(
         &self,
-        row: &sqlx::postgres::PgRow,
+        row: &JsonRow,
         tolerance: &str,
         type_suffix: &str,
-    ) -> Result<ModelTrapezoidalTolerance, sqlx::Error> {
+    ) -> Result<ModelTrapezoidalTolerance, AppError> {
         // For female threads (_f), some tolerances should be 0.0
         let is_female = type_suffix == "_f";
         let is_male = type_suffix == "_m";
@@ -129,10 +163,7 @@ impl ThreadDataService {
         })
     }
 
-    fn extract_basic_dimensions(
-        &self,
-        row: &sqlx::postgres::PgRow,
-    ) -> Result<ModelTrapezoidalOtherDimensions, sqlx::Error> {
+    fn extract_basic_dimensions(&self, row: &JsonRow) -> Result<ModelTrapezoidalOtherDimensions, AppError> {
         Ok(ModelTrapezoidalOtherDimensions {
             p: self.get_f64(row, "p")?,
             a_c: self.get_f64(row, "a_c")?,
@@ -144,25 +175,23 @@ impl ThreadDataService {
         })
     }
 
-    fn get_f64(&self, row: &sqlx::postgres::PgRow, name: &str) -> Result<f64, sqlx::Error> {
-        match row.try_get::<f64, _>(name) {
-            Ok(v) => Ok(v),
-            Err(_) => match row.try_get::<i32, _>(name) {
-                Ok(v) => Ok(v as f64),
-                Err(e) => {
-                    log_error!("Error getting value for {}: {}", name, e);
-                    Err(e)
-                }
-            },
-        }
+    fn get_f64(&self, row: &JsonRow, name: &str) -> Result<f64, AppError> {
+        row.get(name).and_then(|v| v.as_f64()).ok_or_else(|| {
+            log_error!("Error getting value for {}", name);
+            AppError::DatabaseError(format!("column '{}' is missing or not a number", name))
+        })
     }
 
-    fn get_tolerance(&self, row: &sqlx::postgres::PgRow, name: &str) -> Result<f64, sqlx::Error> {
-        match row.try_get::<i32, _>(name) {
-            Ok(v) => Ok((v as f64) / 1000.0),
-            Err(e) => {
-                log_error!("Error getting tolerance for {}: {}", name, e);
-                Err(e)
+    /// Deviations are stored in micrometres
+    fn get_tolerance(&self, row: &JsonRow, name: &str) -> Result<f64, AppError> {
+        match row.get(name).and_then(|v| v.as_f64()) {
+            Some(v) => Ok(v / 1000.0),
+            None => {
+                log_error!("Error getting tolerance for {}", name);
+                Err(AppError::DatabaseError(format!(
+                    "tolerance column '{}' is missing or not a number",
+                    name
+                )))
             }
         }
     }
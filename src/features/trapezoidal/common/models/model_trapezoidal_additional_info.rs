@@ -1,8 +1,9 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::features::trapezoidal::common::enums::TypeTrapezoidalAdditionalInfo;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ModelTrapezoidalAdditionalInfo {
     #[serde(skip)]
     pub type_trapezoidal_additional_info: Option<TypeTrapezoidalAdditionalInfo>,
@@ -1,8 +1,9 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::features::trapezoidal::common::enums::TypeTrapezoidalDiameter;
 
-#[derive(Debug, Serialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, PartialEq, Clone, ToSchema)]
 pub struct ModelTrapezoidalDiameterInfo {
     #[serde(skip)]
     pub type_trapezoidal_diameter: Option<TypeTrapezoidalDiameter>,
@@ -1,26 +1,25 @@
-use crate::features::trapezoidal::v1::diameters::models::{DbModel, ResponseModel};
+use crate::features::trapezoidal::v1::diameters::models::ResponseModel;
 use crate::log_error;
+use crate::shared::database::service::PostgresService;
 use axum::{extract::Extension, http::StatusCode, response::IntoResponse, response::Response, Json};
-use sqlx::PgPool;
-
-const DIAMETERS_QUERY: &str = "
-SELECT DISTINCT ON (diameter, pitch)
-    id,
-    diameter,
-    pitch
-
-FROM
-    trapezoidal.main
-ORDER BY diameter, pitch, id;
-";
-
-pub async fn handle(Extension(pool): Extension<PgPool>) -> Response {
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/v1/trapezoidal/diameters",
+    tag = "trapezoidal",
+    responses(
+        (status = 200, description = "Diameter and pitch combinations", body = Vec<ResponseModel>),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Extension(database): Extension<Arc<PostgresService>>) -> Response {
     // Выполнение запроса к базе данных
-    let db_records = match fetch_diameters(&pool).await {
+    let db_records = match database.repository_trapezoidal.diameters().await {
         Ok(records) => records,
         Err(e) => {
             log_error!("Ошибка при выполнении запроса к базе данных: {}", e);
-            return internal_server_error();
+            return e.into_response();
         }
     };
 
@@ -38,15 +37,3 @@ pub async fn handle(Extension(pool): Extension<PgPool>) -> Response {
 
     (StatusCode::OK, Json(response)).into_response()
 }
-
-async fn fetch_diameters(pool: &PgPool) -> Result<Vec<DbModel>, sqlx::Error> {
-    sqlx::query_as::<_, DbModel>(DIAMETERS_QUERY).fetch_all(pool).await
-}
-
-fn internal_server_error() -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({ "error": "Внутренняя ошибка сервера" })),
-    )
-        .into_response()
-}
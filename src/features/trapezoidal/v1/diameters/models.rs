@@ -1,13 +1,8 @@
 use serde::Serialize;
-use sqlx::FromRow;
+use utoipa::ToSchema;
 
-#[derive(Serialize, FromRow)]
-pub struct DbModel {
-    pub diameter: i32,
-    pub pitch: f64,
-}
-
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+#[schema(as = TrapezoidalDiameter)]
 pub struct ResponseModel {
     pub diameter: String,
     pub pitch: String,
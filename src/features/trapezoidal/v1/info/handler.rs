@@ -1,7 +1,7 @@
 // src/routes/v1/trapezoidal/info/handler_trapezoidal_info.rs
 
 use crate::{
-    analytics::db::handle_thread_analytics,
+    analytics::pipeline::AnalyticsRecorder,
     features::trapezoidal::{
         common::{
             calculators::{calculate_additional_info, calculate_diameter_info, calculate_main_info, get_thread_info},
@@ -9,70 +9,58 @@ use crate::{
         },
         v1::info::models::{request::RequestTrapezoidalInfo, response::ResponseTrapezoidalInfo},
     },
-    shared::error::AppError,
+    shared::{
+        database::{repositories::ThreadUsage, service::PostgresService},
+        enums::ThreadStandard,
+        error::AppError,
+        extract::Query,
+    },
 };
 
 use crate::log_error;
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
-};
-use sqlx::PgPool;
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use std::sync::Arc;
 
+#[utoipa::path(
+    get,
+    path = "/v1/trapezoidal/info",
+    tag = "trapezoidal",
+    params(RequestTrapezoidalInfo),
+    responses(
+        (status = 200, description = "Dimensions and deviations of the thread", body = ResponseTrapezoidalInfo),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
 pub async fn handle(
-    Extension(pool): Extension<PgPool>,
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(analytics): Extension<AnalyticsRecorder>,
     Query(params): Query<RequestTrapezoidalInfo>,
 ) -> Result<impl IntoResponse, AppError> {
-    // Initialize database service
-    let db_service = ThreadDataService::new(pool.clone()); // Clone pool for analytics use
-
-    // Fetch thread data using the core service
-    let thread_data = match db_service
-        .fetch_thread_data(params.diameter, params.pitch, params.type_thread, &params.tolerance)
+    let (response, designation) = lookup(&database, &params)
         .await
-    {
-        Ok(data) => data,
-        Err(e) => {
-            log_error!("Database query error: {}", e);
-            let error_message = if e.to_string().contains("no rows") {
-                "No thread specifications found for the given diameter and pitch"
-            } else {
-                "An error occurred while retrieving thread specifications"
-            };
-            return Ok((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({
-                    "error": error_message,
-                    "details": e.to_string()
-                })),
-            )
-                .into_response());
-        }
-    };
+        .inspect_err(|e| log_error!("Database query error: {}", e))?;
 
-    // Get thread description and designation
-    let (_, designation) = get_thread_info(
+    analytics.record(ThreadUsage::new(
+        designation,
+        ThreadStandard::Trapezoidal,
         params.language,
-        params.type_thread,
-        params.diameter,
-        params.pitch,
-        &params.tolerance,
-    );
+    ));
 
-    // Clone designation for analytics
-    let designation_clone = designation.clone();
-
-    // Clone pool for background task
-    let pool_clone = pool.clone();
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
 
-    // Spawn background task for analytics
-    tokio::spawn(async move {
-        handle_thread_analytics(pool_clone, designation_clone).await;
-    });
+/// Builds the response from the database rows; the second element is the designation for the analytics
+pub async fn lookup(
+    database: &PostgresService,
+    params: &RequestTrapezoidalInfo,
+) -> Result<(ResponseTrapezoidalInfo, String), AppError> {
+    let db_service = ThreadDataService::new(database.repository_trapezoidal.clone());
+    let thread_data = db_service
+        .fetch_thread_data(params.diameter, params.pitch, params.type_thread, &params.tolerance)
+        .await?;
 
-    // Calculate response data
     let (description, designation) = get_thread_info(
         params.language,
         params.type_thread,
@@ -101,10 +89,9 @@ pub async fn handle(
         &thread_data.tolerances,
     );
 
-    // Create the response with all thread info
     let response = ResponseTrapezoidalInfo {
         description,
-        designation,
+        designation: designation.clone(),
         main_info,
         diameter_info,
         additional_info: calculate_additional_info(
@@ -116,5 +103,5 @@ pub async fn handle(
         ),
     };
 
-    Ok((StatusCode::OK, Json(response)).into_response())
+    Ok((response, designation))
 }
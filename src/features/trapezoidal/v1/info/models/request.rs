@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::shared::enums::{Language, ThreadType, Unit};
 
 // Request models
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
 pub struct RequestTrapezoidalInfo {
     pub diameter: i32,
     pub pitch: f64,
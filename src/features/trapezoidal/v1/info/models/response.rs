@@ -1,10 +1,11 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::features::trapezoidal::common::models::ModelTrapezoidalAdditionalInfo;
 use crate::features::trapezoidal::common::models::ModelTrapezoidalDiameterInfo;
 
 // Response models
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ResponseTrapezoidalInfo {
     pub description: String,
     pub designation: String,
@@ -1,20 +1,28 @@
 use axum::body::Body;
 
 use axum::http::Response;
-use axum::{extract::Query, Extension};
-use sqlx::PgPool;
 
 use crate::features::trapezoidal::v1::svg_annotations::models::RequestSvgAnnotation;
 use crate::services::svg::enums::{FontFamily, FontWeight, TextAnchor};
+use crate::services::svg::export::ExportParams;
 use crate::shared::enums::{Language, ThreadStandard, ThreadType};
 use crate::shared::error::AppError;
+use crate::shared::extract::Query;
 
 use crate::services::svg::{SvgService, SvgText, TextOptionsGenerator};
 
-pub async fn handle(
-    Extension(pool): Extension<PgPool>,
-    Query(params): Query<RequestSvgAnnotation>,
-) -> Result<Response<Body>, AppError> {
+#[utoipa::path(
+    get,
+    path = "/v1/trapezoidal/svg-annotations",
+    tag = "drawings",
+    params(RequestSvgAnnotation, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(Query(params): Query<RequestSvgAnnotation>) -> Result<Response<Body>, AppError> {
     let svg_service = SvgService::new("./static/svg");
 
     let mut svg_content = svg_service
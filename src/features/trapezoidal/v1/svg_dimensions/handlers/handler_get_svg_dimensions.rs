@@ -1,51 +1,54 @@
 use axum::body::Body;
 
 use crate::log_error;
-use axum::http::{Response, StatusCode};
+use crate::services::svg::export::ExportParams;
+use axum::http::Response;
 use axum::response::IntoResponse;
-use axum::Json;
-use axum::{extract::Query, Extension};
-use sqlx::PgPool;
+use axum::Extension;
+use std::sync::Arc;
 
 use crate::features::trapezoidal::common::calculators::{calculate_additional_info, calculate_diameter_info};
 use crate::features::trapezoidal::common::db::ThreadDataService;
 use crate::features::trapezoidal::common::enums::{TypeTrapezoidalAdditionalInfo, TypeTrapezoidalDiameter};
 use crate::features::trapezoidal::v1::svg_dimensions::models::RequestSvgDimension;
 use crate::services::svg::enums::{FontFamily, FontWeight, TextAnchor};
+use crate::shared::database::service::PostgresService;
 use crate::shared::enums::{Language, ThreadStandard, ThreadType};
 use crate::shared::error::AppError;
+use crate::shared::extract::Query;
 
+use crate::services::cache::{CachedResponse, ResponseCache};
 use crate::services::svg::{SvgService, SvgText, TextOptionsGenerator};
 
+#[utoipa::path(
+    get,
+    path = "/v1/trapezoidal/svg-dimensions",
+    tag = "drawings",
+    params(RequestSvgDimension, ExportParams),
+    responses(
+        (status = 200, description = "SVG drawing, or PNG/PDF with `format`", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
 pub async fn handle(
-    Extension(pool): Extension<PgPool>,
+    Extension(database): Extension<Arc<PostgresService>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
     Query(params): Query<RequestSvgDimension>,
 ) -> Result<Response<Body>, AppError> {
-    let db_service = ThreadDataService::new(pool);
+    let cache_key = params.cache_key();
+    if let Some(cached) = cache.get(&cache_key) {
+        return Ok(cached.into_response());
+    }
+
+    let db_service = ThreadDataService::new(database.repository_trapezoidal.clone());
 
     // Fetch thread data using the core service
-    let thread_data = match db_service
+    let thread_data = db_service
         .fetch_thread_data(params.diameter, params.pitch, params.thread_type, &params.tolerance)
         .await
-    {
-        Ok(data) => data,
-        Err(e) => {
-            log_error!("Database query error: {}", e);
-            let error_message = if e.to_string().contains("no rows") {
-                "No thread specifications found for the given diameter and pitch"
-            } else {
-                "An error occurred while retrieving thread specifications"
-            };
-            return Ok((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({
-                    "error": error_message,
-                    "details": e.to_string()
-                })),
-            )
-                .into_response());
-        }
-    };
+        .inspect_err(|e| log_error!("Database query error: {}", e))?;
 
     let diameter_info = calculate_diameter_info(
         params.language,
@@ -240,6 +243,7 @@ pub async fn handle(
 
     svg_content = svg_service.append_text_elements(svg_content, multiple_items, &params.theme);
 
-    let response = svg_service.create_svg_response(svg_content);
-    Ok(response)
+    let cached = CachedResponse::new("image/svg+xml; charset=utf-8", svg_content, None);
+    cache.insert(cache_key, cached.clone());
+    Ok(cached.into_response())
 }
@@ -1,8 +1,11 @@
 use serde::Deserialize;
+use utoipa::IntoParams;
 
+use crate::services::cache::CacheKey;
 use crate::shared::enums::{theme::Theme, Language, ThreadType, Unit};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RequestSvgDimension {
     #[serde(rename = "type")]
     pub thread_type: ThreadType,
@@ -14,3 +17,22 @@ pub struct RequestSvgDimension {
     pub units: Unit,
     pub precision: Option<usize>,
 }
+
+impl RequestSvgDimension {
+    /// Cache key of the `/v1/trapezoidal/svg-dimensions` response
+    pub fn cache_key(&self) -> CacheKey {
+        CacheKey::new(
+            "/v1/trapezoidal/svg-dimensions",
+            [
+                ("type", Some(self.thread_type.to_string())),
+                ("theme", Some(self.theme.to_string())),
+                ("tolerance", Some(self.tolerance.clone())),
+                ("diameter", Some(self.diameter.to_string())),
+                ("pitch", Some(self.pitch.to_string())),
+                ("language", Some(self.language.to_string())),
+                ("units", Some(self.units.to_string())),
+                ("precision", self.precision.map(|p| p.to_string())),
+            ],
+        )
+    }
+}
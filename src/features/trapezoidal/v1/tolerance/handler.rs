@@ -1,17 +1,19 @@
 // src/routes/v1/trapezoidal/tolerance/trapezoidal_tolerance_handler.rs
 use crate::{log_error, log_info};
-use axum::{
-    extract::{Extension, Query},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
-};
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Row};
+use serde_json::json;
 use std::collections::HashSet;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Deserialize, Debug)]
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::extract::Query;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ToleranceParams {
     diameter: i32,
     pitch: f64,
@@ -23,13 +25,15 @@ struct ToleranceKey {
     suffix: String,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
+#[schema(as = TrapezoidalToleranceInfo)]
 struct ToleranceInfo {
     tolerance: String,
     formatted: String,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
+#[schema(as = TrapezoidalTolerances)]
 struct ToleranceResponse {
     male: Vec<ToleranceInfo>,
     female: Vec<ToleranceInfo>,
@@ -50,7 +54,22 @@ fn parse_tolerance(tolerance: &str) -> ToleranceKey {
     }
 }
 
-pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<ToleranceParams>) -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/v1/trapezoidal/tolerance",
+    tag = "trapezoidal",
+    params(ToleranceParams),
+    responses(
+        (status = 200, description = "Tolerance classes by type", body = ToleranceResponse),
+        (status = 400, description = "Missing or invalid query parameters"),
+        (status = 404, description = "No thread in the reference tables for these parameters"),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn handle(
+    Extension(database): Extension<Arc<PostgresService>>,
+    Query(params): Query<ToleranceParams>,
+) -> impl IntoResponse {
     log_info!(
         "Handling tolerance request for diameter: {}, pitch: {}",
         params.diameter,
@@ -58,29 +77,11 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Tol
     );
 
     // Get column names to determine available tolerances
-    let columns = match sqlx::query(
-        r#"
-        SELECT column_name
-        FROM information_schema.columns
-        WHERE table_schema = 'trapezoidal'
-        AND table_name = 'main'
-        AND (column_name LIKE 'es_d2_%_m' OR column_name LIKE 'es_d2_%_f')
-        ORDER BY column_name;
-    "#,
-    )
-    .fetch_all(&pool)
-    .await
-    {
-        Ok(rows) => rows,
+    let columns = match database.repository_trapezoidal.tolerance_columns().await {
+        Ok(columns) => columns,
         Err(e) => {
             log_error!("Failed to fetch column information: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to fetch tolerance information"
-                })),
-            )
-                .into_response();
+            return e.into_response();
         }
     };
 
@@ -88,11 +89,10 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Tol
     let mut female_tolerances = HashSet::new();
 
     // Extract tolerance classes from column names
-    for row in columns {
-        let column_name: &str = row.get(0);
+    for column_name in columns {
         if let Some(captures) = regex::Regex::new(r"es_d2_(\d+[a-z])_([mf])")
             .unwrap()
-            .captures(column_name)
+            .captures(&column_name)
         {
             let tolerance = captures.get(1).unwrap().as_str();
             let thread_type = captures.get(2).unwrap().as_str();
@@ -115,22 +115,14 @@ pub async fn handle(Extension(pool): Extension<PgPool>, Query(params): Query<Tol
     }
 
     // Verify thread exists
-    let thread_exists = sqlx::query!(
-        "SELECT EXISTS(SELECT 1 FROM trapezoidal.main WHERE diameter = $1 AND pitch = $2) as exists",
-        params.diameter,
-        params.pitch
-    )
-    .fetch_one(&pool)
-    .await
-    .is_ok_and(|row| row.exists.unwrap_or(false));
+    let thread_exists = database
+        .repository_trapezoidal
+        .find_thread(params.diameter, params.pitch)
+        .await
+        .is_ok_and(|row| row.is_some());
 
     if !thread_exists {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": format!("Thread with diameter {} and pitch {} not found", params.diameter, params.pitch)
-            })),
-        )
+        return AppError::thread_not_found(json!({ "diameter": params.diameter, "pitch": params.pitch }))
             .into_response();
     }
 
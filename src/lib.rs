@@ -0,0 +1,9 @@
+pub mod analytics;
+pub mod features;
+pub mod services;
+pub mod shared;
+
+mod test;
+
+// `log_*` macros resolve through `$crate::logging`
+pub use shared::logging;
@@ -1,41 +1,79 @@
-mod features;
-mod shared;
-use crate::shared::logging::{enums::LogLevel, init::init_logging, structs::LogConfig};
-use axum::Router;
-use std::{net::SocketAddr, sync::Arc};
+use axum::{
+    extract::{ConnectInfo, Request},
+    Router,
+};
+use hyper::{body::Incoming, server::conn::http1, service::service_fn};
+use hyper_util::{rt::TokioIo, server::graceful::GracefulShutdown};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use thread_api::analytics::pipeline::AnalyticsPipeline;
+use thread_api::services::reference_data::{integrity::check_store, ReferenceStore};
+use thread_api::services::shutdown::Drain;
+use thread_api::services::svg::templates::{self, TEMPLATE_DIR};
+use thread_api::services::telemetry;
+use thread_api::shared::logging::{
+    enums::{LogFormat, LogLevel},
+    init::init_logging,
+    structs::LogConfig,
+};
+use thread_api::{features, log_debug, log_error, log_info, log_warn};
 use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tower::ServiceExt;
 
-use crate::shared::{
+use thread_api::shared::{
     database::{migrations::run_migrations, service::PostgresService},
-    logging, middleware,
-    setting::models::{app_config::AppConfig, app_env::AppEnv, app_setting::AppSettings, app_state::AppState},
+    error::AppError,
+    middleware,
+    setting::models::{
+        app_config::{AppConfig, ShutdownConfig},
+        app_env::AppEnv,
+        app_setting::AppSettings,
+        app_state::AppState,
+    },
 };
 
-mod analytics;
-
-mod test;
-
-pub mod services;
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize application settings and logging
     let settings: Arc<AppSettings> = Arc::new(init_app().await);
+    // Query timings come from the statement events of sqlx; spans of requests, queries
+    // and drawings go to the OTLP collector when `[tracing]` is enabled
+    let telemetry = telemetry::install(&settings.config.tracing);
     // Connect to databases
     let postgres_service = Arc::new(initialize_database(settings.clone()).await?);
     let server_address = format!("{}:{}", settings.env.server_address, settings.env.server_port)
         .parse()
         .expect("Invalid server address configuration");
 
+    if settings.config.cache.preload_templates {
+        preload_templates().await;
+    }
+
+    // Usage counters are written in batches by a background worker
+    let (analytics, analytics_pipeline) = AnalyticsPipeline::start(
+        postgres_service.repository_analytics.clone(),
+        &settings.config.analytics,
+    );
+
     // Create application state with all services and dependencies
-    let app_state = Arc::new(AppState::new(settings.clone(), postgres_service).await);
+    let app_state = Arc::new(AppState::new(settings.clone(), postgres_service.clone(), analytics).await);
+    let drain = app_state.drain.clone();
+    app_state.watch_dataset_version();
 
     // Create API router using app_state
     let app_router = create_application_router(app_state);
 
     // Start HTTP server
-    start_http_server(app_router, server_address).await;
+    let served = start_http_server(app_router, server_address, drain, &settings.config.shutdown).await;
+
+    // Requests are finished, write the counters still queued
+    analytics_pipeline.shutdown().await;
+    telemetry.shutdown().await;
+    // Nothing writes to the database anymore
+    postgres_service.close(POOL_CLOSE_TIMEOUT).await;
+    log_info!("Shutdown complete");
 
+    served?;
     Ok(())
 }
 
@@ -48,8 +86,10 @@ async fn init_app() -> AppSettings {
     };
 
     // Setup logging with configured level
-    let log_level = LogLevel::from(app_settings.config.logging.level.as_str());
-    let log_config = LogConfig { level: log_level };
+    let log_config = LogConfig {
+        level: LogLevel::from(app_settings.config.logging.level.as_str()),
+        format: LogFormat::from(app_settings.config.logging.format.as_str()),
+    };
     init_logging(log_config).expect("Failed to initialize logger");
 
     log_info!("Инициализация приложения с конфигурацией: {:?}", app_settings.config);
@@ -70,121 +110,339 @@ async fn init_app() -> AppSettings {
 
 fn create_application_router(app_state: Arc<AppState>) -> Router {
     use axum::routing::{get, post};
-    let pool = app_state.postgres_service.connection.pool().clone();
+    let database = app_state.postgres_service.clone();
+    let response_cache = app_state.response_cache.clone();
+    let analytics = app_state.analytics.clone();
+    let api_key_auth = middleware::ApiKeyAuth::new(
+        database.repository_api_keys.clone(),
+        app_state.settings.config.auth.require_api_key,
+    );
+    let conditional_get =
+        axum::middleware::from_fn_with_state(app_state.conditional_get.clone(), middleware::conditional_get);
+    let trusted_proxies = Arc::new(thread_api::services::rate_limit::TrustedProxies::new(
+        &app_state.settings.config.rate_limit.trusted_proxies,
+    ));
 
     // Error reports router with state
     let error_reports_router = Router::new()
         .route(
             "/v1/error_reports/",
-            post(crate::features::error_reports::handlers::create_error_report),
+            post(features::error_reports::handlers::create_error_report),
         )
-        .with_state(pool.clone());
+        .layer(axum::extract::DefaultBodyLimit::max(
+            app_state.settings.config.error_reports.max_body_bytes,
+        ))
+        .with_state(features::error_reports::handlers::ErrorReportsState {
+            database: database.clone(),
+            limiter: Arc::new(thread_api::services::rate_limit::KeyedRateLimiter::per_minute(
+                app_state.settings.config.error_reports.per_ip_per_minute,
+            )),
+        });
+
+    // SVG routes, rendered to PNG or PDF on `format=png|pdf`
+    let svg_router = Router::new()
+        .route("/v1/metric/svg", get(features::metric::v1::svg::handler::svg))
+        .route(
+            "/v1/imperial/svg-annotations",
+            get(features::imperial::v1::svg_annotations::handlers::handler_get_svg_annotations::handle),
+        )
+        .route(
+            "/v1/imperial/svg-dimensions",
+            get(features::imperial::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle),
+        )
+        .route(
+            "/v1/trapezoidal/svg-dimensions",
+            get(features::trapezoidal::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle),
+        )
+        .route(
+            "/v1/trapezoidal/svg-annotations",
+            get(features::trapezoidal::v1::svg_annotations::handlers::handler_get_svg_annotations::handle),
+        )
+        .route(
+            "/v1/profile/svg",
+            get(features::profile::v1::svg::handlers::handler_get_svg_profile::handle),
+        )
+        .route(
+            "/v1/tolerance-zones/svg",
+            get(features::tolerance_zones::v1::svg::handlers::handler_get_svg_tolerance_zones::handle),
+        )
+        .route_layer(axum::middleware::from_fn(middleware::svg_export))
+        // Outside the export layer, so the ETag covers the converted PNG or PDF
+        .route_layer(conditional_get.clone())
+        .route_layer(axum::middleware::from_fn(middleware::no_store))
+        .layer(axum::Extension(database.clone()));
 
     // Main router with extension-based routes
     let main_router = Router::new()
         // === V1 METRIC ROUTES ===
         .route(
             "/v1/metric/diameters",
-            get(crate::features::metric::v1::diameters::handler::diameters),
-        )
-        .route("/v1/metric/pitch", get(crate::features::metric::v1::pitch::pitch))
-        .route(
-            "/v1/metric/tolerance",
-            get(crate::features::metric::v1::tolerance::tolerance),
+            get(features::metric::v1::diameters::handler::diameters),
         )
-        .route("/v1/metric/info", get(crate::features::metric::v1::info::handler::info))
-        .route("/v1/metric/svg", get(crate::features::metric::v1::svg::handler::svg))
+        .route("/v1/metric/pitch", get(features::metric::v1::pitch::pitch))
+        .route("/v1/metric/tolerance", get(features::metric::v1::tolerance::tolerance))
+        .route("/v1/metric/info", get(features::metric::v1::info::handler::info))
         // === V1 IMPERIAL ROUTES ===
         .route(
             "/v1/imperial/diameters",
-            get(crate::features::imperial::v1::diameters::handler::handle),
+            get(features::imperial::v1::diameters::handler::handle),
         )
         .route(
             "/v1/imperial/tolerance",
-            get(crate::features::imperial::v1::tolerance::handler::handle),
+            get(features::imperial::v1::tolerance::handler::handle),
         )
+        .route("/v1/imperial/info", get(features::imperial::v1::info::handler::handle))
+        // === V1 TRAPEZOIDAL ROUTES ===
         .route(
-            "/v1/imperial/info",
-            get(crate::features::imperial::v1::info::handler::handle),
+            "/v1/trapezoidal/diameters",
+            get(features::trapezoidal::v1::diameters::handler::handle),
         )
         .route(
-            "/v1/imperial/svg-annotations",
-            get(crate::features::imperial::v1::svg_annotations::handlers::handler_get_svg_annotations::handle),
+            "/v1/trapezoidal/tolerance",
+            get(features::trapezoidal::v1::tolerance::handler::handle),
         )
         .route(
-            "/v1/imperial/svg-dimensions",
-            get(crate::features::imperial::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle),
+            "/v1/trapezoidal/info",
+            get(features::trapezoidal::v1::info::handler::handle),
         )
-        // === V1 TRAPEZOIDAL ROUTES ===
+        // === V1 PIPE ROUTES ===
         .route(
-            "/v1/trapezoidal/diameters",
-            get(crate::features::trapezoidal::v1::diameters::handler::handle),
+            "/v1/pipe/diameters",
+            get(features::pipe::v1::diameters::handler::handle),
         )
+        .route("/v1/pipe/info", get(features::pipe::v1::info::handler::handle))
+        // === V2 IMPERIAL ROUTES ===
+        .route("/v2/imperial/info", get(features::imperial::v2::info::handler::handle))
+        // === V1 PROFILE ROUTES ===
         .route(
-            "/v1/trapezoidal/tolerance",
-            get(crate::features::trapezoidal::v1::tolerance::handler::handle),
+            "/v1/profile/dxf",
+            get(features::profile::v1::dxf::handlers::handler_get_dxf_profile::handle),
         )
+        // === V1 MODEL ROUTES ===
         .route(
-            "/v1/trapezoidal/info",
-            get(crate::features::trapezoidal::v1::info::handler::handle),
+            "/v1/model/stl",
+            get(features::model::v1::stl::handlers::handler_get_stl_model::handle),
         )
+        // route_layer covers only the routes above: reference data is cacheable
+        .route_layer(conditional_get)
+        // === V1 BATCH ROUTES ===
+        .route("/v1/batch/info", post(features::batch::handler::handle))
+        // === V1 SEARCH ROUTES ===
+        // Ranking follows the usage counters, so the ETag of the dataset does not cover it
+        .route("/v1/search", get(features::search::handler::handle))
+        // === SYSTEM ROUTES ===
+        .route("/test", get(features::test::test))
+        .route("/health", get(features::health::handler::health))
+        .route("/health/live", get(features::health::handler::live))
+        .route("/health/ready", get(features::health::handler::ready))
+        .route("/metrics", get(features::metrics::handler::metrics))
+        .route_layer(axum::middleware::from_fn(middleware::no_store))
+        .layer(axum::Extension(database.clone()));
+
+    // Admin routes, `Authorization: Bearer <ADMIN_TOKEN>` or an API key with the `admin` scope
+    let admin_token = middleware::AdminToken::new(&app_state.settings.env.admin_token);
+    let admin_router = Router::new()
+        // === V1 ANALYTICS ROUTES ===
+        .route("/v1/analytics/popular", get(features::analytics::handler::popular))
         .route(
-            "/v1/trapezoidal/svg-dimensions",
-            get(crate::features::trapezoidal::v1::svg_dimensions::handlers::handler_get_svg_dimensions::handle),
+            "/v1/analytics/timeseries",
+            get(features::analytics::handler::timeseries),
         )
+        .route("/admin/data-check", get(features::admin::data_check::handler::handle))
+        // === API KEYS ===
         .route(
-            "/v1/trapezoidal/svg-annotations",
-            get(crate::features::trapezoidal::v1::svg_annotations::handlers::handler_get_svg_annotations::handle),
+            "/admin/api-keys",
+            get(features::api_keys::handler::list).post(features::api_keys::handler::issue),
         )
-        // === V1 PIPE ROUTES ===
         .route(
-            "/v1/pipe/diameters",
-            get(crate::features::pipe::v1::diameters::handler::handle),
+            "/admin/api-keys/{id}",
+            axum::routing::delete(features::api_keys::handler::revoke),
         )
-        .route("/v1/pipe/info", get(crate::features::pipe::v1::info::handler::handle))
-        // === V2 IMPERIAL ROUTES ===
+        .route("/admin/api-keys/{id}/rotate", post(features::api_keys::handler::rotate))
+        .route("/admin/api-keys/{id}/usage", get(features::api_keys::handler::usage))
+        // === ERROR REPORT TRIAGE ===
+        .route("/admin/error-reports", get(features::error_reports::handlers::list))
         .route(
-            "/v2/imperial/info",
-            get(crate::features::imperial::v2::info::handler::handle),
+            "/admin/error-reports/groups",
+            get(features::error_reports::handlers::groups),
+        )
+        .route(
+            "/admin/error-reports/resolve",
+            post(features::error_reports::handlers::resolve),
         )
-        // === SYSTEM ROUTES ===
-        .route("/test", get(crate::features::test::test))
         .route(
-            "/health",
-            get({
-                let app_state = Arc::clone(&app_state);
-                move || async move { app_state.health_handler.get_health().await }
-            }),
+            "/admin/error-reports/export",
+            get(features::error_reports::handlers::export),
         )
-        .layer(axum::Extension(pool));
+        .route_layer(axum::middleware::from_fn_with_state(
+            admin_token,
+            middleware::require_admin,
+        ))
+        .route_layer(axum::middleware::from_fn(middleware::no_store))
+        .layer(axum::Extension(database));
 
     // Combine routers
     Router::new()
         .merge(main_router)
+        .merge(admin_router)
+        .merge(svg_router)
         .merge(error_reports_router)
+        .merge(features::openapi::swagger_ui())
         // === MIDDLEWARE ===
+        // The quota is charged only for requests the rate limit let through
+        .layer(axum::middleware::from_fn_with_state(
+            api_key_auth.clone(),
+            middleware::charge_quota,
+        ))
+        // Inside `authenticate`, so that requests with an API key are limited per key
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.rate_limits.clone(),
+            middleware::rate_limit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            api_key_auth,
+            middleware::authenticate,
+        ))
+        .layer(axum::middleware::from_fn(middleware::localize_errors))
         .layer(middleware::create_cors())
-        .layer(middleware::create_trace())
+        .layer(axum::middleware::from_fn(middleware::track_metrics))
+        .layer(axum::middleware::from_fn(middleware::trace_requests))
+        // Outermost, so that the logs, the limits and the handlers agree on the client
+        .layer(axum::middleware::from_fn_with_state(
+            trusted_proxies,
+            middleware::resolve_client_ip,
+        ))
+        .layer(axum::Extension(response_cache))
+        .layer(axum::Extension(analytics))
         .layer(axum::Extension(app_state))
 }
 
+/// Wait for connections still checked out of the pools before exiting anyway
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reads SVG templates into memory so that requests do not touch the disk
+async fn preload_templates() {
+    match templates::preload(TEMPLATE_DIR).await {
+        Ok(count) => log_info!("Preloaded {} SVG templates from {}", count, TEMPLATE_DIR),
+        Err(err) => log_warn!("Failed to preload SVG templates from {}: {}", TEMPLATE_DIR, err),
+    }
+}
+
 /// Starts the HTTP server on the specified address
-async fn start_http_server(app: Router, addr: SocketAddr) {
+/// Serves until a shutdown signal, then drains: readiness reports 503 for
+/// `readiness_delay_seconds` and the listener closes. Open connections finish
+/// the request they are serving and close; those still open after
+/// `drain_timeout_seconds` are aborted
+async fn start_http_server(
+    app: Router,
+    addr: SocketAddr,
+    drain: Drain,
+    config: &ShutdownConfig,
+) -> std::io::Result<()> {
     log_info!("Starting HTTP server on {}", addr);
 
-    let listener = match TcpListener::bind(addr).await {
-        Ok(listener) => listener,
-        Err(err) => {
-            log_error!("Failed to bind to address {}: {}", addr, err);
-            panic!("Cannot start server: {}", err);
+    let listener = TcpListener::bind(addr).await.inspect_err(|err| {
+        log_error!("Failed to bind to address {}: {}", addr, err);
+    })?;
+
+    log_info!("Server started successfully, now accepting connections");
+
+    let readiness_delay = Duration::from_secs(config.readiness_delay_seconds);
+    let drain_timeout = Duration::from_secs(config.drain_timeout_seconds);
+    let stop_accepting = async move {
+        shutdown_signal().await;
+        drain.begin();
+        if !readiness_delay.is_zero() {
+            log_info!(
+                "Readiness reports unavailable, closing the listener in {:?}",
+                readiness_delay
+            );
+            tokio::time::sleep(readiness_delay).await;
         }
     };
+    tokio::pin!(stop_accepting);
 
-    log_info!("Server started successfully, now accepting connections");
+    // Every connection is a task of its own: the graceful handle asks them to
+    // close after the current request, the join set aborts them at the deadline
+    let graceful = GracefulShutdown::new();
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        // E.g. out of file descriptors, give running requests time to free some
+                        log_error!("Failed to accept a connection: {}", err);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+                // The peer address identifies clients that come without `X-Forwarded-For`
+                let app = app.clone();
+                let service = service_fn(move |mut request: Request<Incoming>| {
+                    request.extensions_mut().insert(ConnectInfo(peer));
+                    app.clone().oneshot(request)
+                });
+                let connection = graceful.watch(http1::Builder::new().serve_connection(TokioIo::new(stream), service));
+                connections.spawn(async move {
+                    if let Err(err) = connection.await {
+                        log_debug!("Connection from {} closed with an error: {}", peer, err);
+                    }
+                });
+            }
+            // Finished connections leave the set as they go
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            _ = &mut stop_accepting => break,
+        }
+    }
+    drop(listener);
+    log_info!(
+        "Listener closed, draining {} connection(s) for up to {:?}",
+        connections.len(),
+        drain_timeout
+    );
 
-    if let Err(err) = axum::serve(listener, app).await {
-        log_error!("Server error: {}", err);
-        panic!("Server failed: {}", err);
+    if tokio::time::timeout(drain_timeout, graceful.shutdown()).await.is_err() {
+        log_warn!(
+            "Drain timeout of {:?} elapsed, aborting {} connection(s)",
+            drain_timeout,
+            connections.len()
+        );
+        connections.abort_all();
+    }
+    while connections.join_next().await.is_some() {}
+    log_info!("All connections closed");
+    Ok(())
+}
+
+/// Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            log_error!("Failed to listen for Ctrl+C: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                log_error!("Failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => log_info!("Ctrl+C received, shutting down"),
+        _ = terminate => log_info!("SIGTERM received, shutting down"),
     }
 }
 
@@ -193,8 +451,48 @@ async fn initialize_database(settings: Arc<AppSettings>) -> Result<PostgresServi
 
     let postgres_service = PostgresService::new(&settings).await?;
 
-    log_info!("Running database migrations...");
-    run_migrations(postgres_service.connection.pool()).await?;
+    // The bundled reference data needs no migrations
+    if let Some(pool) = postgres_service.pool() {
+        log_info!("Running database migrations...");
+        run_migrations(pool).await?;
+    }
+
+    if settings.config.database.check_on_startup {
+        check_reference_data(&settings, &postgres_service).await?;
+    }
 
     Ok(postgres_service)
 }
+
+/// Logs every finding of the integrity check; refuses to start on errors if configured
+async fn check_reference_data(
+    settings: &AppSettings,
+    postgres_service: &PostgresService,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log_info!("Checking reference data integrity...");
+    let store = ReferenceStore::for_database(postgres_service, &settings.config.database.data_dir);
+    let report = check_store(&store).await?;
+
+    for finding in &report.findings {
+        log_warn!(
+            "{} {} {}: {}",
+            finding.table,
+            finding.row,
+            finding.column.as_deref().unwrap_or("-"),
+            finding.message
+        );
+    }
+    log_info!(
+        "Reference data check finished: {} error(s), {} warning(s)",
+        report.errors,
+        report.warnings
+    );
+
+    if !report.is_ok() && settings.config.database.fail_on_check_errors {
+        return Err(Box::new(AppError::ValidationError(format!(
+            "reference data has {} error(s), see /admin/data-check",
+            report.errors
+        ))));
+    }
+    Ok(())
+}
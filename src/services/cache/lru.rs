@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Counters of a cache since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub ttl_seconds: u64,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped because the cache was full
+    pub evictions: u64,
+    /// Entries dropped because they outlived the TTL
+    pub expirations: u64,
+    pub hit_ratio: f64,
+}
+
+struct Entry<V> {
+    value: V,
+    inserted_at: Instant,
+    tick: u64,
+}
+
+struct Inner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// Recency order: the smallest tick is the least recently used key
+    order: BTreeMap<u64, K>,
+    next_tick: u64,
+}
+
+/// Bounded LRU cache whose entries also expire after a fixed TTL.
+///
+/// A capacity of zero disables the cache: lookups miss and inserts are dropped.
+pub struct LruCache<K, V> {
+    inner: Mutex<Inner<K, V>>,
+    capacity: usize,
+    ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                next_tick: 0,
+            }),
+            capacity,
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a fresh entry and marks it as the most recently used
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let tick = inner.next_tick;
+        let Some(entry) = inner.entries.get_mut(key) else {
+            drop(inner);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+
+        if entry.inserted_at.elapsed() >= self.ttl {
+            let old_tick = entry.tick;
+            inner.entries.remove(key);
+            inner.order.remove(&old_tick);
+            drop(inner);
+            self.expirations.fetch_add(1, Ordering::Relaxed);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        let old_tick = std::mem::replace(&mut entry.tick, tick);
+        let value = entry.value.clone();
+        inner.next_tick += 1;
+        inner.order.remove(&old_tick);
+        inner.order.insert(tick, key.clone());
+        drop(inner);
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(value)
+    }
+
+    /// Stores a value, evicting the least recently used entry when full
+    pub fn insert(&self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+
+        let entry = Entry {
+            value,
+            inserted_at: Instant::now(),
+            tick,
+        };
+        if let Some(previous) = inner.entries.insert(key.clone(), entry) {
+            inner.order.remove(&previous.tick);
+        }
+        inner.order.insert(tick, key);
+
+        while inner.entries.len() > self.capacity {
+            let Some((_, oldest)) = inner.order.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Drops every entry, keeping the counters
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.entries.clear();
+        inner.order.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+        CacheStats {
+            entries: self.len(),
+            capacity: self.capacity,
+            ttl_seconds: self.ttl.as_secs(),
+            hits,
+            misses,
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            hit_ratio: if lookups == 0 {
+                0.0
+            } else {
+                hits as f64 / lookups as f64
+            },
+        }
+    }
+}
@@ -0,0 +1,109 @@
+pub mod lru;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header::CONTENT_TYPE, HeaderValue, Response, StatusCode},
+    response::IntoResponse,
+};
+
+use crate::shared::setting::models::app_config::CacheConfig;
+
+pub use lru::{CacheStats, LruCache};
+
+/// Key of a cached response: the route plus its parameters in a fixed order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// Builds the key from already parsed parameters, so that the order of the
+    /// query string, letter case of enums and float notation do not matter.
+    /// Parameters set to `None` are left out.
+    pub fn new<'a>(route: &str, params: impl IntoIterator<Item = (&'a str, Option<String>)>) -> Self {
+        let mut params: Vec<(&str, String)> = params
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|v| (name, v.trim().to_string())))
+            .collect();
+        params.sort();
+
+        let query: Vec<String> = params
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        Self(format!("{}?{}", route, query.join("&")))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Successful response body stored in the cache
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub content_type: &'static str,
+    pub body: Bytes,
+    /// Thread designation, so that popularity analytics still count cache hits
+    pub designation: Option<String>,
+}
+
+impl CachedResponse {
+    pub fn new(content_type: &'static str, body: impl Into<Bytes>, designation: Option<String>) -> Self {
+        Self {
+            content_type,
+            body: body.into(),
+            designation,
+        }
+    }
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response<Body> {
+        (
+            StatusCode::OK,
+            [(CONTENT_TYPE, HeaderValue::from_static(self.content_type))],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// In-process cache of computed thread info and rendered SVGs
+pub struct ResponseCache {
+    entries: LruCache<CacheKey, CachedResponse>,
+}
+
+impl ResponseCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        let capacity = if config.enabled { config.capacity } else { 0 };
+        Self {
+            entries: LruCache::new(capacity, Duration::from_secs(config.ttl_seconds)),
+        }
+    }
+
+    /// Cache that never stores anything
+    pub fn disabled() -> Arc<Self> {
+        Arc::new(Self {
+            entries: LruCache::new(0, Duration::ZERO),
+        })
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&self, key: CacheKey, response: CachedResponse) {
+        self.entries.insert(key, response);
+    }
+
+    /// Forgets every response, e.g. after the reference data was changed
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.entries.stats()
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use crate::services::geometry::{Point, Segment};
+
+/// Layer of a DXF drawing with its ACI colour number
+#[derive(Debug, Clone, Copy)]
+pub struct DxfLayer {
+    pub name: &'static str,
+    pub color: i16,
+}
+
+/// Writer of AutoCAD R12 ASCII DXF files, coordinates in millimetres
+pub struct DxfDocument {
+    layers: Vec<DxfLayer>,
+    entities: String,
+    min: Point,
+    max: Point,
+}
+
+impl DxfDocument {
+    pub fn new(layers: &[DxfLayer]) -> Self {
+        Self {
+            layers: layers.to_vec(),
+            entities: String::new(),
+            min: Point::new(f64::MAX, f64::MAX),
+            max: Point::new(f64::MIN, f64::MIN),
+        }
+    }
+
+    pub fn line(&mut self, layer: &DxfLayer, start: Point, end: Point) {
+        self.entity("LINE", layer);
+        self.point(10, start);
+        self.point(11, end);
+    }
+
+    /// Arc around `center` drawn counter-clockwise from `start_deg` to `end_deg`
+    pub fn arc(&mut self, layer: &DxfLayer, center: Point, radius: f64, start_deg: f64, end_deg: f64) {
+        self.entity("ARC", layer);
+        self.point(10, center);
+        self.real(40, radius);
+        self.real(50, start_deg);
+        self.real(51, end_deg);
+        self.extend(Point::new(center.x - radius, center.y - radius));
+        self.extend(Point::new(center.x + radius, center.y + radius));
+    }
+
+    /// Profile segment as a LINE or ARC entity
+    pub fn segment(&mut self, layer: &DxfLayer, segment: &Segment) {
+        match *segment {
+            Segment::Line { start, end } => self.line(layer, start, end),
+            Segment::Arc {
+                start,
+                end,
+                center,
+                radius,
+                ccw,
+            } => {
+                let angle = |p: Point| (p.y - center.y).atan2(p.x - center.x).to_degrees();
+                // DXF arcs always run counter-clockwise
+                let (from, to) = if ccw { (start, end) } else { (end, start) };
+                self.arc(layer, center, radius, angle(from), angle(to));
+            }
+        }
+    }
+
+    /// Single-line text with its baseline starting at `position`.
+    /// Use `%%c` for the diameter sign and `%%d` for degrees.
+    pub fn text(&mut self, layer: &DxfLayer, position: Point, height: f64, value: &str) {
+        self.entity("TEXT", layer);
+        self.point(10, position);
+        self.real(40, height);
+        self.pair(1, value);
+    }
+
+    /// Single-line text centred on `position` horizontally
+    pub fn centered_text(&mut self, layer: &DxfLayer, position: Point, height: f64, value: &str) {
+        self.text(layer, position, height, value);
+        self.pair(72, 1);
+        self.point(11, position);
+    }
+
+    /// Complete DXF file
+    pub fn finish(self) -> String {
+        let mut out = String::new();
+        let (min, max) = if self.min.x <= self.max.x {
+            (self.min, self.max)
+        } else {
+            (Point::new(0.0, 0.0), Point::new(0.0, 0.0))
+        };
+
+        write_pair(&mut out, 0, "SECTION");
+        write_pair(&mut out, 2, "HEADER");
+        write_pair(&mut out, 9, "$ACADVER");
+        write_pair(&mut out, 1, "AC1009");
+        write_pair(&mut out, 9, "$EXTMIN");
+        write_point(&mut out, 10, min);
+        write_pair(&mut out, 9, "$EXTMAX");
+        write_point(&mut out, 10, max);
+        write_pair(&mut out, 0, "ENDSEC");
+
+        write_pair(&mut out, 0, "SECTION");
+        write_pair(&mut out, 2, "TABLES");
+        write_pair(&mut out, 0, "TABLE");
+        write_pair(&mut out, 2, "LTYPE");
+        write_pair(&mut out, 70, 1);
+        write_pair(&mut out, 0, "LTYPE");
+        write_pair(&mut out, 2, "CONTINUOUS");
+        write_pair(&mut out, 70, 0);
+        write_pair(&mut out, 3, "Solid line");
+        write_pair(&mut out, 72, 65);
+        write_pair(&mut out, 73, 0);
+        write_pair(&mut out, 40, "0.0");
+        write_pair(&mut out, 0, "ENDTAB");
+        write_pair(&mut out, 0, "TABLE");
+        write_pair(&mut out, 2, "LAYER");
+        write_pair(&mut out, 70, self.layers.len());
+        for layer in &self.layers {
+            write_pair(&mut out, 0, "LAYER");
+            write_pair(&mut out, 2, layer.name);
+            write_pair(&mut out, 70, 0);
+            write_pair(&mut out, 62, layer.color);
+            write_pair(&mut out, 6, "CONTINUOUS");
+        }
+        write_pair(&mut out, 0, "ENDTAB");
+        write_pair(&mut out, 0, "ENDSEC");
+
+        write_pair(&mut out, 0, "SECTION");
+        write_pair(&mut out, 2, "ENTITIES");
+        out.push_str(&self.entities);
+        write_pair(&mut out, 0, "ENDSEC");
+        write_pair(&mut out, 0, "EOF");
+        out
+    }
+
+    fn entity(&mut self, kind: &str, layer: &DxfLayer) {
+        self.pair(0, kind);
+        self.pair(8, layer.name);
+    }
+
+    fn pair(&mut self, code: u16, value: impl std::fmt::Display) {
+        write_pair(&mut self.entities, code, value);
+    }
+
+    fn real(&mut self, code: u16, value: f64) {
+        write_pair(&mut self.entities, code, format_real(value));
+    }
+
+    fn point(&mut self, code: u16, point: Point) {
+        write_point(&mut self.entities, code, point);
+        self.extend(point);
+    }
+
+    fn extend(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+}
+
+/// Group code and value, each on its own line
+fn write_pair(out: &mut String, code: u16, value: impl std::fmt::Display) {
+    let _ = write!(out, "{:>3}\r\n{}\r\n", code, value);
+}
+
+/// 2D point as the X, Y and Z group codes `code`, `code + 10` and `code + 20`
+fn write_point(out: &mut String, code: u16, point: Point) {
+    write_pair(out, code, format_real(point.x));
+    write_pair(out, code + 10, format_real(point.y));
+    write_pair(out, code + 20, "0.0");
+}
+
+fn format_real(value: f64) -> String {
+    let value = if value.abs() < 5e-7 { 0.0 } else { value };
+    let mut formatted = format!("{:.6}", value);
+    while formatted.ends_with('0') && !formatted.ends_with(".0") {
+        formatted.pop();
+    }
+    formatted
+}
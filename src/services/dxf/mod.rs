@@ -0,0 +1,5 @@
+//! DXF (R12, ASCII) drawings for import into CAD
+pub mod document;
+pub mod profile_exporter;
+
+pub use document::DxfDocument;
@@ -0,0 +1,157 @@
+use crate::services::geometry::{Point, ProfileOutline, ThreadProfile};
+use crate::services::svg::tolerance_renderer::{MaterialCondition, ToleranceZones};
+use crate::shared::enums::ThreadType;
+use crate::shared::utils::number::NumberFormatter;
+
+use super::document::{DxfDocument, DxfLayer};
+
+pub const LAYER_BASIC_PROFILE: DxfLayer = DxfLayer {
+    name: "BASIC_PROFILE",
+    color: 7,
+};
+pub const LAYER_MAX_MATERIAL: DxfLayer = DxfLayer {
+    name: "LIMIT_MAX_MATERIAL",
+    color: 1,
+};
+pub const LAYER_MIN_MATERIAL: DxfLayer = DxfLayer {
+    name: "LIMIT_MIN_MATERIAL",
+    color: 5,
+};
+pub const LAYER_DIMENSIONS: DxfLayer = DxfLayer {
+    name: "DIMENSIONS",
+    color: 3,
+};
+
+/// Parameters of a profile DXF
+#[derive(Debug, Clone)]
+pub struct ProfileDxfOptions {
+    pub title: String,
+    pub precision: usize,
+    pub pitches: usize,
+}
+
+/// Exports the axial section of a thread: `x` along the axis, `y` the distance from the axis, in millimetres
+pub struct ProfileDxfExporter;
+
+impl ProfileDxfExporter {
+    /// Basic profile, and with `zones` the profiles at maximum and minimum material condition
+    pub fn export(profile: &ThreadProfile, zones: Option<&ToleranceZones>, options: &ProfileDxfOptions) -> String {
+        let mut dxf = DxfDocument::new(&[
+            LAYER_BASIC_PROFILE,
+            LAYER_MAX_MATERIAL,
+            LAYER_MIN_MATERIAL,
+            LAYER_DIMENSIONS,
+        ]);
+        let limits = zones.map(|zones| {
+            (
+                zones.material_profile(profile, MaterialCondition::Maximum),
+                zones.material_profile(profile, MaterialCondition::Minimum),
+            )
+        });
+
+        for segment in &ProfileOutline::build(profile, options.pitches).segments {
+            dxf.segment(&LAYER_BASIC_PROFILE, segment);
+        }
+        if let Some((max_material, min_material)) = &limits {
+            for segment in &ProfileOutline::build(max_material, options.pitches).segments {
+                dxf.segment(&LAYER_MAX_MATERIAL, segment);
+            }
+            for segment in &ProfileOutline::build(min_material, options.pitches).segments {
+                dxf.segment(&LAYER_MIN_MATERIAL, segment);
+            }
+        }
+
+        Self::draw_dimensions(&mut dxf, profile, limits.as_ref(), options);
+        dxf.finish()
+    }
+
+    fn draw_dimensions(
+        dxf: &mut DxfDocument,
+        profile: &ThreadProfile,
+        limits: Option<&(ThreadProfile, ThreadProfile)>,
+        options: &ProfileDxfOptions,
+    ) {
+        let p = profile.pitch;
+        let length = options.pitches as f64 * p;
+        let text_height = 0.08 * p;
+        let number = |value: f64| NumberFormatter::format_number_trim_zeros(value, Some(options.precision));
+        let top = limits
+            .map(|(max, min)| max.major_diameter.max(min.major_diameter))
+            .unwrap_or(0.0)
+            .max(profile.major_diameter)
+            / 2.0;
+
+        // Reference lines of the basic diameters, labelled with the limits of size when known
+        let basic = [profile.major_diameter, profile.pitch_diameter, profile.minor_diameter];
+        for (i, (diameter, symbol)) in basic.iter().zip(profile.diameter_symbols()).enumerate() {
+            let y = diameter / 2.0;
+            dxf.line(
+                &LAYER_DIMENSIONS,
+                Point::new(-0.25 * p, y),
+                Point::new(length + 0.25 * p, y),
+            );
+
+            let mut label = format!("{} = %%c{}", symbol, number(*diameter));
+            if let Some((max_material, min_material)) = limits {
+                let diameters = |t: &ThreadProfile| [t.major_diameter, t.pitch_diameter, t.minor_diameter][i];
+                let (a, b) = (diameters(max_material), diameters(min_material));
+                label.push_str(&format!(" ({} / {})", number(a.max(b)), number(a.min(b))));
+            }
+            dxf.text(
+                &LAYER_DIMENSIONS,
+                Point::new(length + 0.35 * p, y - text_height / 2.0),
+                text_height,
+                &label,
+            );
+        }
+
+        // Pitch between the centres of two neighbouring crests
+        let first_crest = match profile.thread_type {
+            ThreadType::Male => p / 2.0,
+            ThreadType::Female => 0.0,
+        };
+        let y = top + 0.3 * p;
+        for x in [first_crest, first_crest + p] {
+            dxf.line(
+                &LAYER_DIMENSIONS,
+                Point::new(x, profile.crest_level),
+                Point::new(x, y + 0.05 * p),
+            );
+        }
+        Self::dimension_line(dxf, Point::new(first_crest, y), Point::new(first_crest + p, y), p);
+        dxf.centered_text(
+            &LAYER_DIMENSIONS,
+            Point::new(first_crest + p / 2.0, y + 0.04 * p),
+            text_height,
+            &format!("P = {}", number(p)),
+        );
+        dxf.text(
+            &LAYER_DIMENSIONS,
+            Point::new(first_crest + 1.1 * p, y - text_height / 2.0),
+            text_height,
+            &format!("{}%%d", number(profile.flank_angle)),
+        );
+
+        dxf.text(
+            &LAYER_DIMENSIONS,
+            Point::new(0.0, y + 0.3 * p),
+            1.5 * text_height,
+            &options.title,
+        );
+    }
+
+    /// Horizontal dimension line with open arrowheads on both ends
+    fn dimension_line(dxf: &mut DxfDocument, start: Point, end: Point, pitch: f64) {
+        let (length, spread) = (0.06 * pitch, 0.02 * pitch);
+        dxf.line(&LAYER_DIMENSIONS, start, end);
+        for (tip, direction) in [(start, 1.0), (end, -1.0)] {
+            for side in [-1.0, 1.0] {
+                dxf.line(
+                    &LAYER_DIMENSIONS,
+                    tip,
+                    Point::new(tip.x + direction * length, tip.y + side * spread),
+                );
+            }
+        }
+    }
+}
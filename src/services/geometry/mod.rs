@@ -0,0 +1,6 @@
+//! Thread profile geometry computed from the standards' formulas
+pub mod outline;
+pub mod profile;
+
+pub use outline::{Point, ProfileOutline, Segment};
+pub use profile::ThreadProfile;
@@ -0,0 +1,395 @@
+use crate::shared::enums::ThreadType;
+
+use super::profile::ThreadProfile;
+
+/// Point of an axial section: `x` along the thread axis, `y` distance from the axis (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+
+    fn scale(self, k: f64) -> Point {
+        Point::new(self.x * k, self.y * k)
+    }
+
+    fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    fn normalized(self) -> Point {
+        let len = self.length();
+        if len > 0.0 {
+            self.scale(1.0 / len)
+        } else {
+            self
+        }
+    }
+}
+
+/// Element of a profile outline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    Line {
+        start: Point,
+        end: Point,
+    },
+    /// Circular arc from `start` to `end` around `center`, counter-clockwise when `ccw` is set
+    Arc {
+        start: Point,
+        end: Point,
+        center: Point,
+        radius: f64,
+        ccw: bool,
+    },
+}
+
+impl Segment {
+    pub fn start(&self) -> Point {
+        match self {
+            Segment::Line { start, .. } | Segment::Arc { start, .. } => *start,
+        }
+    }
+
+    pub fn end(&self) -> Point {
+        match self {
+            Segment::Line { end, .. } | Segment::Arc { end, .. } => *end,
+        }
+    }
+
+    /// Approximates the segment with points spaced at most `max_step_deg` degrees apart on arcs.
+    /// The start point is not included so consecutive segments can be chained.
+    pub fn tessellate(&self, max_step_deg: f64) -> Vec<Point> {
+        match *self {
+            Segment::Line { end, .. } => vec![end],
+            Segment::Arc {
+                start,
+                end,
+                center,
+                radius,
+                ccw,
+            } => {
+                let a0 = (start.y - center.y).atan2(start.x - center.x);
+                let a1 = (end.y - center.y).atan2(end.x - center.x);
+                let mut sweep = a1 - a0;
+                if ccw && sweep < 0.0 {
+                    sweep += std::f64::consts::TAU;
+                } else if !ccw && sweep > 0.0 {
+                    sweep -= std::f64::consts::TAU;
+                }
+                let steps = ((sweep.abs().to_degrees() / max_step_deg).ceil() as usize).max(1);
+                (1..=steps)
+                    .map(|i| {
+                        let a = a0 + sweep * i as f64 / steps as f64;
+                        Point::new(center.x + radius * a.cos(), center.y + radius * a.sin())
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Outline of a thread profile over several pitches in an axial section.
+/// The outline runs from left to right along the axis.
+#[derive(Debug, Clone)]
+pub struct ProfileOutline {
+    pub segments: Vec<Segment>,
+}
+
+impl ProfileOutline {
+    /// Builds the outline of `pitches` consecutive teeth of the profile.
+    ///
+    /// The external thread starts at the centre of a root, the internal thread at the
+    /// centre of a crest, so both outlines of a mating pair line up on the same x axis.
+    pub fn build(profile: &ThreadProfile, pitches: usize) -> Self {
+        let p = profile.pitch;
+        let crest = Feature::crest(profile);
+        let root = Feature::root(profile);
+        let (first, second) = match profile.thread_type {
+            ThreadType::Male => (root, crest),
+            ThreadType::Female => (crest, root),
+        };
+
+        // Sharp polygon with the fillet radius requested on each corner. It starts and ends
+        // half a pitch outside the requested range so that the end features get rounded too.
+        let mut vertices: Vec<(Point, f64)> = vec![(second.center(-p / 2.0), 0.0)];
+        second.push_right_half(&mut vertices, -p / 2.0);
+        for k in 0..=pitches {
+            first.push_full(&mut vertices, k as f64 * p);
+            if k < pitches {
+                second.push_full(&mut vertices, k as f64 * p + p / 2.0);
+            }
+        }
+        let end = pitches as f64 * p + p / 2.0;
+        second.push_left_half(&mut vertices, end);
+        vertices.push((second.center(end), 0.0));
+        vertices.dedup_by(|a, b| a.0.sub(b.0).length() < 1e-9);
+
+        Self {
+            segments: clip_x(&fillet_polyline(&vertices), 0.0, pitches as f64 * p),
+        }
+    }
+
+    /// Tessellated polyline of the whole outline
+    pub fn points(&self, max_step_deg: f64) -> Vec<Point> {
+        let mut points = Vec::new();
+        if let Some(first) = self.segments.first() {
+            points.push(first.start());
+        }
+        for segment in &self.segments {
+            points.extend(segment.tessellate(max_step_deg));
+        }
+        points
+    }
+
+    /// Outline shifted along the axis
+    pub fn translated(&self, dx: f64) -> Self {
+        let shift = |p: Point| Point::new(p.x + dx, p.y);
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| match *segment {
+                Segment::Line { start, end } => Segment::Line {
+                    start: shift(start),
+                    end: shift(end),
+                },
+                Segment::Arc {
+                    start,
+                    end,
+                    center,
+                    radius,
+                    ccw,
+                } => Segment::Arc {
+                    start: shift(start),
+                    end: shift(end),
+                    center: shift(center),
+                    radius,
+                    ccw,
+                },
+            })
+            .collect();
+        Self { segments }
+    }
+}
+
+/// Crest or root of a profile: its radial level, half-width of the flat and corner radius
+#[derive(Debug, Clone, Copy)]
+struct Feature {
+    level: f64,
+    half_flat: f64,
+    radius: f64,
+    /// Sharp tip used instead of the flat when the radius rounds the whole feature
+    tip: Option<f64>,
+}
+
+impl Feature {
+    fn crest(profile: &ThreadProfile) -> Self {
+        let outward = match profile.thread_type {
+            ThreadType::Male => 1.0,
+            ThreadType::Female => -1.0,
+        };
+        Self {
+            level: profile.crest_level,
+            half_flat: profile.crest_flat() / 2.0,
+            radius: profile.crest_radius,
+            tip: profile
+                .is_crest_rounded()
+                .then_some(profile.crest_level + outward * profile.crest_truncation),
+        }
+    }
+
+    fn root(profile: &ThreadProfile) -> Self {
+        let outward = match profile.thread_type {
+            ThreadType::Male => -1.0,
+            ThreadType::Female => 1.0,
+        };
+        Self {
+            level: profile.root_level,
+            half_flat: profile.root_flat() / 2.0,
+            radius: profile.root_radius,
+            tip: profile
+                .is_root_rounded()
+                .then_some(profile.root_level + outward * profile.root_truncation),
+        }
+    }
+
+    /// Point in the middle of the feature
+    fn center(&self, x: f64) -> Point {
+        Point::new(x, self.tip.unwrap_or(self.level))
+    }
+
+    fn push_full(&self, vertices: &mut Vec<(Point, f64)>, center: f64) {
+        match self.tip {
+            Some(tip) => vertices.push((Point::new(center, tip), self.radius)),
+            None => {
+                vertices.push((Point::new(center - self.half_flat, self.level), self.radius));
+                vertices.push((Point::new(center + self.half_flat, self.level), self.radius));
+            }
+        }
+    }
+
+    fn push_right_half(&self, vertices: &mut Vec<(Point, f64)>, center: f64) {
+        match self.tip {
+            Some(tip) => vertices.push((Point::new(center, tip), self.radius)),
+            None => vertices.push((Point::new(center + self.half_flat, self.level), self.radius)),
+        }
+    }
+
+    fn push_left_half(&self, vertices: &mut Vec<(Point, f64)>, center: f64) {
+        match self.tip {
+            Some(tip) => vertices.push((Point::new(center, tip), self.radius)),
+            None => vertices.push((Point::new(center - self.half_flat, self.level), self.radius)),
+        }
+    }
+}
+
+/// Replaces the corners of a polyline with tangent arcs of the requested radius.
+/// End points are never rounded; radii that do not fit are reduced.
+fn fillet_polyline(vertices: &[(Point, f64)]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    if vertices.len() < 2 {
+        return segments;
+    }
+
+    let mut cursor = vertices[0].0;
+    for i in 1..vertices.len() {
+        let (vertex, radius) = vertices[i];
+        let is_last = i == vertices.len() - 1;
+        if is_last || radius <= 0.0 {
+            segments.push(Segment::Line {
+                start: cursor,
+                end: vertex,
+            });
+            cursor = vertex;
+            continue;
+        }
+
+        let prev = vertices[i - 1].0;
+        let next = vertices[i + 1].0;
+        let u1 = prev.sub(vertex).normalized();
+        let u2 = next.sub(vertex).normalized();
+        let cos = (u1.x * u2.x + u1.y * u2.y).clamp(-1.0, 1.0);
+        let theta = cos.acos();
+        if theta < 1e-6 || (std::f64::consts::PI - theta) < 1e-6 {
+            segments.push(Segment::Line {
+                start: cursor,
+                end: vertex,
+            });
+            cursor = vertex;
+            continue;
+        }
+
+        // Tangent length, limited to half of each neighbouring segment
+        let half_tan = (theta / 2.0).tan();
+        let max_t = 0.5 * prev.sub(vertex).length().min(next.sub(vertex).length());
+        let t = (radius / half_tan).min(max_t);
+        let r = t * half_tan;
+
+        let t1 = vertex.add(u1.scale(t));
+        let t2 = vertex.add(u2.scale(t));
+        let bisector = u1.add(u2).normalized();
+        let center = vertex.add(bisector.scale(r / (theta / 2.0).sin()));
+        let d1 = t1.sub(center);
+        let d2 = t2.sub(center);
+        let ccw = d1.x * d2.y - d1.y * d2.x > 0.0;
+
+        if cursor.sub(t1).length() > 1e-9 {
+            segments.push(Segment::Line { start: cursor, end: t1 });
+        }
+        segments.push(Segment::Arc {
+            start: t1,
+            end: t2,
+            center,
+            radius: r,
+            ccw,
+        });
+        cursor = t2;
+    }
+
+    segments
+}
+
+/// Keeps the part of the outline between `x0` and `x1`, splitting the boundary segments
+fn clip_x(segments: &[Segment], x0: f64, x1: f64) -> Vec<Segment> {
+    const EPS: f64 = 1e-9;
+    let inside = |p: Point| p.x >= x0 - EPS && p.x <= x1 + EPS;
+
+    let mut result = Vec::new();
+    for segment in segments {
+        let (start, end) = (segment.start(), segment.end());
+        if inside(start) && inside(end) {
+            result.push(*segment);
+            continue;
+        }
+        if (start.x < x0 && end.x < x0) || (start.x > x1 && end.x > x1) {
+            continue;
+        }
+
+        let from = if start.x < x0 { Some(x0) } else { None };
+        let to = if end.x > x1 { Some(x1) } else { None };
+        match *segment {
+            Segment::Line { start, end } => {
+                let at = |x: f64| {
+                    let t = (x - start.x) / (end.x - start.x);
+                    Point::new(x, start.y + t * (end.y - start.y))
+                };
+                result.push(Segment::Line {
+                    start: from.map(at).unwrap_or(start),
+                    end: to.map(at).unwrap_or(end),
+                });
+            }
+            Segment::Arc {
+                start,
+                end,
+                center,
+                radius,
+                ccw,
+            } => {
+                let at = |x: f64| arc_point_at_x(start, end, center, radius, ccw, x);
+                result.push(Segment::Arc {
+                    start: from.and_then(at).unwrap_or(start),
+                    end: to.and_then(at).unwrap_or(end),
+                    center,
+                    radius,
+                    ccw,
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Point of an arc with the given x coordinate lying within the arc's sweep
+fn arc_point_at_x(start: Point, end: Point, center: Point, radius: f64, ccw: bool, x: f64) -> Option<Point> {
+    let cos = ((x - center.x) / radius).clamp(-1.0, 1.0);
+    let a0 = (start.y - center.y).atan2(start.x - center.x);
+    let a1 = (end.y - center.y).atan2(end.x - center.x);
+    let sweep = |from: f64, to: f64| {
+        let mut s = to - from;
+        if ccw && s < 0.0 {
+            s += std::f64::consts::TAU;
+        } else if !ccw && s > 0.0 {
+            s -= std::f64::consts::TAU;
+        }
+        s
+    };
+    let total = sweep(a0, a1);
+
+    [cos.acos(), -cos.acos()]
+        .into_iter()
+        .find(|&a| sweep(a0, a).abs() <= total.abs() + 1e-9)
+        .map(|a| Point::new(x, center.y + radius * a.sin()))
+}
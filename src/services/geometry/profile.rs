@@ -0,0 +1,319 @@
+use crate::shared::enums::{ThreadStandard, ThreadType};
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Basic (design) profile of a single thread, all lengths in millimetres.
+///
+/// "Crest" and "root" always refer to the selected thread type: for an external
+/// thread the crest lies on the major diameter, for an internal thread on the minor one.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadProfile {
+    pub standard: ThreadStandard,
+    pub thread_type: ThreadType,
+    pub pitch: f64,
+    /// Included flank angle in degrees (60°, 55°, 30°)
+    pub flank_angle: f64,
+    /// Height of the fundamental triangle (H), or H1 for trapezoidal threads
+    pub fundamental_height: f64,
+    pub major_diameter: f64,
+    pub pitch_diameter: f64,
+    pub minor_diameter: f64,
+    /// Distance from the axis to the crest of the selected thread
+    pub crest_level: f64,
+    /// Rounding radius of the crest (0 for a sharp-cornered flat)
+    pub crest_radius: f64,
+    /// Distance from the axis to the root of the selected thread
+    pub root_level: f64,
+    /// Rounding radius of the root (0 for a sharp-cornered flat)
+    pub root_radius: f64,
+    /// Distance from the sharp fundamental triangle tip to the crest
+    pub crest_truncation: f64,
+    /// Distance from the sharp fundamental triangle tip to the root
+    pub root_truncation: f64,
+}
+
+impl ThreadProfile {
+    /// ISO 68-1 metric profile; the external root is rounded with R = H/6 (ISO 965-1 d3).
+    pub fn metric(thread_type: ThreadType, diameter: f64, pitch: f64) -> Self {
+        Self::sixty_degree(ThreadStandard::Metric, thread_type, diameter, pitch)
+    }
+
+    /// ASME B1.1 unified profile, `diameter` and `tpi` in inches.
+    /// The external root uses the UNR maximum radius 0.144P.
+    pub fn unified(thread_type: ThreadType, diameter_inch: f64, tpi: f64) -> Self {
+        Self::sixty_degree(
+            ThreadStandard::Imperial,
+            thread_type,
+            diameter_inch * MM_PER_INCH,
+            MM_PER_INCH / tpi,
+        )
+    }
+
+    fn sixty_degree(standard: ThreadStandard, thread_type: ThreadType, d: f64, p: f64) -> Self {
+        let h = 3f64.sqrt() / 2.0 * p;
+        let d2 = d - 0.75 * h;
+        let d1 = d - 1.25 * h;
+
+        match thread_type {
+            // Crest flat P/8 on the major diameter, rounded root on d3
+            ThreadType::Male => Self {
+                standard,
+                thread_type,
+                pitch: p,
+                flank_angle: 60.0,
+                fundamental_height: h,
+                major_diameter: d,
+                pitch_diameter: d2,
+                minor_diameter: d - 17.0 / 12.0 * h,
+                crest_level: d / 2.0,
+                crest_radius: 0.0,
+                crest_truncation: h / 8.0,
+                root_level: d / 2.0 - 17.0 / 24.0 * h,
+                root_radius: h / 6.0,
+                root_truncation: h / 6.0,
+            },
+            // Crest flat P/4 on D1, root flat P/8 on D
+            ThreadType::Female => Self {
+                standard,
+                thread_type,
+                pitch: p,
+                flank_angle: 60.0,
+                fundamental_height: h,
+                major_diameter: d,
+                pitch_diameter: d2,
+                minor_diameter: d1,
+                crest_level: d1 / 2.0,
+                crest_radius: 0.0,
+                crest_truncation: h / 4.0,
+                root_level: d / 2.0,
+                root_radius: 0.0,
+                root_truncation: h / 8.0,
+            },
+        }
+    }
+
+    /// ISO 2904 trapezoidal profile with crest clearance ac and the maximum R1/R2 radii.
+    pub fn trapezoidal(thread_type: ThreadType, diameter: f64, pitch: f64) -> Self {
+        let ac = Self::trapezoidal_clearance(pitch);
+        let h1 = 0.5 * pitch;
+        let h3 = h1 + ac;
+        let d2 = diameter - 0.5 * pitch;
+
+        // The 30° fundamental triangle is 1.866P high, H1 is the working depth
+        let triangle = pitch / (2.0 * 15f64.to_radians().tan());
+        let sharp_crest = d2 / 2.0 + triangle / 2.0;
+
+        match thread_type {
+            ThreadType::Male => Self {
+                standard: ThreadStandard::Trapezoidal,
+                thread_type,
+                pitch,
+                flank_angle: 30.0,
+                fundamental_height: h1,
+                major_diameter: diameter,
+                pitch_diameter: d2,
+                minor_diameter: diameter - 2.0 * h3,
+                crest_level: diameter / 2.0,
+                crest_radius: 0.5 * ac,
+                crest_truncation: sharp_crest - diameter / 2.0,
+                root_level: diameter / 2.0 - h3,
+                root_radius: ac,
+                root_truncation: (diameter / 2.0 - h3) - (sharp_crest - triangle),
+            },
+            ThreadType::Female => Self {
+                standard: ThreadStandard::Trapezoidal,
+                thread_type,
+                pitch,
+                flank_angle: 30.0,
+                fundamental_height: h1,
+                major_diameter: diameter + 2.0 * ac,
+                pitch_diameter: d2,
+                minor_diameter: diameter - 2.0 * h1,
+                crest_level: diameter / 2.0 - h1,
+                crest_radius: 0.5 * ac,
+                crest_truncation: (diameter / 2.0 - h1) - (sharp_crest - triangle),
+                root_level: diameter / 2.0 + ac,
+                root_radius: ac,
+                root_truncation: sharp_crest - (diameter / 2.0 + ac),
+            },
+        }
+    }
+
+    /// ISO 228-1 / Whitworth 55° profile with crests and roots rounded by r = 0.137329P.
+    pub fn pipe(thread_type: ThreadType, diameter: f64, pitch: f64) -> Self {
+        let h = 0.960491 * pitch;
+        let depth = 0.640327 * pitch;
+        let r = 0.137329 * pitch;
+        let (crest_level, root_level) = match thread_type {
+            ThreadType::Male => (diameter / 2.0, diameter / 2.0 - depth),
+            ThreadType::Female => (diameter / 2.0 - depth, diameter / 2.0),
+        };
+
+        Self {
+            standard: ThreadStandard::Pipe,
+            thread_type,
+            pitch,
+            flank_angle: 55.0,
+            fundamental_height: h,
+            major_diameter: diameter,
+            pitch_diameter: diameter - depth,
+            minor_diameter: diameter - 2.0 * depth,
+            crest_level,
+            crest_radius: r,
+            crest_truncation: h / 6.0,
+            root_level,
+            root_radius: r,
+            root_truncation: h / 6.0,
+        }
+    }
+
+    /// Builds the profile for a standard from the nominal size.
+    /// Imperial sizes are given in inches with `pitch` = 1/TPI, all others in millimetres.
+    pub fn for_standard(standard: ThreadStandard, thread_type: ThreadType, diameter: f64, pitch: f64) -> Self {
+        match standard {
+            ThreadStandard::Metric => Self::metric(thread_type, diameter, pitch),
+            ThreadStandard::Imperial => Self::unified(thread_type, diameter, 1.0 / pitch),
+            ThreadStandard::Trapezoidal => Self::trapezoidal(thread_type, diameter, pitch),
+            ThreadStandard::Pipe => Self::pipe(thread_type, diameter, pitch),
+        }
+    }
+
+    /// Nominal size the profile was built from, in millimetres
+    pub fn nominal_diameter(&self) -> f64 {
+        match (self.standard, self.thread_type) {
+            (ThreadStandard::Trapezoidal, ThreadType::Female) => {
+                self.major_diameter - 2.0 * Self::trapezoidal_clearance(self.pitch)
+            }
+            _ => self.major_diameter,
+        }
+    }
+
+    /// Profile of the mating thread of the same size
+    pub fn mating(&self) -> Self {
+        let thread_type = match self.thread_type {
+            ThreadType::Male => ThreadType::Female,
+            ThreadType::Female => ThreadType::Male,
+        };
+        let diameter = self.nominal_diameter();
+        match self.standard {
+            ThreadStandard::Metric | ThreadStandard::Imperial => {
+                Self::sixty_degree(self.standard, thread_type, diameter, self.pitch)
+            }
+            ThreadStandard::Trapezoidal => Self::trapezoidal(thread_type, diameter, self.pitch),
+            ThreadStandard::Pipe => Self::pipe(thread_type, diameter, self.pitch),
+        }
+    }
+
+    /// Crest clearance ac according to ISO 2904
+    pub fn trapezoidal_clearance(pitch: f64) -> f64 {
+        if pitch <= 1.5 {
+            0.15
+        } else if pitch <= 5.0 {
+            0.25
+        } else if pitch <= 12.0 {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// Symbols of the major, pitch and minor diameters of the selected thread
+    pub fn diameter_symbols(&self) -> [&'static str; 3] {
+        match (self.standard, self.thread_type) {
+            (ThreadStandard::Metric | ThreadStandard::Trapezoidal, ThreadType::Male) => ["d", "d2", "d3"],
+            (_, ThreadType::Male) => ["d", "d2", "d1"],
+            (ThreadStandard::Trapezoidal, ThreadType::Female) => ["D4", "D2", "D1"],
+            (_, ThreadType::Female) => ["D", "D2", "D1"],
+        }
+    }
+
+    /// Half of the included flank angle in radians
+    pub fn half_angle(&self) -> f64 {
+        (self.flank_angle / 2.0).to_radians()
+    }
+
+    /// Radial depth between crest and root of the selected thread
+    pub fn thread_depth(&self) -> f64 {
+        (self.crest_level - self.root_level).abs()
+    }
+
+    /// Half-width of a tooth of the selected thread at the given radius, measured from the tooth axis.
+    /// Teeth are P/2 wide on the pitch line and narrow towards the crest.
+    pub fn tooth_half_width(&self, radius: f64) -> f64 {
+        let towards_crest = match self.thread_type {
+            ThreadType::Male => radius - self.pitch_diameter / 2.0,
+            ThreadType::Female => self.pitch_diameter / 2.0 - radius,
+        };
+        self.pitch / 4.0 - towards_crest * self.half_angle().tan()
+    }
+
+    /// Width of the flat on the crest (before rounding)
+    pub fn crest_flat(&self) -> f64 {
+        (2.0 * self.tooth_half_width(self.crest_level)).max(0.0)
+    }
+
+    /// Width of the flat at the root (before rounding)
+    pub fn root_flat(&self) -> f64 {
+        (self.pitch - 2.0 * self.tooth_half_width(self.root_level)).max(0.0)
+    }
+
+    /// Whether the crest radius rounds the whole crest, leaving no flat
+    pub fn is_crest_rounded(&self) -> bool {
+        self.is_fully_rounded(self.crest_radius, self.crest_truncation)
+    }
+
+    /// Whether the root radius rounds the whole root, leaving no flat
+    pub fn is_root_rounded(&self) -> bool {
+        self.is_fully_rounded(self.root_radius, self.root_truncation)
+    }
+
+    /// A radius tangent to both flanks reaches r(1/sin(α/2) - 1) below the sharp tip
+    fn is_fully_rounded(&self, radius: f64, truncation: f64) -> bool {
+        radius > 0.0 && truncation <= radius * (1.0 / self.half_angle().sin() - 1.0) + 1e-5 * self.pitch
+    }
+
+    /// Same thread with all radial levels shifted by `offset` (positive = outwards).
+    /// Used to draw limit-of-size profiles and printer clearances.
+    pub fn offset_radially(&self, offset: f64) -> Self {
+        Self {
+            major_diameter: self.major_diameter + 2.0 * offset,
+            pitch_diameter: self.pitch_diameter + 2.0 * offset,
+            minor_diameter: self.minor_diameter + 2.0 * offset,
+            crest_level: self.crest_level + offset,
+            root_level: self.root_level + offset,
+            ..*self
+        }
+    }
+
+    /// Same thread made to the given major, pitch and minor diameters, e.g. a limit of size.
+    /// Radii are kept; truncations follow from the fundamental triangle on the new pitch line.
+    pub fn with_diameters(&self, major: f64, pitch: f64, minor: f64) -> Self {
+        let half_triangle = self.pitch / (4.0 * self.half_angle().tan());
+        let (sharp_crest, sharp_root) = (pitch / 2.0 + half_triangle, pitch / 2.0 - half_triangle);
+        let (crest_level, root_level, crest_truncation, root_truncation) = match self.thread_type {
+            ThreadType::Male => (
+                major / 2.0,
+                minor / 2.0,
+                sharp_crest - major / 2.0,
+                minor / 2.0 - sharp_root,
+            ),
+            ThreadType::Female => (
+                minor / 2.0,
+                major / 2.0,
+                minor / 2.0 - sharp_root,
+                sharp_crest - major / 2.0,
+            ),
+        };
+
+        Self {
+            major_diameter: major,
+            pitch_diameter: pitch,
+            minor_diameter: minor,
+            crest_level,
+            root_level,
+            crest_truncation,
+            root_truncation,
+            ..*self
+        }
+    }
+}
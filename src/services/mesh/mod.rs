@@ -0,0 +1,6 @@
+//! Triangle meshes of threads for 3D printing and CAD
+pub mod stl;
+pub mod thread_mesh;
+
+pub use stl::{StlEncoding, StlWriter};
+pub use thread_mesh::{Hand, ThreadMesh, ThreadMeshOptions, TriangleMesh};
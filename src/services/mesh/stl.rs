@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::thread_mesh::TriangleMesh;
+
+/// STL flavour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StlEncoding {
+    #[default]
+    Binary,
+    Ascii,
+}
+
+/// Writes meshes as STL, the exchange format of slicers
+pub struct StlWriter;
+
+impl StlWriter {
+    pub fn write(mesh: &TriangleMesh, name: &str, encoding: StlEncoding) -> Vec<u8> {
+        match encoding {
+            StlEncoding::Binary => Self::binary(mesh, name),
+            StlEncoding::Ascii => Self::ascii(mesh, name).into_bytes(),
+        }
+    }
+
+    /// 80-byte header, triangle count and 50 bytes per facet, little-endian
+    pub fn binary(mesh: &TriangleMesh, name: &str) -> Vec<u8> {
+        let mut out = Vec::with_capacity(84 + 50 * mesh.triangles.len());
+        let mut header = [0u8; 80];
+        let name = name.as_bytes();
+        let len = name.len().min(80);
+        header[..len].copy_from_slice(&name[..len]);
+        out.extend_from_slice(&header);
+        out.extend_from_slice(&(mesh.triangles.len() as u32).to_le_bytes());
+
+        for facet in Self::facets(mesh) {
+            for vector in facet {
+                for value in vector {
+                    out.extend_from_slice(&(value as f32).to_le_bytes());
+                }
+            }
+            out.extend_from_slice(&0u16.to_le_bytes());
+        }
+        out
+    }
+
+    pub fn ascii(mesh: &TriangleMesh, name: &str) -> String {
+        // The solid name ends at the first whitespace for most readers
+        let name: String = name.chars().filter(|c| c.is_ascii_graphic()).collect();
+        let mut out = format!("solid {}\n", name);
+        for [normal, a, b, c] in Self::facets(mesh) {
+            let _ = writeln!(out, "  facet normal {:e} {:e} {:e}", normal[0], normal[1], normal[2]);
+            out.push_str("    outer loop\n");
+            for vertex in [a, b, c] {
+                let _ = writeln!(out, "      vertex {:e} {:e} {:e}", vertex[0], vertex[1], vertex[2]);
+            }
+            out.push_str("    endloop\n  endfacet\n");
+        }
+        let _ = writeln!(out, "endsolid {}", name);
+        out
+    }
+
+    /// Unit normal followed by the three vertices of every triangle
+    fn facets(mesh: &TriangleMesh) -> impl Iterator<Item = [[f64; 3]; 4]> + '_ {
+        mesh.triangles.iter().map(|&[a, b, c]| {
+            let (a, b, c) = (mesh.vertices[a], mesh.vertices[b], mesh.vertices[c]);
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            let normal = if length > 0.0 {
+                [n[0] / length, n[1] / length, n[2] / length]
+            } else {
+                [0.0; 3]
+            };
+            [normal, a, b, c]
+        })
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::services::geometry::{Point, ProfileOutline, ThreadProfile};
+
+/// Direction of the helix
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Hand {
+    #[default]
+    Right,
+    Left,
+}
+
+/// Parameters of a thread mesh
+#[derive(Debug, Clone)]
+pub struct ThreadMeshOptions {
+    pub hand: Hand,
+    /// Number of thread starts; the lead is `starts × pitch`
+    pub starts: usize,
+    /// Threaded length along the axis in millimetres
+    pub length: f64,
+    /// Facets per turn
+    pub segments: usize,
+}
+
+/// Indexed triangle mesh, counter-clockwise triangles seen from outside, coordinates in millimetres
+#[derive(Debug, Clone, Default)]
+pub struct TriangleMesh {
+    pub vertices: Vec<[f64; 3]>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl TriangleMesh {
+    /// Enclosed volume by the divergence theorem; positive for an outward-oriented closed mesh
+    pub fn volume(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let (a, b, c) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+                (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0]))
+                    / 6.0
+            })
+            .sum()
+    }
+
+    /// Whether every edge is shared by exactly two triangles running it in opposite directions
+    pub fn is_watertight(&self) -> bool {
+        let mut edges: HashMap<(usize, usize), i32> = HashMap::new();
+        for &[a, b, c] in &self.triangles {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((from, to)).or_default() += 1;
+            }
+        }
+        edges
+            .iter()
+            .all(|(&(from, to), &count)| count == 1 && edges.get(&(to, from)) == Some(&1))
+    }
+}
+
+/// Builds closed helical solids from a thread profile.
+///
+/// The solid is bounded by the thread surface and two end caps, so the same mesh is
+/// the body of an external thread or the cavity (the space to remove) of an internal one.
+pub struct ThreadMesh;
+
+impl ThreadMesh {
+    /// Triangles of the helical surface before clipping. The spare pitches cut off at both
+    /// ends outnumber the cap triangles, so this bounds the facets of `build` without building it
+    pub fn surface_facets(profile: &ThreadProfile, options: &ThreadMeshOptions) -> usize {
+        let rows = Self::pitches(profile, options) * Self::section(profile).len();
+        rows.saturating_mul(options.segments.max(3)).saturating_mul(2)
+    }
+
+    /// One pitch of the axial section; the outline repeats with the pitch
+    fn section(profile: &ThreadProfile) -> Vec<Point> {
+        let mut section = ProfileOutline::build(profile, 1).points(5.0);
+        section.pop();
+        section
+    }
+
+    /// Enough pitches for every column to cover [0, length] with a pitch to spare at both ends
+    fn pitches(profile: &ThreadProfile, options: &ThreadMeshOptions) -> usize {
+        let lead_pitches = options.starts as i64;
+        ((options.length / profile.pitch).ceil() as i64 + 2 * lead_pitches + 3) as usize
+    }
+
+    pub fn build(profile: &ThreadProfile, options: &ThreadMeshOptions) -> TriangleMesh {
+        let pitch = profile.pitch;
+        let lead = options.starts as f64 * pitch;
+        let segments = options.segments.max(3);
+        let advance = match options.hand {
+            Hand::Right => lead,
+            Hand::Left => -lead,
+        };
+
+        let section = Self::section(profile);
+        let per_pitch = section.len();
+        let first_pitch = -(options.starts as i64) - 1;
+        let pitches = Self::pitches(profile, options);
+        let rows = pitches * per_pitch + 1;
+        let axial = |row: usize| {
+            let point = section[row % per_pitch];
+            (first_pitch as f64 + (row / per_pitch) as f64) * pitch + point.x
+        };
+        let radius = |row: usize| section[row % per_pitch].y;
+
+        // Columns are offset by half a facet so that no vertex falls exactly on an end plane
+        let mut mesh = TriangleMesh::default();
+        for column in 0..segments {
+            let turn = (column as f64 + 0.5) / segments as f64;
+            let (sin, cos) = (turn * TAU).sin_cos();
+            for row in 0..rows {
+                let r = radius(row);
+                mesh.vertices.push([r * cos, r * sin, axial(row) + turn * advance]);
+            }
+        }
+        let index = |column: usize, row: usize| column * rows + row;
+
+        // After a full turn the helix has advanced by the lead, i.e. by `starts` pitches:
+        // the last column joins the first one `starts` pitches further along (back for a left hand)
+        let seam_shift = options.starts * per_pitch;
+        let mut surface = Vec::new();
+        for column in 0..segments {
+            let next = (column + 1) % segments;
+            let (range, offset) = match (next, options.hand) {
+                (0, Hand::Right) => (0..rows - 1 - seam_shift, seam_shift as isize),
+                (0, Hand::Left) => (seam_shift..rows - 1, -(seam_shift as isize)),
+                _ => (0..rows - 1, 0),
+            };
+            for row in range {
+                let next_row = (row as isize + offset) as usize;
+                let quad = [
+                    index(column, row),
+                    index(next, next_row),
+                    index(next, next_row + 1),
+                    index(column, row + 1),
+                ];
+                surface.push([quad[0], quad[1], quad[2]]);
+                surface.push([quad[0], quad[2], quad[3]]);
+            }
+        }
+
+        let surface = Self::clip(&mut mesh, surface, 0.0, false);
+        mesh.triangles = Self::clip(&mut mesh, surface, options.length, true);
+        mesh
+    }
+
+    /// Cuts the triangles at the plane `z = level`, keeps the side below it (`keep_below`) or above it,
+    /// and closes the cut with a cap fanned around the axis
+    fn clip(mesh: &mut TriangleMesh, triangles: Vec<[usize; 3]>, level: f64, keep_below: bool) -> Vec<[usize; 3]> {
+        let inside = |vertex: [f64; 3]| (vertex[2] <= level) == keep_below;
+        let mut cuts: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut cut_edges = Vec::new();
+        let mut result = Vec::new();
+
+        for triangle in triangles {
+            let flags = triangle.map(|i| inside(mesh.vertices[i]));
+            if flags.iter().all(|&f| f) {
+                result.push(triangle);
+                continue;
+            }
+            if !flags.iter().any(|&f| f) {
+                continue;
+            }
+
+            // Sutherland-Hodgman on a single triangle; cut points are shared through their edge
+            let mut polygon = Vec::with_capacity(4);
+            let mut exit = None;
+            let mut entry = None;
+            for k in 0..3 {
+                let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+                if flags[k] {
+                    polygon.push(a);
+                }
+                if flags[k] != flags[(k + 1) % 3] {
+                    let key = (a.min(b), a.max(b));
+                    let cut = *cuts
+                        .entry(key)
+                        .or_insert_with(|| Self::push_cut(&mut mesh.vertices, key, level));
+                    polygon.push(cut);
+                    if flags[k] {
+                        exit = Some(cut);
+                    } else {
+                        entry = Some(cut);
+                    }
+                }
+            }
+            for k in 1..polygon.len() - 1 {
+                result.push([polygon[0], polygon[k], polygon[k + 1]]);
+            }
+            if let (Some(exit), Some(entry)) = (exit, entry) {
+                cut_edges.push((exit, entry));
+            }
+        }
+
+        // The cut is star-shaped around the axis, so a fan from the centre closes it
+        let center = mesh.vertices.len();
+        mesh.vertices.push([0.0, 0.0, level]);
+        for (exit, entry) in cut_edges {
+            result.push([entry, exit, center]);
+        }
+        result
+    }
+
+    fn push_cut(vertices: &mut Vec<[f64; 3]>, (a, b): (usize, usize), level: f64) -> usize {
+        let (p, q) = (vertices[a], vertices[b]);
+        let t = (level - p[2]) / (q[2] - p[2]);
+        vertices.push([p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1]), level]);
+        vertices.len() - 1
+    }
+}
@@ -0,0 +1,144 @@
+mod queries;
+mod text;
+
+pub use queries::{QueryMetricsLayer, SQLX_QUERY_TARGET};
+pub use text::PrometheusText;
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds of the HTTP latency buckets, seconds
+const REQUEST_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+/// Queries of the reference tables take a millisecond or so
+const QUERY_BUCKETS: &[f64] = &[0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0];
+/// PNG and PDF conversion of a drawing takes up to seconds
+const RENDER_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Observations of one label set
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    /// Observations per bucket, not cumulative; the last one is `+Inf`
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Labels of a sample in label order, e.g. `[("route", "/v1/metric/svg"), ("status", "200")]`
+pub type Labels = Vec<(&'static str, String)>;
+
+type Family<T> = Mutex<BTreeMap<Labels, T>>;
+
+/// Counters and histograms filled while serving; gauges such as the pool size are
+/// read from their owners when `/metrics` is scraped
+#[derive(Default)]
+pub struct Metrics {
+    requests: Family<u64>,
+    request_durations: Family<Histogram>,
+    query_durations: Family<Histogram>,
+    render_durations: Family<Histogram>,
+    errors: Family<u64>,
+}
+
+fn lock<T>(family: &Family<T>) -> std::sync::MutexGuard<'_, BTreeMap<Labels, T>> {
+    family.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Metrics {
+    /// One registry per process, so the database and SVG layers need no handle
+    pub fn global() -> &'static Metrics {
+        static METRICS: OnceLock<Metrics> = OnceLock::new();
+        METRICS.get_or_init(Metrics::default)
+    }
+
+    /// A served request; `route` is the matched route template, not the raw path
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let labels: Labels = vec![
+            ("method", method.to_string()),
+            ("route", route.to_string()),
+            ("status", status.to_string()),
+        ];
+        *lock(&self.requests).entry(labels.clone()).or_default() += 1;
+        lock(&self.request_durations)
+            .entry(labels)
+            .or_insert_with(|| Histogram::new(REQUEST_BUCKETS))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// `statement` is the SQL keyword: "select", "insert", ...
+    pub fn observe_query(&self, statement: &str, elapsed: Duration) {
+        lock(&self.query_durations)
+            .entry(vec![("statement", statement.to_string())])
+            .or_insert_with(|| Histogram::new(QUERY_BUCKETS))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Drawing of `route` produced as `format`: "svg" by the handler, "png" or "pdf" by the export
+    pub fn observe_render(&self, route: &str, format: &str, elapsed: Duration) {
+        lock(&self.render_durations)
+            .entry(vec![("route", route.to_string()), ("format", format.to_string())])
+            .or_insert_with(|| Histogram::new(RENDER_BUCKETS))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// An `AppError` answered to a client, by `error_code`
+    pub fn count_error(&self, code: &str) {
+        *lock(&self.errors).entry(vec![("code", code.to_string())]).or_default() += 1;
+    }
+
+    /// Writes the counters and histograms in the Prometheus text format
+    pub fn render(&self, text: &mut PrometheusText) {
+        text.counter(
+            "http_requests_total",
+            "Requests served by method, route and status",
+            lock(&self.requests).iter(),
+        );
+        text.histogram(
+            "http_request_duration_seconds",
+            "Time to the response head by method, route and status",
+            &lock(&self.request_durations),
+        );
+        text.histogram(
+            "db_query_duration_seconds",
+            "Database query time by SQL statement",
+            &lock(&self.query_durations),
+        );
+        text.histogram(
+            "svg_render_duration_seconds",
+            "Drawing time by route and output format",
+            &lock(&self.render_durations),
+        );
+        text.counter(
+            "app_errors_total",
+            "Error responses by AppError code",
+            lock(&self.errors).iter(),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Debug;
+use std::time::Duration;
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::layer::{Context, Layer};
+
+use super::Metrics;
+use crate::services::telemetry;
+
+/// Target of the event sqlx emits after every statement, with `summary` and `elapsed_secs`
+pub const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// Turns the statement events of sqlx into `db_query_duration_seconds`, for the
+/// PostgreSQL and SQLite pools alike, and into spans of the request being traced.
+/// It is installed with a filter on `SQLX_QUERY_TARGET`, so it sees nothing else
+#[derive(Default)]
+pub struct QueryMetricsLayer;
+
+impl QueryMetricsLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Default)]
+struct QueryEvent {
+    summary: Option<String>,
+    elapsed_secs: Option<f64>,
+}
+
+impl Visit for QueryEvent {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "summary" {
+            self.summary = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "summary" {
+            self.summary = Some(format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for QueryMetricsLayer {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut query = QueryEvent::default();
+        event.record(&mut query);
+        let Some(elapsed) = query.elapsed_secs else {
+            return;
+        };
+        // "SELECT id, designation …" is counted as "select"; the summary is the first words
+        // on one line, so a script starting with a comment is only known as "other"
+        let statement = match query
+            .summary
+            .as_deref()
+            .and_then(|summary| summary.split_whitespace().next())
+        {
+            Some(word) if word.chars().all(|c| c.is_ascii_alphabetic()) => word.to_lowercase(),
+            Some(_) => "other".to_string(),
+            None => "unknown".to_string(),
+        };
+        let elapsed = Duration::from_secs_f64(elapsed.max(0.0));
+        Metrics::global().observe_query(&statement, elapsed);
+        telemetry::record_query(&statement, query.summary.as_deref().unwrap_or_default(), elapsed);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::{Histogram, Labels};
+
+/// Prometheus text exposition format 0.0.4
+#[derive(Default)]
+pub struct PrometheusText {
+    out: String,
+}
+
+impl PrometheusText {
+    pub const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4; charset=utf-8";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_string(self) -> String {
+        self.out
+    }
+
+    fn header(&mut self, name: &str, help: &str, kind: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, String)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{}=\"{}\"", label, escape(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {}", value);
+    }
+
+    /// Samples of a `BTreeMap<Labels, u64>` or of `[(Labels, u64)]` read at scrape time
+    pub fn counter<'a>(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (&'a Labels, &'a u64)>) {
+        self.header(name, help, "counter");
+        for (labels, value) in samples {
+            self.sample(name, labels, *value as f64);
+        }
+    }
+
+    /// Gauges of the scrape time, e.g. `[(vec![("state", "idle".into())], 3.0)]`
+    pub fn gauge(&mut self, name: &str, help: &str, samples: &[(Labels, f64)]) {
+        self.header(name, help, "gauge");
+        for (labels, value) in samples {
+            self.sample(name, labels, *value);
+        }
+    }
+
+    pub fn histogram(&mut self, name: &str, help: &str, samples: &BTreeMap<Labels, Histogram>) {
+        self.header(name, help, "histogram");
+        let bucket_name = format!("{}_bucket", name);
+        for (labels, histogram) in samples {
+            let mut cumulative = 0;
+            for (i, count) in histogram.counts.iter().enumerate() {
+                cumulative += count;
+                let le = histogram
+                    .bounds
+                    .get(i)
+                    .map_or_else(|| "+Inf".to_string(), |bound| bound.to_string());
+                let mut bucket_labels = labels.clone();
+                bucket_labels.push(("le", le));
+                self.sample(&bucket_name, &bucket_labels, cumulative as f64);
+            }
+            self.sample(&format!("{}_sum", name), labels, histogram.sum);
+            self.sample(&format!("{}_count", name), labels, histogram.count as f64);
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -1 +1,11 @@
+pub mod cache;
+pub mod dxf;
+pub mod geometry;
+pub mod mesh;
+pub mod metrics;
+pub mod rate_limit;
+pub mod reference_data;
+pub mod search;
+pub mod shutdown;
 pub mod svg;
+pub mod telemetry;
@@ -0,0 +1,122 @@
+mod policy;
+mod proxies;
+
+pub use policy::{RateLimitPolicy, RateLimitStats, RateLimits};
+pub use proxies::TrustedProxies;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Keys kept at most; past it the least recently seen key is forgotten, which bounds
+/// the memory taken by one-off clients
+const MAX_KEYS: usize = 10_000;
+
+/// Token bucket per key, e.g. per client IP: `capacity` requests at once,
+/// then one more every `1 / refill_per_second` seconds
+pub struct KeyedRateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Position of the key in `Buckets::recent`
+    touched: u64,
+}
+
+/// Buckets with their keys ordered by last use, so the stalest one is evicted in O(log n)
+#[derive(Default)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    recent: BTreeMap<u64, String>,
+    next_touch: u64,
+}
+
+impl KeyedRateLimiter {
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self {
+        Self {
+            capacity: f64::from(capacity.max(1)),
+            refill_per_second: refill_per_second.max(f64::MIN_POSITIVE),
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// `requests` per minute, all of them allowed in a burst
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, f64::from(requests.max(1)) / 60.0)
+    }
+
+    /// Takes a token of `key`; without one returns the wait until the next token
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        self.check_at(key, Instant::now())
+    }
+
+    pub fn check_at(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        self.take_at(key, 1, now)
+    }
+
+    /// Takes `tokens` tokens of `key` at once, e.g. one per row of a batch. More tokens
+    /// than the bucket holds need a full bucket and leave it in debt, so such a request
+    /// is not refused forever but the client waits as long as for `tokens` requests
+    pub fn take(&self, key: &str, tokens: u32) -> Result<(), Duration> {
+        self.take_at(key, tokens, Instant::now())
+    }
+
+    pub fn take_at(&self, key: &str, tokens: u32, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Buckets {
+            by_key,
+            recent,
+            next_touch,
+        } = &mut *buckets;
+        let touched = *next_touch;
+        *next_touch += 1;
+
+        if let Some(bucket) = by_key.get(key) {
+            recent.remove(&bucket.touched);
+        } else if by_key.len() >= MAX_KEYS {
+            if let Some((_, stalest)) = recent.pop_first() {
+                by_key.remove(&stalest);
+            }
+        }
+        recent.insert(touched, key.to_string());
+
+        let bucket = by_key.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            updated: now,
+            touched,
+        });
+        bucket.tokens = self.refilled(*bucket, now);
+        bucket.updated = now;
+        bucket.touched = touched;
+
+        let tokens = f64::from(tokens.max(1));
+        let needed = tokens.min(self.capacity);
+        if bucket.tokens >= needed {
+            bucket.tokens -= tokens;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (needed - bucket.tokens) / self.refill_per_second,
+            ))
+        }
+    }
+
+    /// Keys with a bucket, full ones included until they are evicted
+    pub fn tracked(&self) -> usize {
+        self.buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .by_key
+            .len()
+    }
+
+    fn refilled(&self, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.refill_per_second).min(self.capacity)
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use utoipa::ToSchema;
+
+use super::KeyedRateLimiter;
+use crate::shared::setting::models::app_config::{RateLimitConfig, RateLimitPolicyConfig};
+
+/// Counters of one policy since startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitStats {
+    pub policy: String,
+    /// Requests let through
+    pub allowed: u64,
+    /// Requests answered with 429
+    pub limited: u64,
+    /// Client IPs and API keys with a bucket
+    pub tracked_ips: usize,
+    pub tracked_keys: usize,
+}
+
+/// Buckets of the routes under `paths`: one per client IP and one per API key
+pub struct RateLimitPolicy {
+    name: String,
+    paths: Vec<String>,
+    per_ip: KeyedRateLimiter,
+    per_key: KeyedRateLimiter,
+    allowed: AtomicU64,
+    limited: AtomicU64,
+}
+
+impl RateLimitPolicy {
+    fn new(config: &RateLimitPolicyConfig) -> Self {
+        let per_key_per_minute = config.per_key_per_minute.unwrap_or(config.per_ip_per_minute);
+        let bucket = |per_minute: u32| {
+            KeyedRateLimiter::new(config.burst.unwrap_or(per_minute), f64::from(per_minute.max(1)) / 60.0)
+        };
+        Self {
+            name: config.name.clone(),
+            paths: config.paths.clone(),
+            per_ip: bucket(config.per_ip_per_minute),
+            per_key: bucket(per_key_per_minute),
+            allowed: AtomicU64::new(0),
+            limited: AtomicU64::new(0),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Longest prefix of `paths` that `path` starts with
+    fn matched_len(&self, path: &str) -> Option<usize> {
+        self.paths
+            .iter()
+            .filter(|prefix| path.starts_with(prefix.as_str()))
+            .map(String::len)
+            .max()
+    }
+
+    /// Takes a token of the API key, or of the client IP for requests without a key;
+    /// without one returns the wait until the next token
+    pub fn check(&self, api_key_id: Option<i64>, client_ip: &str) -> Result<(), Duration> {
+        self.take(api_key_id, client_ip, 1)
+    }
+
+    /// [`check`](Self::check) for a request that stands for `tokens` lookups
+    pub fn take(&self, api_key_id: Option<i64>, client_ip: &str, tokens: u32) -> Result<(), Duration> {
+        let result = match api_key_id {
+            Some(id) => self.per_key.take(&id.to_string(), tokens),
+            None => self.per_ip.take(client_ip, tokens),
+        };
+        let counter = if result.is_ok() { &self.allowed } else { &self.limited };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    pub fn stats(&self) -> RateLimitStats {
+        RateLimitStats {
+            policy: self.name.clone(),
+            allowed: self.allowed.load(Ordering::Relaxed),
+            limited: self.limited.load(Ordering::Relaxed),
+            tracked_ips: self.per_ip.tracked(),
+            tracked_keys: self.per_key.tracked(),
+        }
+    }
+}
+
+/// Policies of `[rate_limit]` in the configuration
+pub struct RateLimits {
+    policies: Vec<RateLimitPolicy>,
+}
+
+impl RateLimits {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let policies = if config.enabled {
+            config.policies.iter().map(RateLimitPolicy::new).collect()
+        } else {
+            Vec::new()
+        };
+        Self { policies }
+    }
+
+    /// The policy with the most specific matching prefix; `None` leaves the route unlimited
+    pub fn policy_for(&self, path: &str) -> Option<&RateLimitPolicy> {
+        self.policies
+            .iter()
+            .filter_map(|policy| policy.matched_len(path).map(|len| (len, policy)))
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, policy)| policy)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    pub fn stats(&self) -> Vec<RateLimitStats> {
+        self.policies.iter().map(RateLimitPolicy::stats).collect()
+    }
+}
@@ -0,0 +1,88 @@
+use axum::http::HeaderMap;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::log_warn;
+
+/// Reverse proxies whose `X-Forwarded-For` is believed, from `rate_limit.trusted_proxies`:
+/// addresses such as "127.0.0.1" and networks such as "172.16.0.0/12"
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    /// Entries that do not parse are skipped with a warning
+    pub fn new(entries: &[String]) -> Self {
+        let networks = entries
+            .iter()
+            .filter_map(|entry| {
+                let network = parse_network(entry);
+                if network.is_none() {
+                    log_warn!("Ignoring invalid rate_limit.trusted_proxies entry '{}'", entry);
+                }
+                network
+            })
+            .collect();
+        Self { networks }
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks
+            .iter()
+            .any(|(network, prefix)| in_network(ip, *network, *prefix))
+    }
+
+    /// Address of the client. `X-Forwarded-For` is read only when the peer is a trusted
+    /// proxy, from the right: the first entry that is not a trusted proxy itself was
+    /// appended by the outermost one, entries before it come from the client and can be forged
+    pub fn client_ip(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> String {
+        let Some(peer) = peer else {
+            return "unknown".to_string();
+        };
+        if !self.contains(peer.ip()) {
+            return peer.ip().to_canonical().to_string();
+        }
+
+        let forwarded: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        let client = forwarded
+            .iter()
+            .rev()
+            .find(|entry| entry.parse::<IpAddr>().map_or(true, |ip| !self.contains(ip)));
+        match client.or(forwarded.first()) {
+            Some(entry) if *entry != "unknown" => entry.to_string(),
+            _ => peer.ip().to_canonical().to_string(),
+        }
+    }
+}
+
+fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = match entry.trim().split_once('/') {
+        Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+        None => (entry.trim().parse::<IpAddr>().ok()?, None),
+    };
+    let bits = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(bits);
+    (prefix <= bits).then_some((address, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
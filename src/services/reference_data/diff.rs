@@ -0,0 +1,142 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::shared::utils::db::JsonRow;
+
+use super::tables::ReferenceTable;
+
+/// Row present in both versions with different values
+#[derive(Debug, Clone)]
+pub struct RowChange {
+    pub key: Value,
+    pub before: JsonRow,
+    pub after: JsonRow,
+    /// Changed columns, in name order
+    pub columns: Vec<String>,
+}
+
+/// Changes turning the stored table into the edited file, matched by the table key
+#[derive(Debug, Clone, Default)]
+pub struct TableDiff {
+    pub inserted: Vec<JsonRow>,
+    pub updated: Vec<RowChange>,
+    pub deleted: Vec<JsonRow>,
+}
+
+impl TableDiff {
+    pub fn between(table: ReferenceTable, current: &[JsonRow], edited: &[JsonRow]) -> Self {
+        let key = table.key();
+        let current_by_key: HashMap<String, &JsonRow> = current
+            .iter()
+            .filter_map(|row| Some((key_text(row.get(key)?), row)))
+            .collect();
+        let edited_keys: Vec<String> = edited.iter().filter_map(|row| row.get(key).map(key_text)).collect();
+
+        let mut diff = Self::default();
+        for row in edited {
+            let Some(key_value) = row.get(key) else {
+                continue;
+            };
+            match current_by_key.get(&key_text(key_value)) {
+                None => diff.inserted.push(row.clone()),
+                Some(before) => {
+                    let mut names: Vec<&String> = before.keys().chain(row.keys()).collect();
+                    names.sort();
+                    names.dedup();
+                    let columns: Vec<String> = names
+                        .into_iter()
+                        .filter(|name| !same_value(before.get(*name), row.get(*name)))
+                        .cloned()
+                        .collect();
+                    if !columns.is_empty() {
+                        diff.updated.push(RowChange {
+                            key: key_value.clone(),
+                            before: (*before).clone(),
+                            after: row.clone(),
+                            columns,
+                        });
+                    }
+                }
+            }
+        }
+        diff.deleted = current
+            .iter()
+            .filter(|row| {
+                row.get(key)
+                    .is_some_and(|value| !edited_keys.contains(&key_text(value)))
+            })
+            .cloned()
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Human-readable summary: `+` inserted, `~` changed and `-` deleted rows
+    pub fn preview(&self, table: ReferenceTable) -> String {
+        let key = table.key();
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{}: {} inserted, {} updated, {} deleted",
+            table,
+            self.inserted.len(),
+            self.updated.len(),
+            self.deleted.len()
+        );
+        for row in &self.inserted {
+            let _ = writeln!(out, "+ {}={}", key, row.get(key).map(key_text).unwrap_or_default());
+        }
+        for change in &self.updated {
+            let _ = writeln!(out, "~ {}={}", key, key_text(&change.key));
+            for column in &change.columns {
+                let _ = writeln!(
+                    out,
+                    "    {}: {} -> {}",
+                    column,
+                    display(change.before.get(column)),
+                    display(change.after.get(column))
+                );
+            }
+        }
+        for row in &self.deleted {
+            let _ = writeln!(out, "- {}={}", key, row.get(key).map(key_text).unwrap_or_default());
+        }
+        out
+    }
+}
+
+/// Key as text; `4` and `4.0` read from different sources compare equal
+pub fn key_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other
+            .as_f64()
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| other.to_string()),
+    }
+}
+
+/// Missing and NULL are the same; numbers are compared within 1e-9
+fn same_value(a: Option<&Value>, b: Option<&Value>) -> bool {
+    let a = a.filter(|v| !v.is_null());
+    let b = b.filter(|v| !v.is_null());
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(x), Some(y)) => (x - y).abs() < 1e-9,
+            _ => a == b,
+        },
+        _ => false,
+    }
+}
+
+fn display(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "NULL".to_string(),
+        Some(value) => value.to_string(),
+    }
+}
@@ -0,0 +1,114 @@
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::shared::error::AppError;
+use crate::shared::utils::db::JsonRow;
+
+use super::tables::Column;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    /// Array of objects, the format of the bundled `data/` tables
+    Json,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::Json => "json",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(FileFormat::Csv),
+            "json" => Ok(FileFormat::Json),
+            _ => Err(format!("unknown format '{}', expected csv or json", s)),
+        }
+    }
+}
+
+fn fs_error(path: &Path, e: impl fmt::Display) -> AppError {
+    AppError::FileSystemError(format!("{}: {}", path.display(), e))
+}
+
+/// Writes rows with the columns in table order; NULLs become empty CSV fields
+pub fn write_rows(path: &Path, format: FileFormat, columns: &[Column], rows: &[JsonRow]) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| fs_error(parent, e))?;
+    }
+
+    match format {
+        FileFormat::Json => {
+            let content = serde_json::to_string_pretty(rows).map_err(|e| fs_error(path, e))?;
+            fs::write(path, content + "\n").map_err(|e| fs_error(path, e))
+        }
+        FileFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path).map_err(|e| fs_error(path, e))?;
+            writer
+                .write_record(columns.iter().map(|column| column.name.as_str()))
+                .map_err(|e| fs_error(path, e))?;
+            for row in rows {
+                let record = columns.iter().map(|column| match row.get(&column.name) {
+                    None | Some(Value::Null) => String::new(),
+                    Some(Value::String(text)) => text.clone(),
+                    Some(value) => value.to_string(),
+                });
+                writer.write_record(record).map_err(|e| fs_error(path, e))?;
+            }
+            writer.flush().map_err(|e| fs_error(path, e))
+        }
+    }
+}
+
+/// Reads an edited file as loosely typed rows: CSV fields stay strings (empty
+/// fields are NULL) until `validate_rows` parses them against the table
+pub fn read_rows(path: &Path, format: FileFormat) -> Result<Vec<JsonRow>, AppError> {
+    match format {
+        FileFormat::Json => {
+            let content = fs::read_to_string(path).map_err(|e| fs_error(path, e))?;
+            serde_json::from_str(&content).map_err(|e| AppError::ValidationError(format!("{}: {}", path.display(), e)))
+        }
+        FileFormat::Csv => {
+            let mut reader = csv::Reader::from_path(path).map_err(|e| fs_error(path, e))?;
+            let headers = reader.headers().map_err(|e| fs_error(path, e))?.clone();
+            let mut rows = Vec::new();
+            for record in reader.records() {
+                let record = record.map_err(|e| AppError::ValidationError(format!("{}: {}", path.display(), e)))?;
+                let row = headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(name, field)| {
+                        let value = if field.trim().is_empty() {
+                            Value::Null
+                        } else {
+                            Value::from(field)
+                        };
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                rows.push(row);
+            }
+            Ok(rows)
+        }
+    }
+}
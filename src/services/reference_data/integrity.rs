@@ -0,0 +1,539 @@
+//! Consistency checks of the reference tables. Problems such as inverted
+//! limits or a NULL `es_d2_7e_m` otherwise only show up as failed requests.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use utoipa::ToSchema;
+
+use crate::shared::error::AppError;
+use crate::shared::utils::db::JsonRow;
+
+use super::diff::key_text;
+use super::store::ReferenceStore;
+use super::tables::{Column, ReferenceTable};
+
+/// Basic profile height of ISO 228-1 pipe threads, H = 0.960491P, h = 2/3 H
+const PIPE_PROFILE_HEIGHT: f64 = 0.640327;
+/// Basic pitch diameter of unified threads, d2 = d - 0.649519/n
+const UNIFIED_PITCH_FACTOR: f64 = 0.649519;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheck {
+    /// Minimum above maximum, or diameters out of major > pitch > minor order
+    Limits,
+    /// Upper deviation below the lower one
+    Deviation,
+    /// Stored value differs from the one computed by the standard's formula
+    Formula,
+    /// Tolerance column read for a class is NULL or absent
+    MissingTolerance,
+    /// Row another table depends on is absent
+    MissingRow,
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Breaks or falsifies responses
+    Error,
+    /// Suspicious, e.g. off from the formula by more than rounding
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrityFinding {
+    pub table: String,
+    /// `id=12`, or `*` for the whole table
+    pub row: String,
+    pub column: Option<String>,
+    pub check: IntegrityCheck,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrityReport {
+    pub checked_at: DateTime<Utc>,
+    /// Rows checked per table
+    pub rows: BTreeMap<String, usize>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors == 0
+    }
+}
+
+/// Rows of one table with its columns
+pub struct TableData {
+    pub table: ReferenceTable,
+    pub columns: Vec<Column>,
+    pub rows: Vec<JsonRow>,
+}
+
+/// Reads every reference table from the store and checks it
+pub async fn check_store(store: &ReferenceStore) -> Result<IntegrityReport, AppError> {
+    let mut tables = Vec::new();
+    for table in ReferenceTable::ALL {
+        tables.push(TableData {
+            table,
+            columns: store.columns(table).await?,
+            rows: store.rows(table).await?,
+        });
+    }
+    Ok(check_tables(&tables))
+}
+
+pub fn check_tables(tables: &[TableData]) -> IntegrityReport {
+    let mut findings = Findings::default();
+    let basic_dimensions = tables
+        .iter()
+        .find(|data| data.table == ReferenceTable::TrapezoidalBasicDimensions)
+        .map(|data| data.rows.as_slice())
+        .unwrap_or_default();
+
+    for data in tables {
+        match data.table {
+            ReferenceTable::MetricMain => check_metric(data, &mut findings),
+            ReferenceTable::MetricToleranceGrades => check_tolerance_grades(data, &mut findings),
+            ReferenceTable::MetricFundamentalDeviations => {
+                findings.duplicates(data.table, &data.rows, &["position", "pitch"])
+            }
+            ReferenceTable::ImperialMain => check_imperial(data, &mut findings),
+            ReferenceTable::TrapezoidalMain => check_trapezoidal(data, basic_dimensions, &mut findings),
+            ReferenceTable::TrapezoidalBasicDimensions => check_basic_dimensions(data, &mut findings),
+            ReferenceTable::PipeMain => check_pipe(data, &mut findings),
+        }
+    }
+
+    let findings = findings.0;
+    IntegrityReport {
+        checked_at: Utc::now(),
+        rows: tables
+            .iter()
+            .map(|data| (data.table.to_string(), data.rows.len()))
+            .collect(),
+        errors: findings.iter().filter(|f| f.severity == Severity::Error).count(),
+        warnings: findings.iter().filter(|f| f.severity == Severity::Warning).count(),
+        findings,
+    }
+}
+
+#[derive(Default)]
+struct Findings(Vec<IntegrityFinding>);
+
+impl Findings {
+    fn push(
+        &mut self,
+        table: ReferenceTable,
+        row: String,
+        column: Option<&str>,
+        check: IntegrityCheck,
+        severity: Severity,
+        message: String,
+    ) {
+        self.0.push(IntegrityFinding {
+            table: table.to_string(),
+            row,
+            column: column.map(str::to_string),
+            check,
+            severity,
+            message,
+        });
+    }
+
+    /// `min <= max` for every pair present in the row
+    fn limits(&mut self, table: ReferenceTable, row: &JsonRow, pairs: &[(&str, &str)]) {
+        for (min, max) in pairs {
+            if let (Some(low), Some(high)) = (number(row, min), number(row, max)) {
+                if low > high {
+                    self.push(
+                        table,
+                        label(table, row),
+                        Some(min),
+                        IntegrityCheck::Limits,
+                        Severity::Error,
+                        format!("{} = {} is above {} = {}", min, low, max, high),
+                    );
+                }
+            }
+        }
+    }
+
+    /// `smaller < larger` for diameters that must not overlap
+    fn order(&mut self, table: ReferenceTable, row: &JsonRow, pairs: &[(&str, &str)]) {
+        for (smaller, larger) in pairs {
+            if let (Some(low), Some(high)) = (number(row, smaller), number(row, larger)) {
+                if low >= high {
+                    self.push(
+                        table,
+                        label(table, row),
+                        Some(smaller),
+                        IntegrityCheck::Limits,
+                        Severity::Error,
+                        format!("{} = {} is not below {} = {}", smaller, low, larger, high),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Stored value within `tolerance` of the computed one
+    fn formula(
+        &mut self,
+        table: ReferenceTable,
+        row: &JsonRow,
+        column: &str,
+        expected: Option<f64>,
+        tolerance: f64,
+        formula: &str,
+    ) {
+        if let (Some(actual), Some(expected)) = (number(row, column), expected) {
+            if (actual - expected).abs() > tolerance {
+                self.push(
+                    table,
+                    label(table, row),
+                    Some(column),
+                    IntegrityCheck::Formula,
+                    Severity::Warning,
+                    format!("{} = {}, {} gives {:.4}", column, actual, formula, expected),
+                );
+            }
+        }
+    }
+
+    /// Rows sharing the same designation
+    fn duplicates(&mut self, table: ReferenceTable, rows: &[JsonRow], columns: &[&str]) {
+        let mut seen: HashMap<Vec<String>, String> = HashMap::new();
+        for row in rows {
+            let designation: Vec<String> = columns
+                .iter()
+                .map(|column| row.get(*column).map(key_text).unwrap_or_default())
+                .collect();
+            if let Some(first) = seen.get(&designation) {
+                self.push(
+                    table,
+                    label(table, row),
+                    None,
+                    IntegrityCheck::Duplicate,
+                    Severity::Error,
+                    format!("same {} as {}", columns.join(", "), first),
+                );
+            } else {
+                seen.insert(designation, label(table, row));
+            }
+        }
+    }
+}
+
+fn number(row: &JsonRow, column: &str) -> Option<f64> {
+    row.get(column).and_then(Value::as_f64)
+}
+
+fn label(table: ReferenceTable, row: &JsonRow) -> String {
+    format!(
+        "{}={}",
+        table.key(),
+        row.get(table.key()).map(key_text).unwrap_or_default()
+    )
+}
+
+/// ISO 261: one coarse pitch per diameter at most
+fn check_metric(data: &TableData, findings: &mut Findings) {
+    let table = data.table;
+    for row in &data.rows {
+        findings.order(table, row, &[("pitch", "diameter")]);
+    }
+    findings.duplicates(table, &data.rows, &["diameter", "pitch"]);
+    let coarse: Vec<JsonRow> = data
+        .rows
+        .iter()
+        .filter(|row| number(row, "type_pitch") == Some(0.0))
+        .cloned()
+        .collect();
+    findings.duplicates(table, &coarse, &["diameter", "type_pitch"]);
+}
+
+/// ISO 965-1 tolerances: one value per grade and pitch (and diameter range for Td2, TD2)
+fn check_tolerance_grades(data: &TableData, findings: &mut Findings) {
+    let table = data.table;
+    for row in &data.rows {
+        findings.limits(table, row, &[("diameter_min", "diameter_max")]);
+    }
+    findings.duplicates(table, &data.rows, &["kind", "grade", "pitch", "diameter_min"]);
+}
+
+/// ASME B1.1, values in inches
+fn check_imperial(data: &TableData, findings: &mut Findings) {
+    let table = data.table;
+    for row in &data.rows {
+        findings.limits(
+            table,
+            row,
+            &[
+                ("major_diam_min_m", "major_diam_max_m"),
+                ("pitch_diameter_min_m", "pitch_diameter_max_m"),
+                ("minor_diameter_min_f", "minor_diameter_max_f"),
+                ("pitch_diameter_min_f", "pitch_diameter_max_f"),
+            ],
+        );
+        findings.order(
+            table,
+            row,
+            &[
+                ("unr_minor_diameter_max_m", "pitch_diameter_min_m"),
+                ("pitch_diameter_max_m", "major_diam_min_m"),
+                ("minor_diameter_max_f", "pitch_diameter_min_f"),
+                ("pitch_diameter_max_f", "major_diameter_min_f"),
+            ],
+        );
+
+        let basic = number(row, "diameter_2");
+        let pitch_diameter = basic
+            .zip(number(row, "tpi"))
+            .map(|(d, tpi)| d - UNIFIED_PITCH_FACTOR / tpi);
+        let difference = |max: &str, min: &str| number(row, max).zip(number(row, min)).map(|(a, b)| a - b);
+        findings.formula(
+            table,
+            row,
+            "pitch_diameter_tolerance_m",
+            difference("pitch_diameter_max_m", "pitch_diameter_min_m"),
+            0.0002,
+            "max - min",
+        );
+        findings.formula(
+            table,
+            row,
+            "pitch_diameter_tolerance_f",
+            difference("pitch_diameter_max_f", "pitch_diameter_min_f"),
+            0.0002,
+            "max - min",
+        );
+        findings.formula(
+            table,
+            row,
+            "major_diam_max_m",
+            basic.zip(number(row, "allowance")).map(|(d, es)| d - es),
+            0.0002,
+            "d - allowance",
+        );
+        findings.formula(
+            table,
+            row,
+            "pitch_diameter_min_f",
+            pitch_diameter,
+            0.0002,
+            "d - 0.649519/n",
+        );
+        findings.formula(table, row, "major_diameter_min_f", basic, 0.0002, "d");
+    }
+
+    findings.duplicates(table, &data.rows, &["diameter", "tpi", "class_m"]);
+    findings.duplicates(table, &data.rows, &["diameter", "tpi", "class_f"]);
+}
+
+/// Columns `ThreadDataService::extract_tolerances` reads for one side
+fn tolerance_columns(side: &str) -> &'static [&'static str] {
+    match side {
+        "m" => &["es_d", "ei_d", "es_d2", "ei_d2", "es_d3", "ei_d3"],
+        _ => &["es_d1", "ei_d1", "es_d2", "ei_d2", "ei_d4"],
+    }
+}
+
+/// ISO 2904, diameters in mm and deviations in µm
+fn check_trapezoidal(data: &TableData, basic_dimensions: &[JsonRow], findings: &mut Findings) {
+    let table = data.table;
+
+    // Classes offered by the tolerance endpoint, one per `es_d2_<class>_<side>` column
+    let classes: BTreeSet<(String, String)> = data
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let rest = column.name.strip_prefix("es_d2_")?;
+            let (class, side) = rest.rsplit_once('_')?;
+            matches!(side, "m" | "f").then(|| (class.to_string(), side.to_string()))
+        })
+        .collect();
+    let defined: BTreeSet<&str> = data.columns.iter().map(|column| column.name.as_str()).collect();
+
+    let mut absent = BTreeSet::new();
+    for (class, side) in &classes {
+        for prefix in tolerance_columns(side) {
+            let column = format!("{}_{}_{}", prefix, class, side);
+            if !defined.contains(column.as_str()) {
+                findings.push(
+                    table,
+                    "*".to_string(),
+                    Some(&column),
+                    IntegrityCheck::MissingTolerance,
+                    Severity::Error,
+                    format!("class {} is offered but the column does not exist", class),
+                );
+                absent.insert(column);
+            }
+        }
+    }
+
+    for row in &data.rows {
+        let diameter = number(row, "diameter");
+        let pitch = number(row, "pitch");
+
+        findings.order(
+            table,
+            row,
+            &[("d3", "d1"), ("d1", "d2_d2"), ("d2_d2", "diameter"), ("diameter", "d4")],
+        );
+
+        let a_c = pitch.and_then(|p| {
+            basic_dimensions
+                .iter()
+                .find(|basic| number(basic, "p").is_some_and(|value| (value - p).abs() < 1e-9))
+                .and_then(|basic| number(basic, "a_c"))
+        });
+        if let Some(p) = pitch {
+            if a_c.is_none() {
+                findings.push(
+                    table,
+                    label(table, row),
+                    Some("pitch"),
+                    IntegrityCheck::MissingRow,
+                    Severity::Error,
+                    format!("no trapezoidal.basic_dimensions row for P = {}", p),
+                );
+            }
+        }
+        let dp = diameter.zip(pitch);
+        findings.formula(table, row, "d2_d2", dp.map(|(d, p)| d - 0.5 * p), 0.001, "d - 0.5P");
+        findings.formula(table, row, "d1", dp.map(|(d, p)| d - p), 0.001, "d - P");
+        findings.formula(
+            table,
+            row,
+            "d3",
+            dp.zip(a_c).map(|((d, p), a_c)| d - p - 2.0 * a_c),
+            0.001,
+            "d - P - 2ac",
+        );
+        findings.formula(
+            table,
+            row,
+            "d4",
+            diameter.zip(a_c).map(|(d, a_c)| d + 2.0 * a_c),
+            0.001,
+            "d + 2ac",
+        );
+
+        for (class, side) in &classes {
+            for prefix in tolerance_columns(side) {
+                let column = format!("{}_{}_{}", prefix, class, side);
+                if !absent.contains(&column) && !row.contains_key(&column) {
+                    findings.push(
+                        table,
+                        label(table, row),
+                        Some(&column),
+                        IntegrityCheck::MissingTolerance,
+                        Severity::Error,
+                        format!("NULL, class {} of this thread cannot be served", class),
+                    );
+                }
+            }
+            for diameter in ["d", "d1", "d2", "d3"] {
+                let es = format!("es_{}_{}_{}", diameter, class, side);
+                let ei = format!("ei_{}_{}_{}", diameter, class, side);
+                if let (Some(upper), Some(lower)) = (number(row, &es), number(row, &ei)) {
+                    if upper < lower {
+                        findings.push(
+                            table,
+                            label(table, row),
+                            Some(&es),
+                            IntegrityCheck::Deviation,
+                            Severity::Error,
+                            format!("es = {} is below ei = {} ({})", upper, lower, ei),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    findings.duplicates(table, &data.rows, &["diameter", "pitch"]);
+}
+
+fn check_basic_dimensions(data: &TableData, findings: &mut Findings) {
+    let table = data.table;
+    for row in &data.rows {
+        let pitch = number(row, "p");
+        findings.formula(table, row, "h1", pitch.map(|p| 0.5 * p), 0.001, "0.5P");
+        findings.formula(table, row, "z", pitch.map(|p| 0.25 * p), 0.001, "0.25P");
+        findings.formula(
+            table,
+            row,
+            "h4_h3",
+            pitch.zip(number(row, "a_c")).map(|(p, a_c)| 0.5 * p + a_c),
+            0.001,
+            "0.5P + ac",
+        );
+    }
+}
+
+/// ISO 228-1, values in mm
+fn check_pipe(data: &TableData, findings: &mut Findings) {
+    let table = data.table;
+    for row in &data.rows {
+        findings.limits(
+            table,
+            row,
+            &[
+                ("ex_major_dia_min", "ex_major_dia_max"),
+                ("ex_pitch_diam_min", "ex_pitch_diam_max"),
+                ("in_minor_dia_min", "in_minor_dia_max"),
+                ("in_pitch_diam_min", "in_pitch_diam_max"),
+            ],
+        );
+        findings.order(
+            table,
+            row,
+            &[
+                ("ex_minor_dia_max", "ex_pitch_diam_min"),
+                ("ex_pitch_diam_max", "ex_major_dia_min"),
+                ("in_minor_dia_max", "in_pitch_diam_min"),
+                ("in_pitch_diam_max", "in_major_dia_min"),
+            ],
+        );
+
+        let pitch = number(row, "thread_pitch");
+        let major = number(row, "ex_major_dia_max");
+        findings.formula(
+            table,
+            row,
+            "thread_pitch",
+            number(row, "thread_per").map(|n| 25.4 / n),
+            0.001,
+            "25.4/n",
+        );
+        findings.formula(
+            table,
+            row,
+            "ex_pitch_diam_max",
+            major.zip(pitch).map(|(d, p)| d - PIPE_PROFILE_HEIGHT * p),
+            0.002,
+            "d - 0.640327P",
+        );
+        findings.formula(
+            table,
+            row,
+            "in_minor_dia_min",
+            major.zip(pitch).map(|(d, p)| d - 2.0 * PIPE_PROFILE_HEIGHT * p),
+            0.002,
+            "d - 1.280654P",
+        );
+    }
+
+    findings.duplicates(table, &data.rows, &["designation_2", "class_name"]);
+}
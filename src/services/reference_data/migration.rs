@@ -0,0 +1,104 @@
+use chrono::Utc;
+use serde_json::Value;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::shared::error::AppError;
+use crate::shared::utils::db::JsonRow;
+
+use super::diff::TableDiff;
+use super::tables::{Column, ColumnKind, ReferenceTable};
+
+/// PostgreSQL migration reproducing the diff. Statements are idempotent, so a
+/// database the diff was already imported into stays unchanged.
+pub fn render_migration(table: ReferenceTable, columns: &[Column], diff: &TableDiff, source: &str) -> String {
+    let key = table.key();
+    let mut sql = String::new();
+    let _ = writeln!(
+        sql,
+        "-- {} from {}: {} inserted, {} updated, {} deleted",
+        table,
+        source,
+        diff.inserted.len(),
+        diff.updated.len(),
+        diff.deleted.len()
+    );
+
+    for row in &diff.deleted {
+        let _ = writeln!(sql, "DELETE FROM {} WHERE {} = {};", table, key, literal(row.get(key)));
+    }
+
+    for change in &diff.updated {
+        let assignments: Vec<String> = change
+            .columns
+            .iter()
+            .map(|column| format!("{} = {}", column, literal(change.after.get(column))))
+            .collect();
+        let _ = writeln!(
+            sql,
+            "UPDATE {} SET {} WHERE {} = {};",
+            table,
+            assignments.join(", "),
+            key,
+            literal(Some(&change.key))
+        );
+    }
+
+    for row in &diff.inserted {
+        let _ = writeln!(sql, "{}", insert(table, columns, row));
+    }
+    sql
+}
+
+fn insert(table: ReferenceTable, columns: &[Column], row: &JsonRow) -> String {
+    let names: Vec<&str> = columns.iter().map(|column| column.name.as_str()).collect();
+    // NULLs of an INSERT ... SELECT need a type
+    let values: Vec<String> = columns
+        .iter()
+        .map(|column| match row.get(&column.name) {
+            None | Some(Value::Null) => match column.kind {
+                ColumnKind::Integer => "NULL::bigint".to_string(),
+                ColumnKind::Real => "NULL::double precision".to_string(),
+                ColumnKind::Text => "NULL::text".to_string(),
+            },
+            value => literal(value),
+        })
+        .collect();
+    format!(
+        "INSERT INTO {table} ({}) SELECT {} WHERE NOT EXISTS (SELECT 1 FROM {table} WHERE {key} = {});",
+        names.join(", "),
+        values.join(", "),
+        literal(row.get(table.key())),
+        table = table,
+        key = table.key(),
+    )
+}
+
+fn literal(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "NULL".to_string(),
+        Some(Value::String(text)) => format!("'{}'", text.replace('\'', "''")),
+        Some(Value::Bool(flag)) => flag.to_string(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// Writes `<dir>/<timestamp>_<name>.sql`, picked up by `run_migrations` on the next start
+pub fn write_migration(dir: &Path, name: &str, sql: &str) -> Result<PathBuf, AppError> {
+    let description: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = dir.join(format!("{}_{}.sql", Utc::now().format("%Y%m%d%H%M%S"), description));
+
+    fs::create_dir_all(dir).map_err(|e| AppError::FileSystemError(format!("{}: {}", dir.display(), e)))?;
+    fs::write(&path, sql).map_err(|e| AppError::FileSystemError(format!("{}: {}", path.display(), e)))?;
+    Ok(path)
+}
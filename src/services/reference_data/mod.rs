@@ -0,0 +1,16 @@
+//! Export, validation and transactional import of the editable reference
+//! tables, used by the `thread-api-admin` binary
+
+pub mod diff;
+pub mod files;
+pub mod integrity;
+pub mod migration;
+pub mod store;
+pub mod tables;
+pub mod validate;
+
+pub use diff::TableDiff;
+pub use files::FileFormat;
+pub use store::ReferenceStore;
+pub use tables::{Column, ColumnKind, ReferenceTable};
+pub use validate::{validate_rows, Finding};
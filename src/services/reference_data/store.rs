@@ -0,0 +1,282 @@
+use serde_json::Value;
+use sqlx::{query::Query, Database, Encode, PgPool, Row, SqlitePool, Type};
+use std::path::{Path, PathBuf};
+
+use crate::log_info;
+use crate::shared::database::service::PostgresService;
+use crate::shared::error::AppError;
+use crate::shared::utils::db::{fetch_json_rows, fetch_sqlite_json_rows, JsonRow};
+
+use super::diff::TableDiff;
+use super::files::{read_rows, FileFormat};
+use super::tables::{Column, ColumnKind, ReferenceTable};
+
+/// Storage of the reference tables
+pub enum ReferenceStore {
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+    /// Bundled JSON tables of the memory backend, read-only
+    Files(PathBuf),
+}
+
+/// Statement with its values in placeholder order
+struct Statement<'a> {
+    sql: String,
+    binds: Vec<(&'a Column, Option<&'a Value>)>,
+}
+
+impl ReferenceStore {
+    /// Store behind the configured backend; `data_dir` holds the tables of the memory backend
+    pub fn for_database(database: &PostgresService, data_dir: &Path) -> Self {
+        if let Some(pool) = database.pool() {
+            ReferenceStore::Postgres(pool.clone())
+        } else if let Some(sqlite) = &database.sqlite {
+            ReferenceStore::Sqlite(sqlite.pool().clone())
+        } else {
+            ReferenceStore::Files(data_dir.to_path_buf())
+        }
+    }
+
+    /// `imperial.main` in PostgreSQL, `imperial_main` in SQLite
+    pub fn table_name(&self, table: ReferenceTable) -> String {
+        match self {
+            ReferenceStore::Postgres(_) => table.to_string(),
+            ReferenceStore::Sqlite(_) => format!("{}_{}", table.schema(), table.table()),
+            ReferenceStore::Files(dir) => table.file_path(dir, FileFormat::Json).display().to_string(),
+        }
+    }
+
+    /// `$n` in PostgreSQL, `?n` in SQLite
+    fn placeholder(&self, index: usize) -> String {
+        match self {
+            ReferenceStore::Sqlite(_) => format!("?{}", index),
+            _ => format!("${}", index),
+        }
+    }
+
+    /// Columns in table order
+    pub async fn columns(&self, table: ReferenceTable) -> Result<Vec<Column>, AppError> {
+        let columns: Vec<Column> = match self {
+            ReferenceStore::Postgres(pool) => {
+                let rows = sqlx::query(
+                    "
+SELECT column_name, data_type, is_nullable
+FROM information_schema.columns
+WHERE table_schema = $1 AND table_name = $2
+ORDER BY ordinal_position;
+",
+                )
+                .bind(table.schema())
+                .bind(table.table())
+                .fetch_all(pool)
+                .await?;
+                rows.iter()
+                    .map(|row| Column {
+                        name: row.get("column_name"),
+                        kind: match row.get::<String, _>("data_type").as_str() {
+                            "smallint" | "integer" | "bigint" => ColumnKind::Integer,
+                            "real" | "double precision" | "numeric" => ColumnKind::Real,
+                            _ => ColumnKind::Text,
+                        },
+                        nullable: row.get::<String, _>("is_nullable") == "YES",
+                    })
+                    .collect()
+            }
+            ReferenceStore::Sqlite(pool) => {
+                let rows = sqlx::query("SELECT name, type, \"notnull\" FROM pragma_table_info(?) ORDER BY cid")
+                    .bind(self.table_name(table))
+                    .fetch_all(pool)
+                    .await?;
+                rows.iter()
+                    .map(|row| Column {
+                        name: row.get("name"),
+                        kind: match row.get::<String, _>("type").to_uppercase().as_str() {
+                            "INTEGER" => ColumnKind::Integer,
+                            "REAL" => ColumnKind::Real,
+                            _ => ColumnKind::Text,
+                        },
+                        nullable: row.get::<i64, _>("notnull") == 0,
+                    })
+                    .collect()
+            }
+            ReferenceStore::Files(_) => Self::infer_columns(&self.rows(table).await?),
+        };
+
+        if columns.is_empty() && !matches!(self, ReferenceStore::Files(_)) {
+            return Err(AppError::DatabaseError(format!(
+                "table {} does not exist",
+                self.table_name(table)
+            )));
+        }
+        Ok(columns)
+    }
+
+    /// All rows ordered by the table key
+    pub async fn rows(&self, table: ReferenceTable) -> Result<Vec<JsonRow>, AppError> {
+        let query = format!("SELECT * FROM {} ORDER BY {}", self.table_name(table), table.key());
+        let rows = match self {
+            ReferenceStore::Postgres(pool) => fetch_json_rows(pool, &query, |q| q).await?,
+            ReferenceStore::Sqlite(pool) => fetch_sqlite_json_rows(pool, &query, |q| q).await?,
+            ReferenceStore::Files(dir) => {
+                let mut rows = read_rows(&table.file_path(dir, FileFormat::Json), FileFormat::Json)?;
+                rows.sort_by(|a, b| {
+                    let key = |row: &JsonRow| row.get(table.key()).and_then(Value::as_f64);
+                    key(a).unwrap_or_default().total_cmp(&key(b).unwrap_or_default())
+                });
+                rows
+            }
+        };
+        Ok(rows)
+    }
+
+    /// Columns of schemaless JSON rows: every key seen, typed by its values
+    fn infer_columns(rows: &[JsonRow]) -> Vec<Column> {
+        let mut columns: Vec<Column> = Vec::new();
+        for (name, value) in rows.iter().flatten() {
+            let kind = match value {
+                Value::Number(n) if n.is_i64() => ColumnKind::Integer,
+                Value::Number(_) => ColumnKind::Real,
+                _ => ColumnKind::Text,
+            };
+            match columns.iter_mut().find(|column| &column.name == name) {
+                Some(column) if column.kind == ColumnKind::Integer && kind == ColumnKind::Real => {
+                    column.kind = ColumnKind::Real
+                }
+                Some(_) => {}
+                None => columns.push(Column {
+                    name: name.clone(),
+                    kind,
+                    nullable: true,
+                }),
+            }
+        }
+        columns
+    }
+
+    /// Applies the diff in one transaction; nothing is written if a statement fails
+    pub async fn apply(&self, table: ReferenceTable, columns: &[Column], diff: &TableDiff) -> Result<(), AppError> {
+        let statements = self.statements(table, columns, diff)?;
+        log_info!(
+            "Applying {} statement(s) to {}",
+            statements.len(),
+            self.table_name(table)
+        );
+
+        match self {
+            ReferenceStore::Postgres(pool) => {
+                let mut transaction = pool.begin().await?;
+                for statement in &statements {
+                    let mut query = sqlx::query(&statement.sql);
+                    for (column, value) in &statement.binds {
+                        query = bind(query, column, *value);
+                    }
+                    query.execute(&mut *transaction).await?;
+                }
+                transaction.commit().await?;
+            }
+            ReferenceStore::Sqlite(pool) => {
+                let mut transaction = pool.begin().await?;
+                for statement in &statements {
+                    let mut query = sqlx::query(&statement.sql);
+                    for (column, value) in &statement.binds {
+                        query = bind(query, column, *value);
+                    }
+                    query.execute(&mut *transaction).await?;
+                }
+                transaction.commit().await?;
+            }
+            ReferenceStore::Files(_) => {
+                return Err(AppError::ConfigurationError {
+                    message: format!("{} is bundled data, edit the file instead", self.table_name(table)),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn statements<'a>(
+        &self,
+        table: ReferenceTable,
+        columns: &'a [Column],
+        diff: &'a TableDiff,
+    ) -> Result<Vec<Statement<'a>>, AppError> {
+        let name = self.table_name(table);
+        let key = columns
+            .iter()
+            .find(|column| column.name == table.key())
+            .ok_or_else(|| AppError::DatabaseError(format!("table {} has no column {}", name, table.key())))?;
+        let mut statements = Vec::new();
+
+        for row in &diff.deleted {
+            statements.push(Statement {
+                sql: format!("DELETE FROM {} WHERE {} = {}", name, key.name, self.placeholder(1)),
+                binds: vec![(key, row.get(&key.name))],
+            });
+        }
+
+        for change in &diff.updated {
+            let changed: Vec<&Column> = columns
+                .iter()
+                .filter(|column| change.columns.contains(&column.name))
+                .collect();
+            let assignments: Vec<String> = changed
+                .iter()
+                .enumerate()
+                .map(|(index, column)| format!("{} = {}", column.name, self.placeholder(index + 1)))
+                .collect();
+            let mut binds: Vec<(&Column, Option<&Value>)> = changed
+                .iter()
+                .map(|column| (*column, change.after.get(&column.name)))
+                .collect();
+            binds.push((key, Some(&change.key)));
+            statements.push(Statement {
+                sql: format!(
+                    "UPDATE {} SET {} WHERE {} = {}",
+                    name,
+                    assignments.join(", "),
+                    key.name,
+                    self.placeholder(binds.len())
+                ),
+                binds,
+            });
+        }
+
+        for row in &diff.inserted {
+            let names: Vec<&str> = columns.iter().map(|column| column.name.as_str()).collect();
+            let placeholders: Vec<String> = (1..=columns.len()).map(|index| self.placeholder(index)).collect();
+            statements.push(Statement {
+                sql: format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    name,
+                    names.join(", "),
+                    placeholders.join(", ")
+                ),
+                binds: columns.iter().map(|column| (column, row.get(&column.name))).collect(),
+            });
+        }
+
+        Ok(statements)
+    }
+}
+
+/// Binds a value with the type of its column, so NULLs are typed as well
+fn bind<'q, DB: Database>(
+    query: Query<'q, DB, <DB as Database>::Arguments<'q>>,
+    column: &Column,
+    value: Option<&Value>,
+) -> Query<'q, DB, <DB as Database>::Arguments<'q>>
+where
+    Option<i64>: Encode<'q, DB> + Type<DB>,
+    Option<f64>: Encode<'q, DB> + Type<DB>,
+    Option<String>: Encode<'q, DB> + Type<DB>,
+{
+    let value = value.filter(|value| !value.is_null());
+    match column.kind {
+        ColumnKind::Integer => query.bind(value.and_then(Value::as_i64)),
+        ColumnKind::Real => query.bind(value.and_then(Value::as_f64)),
+        ColumnKind::Text => query.bind(value.map(|value| match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        })),
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::files::FileFormat;
+
+/// Reference tables edited outside the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceTable {
+    MetricMain,
+    MetricToleranceGrades,
+    MetricFundamentalDeviations,
+    ImperialMain,
+    TrapezoidalMain,
+    TrapezoidalBasicDimensions,
+    PipeMain,
+}
+
+impl ReferenceTable {
+    pub const ALL: [ReferenceTable; 7] = [
+        ReferenceTable::MetricMain,
+        ReferenceTable::MetricToleranceGrades,
+        ReferenceTable::MetricFundamentalDeviations,
+        ReferenceTable::ImperialMain,
+        ReferenceTable::TrapezoidalMain,
+        ReferenceTable::TrapezoidalBasicDimensions,
+        ReferenceTable::PipeMain,
+    ];
+
+    pub fn schema(&self) -> &'static str {
+        match self {
+            ReferenceTable::MetricMain
+            | ReferenceTable::MetricToleranceGrades
+            | ReferenceTable::MetricFundamentalDeviations => "metric",
+            ReferenceTable::ImperialMain => "imperial",
+            ReferenceTable::TrapezoidalMain | ReferenceTable::TrapezoidalBasicDimensions => "trapezoidal",
+            ReferenceTable::PipeMain => "pipe",
+        }
+    }
+
+    pub fn table(&self) -> &'static str {
+        match self {
+            ReferenceTable::MetricToleranceGrades => "tolerance_grades",
+            ReferenceTable::MetricFundamentalDeviations => "fundamental_deviations",
+            ReferenceTable::TrapezoidalBasicDimensions => "basic_dimensions",
+            _ => "main",
+        }
+    }
+
+    /// Column identifying a row; `basic_dimensions` has one row per pitch
+    pub fn key(&self) -> &'static str {
+        match self {
+            ReferenceTable::TrapezoidalBasicDimensions => "p",
+            _ => "id",
+        }
+    }
+
+    /// `<dir>/<schema>/<table>.<ext>`, the layout of the bundled `data/` directory
+    pub fn file_path(&self, dir: &Path, format: FileFormat) -> PathBuf {
+        dir.join(self.schema())
+            .join(format!("{}.{}", self.table(), format.extension()))
+    }
+
+    /// Table of an exported file, from its directory and file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let table = path.file_stem()?.to_str()?;
+        let schema = path.parent()?.file_name()?.to_str()?;
+        format!("{}.{}", schema, table).parse().ok()
+    }
+}
+
+impl fmt::Display for ReferenceTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.schema(), self.table())
+    }
+}
+
+impl FromStr for ReferenceTable {
+    type Err = String;
+
+    /// Accepts the PostgreSQL name (`pipe.main`) and the SQLite one (`pipe_main`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|table| s == table.to_string() || s == format!("{}_{}", table.schema(), table.table()))
+            .ok_or_else(|| {
+                let known: Vec<String> = Self::ALL.iter().map(|table| table.to_string()).collect();
+                format!("unknown table '{}', expected one of: {}", s, known.join(", "))
+            })
+    }
+}
+
+/// Storage class of a column, enough to parse and bind edited values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Integer,
+    Real,
+    Text,
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    pub kind: ColumnKind,
+    pub nullable: bool,
+}
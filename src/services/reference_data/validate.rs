@@ -0,0 +1,109 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::shared::utils::db::JsonRow;
+
+use super::diff::key_text;
+use super::tables::{Column, ColumnKind, ReferenceTable};
+
+/// Problem in one row of an edited file; rows are numbered from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub row: usize,
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "row {}, column '{}': {}", self.row, column, self.message),
+            None => write!(f, "row {}: {}", self.row, self.message),
+        }
+    }
+}
+
+/// Parses the values of every row against the table columns. Returns the typed
+/// rows, which are only safe to import when no findings are reported.
+pub fn validate_rows(table: ReferenceTable, columns: &[Column], rows: Vec<JsonRow>) -> (Vec<JsonRow>, Vec<Finding>) {
+    let mut findings = Vec::new();
+    let mut keys = HashSet::new();
+    let mut typed_rows = Vec::with_capacity(rows.len());
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let number = index + 1;
+        let mut finding = |column: Option<&str>, message: String| {
+            findings.push(Finding {
+                row: number,
+                column: column.map(str::to_string),
+                message,
+            })
+        };
+
+        for name in row.keys() {
+            if !columns.iter().any(|column| &column.name == name) {
+                finding(Some(name), format!("unknown column of {}", table));
+            }
+        }
+
+        let mut typed = JsonRow::new();
+        for column in columns {
+            let value = row.get(&column.name).cloned().unwrap_or(Value::Null);
+            match parse_value(column.kind, value) {
+                Ok(Value::Null) if !column.nullable || column.name == table.key() => {
+                    finding(Some(&column.name), "value is required".to_string())
+                }
+                Ok(Value::Null) => {}
+                Ok(value) => {
+                    typed.insert(column.name.clone(), value);
+                }
+                Err(message) => finding(Some(&column.name), message),
+            }
+        }
+
+        if let Some(key) = typed.get(table.key()) {
+            if !keys.insert(key_text(key)) {
+                finding(Some(table.key()), format!("duplicate key {}", key_text(key)));
+            }
+        }
+        typed_rows.push(typed);
+    }
+
+    (typed_rows, findings)
+}
+
+fn parse_value(kind: ColumnKind, value: Value) -> Result<Value, String> {
+    let text = match &value {
+        Value::Null => return Ok(Value::Null),
+        Value::String(text) => Some(text.trim().to_string()),
+        _ => None,
+    };
+
+    match kind {
+        ColumnKind::Text => Ok(match value {
+            Value::String(text) => Value::String(text),
+            other => Value::String(other.to_string()),
+        }),
+        ColumnKind::Integer => {
+            let number = match &text {
+                Some(text) => text.parse::<f64>().ok(),
+                None => value.as_f64(),
+            };
+            match number {
+                Some(n) if n.fract() == 0.0 => Ok(Value::from(n as i64)),
+                _ => Err(format!("expected an integer, got {}", value)),
+            }
+        }
+        ColumnKind::Real => {
+            let number = match &text {
+                Some(text) => text.parse::<f64>().ok(),
+                None => value.as_f64(),
+            };
+            number
+                .filter(|n| n.is_finite())
+                .map(Value::from)
+                .ok_or_else(|| format!("expected a number, got {}", value))
+        }
+    }
+}
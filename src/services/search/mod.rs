@@ -0,0 +1,321 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+use crate::log_info;
+use crate::shared::database::service::PostgresService;
+use crate::shared::enums::ThreadStandard;
+use crate::shared::error::AppError;
+use crate::shared::utils::number::NumberFormatter;
+
+/// One thread size that can be suggested
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchEntry {
+    /// Designation as the drawings print it, e.g. "M10×1.25", "1/4 - 20 UNC", "Tr 20 x 4", "G 1/2 - 14"
+    pub designation: String,
+    /// Other spelling of the same size, e.g. the decimal imperial diameter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub standard: ThreadStandard,
+    /// Parameters identifying the size on the standard's routes; `ids` of the pipe rows
+    pub params: Value,
+    #[serde(skip)]
+    keys: Vec<String>,
+}
+
+impl SearchEntry {
+    fn new(standard: ThreadStandard, designation: String, alias: Option<String>, params: Value) -> Self {
+        let keys = std::iter::once(&designation)
+            .chain(&alias)
+            .map(|text| normalize(text))
+            .collect();
+        Self {
+            designation,
+            alias,
+            standard,
+            params,
+            keys,
+        }
+    }
+
+    /// Best match tier of the normalized query, lower is better
+    fn tier(&self, query: &str) -> Option<u8> {
+        self.keys.iter().filter_map(|key| match_tier(key, query)).min()
+    }
+}
+
+/// Suggestion with the number of lookups of the size
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub entry: SearchEntry,
+    pub popularity: i64,
+}
+
+/// How long lookup totals are reused before the usage table is summed again
+const POPULARITY_REFRESH: Duration = Duration::from_secs(60);
+
+/// Lookup totals per size key, as of `counted_at`
+struct Popularity {
+    totals: Arc<HashMap<String, i64>>,
+    counted_at: Instant,
+}
+
+/// Designations of every thread size, collected from the reference tables on
+/// the first search and rebuilt when the dataset version changes. Their lookup
+/// totals are recounted at most once per `refresh`, not on every keystroke.
+pub struct SearchIndex {
+    entries: RwLock<Option<Arc<Vec<SearchEntry>>>>,
+    /// Lets one search or rebuild collect the designations while the others wait
+    build: Mutex<()>,
+    popularity: RwLock<Option<Popularity>>,
+    /// Lets one search recount while the others wait for its totals
+    recount: Mutex<()>,
+    refresh: Duration,
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self::with_refresh(POPULARITY_REFRESH)
+    }
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_refresh(refresh: Duration) -> Self {
+        Self {
+            entries: RwLock::new(None),
+            build: Mutex::new(()),
+            popularity: RwLock::new(None),
+            recount: Mutex::new(()),
+            refresh,
+        }
+    }
+
+    fn built_entries(&self) -> Option<Arc<Vec<SearchEntry>>> {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Designations built on the first call and reused until `rebuild`
+    async fn entries(&self, database: &PostgresService) -> Result<Arc<Vec<SearchEntry>>, AppError> {
+        if let Some(entries) = self.built_entries() {
+            return Ok(entries);
+        }
+        let _build = self.build.lock().await;
+        if let Some(entries) = self.built_entries() {
+            return Ok(entries);
+        }
+
+        let entries = Arc::new(build_entries(database).await?);
+        *self.entries.write().unwrap_or_else(|e| e.into_inner()) = Some(entries.clone());
+        Ok(entries)
+    }
+
+    /// Collects the designations again after the reference data changed; the
+    /// searches keep the old ones until the new are ready. On failure the index
+    /// is dropped, so the next search builds it instead of answering stale rows.
+    pub async fn rebuild(&self, database: &PostgresService) -> Result<(), AppError> {
+        let _build = self.build.lock().await;
+        let entries = build_entries(database).await;
+        // Totals are keyed by the designations of the old index
+        *self.popularity.write().unwrap_or_else(|e| e.into_inner()) = None;
+        let mut current = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        match entries {
+            Ok(entries) => {
+                *current = Some(Arc::new(entries));
+                Ok(())
+            }
+            Err(e) => {
+                *current = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn fresh_totals(&self) -> Option<Arc<HashMap<String, i64>>> {
+        let popularity = self.popularity.read().unwrap_or_else(|e| e.into_inner());
+        popularity
+            .as_ref()
+            .filter(|popularity| popularity.counted_at.elapsed() < self.refresh)
+            .map(|popularity| popularity.totals.clone())
+    }
+
+    /// Totals per size key, recounted from the usage table when older than `refresh`
+    async fn totals(
+        &self,
+        database: &PostgresService,
+        entries: &[SearchEntry],
+    ) -> Result<Arc<HashMap<String, i64>>, AppError> {
+        if let Some(totals) = self.fresh_totals() {
+            return Ok(totals);
+        }
+        let _recount = self.recount.lock().await;
+        if let Some(totals) = self.fresh_totals() {
+            return Ok(totals);
+        }
+
+        let usage = database.repository_analytics.usage_counts().await?;
+        let totals = Arc::new(popularity_totals(entries, &usage));
+        *self.popularity.write().unwrap_or_else(|e| e.into_inner()) = Some(Popularity {
+            totals: totals.clone(),
+            counted_at: Instant::now(),
+        });
+        Ok(totals)
+    }
+
+    /// Sizes matching `query`: exact, prefix, substring and then fuzzy matches,
+    /// each group ordered by popularity
+    pub async fn search(
+        &self,
+        database: &PostgresService,
+        query: &str,
+        standard: Option<ThreadStandard>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, AppError> {
+        let query = normalize(query);
+        let entries = self.entries(database).await?;
+
+        let matches: Vec<(u8, usize, &SearchEntry)> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| standard.is_none_or(|standard| entry.standard == standard))
+            .filter_map(|(position, entry)| entry.tier(&query).map(|tier| (tier, position, entry)))
+            .collect();
+        if matches.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let totals = self.totals(database, &entries).await?;
+        let popularity = |entry: &SearchEntry| totals.get(&entry.keys[0]).copied().unwrap_or_default();
+        let mut hits: Vec<(u8, usize, i64, &SearchEntry)> = matches
+            .into_iter()
+            .map(|(tier, position, entry)| (tier, position, popularity(entry), entry))
+            .collect();
+        hits.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)).then(a.1.cmp(&b.1)));
+
+        Ok(hits
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, popularity, entry)| SearchHit {
+                entry: entry.clone(),
+                popularity,
+            })
+            .collect())
+    }
+}
+
+async fn build_entries(database: &PostgresService) -> Result<Vec<SearchEntry>, AppError> {
+    let number = |value: f64| NumberFormatter::format_number_trim_zeros(value, Some(3));
+    let mut entries = Vec::new();
+
+    // Coarse pitches are left out of metric designations, as on drawings
+    for row in database.repository_metric.get_all_pitches().await? {
+        let (Some(diameter), Some(pitch)) = (
+            row.get("diameter").and_then(Value::as_f64),
+            row.get("pitch").and_then(Value::as_f64),
+        ) else {
+            continue;
+        };
+        let coarse = row.get("type_pitch").and_then(Value::as_i64) == Some(0);
+        let full = format!("M{}×{}", number(diameter), number(pitch));
+        let (designation, alias) = if coarse {
+            (format!("M{}", number(diameter)), Some(full))
+        } else {
+            (full, None)
+        };
+        let params = json!({ "diameter": diameter, "pitch": pitch });
+        entries.push(SearchEntry::new(ThreadStandard::Metric, designation, alias, params));
+    }
+
+    for row in database.repository_imperial.unique_diameters().await? {
+        let designation = format!("{} - {} {}", row.diameter, row.tpi, row.series_designation);
+        let alias = format!("{} - {} {}", row.diameter_2, row.tpi, row.series_designation);
+        let params = json!({ "diameter": row.diameter, "tpi": row.tpi });
+        entries.push(SearchEntry::new(
+            ThreadStandard::Imperial,
+            designation,
+            Some(alias),
+            params,
+        ));
+    }
+
+    for row in database.repository_trapezoidal.diameters().await? {
+        let designation = format!("Tr {} x {}", row.diameter, row.pitch);
+        let params = json!({ "diameter": row.diameter, "pitch": row.pitch });
+        entries.push(SearchEntry::new(ThreadStandard::Trapezoidal, designation, None, params));
+    }
+
+    // External and internal rows of a size share the designation
+    let mut pipe: BTreeMap<String, Vec<i32>> = BTreeMap::new();
+    let mut pipe_order = Vec::new();
+    for row in database.repository_pipe.list().await? {
+        let designation = format!("G {} - {}", row.designation_2, row.thread_per);
+        if !pipe.contains_key(&designation) {
+            pipe_order.push(designation.clone());
+        }
+        pipe.entry(designation).or_default().push(row.id);
+    }
+    for designation in pipe_order {
+        let params = json!({ "ids": pipe[&designation] });
+        entries.push(SearchEntry::new(ThreadStandard::Pipe, designation, None, params));
+    }
+
+    log_info!("Search index built with {} designations", entries.len());
+    Ok(entries)
+}
+
+/// Case, spaces and the multiplication sign do not matter: "m10x1.25" finds "M10×1.25"
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '×' { 'x' } else { c })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn match_tier(key: &str, query: &str) -> Option<u8> {
+    if key == query {
+        Some(0)
+    } else if key.starts_with(query) {
+        Some(1)
+    } else if key.contains(query) {
+        Some(2)
+    } else if query.chars().count() >= 2 && is_subsequence(query, key) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+/// All characters of `query` appear in `key` in the same order: "m125" finds "M12×1.5"
+fn is_subsequence(query: &str, key: &str) -> bool {
+    let mut key = key.chars();
+    query.chars().all(|wanted| key.any(|c| c == wanted))
+}
+
+/// Lookups of every recorded designation of a size, whatever the tolerance class:
+/// "M10" counts "M10-6g" and "M10-6H" but not "M10×1.25-6g". A designation is
+/// matched to the sizes whose key is its whole text or the part before a '-'.
+fn popularity_totals(entries: &[SearchEntry], usage: &[(String, i64)]) -> HashMap<String, i64> {
+    let mut totals: HashMap<String, i64> = entries.iter().map(|entry| (entry.keys[0].clone(), 0)).collect();
+    for (designation, count) in usage {
+        let designation = normalize(designation);
+        let prefixes = designation
+            .match_indices('-')
+            .map(|(end, _)| &designation[..end])
+            .chain(std::iter::once(designation.as_str()));
+        for prefix in prefixes {
+            if let Some(total) = totals.get_mut(prefix) {
+                *total += count;
+            }
+        }
+    }
+    totals
+}
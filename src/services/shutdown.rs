@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Set once a shutdown signal arrives: readiness answers 503 from then on, so the
+/// load balancer stops sending requests before the listener closes
+#[derive(Debug, Clone, Default)]
+pub struct Drain {
+    draining: Arc<AtomicBool>,
+}
+
+impl Drain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn begin(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use crate::shared::enums::Theme;
+
+use super::models::{SvgText, SvgTextOptions};
+use super::svg_service::SvgService;
+
+/// Stroke styles used in generated technical drawings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    /// Visible outline of the part
+    Outline,
+    /// Dimension and extension lines
+    Thin,
+    /// Centre and reference lines (dash-dot)
+    Reference,
+    /// Mating or limit profiles (dashed)
+    Dashed,
+}
+
+/// Builder for SVG documents drawn from computed geometry instead of a static template
+pub struct SvgDrawing<'a> {
+    service: &'a SvgService,
+    theme: Theme,
+    width: f64,
+    height: f64,
+    elements: String,
+}
+
+impl<'a> SvgDrawing<'a> {
+    pub fn new(service: &'a SvgService, theme: Theme, width: f64, height: f64) -> Self {
+        Self {
+            service,
+            theme,
+            width,
+            height,
+            elements: String::new(),
+        }
+    }
+
+    /// Main stroke colour, the same palette as the static templates
+    pub fn stroke_color(&self) -> &'static str {
+        match self.theme {
+            Theme::Light => "black",
+            Theme::Dark => "#C9B8B8",
+        }
+    }
+
+    /// Fill colour for material sections
+    pub fn material_color(&self) -> &'static str {
+        match self.theme {
+            Theme::Light => "#034684",
+            Theme::Dark => "#C9B8B8",
+        }
+    }
+
+    fn stroke_attributes(&self, style: LineStyle) -> String {
+        let (width, dash) = match style {
+            LineStyle::Outline => (2.83, None),
+            LineStyle::Thin => (1.0, None),
+            LineStyle::Reference => (0.8, Some("18 4 3 4")),
+            LineStyle::Dashed => (1.2, Some("8 5")),
+        };
+        let mut attributes = format!(
+            r#"stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round""#,
+            self.stroke_color(),
+            width
+        );
+        if let Some(dash) = dash {
+            let _ = write!(attributes, r#" stroke-dasharray="{}""#, dash);
+        }
+        attributes
+    }
+
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, style: LineStyle) {
+        let _ = write!(
+            self.elements,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" {}/>"#,
+            x1,
+            y1,
+            x2,
+            y2,
+            self.stroke_attributes(style)
+        );
+    }
+
+    /// Open polyline through the given points
+    pub fn polyline(&mut self, points: &[(f64, f64)], style: LineStyle) {
+        let _ = write!(
+            self.elements,
+            r#"<polyline points="{}" fill="none" {}/>"#,
+            Self::format_points(points),
+            self.stroke_attributes(style)
+        );
+    }
+
+    /// Closed, filled polygon without outline
+    pub fn filled_polygon(&mut self, points: &[(f64, f64)], fill: &str, opacity: f64) {
+        let _ = write!(
+            self.elements,
+            r#"<polygon points="{}" fill="{}" fill-opacity="{}" stroke="none"/>"#,
+            Self::format_points(points),
+            fill,
+            opacity
+        );
+    }
+
+    /// Circular arc around (cx, cy) from angle `a0` to `a1` (degrees, SVG orientation)
+    pub fn arc(&mut self, cx: f64, cy: f64, radius: f64, a0: f64, a1: f64, style: LineStyle) {
+        let (x0, y0) = (cx + radius * a0.to_radians().cos(), cy + radius * a0.to_radians().sin());
+        let (x1, y1) = (cx + radius * a1.to_radians().cos(), cy + radius * a1.to_radians().sin());
+        let large = if (a1 - a0).abs() > 180.0 { 1 } else { 0 };
+        let sweep = if a1 > a0 { 1 } else { 0 };
+        let _ = write!(
+            self.elements,
+            r#"<path d="M{:.2} {:.2} A{:.2} {:.2} 0 {} {} {:.2} {:.2}" fill="none" {}/>"#,
+            x0,
+            y0,
+            radius,
+            radius,
+            large,
+            sweep,
+            x1,
+            y1,
+            self.stroke_attributes(style)
+        );
+    }
+
+    /// Filled arrowhead with its tip at (x, y) pointing in `direction` (radians, SVG orientation)
+    pub fn arrowhead(&mut self, x: f64, y: f64, direction: f64) {
+        const LENGTH: f64 = 14.0;
+        const HALF_WIDTH: f64 = 4.0;
+        let (dx, dy) = (direction.cos(), direction.sin());
+        let (bx, by) = (x - dx * LENGTH, y - dy * LENGTH);
+        let points = [
+            (x, y),
+            (bx - dy * HALF_WIDTH, by + dx * HALF_WIDTH),
+            (bx + dy * HALF_WIDTH, by - dx * HALF_WIDTH),
+        ];
+        let _ = write!(
+            self.elements,
+            r#"<polygon points="{}" fill="{}" stroke="none"/>"#,
+            Self::format_points(&points),
+            self.stroke_color()
+        );
+    }
+
+    /// Dimension line between two points with arrowheads on both ends
+    pub fn dimension_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let direction = (y2 - y1).atan2(x2 - x1);
+        self.line(x1, y1, x2, y2, LineStyle::Thin);
+        self.arrowhead(x2, y2, direction);
+        self.arrowhead(x1, y1, direction + std::f64::consts::PI);
+    }
+
+    pub fn text(&mut self, item: SvgText, options: SvgTextOptions) {
+        self.elements
+            .push_str(&self.service.generate_svg_text(&item, &self.theme, &options));
+    }
+
+    /// Complete SVG document
+    pub fn finish(self) -> String {
+        format!(
+            r#"<svg width="{w}" height="{h}" viewBox="0 0 {w} {h}" fill="none" xmlns="http://www.w3.org/2000/svg">{elements}</svg>"#,
+            w = self.width,
+            h = self.height,
+            elements = self.elements
+        )
+    }
+
+    fn format_points(points: &[(f64, f64)]) -> String {
+        points
+            .iter()
+            .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -1,4 +1,4 @@
-use strum::{Display, EnumString};
+use strum::{Display, EnumIter, EnumString};
 
 #[derive(Debug, Clone, Copy, Display, EnumString, Default)] // Added Copy trait
 #[strum(serialize_all = "lowercase")]
@@ -40,7 +40,7 @@ pub enum FontWeight {
     W900,
 }
 
-#[derive(Debug, Clone, Display, Default, Copy)]
+#[derive(Debug, Clone, Display, Default, Copy, EnumIter)]
 pub enum FontFamily {
     #[strum(serialize = "Arial")]
     #[default]
@@ -60,3 +60,18 @@ pub enum FontFamily {
     #[strum(serialize = "Impact")]
     Impact,
 }
+
+impl FontFamily {
+    /// Bundled typeface (static/fonts) used in place of this family when rendering to PNG/PDF
+    pub fn bundled_family(&self) -> &'static str {
+        match self {
+            FontFamily::Arial
+            | FontFamily::Helvetica
+            | FontFamily::Verdana
+            | FontFamily::ComicSans
+            | FontFamily::Impact => "DejaVu Sans",
+            FontFamily::TimesNewRoman | FontFamily::Georgia => "DejaVu Serif",
+            FontFamily::Courier => "DejaVu Sans Mono",
+        }
+    }
+}
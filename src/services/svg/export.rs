@@ -0,0 +1,233 @@
+use std::sync::OnceLock;
+
+use resvg::tiny_skia;
+use resvg::usvg::{self, fontdb, Align, AspectRatio, PostProcessingSteps, TreeParsing, TreePostProc};
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::shared::error::AppError;
+
+use super::enums::FontFamily;
+
+/// Fonts compiled into the binary so PNG/PDF output does not depend on the host
+const BUNDLED_FONTS: [&[u8]; 6] = [
+    include_bytes!("../../../static/fonts/DejaVuSans.ttf"),
+    include_bytes!("../../../static/fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../../../static/fonts/DejaVuSerif.ttf"),
+    include_bytes!("../../../static/fonts/DejaVuSerif-Bold.ttf"),
+    include_bytes!("../../../static/fonts/DejaVuSansMono.ttf"),
+    include_bytes!("../../../static/fonts/DejaVuSansMono-Bold.ttf"),
+];
+/// Nominal resolution of SVG user units
+const SVG_DPI: f32 = 96.0;
+const MAX_DPI: f32 = 1200.0;
+/// Largest side of a PNG in pixels
+const MAX_RASTER_SIDE: u32 = 8192;
+const POINTS_PER_INCH: f32 = 72.0;
+const MM_PER_INCH: f32 = 25.4;
+
+/// Output format of the SVG endpoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Svg,
+    Png,
+    Pdf,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "image/svg+xml; charset=utf-8",
+            ExportFormat::Png => "image/png",
+            ExportFormat::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "svg",
+            ExportFormat::Png => "png",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// PDF page size; `original` keeps the drawing size at 96 dpi
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSize {
+    #[default]
+    Original,
+    A3,
+    A4,
+    A5,
+    Letter,
+}
+
+impl PageSize {
+    /// Portrait width and height in millimetres
+    fn size_mm(&self) -> Option<(f32, f32)> {
+        match self {
+            PageSize::Original => None,
+            PageSize::A3 => Some((297.0, 420.0)),
+            PageSize::A4 => Some((210.0, 297.0)),
+            PageSize::A5 => Some((148.0, 210.0)),
+            PageSize::Letter => Some((215.9, 279.4)),
+        }
+    }
+}
+
+/// Query parameters shared by all SVG endpoints
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// PNG resolution, 96 by default
+    pub dpi: Option<f32>,
+    /// PNG width in pixels; with `height` the drawing is fitted into the box
+    pub width: Option<u32>,
+    /// PNG height in pixels
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub page: PageSize,
+}
+
+/// Renders generated SVG documents to PNG and PDF
+pub struct SvgExporter {
+    fontdb: fontdb::Database,
+}
+
+impl SvgExporter {
+    /// Shared exporter; the font database is built once on first use
+    pub fn global() -> &'static SvgExporter {
+        static EXPORTER: OnceLock<SvgExporter> = OnceLock::new();
+        EXPORTER.get_or_init(SvgExporter::new)
+    }
+
+    pub fn new() -> Self {
+        let mut fontdb = fontdb::Database::new();
+        for font in BUNDLED_FONTS {
+            fontdb.load_font_data(font.to_vec());
+        }
+
+        // Register every FontFamily name as an alias of its bundled typeface
+        let bundled: Vec<fontdb::FaceInfo> = fontdb.faces().cloned().collect();
+        for family in FontFamily::iter() {
+            let alias = family.to_string();
+            for face in bundled
+                .iter()
+                .filter(|face| face.families.iter().any(|(name, _)| name == family.bundled_family()))
+            {
+                let mut aliased = face.clone();
+                aliased.families = vec![(alias.clone(), fontdb::Language::English_UnitedStates)];
+                fontdb.push_face_info(aliased);
+            }
+        }
+        fontdb.set_sans_serif_family("DejaVu Sans");
+        fontdb.set_serif_family("DejaVu Serif");
+        fontdb.set_monospace_family("DejaVu Sans Mono");
+
+        Self { fontdb }
+    }
+
+    pub fn export(&self, svg: &str, params: &ExportParams) -> Result<Vec<u8>, AppError> {
+        match params.format {
+            ExportFormat::Svg => Ok(svg.as_bytes().to_vec()),
+            ExportFormat::Png => self.to_png(svg, params),
+            ExportFormat::Pdf => self.to_pdf(svg, params.page),
+        }
+    }
+
+    /// Parses the document and converts all text to outlines with the bundled fonts
+    fn parse(&self, svg: &str) -> Result<usvg::Tree, AppError> {
+        let options = usvg::Options {
+            font_family: FontFamily::default().bundled_family().to_string(),
+            ..usvg::Options::default()
+        };
+        let mut tree = usvg::Tree::from_str(svg, &options).map_err(|e| AppError::InvalidSvgTemplate(e.to_string()))?;
+        tree.postprocess(PostProcessingSteps::default(), &self.fontdb);
+        Ok(tree)
+    }
+
+    pub fn to_png(&self, svg: &str, params: &ExportParams) -> Result<Vec<u8>, AppError> {
+        let tree = self.parse(svg)?;
+        let (width, height) = (tree.size.width(), tree.size.height());
+
+        let scale = match (params.width, params.height) {
+            (Some(w), Some(h)) => (w as f32 / width).min(h as f32 / height),
+            (Some(w), None) => w as f32 / width,
+            (None, Some(h)) => h as f32 / height,
+            (None, None) => {
+                let dpi = params.dpi.unwrap_or(SVG_DPI);
+                if !(dpi > 0.0 && dpi <= MAX_DPI) {
+                    return Err(AppError::ValidationError(format!(
+                        "dpi must be between 1 and {}",
+                        MAX_DPI
+                    )));
+                }
+                dpi / SVG_DPI
+            }
+        };
+
+        let pixel_width = (width * scale).round() as u32;
+        let pixel_height = (height * scale).round() as u32;
+        if pixel_width == 0 || pixel_height == 0 || pixel_width.max(pixel_height) > MAX_RASTER_SIDE {
+            return Err(AppError::ValidationError(format!(
+                "image size must be between 1 and {} pixels per side",
+                MAX_RASTER_SIDE
+            )));
+        }
+
+        let mut pixmap = tiny_skia::Pixmap::new(pixel_width, pixel_height).ok_or_else(|| AppError::InternalError {
+            message: "failed to allocate PNG canvas".to_string(),
+        })?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        pixmap.encode_png().map_err(|e| AppError::InternalError {
+            message: format!("PNG encoding failed: {}", e),
+        })
+    }
+
+    /// Single-page PDF; fixed page sizes are oriented like the drawing and fit it centred
+    pub fn to_pdf(&self, svg: &str, page: PageSize) -> Result<Vec<u8>, AppError> {
+        let tree = self.parse(svg)?;
+
+        let options = match page.size_mm() {
+            None => svg2pdf::Options {
+                dpi: SVG_DPI,
+                ..svg2pdf::Options::default()
+            },
+            Some((short, long)) => {
+                let landscape = tree.size.width() > tree.size.height();
+                let (width, height) = if landscape { (long, short) } else { (short, long) };
+                let to_points = |mm: f32| mm / MM_PER_INCH * POINTS_PER_INCH;
+                svg2pdf::Options {
+                    viewport: usvg::Size::from_wh(to_points(width), to_points(height)),
+                    aspect: Some(AspectRatio {
+                        defer: false,
+                        align: Align::XMidYMid,
+                        slice: false,
+                    }),
+                    dpi: POINTS_PER_INCH,
+                    ..svg2pdf::Options::default()
+                }
+            }
+        };
+
+        Ok(svg2pdf::convert_tree(&tree, options))
+    }
+}
+
+impl Default for SvgExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,12 @@
+pub mod drawing;
 pub mod enums;
+pub mod export;
 pub mod models;
+pub mod profile_renderer;
 pub mod svg_service;
+pub mod templates;
 pub mod text_options;
+pub mod tolerance_renderer;
 use crate::shared;
 
 pub use models::*;
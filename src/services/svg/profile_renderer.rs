@@ -0,0 +1,428 @@
+use crate::services::geometry::{ProfileOutline, ThreadProfile};
+use crate::shared::enums::{Language, Theme, ThreadStandard, ThreadType, Unit};
+use crate::shared::utils::number::NumberFormatter;
+
+use super::drawing::{LineStyle, SvgDrawing};
+use super::enums::{FontFamily, FontWeight, TextAnchor};
+use super::models::{SvgText, SvgTextOptions};
+use super::svg_service::SvgService;
+use super::text_options::TextOptionsGenerator;
+
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 800.0;
+const MARGIN_LEFT: f64 = 170.0;
+const MARGIN_RIGHT: f64 = 250.0;
+const MARGIN_TOP: f64 = 140.0;
+const MARGIN_BOTTOM: f64 = 130.0;
+/// Pixels per millimetre at 96 dpi, used for the scale label
+const PX_PER_MM: f64 = 96.0 / 25.4;
+
+/// Parameters of a generated profile drawing
+#[derive(Debug, Clone)]
+pub struct ProfileDrawingOptions {
+    pub title: String,
+    pub theme: Theme,
+    pub language: Language,
+    pub units: Unit,
+    pub precision: Option<usize>,
+    /// Number of pitches shown
+    pub pitches: usize,
+}
+
+/// Draws a thread profile to scale with dimension lines computed from its geometry
+pub struct ProfileRenderer<'a> {
+    service: &'a SvgService,
+}
+
+/// Mapping from profile coordinates (mm) to canvas pixels
+struct Viewport {
+    scale: f64,
+    origin_x: f64,
+    origin_y: f64,
+    top: f64,
+    bottom: f64,
+    length: f64,
+}
+
+impl Viewport {
+    fn x(&self, x: f64) -> f64 {
+        self.origin_x + x * self.scale
+    }
+
+    fn y(&self, y: f64) -> f64 {
+        self.origin_y + (self.top - y) * self.scale
+    }
+}
+
+impl<'a> ProfileRenderer<'a> {
+    pub fn new(service: &'a SvgService) -> Self {
+        Self { service }
+    }
+
+    pub fn render(&self, profile: &ThreadProfile, options: &ProfileDrawingOptions) -> String {
+        let pitches = options.pitches.max(3);
+        let mating = profile.mating();
+        let p = profile.pitch;
+        let depth = profile.thread_depth();
+
+        // Radial band shown on the drawing, with room for the material on both sides
+        let levels = [
+            profile.crest_level,
+            profile.root_level,
+            mating.crest_level,
+            mating.root_level,
+        ];
+        let padding = 0.3 * depth;
+        let top = levels.iter().cloned().fold(f64::MIN, f64::max) + padding;
+        let bottom = levels.iter().cloned().fold(f64::MAX, f64::min) - padding;
+
+        let length = pitches as f64 * p;
+        let available_w = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let available_h = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let scale = (available_w / length).min(available_h / (top - bottom));
+        let view = Viewport {
+            scale,
+            origin_x: MARGIN_LEFT + (available_w - length * scale) / 2.0,
+            origin_y: MARGIN_TOP + (available_h - (top - bottom) * scale) / 2.0,
+            top,
+            bottom,
+            length,
+        };
+
+        let mut drawing = SvgDrawing::new(self.service, options.theme, WIDTH, HEIGHT);
+        self.draw_material(&mut drawing, &view, profile, pitches);
+        self.draw_reference_lines(&mut drawing, &view, profile, options);
+
+        let mating_points = Self::to_canvas(&view, &ProfileOutline::build(&mating, pitches));
+        drawing.polyline(&mating_points, LineStyle::Dashed);
+        let points = Self::to_canvas(&view, &ProfileOutline::build(profile, pitches));
+        drawing.polyline(&points, LineStyle::Outline);
+
+        self.draw_depth(&mut drawing, &view, profile, options);
+        self.draw_pitch(&mut drawing, &view, profile, options);
+        self.draw_flank_angle(&mut drawing, &view, profile);
+        self.draw_crest(&mut drawing, &view, profile, pitches, options);
+        self.draw_root(&mut drawing, &view, profile, pitches, options);
+        self.draw_captions(&mut drawing, scale, options);
+
+        drawing.finish()
+    }
+
+    fn to_canvas(view: &Viewport, outline: &ProfileOutline) -> Vec<(f64, f64)> {
+        outline
+            .points(5.0)
+            .into_iter()
+            .map(|point| (view.x(point.x), view.y(point.y)))
+            .collect()
+    }
+
+    fn format(value: f64, options: &ProfileDrawingOptions) -> String {
+        NumberFormatter::convert_and_round_to_string(value, &Unit::Mm, &options.units, options.precision, false)
+    }
+
+    fn text_options(&self, size: f64, anchor: TextAnchor) -> SvgTextOptions {
+        self.service
+            .create_custom_text_options(size, 0.0, anchor, FontWeight::Normal, FontFamily::Arial)
+    }
+
+    /// Fills the material side of the selected thread
+    fn draw_material(&self, drawing: &mut SvgDrawing, view: &Viewport, profile: &ThreadProfile, pitches: usize) {
+        let mut polygon = Self::to_canvas(view, &ProfileOutline::build(profile, pitches));
+        let edge = match profile.thread_type {
+            ThreadType::Male => view.y(view.bottom),
+            ThreadType::Female => view.y(view.top),
+        };
+        polygon.push((view.x(view.length), edge));
+        polygon.push((view.x(0.0), edge));
+        let color = drawing.material_color();
+        drawing.filled_polygon(&polygon, color, 0.18);
+    }
+
+    /// Major, pitch and minor diameter lines with their values on the right
+    fn draw_reference_lines(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        profile: &ThreadProfile,
+        options: &ProfileDrawingOptions,
+    ) {
+        let [major, pitch, minor] = profile.diameter_symbols();
+        let start = view.x(0.0) - 25.0;
+        let end = view.x(view.length) + 30.0;
+        let label_options = self.text_options(22.0, TextAnchor::Start);
+
+        for (symbol, diameter) in [
+            (major, profile.major_diameter),
+            (pitch, profile.pitch_diameter),
+            (minor, profile.minor_diameter),
+        ] {
+            let y = view.y(diameter / 2.0);
+            drawing.line(start, y, end, y, LineStyle::Reference);
+            drawing.text(
+                SvgText::new(
+                    end + 10.0,
+                    y + 7.0,
+                    format!("{} = ø{}", symbol, Self::format(diameter, options)),
+                ),
+                label_options,
+            );
+        }
+    }
+
+    /// Vertical dimension of the thread depth on the left
+    fn draw_depth(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        profile: &ThreadProfile,
+        options: &ProfileDrawingOptions,
+    ) {
+        let symbol = match (profile.standard, profile.thread_type) {
+            (ThreadStandard::Pipe, _) => "h",
+            (ThreadStandard::Trapezoidal, ThreadType::Female) => "H4",
+            (_, ThreadType::Male) => "h3",
+            (_, ThreadType::Female) => "H1",
+        };
+        let x = view.x(0.0) - 70.0;
+        let (y1, y2) = (view.y(profile.crest_level), view.y(profile.root_level));
+        drawing.line(view.x(0.0) - 30.0, y1, x - 10.0, y1, LineStyle::Thin);
+        drawing.line(view.x(0.0) - 30.0, y2, x - 10.0, y2, LineStyle::Thin);
+        drawing.dimension_line(x, y1, x, y2);
+        drawing.text(
+            SvgText::new(
+                x - 10.0,
+                (y1 + y2) / 2.0,
+                format!("{} = {}", symbol, Self::format(profile.thread_depth(), options)),
+            ),
+            self.text_options(22.0, TextAnchor::Middle)
+                .with(|o| o.rotation_angle = -90.0),
+        );
+    }
+
+    /// Outward direction of the crest of the selected thread on the canvas (-1 = up)
+    fn crest_side(profile: &ThreadProfile) -> f64 {
+        match profile.thread_type {
+            ThreadType::Male => -1.0,
+            ThreadType::Female => 1.0,
+        }
+    }
+
+    /// Centres of crests lying strictly inside the drawn range
+    fn crest_centers(profile: &ThreadProfile, pitches: usize) -> Vec<f64> {
+        let p = profile.pitch;
+        match profile.thread_type {
+            ThreadType::Male => (0..pitches).map(|k| (k as f64 + 0.5) * p).collect(),
+            ThreadType::Female => (1..pitches).map(|k| k as f64 * p).collect(),
+        }
+    }
+
+    /// Centres of roots lying strictly inside the drawn range
+    fn root_centers(profile: &ThreadProfile, pitches: usize) -> Vec<f64> {
+        let p = profile.pitch;
+        match profile.thread_type {
+            ThreadType::Male => (1..pitches).map(|k| k as f64 * p).collect(),
+            ThreadType::Female => (0..pitches).map(|k| (k as f64 + 0.5) * p).collect(),
+        }
+    }
+
+    /// Pitch between two neighbouring roots, placed on the root side
+    fn draw_pitch(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        profile: &ThreadProfile,
+        options: &ProfileDrawingOptions,
+    ) {
+        let side = -Self::crest_side(profile);
+        let first = match profile.thread_type {
+            ThreadType::Male => 0.0,
+            ThreadType::Female => profile.pitch / 2.0,
+        };
+        let (x1, x2) = (view.x(first), view.x(first + profile.pitch));
+        let root_y = view.y(profile.root_level);
+        let edge = if side > 0.0 {
+            view.y(view.bottom)
+        } else {
+            view.y(view.top)
+        };
+        let y = edge + side * 40.0;
+
+        drawing.line(x1, root_y, x1, y + side * 12.0, LineStyle::Thin);
+        drawing.line(x2, root_y, x2, y + side * 12.0, LineStyle::Thin);
+        drawing.dimension_line(x1, y, x2, y);
+        drawing.text(
+            SvgText::new(
+                (x1 + x2) / 2.0,
+                y - 8.0,
+                format!("P = {}", Self::format(profile.pitch, options)),
+            ),
+            self.text_options(24.0, TextAnchor::Middle),
+        );
+    }
+
+    /// Flank lines extended to the sharp tip of the first tooth with the included angle
+    fn draw_flank_angle(&self, drawing: &mut SvgDrawing, view: &Viewport, profile: &ThreadProfile) {
+        let center = match profile.thread_type {
+            ThreadType::Male => profile.pitch / 2.0,
+            ThreadType::Female => profile.pitch,
+        };
+        let outward = -Self::crest_side(profile);
+        let tip_level = profile.crest_level + outward * profile.crest_truncation;
+        let reach = profile.crest_truncation + profile.thread_depth();
+        let spread = reach * profile.half_angle().tan();
+        let (tip_x, tip_y) = (view.x(center), view.y(tip_level));
+        let end_y = view.y(tip_level - outward * reach);
+
+        drawing.line(tip_x, tip_y, view.x(center - spread), end_y, LineStyle::Thin);
+        drawing.line(tip_x, tip_y, view.x(center + spread), end_y, LineStyle::Thin);
+
+        let radius = (profile.crest_truncation + 0.45 * profile.thread_depth()) * view.scale;
+        let inward = if outward > 0.0 { 90.0 } else { -90.0 };
+        let half = profile.flank_angle / 2.0;
+        drawing.arc(tip_x, tip_y, radius, inward - half, inward + half, LineStyle::Thin);
+
+        let label_y = tip_y + (radius + 26.0) * if outward > 0.0 { 1.0 } else { -1.0 } + 8.0;
+        drawing.text(
+            SvgText::new(tip_x, label_y, format!("{}°", profile.flank_angle)),
+            self.text_options(22.0, TextAnchor::Middle),
+        );
+    }
+
+    /// Crest flat width, or the crest radius when the crest is fully rounded
+    fn draw_crest(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        profile: &ThreadProfile,
+        pitches: usize,
+        options: &ProfileDrawingOptions,
+    ) {
+        let Some(&center) = Self::crest_centers(profile, pitches).last() else {
+            return;
+        };
+        let side = Self::crest_side(profile);
+        self.draw_feature(
+            drawing,
+            view,
+            center,
+            profile.crest_level,
+            side,
+            profile.crest_flat(),
+            profile.crest_radius,
+            profile.is_crest_rounded(),
+            options,
+        );
+    }
+
+    /// Root flat width, or the root radius when the root is fully rounded
+    fn draw_root(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        profile: &ThreadProfile,
+        pitches: usize,
+        options: &ProfileDrawingOptions,
+    ) {
+        let Some(&center) = Self::root_centers(profile, pitches).last() else {
+            return;
+        };
+        let side = -Self::crest_side(profile);
+        self.draw_feature(
+            drawing,
+            view,
+            center,
+            profile.root_level,
+            side,
+            profile.root_flat(),
+            profile.root_radius,
+            profile.is_root_rounded(),
+            options,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_feature(
+        &self,
+        drawing: &mut SvgDrawing,
+        view: &Viewport,
+        center: f64,
+        level: f64,
+        side: f64,
+        flat: f64,
+        radius: f64,
+        rounded: bool,
+        options: &ProfileDrawingOptions,
+    ) {
+        let y = view.y(level);
+        if rounded {
+            // Leader from the rounded feature to the radius value
+            let (x0, y0) = (view.x(center), y);
+            let (x1, y1) = (x0 + 45.0, y0 + side * 45.0);
+            drawing.line(x0, y0, x1, y1, LineStyle::Thin);
+            drawing.line(x1, y1, x1 + 80.0, y1, LineStyle::Thin);
+            drawing.arrowhead(x0, y0, (y0 - y1).atan2(x0 - x1));
+            drawing.text(
+                SvgText::new(x1 + 4.0, y1 - 6.0, format!("R{}", Self::format(radius, options))),
+                self.text_options(20.0, TextAnchor::Start),
+            );
+            return;
+        }
+
+        let (x1, x2) = (view.x(center - flat / 2.0), view.x(center + flat / 2.0));
+        let dimension_y = y + side * 28.0;
+        drawing.line(x1, y, x1, dimension_y + side * 8.0, LineStyle::Thin);
+        drawing.line(x2, y, x2, dimension_y + side * 8.0, LineStyle::Thin);
+        // Narrow flats get the arrows from outside
+        if x2 - x1 > 40.0 {
+            drawing.dimension_line(x1, dimension_y, x2, dimension_y);
+        } else {
+            drawing.line(x1 - 30.0, dimension_y, x2 + 30.0, dimension_y, LineStyle::Thin);
+            drawing.arrowhead(x1, dimension_y, 0.0);
+            drawing.arrowhead(x2, dimension_y, std::f64::consts::PI);
+        }
+
+        let mut label = Self::format(flat, options);
+        if radius > 0.0 {
+            label = format!("{} (R{})", label, Self::format(radius, options));
+        }
+        let text_y = if side > 0.0 {
+            dimension_y + 26.0
+        } else {
+            dimension_y - 8.0
+        };
+        drawing.text(
+            SvgText::new((x1 + x2) / 2.0, text_y, label),
+            self.text_options(20.0, TextAnchor::Middle),
+        );
+    }
+
+    /// Title, scale and legend
+    fn draw_captions(&self, drawing: &mut SvgDrawing, scale: f64, options: &ProfileDrawingOptions) {
+        let (scale_label, mating_label) = match options.language {
+            Language::Ru => ("Масштаб", "Сопрягаемый профиль"),
+            Language::En => ("Scale", "Mating profile"),
+        };
+        let ratio = scale / PX_PER_MM;
+        let scale_value = if ratio >= 1.0 {
+            format!("{}:1", NumberFormatter::format_number_trim_zeros(ratio, Some(1)))
+        } else {
+            format!("1:{}", NumberFormatter::format_number_trim_zeros(1.0 / ratio, Some(1)))
+        };
+
+        drawing.text(
+            SvgText::new(40.0, 60.0, options.title.clone()),
+            self.service.text_option_40_0_start_bold(),
+        );
+        drawing.text(
+            SvgText::new(WIDTH - 40.0, 60.0, format!("{} {}", scale_label, scale_value)),
+            self.text_options(24.0, TextAnchor::End),
+        );
+
+        let y = HEIGHT - 35.0;
+        drawing.line(40.0, y - 7.0, 110.0, y - 7.0, LineStyle::Dashed);
+        drawing.text(
+            SvgText::new(125.0, y, mating_label),
+            self.text_options(22.0, TextAnchor::Start),
+        );
+    }
+}
@@ -4,14 +4,16 @@ use axum::{
     response::IntoResponse,
 };
 use std::path::PathBuf;
-use tokio::fs;
+use tracing::Instrument;
 
 use crate::{
+    services::telemetry,
     shared::enums::{Theme, ThreadStandard, ThreadType},
     shared::error::AppError,
 };
 
 use super::models::{SvgText, SvgTextOptions};
+use super::templates;
 
 pub struct SvgService {
     base_path: PathBuf,
@@ -31,26 +33,33 @@ impl SvgService {
         thread_type: ThreadType,
         theme: Theme,
     ) -> Result<String, AppError> {
-        let file_name = self.generate_file_name(thread_type, theme, thread_standard);
-        self.read_svg_file(&file_name).await
+        let file_name = Self::template_file_name(thread_standard, thread_type, theme);
+        let span = tracing::info_span!(
+            "SvgService::load_template",
+            svg.template = file_name.as_str(),
+            otel.status_code = tracing::field::Empty,
+            otel.status_description = tracing::field::Empty,
+        );
+
+        let template = self.read_svg_file(&file_name).instrument(span.clone()).await;
+        if let Err(e) = &template {
+            telemetry::set_error(&span, e);
+        }
+        template
     }
 
-    /// Generates file name based on parameters
-    fn generate_file_name(
-        &self,
-        enum_type_thread: ThreadType,
-        enum_theme: Theme,
-        enum_thread: ThreadStandard,
-    ) -> String {
-        format!("{}-thread-{}-{}.svg", enum_thread, enum_type_thread, enum_theme)
+    /// Template file name, e.g. `imperial-thread-male-light.svg`
+    pub fn template_file_name(thread_standard: ThreadStandard, thread_type: ThreadType, theme: Theme) -> String {
+        format!("{}-thread-{}-{}.svg", thread_standard, thread_type, theme)
     }
 
-    /// Reads SVG file from filesystem
+    /// Reads SVG file from the preloaded templates, falling back to the filesystem
     async fn read_svg_file(&self, file_name: &str) -> Result<String, AppError> {
         let file_path = self.base_path.join(file_name);
 
-        fs::read_to_string(file_path)
+        templates::load(file_path)
             .await
+            .map(|template| template.to_string())
             .map_err(|e| AppError::FileSystemError(e.to_string()))
     }
 
@@ -94,6 +103,8 @@ impl SvgService {
         items: Vec<(SvgText, SvgTextOptions)>,
         theme: &Theme,
     ) -> String {
+        let _span = tracing::info_span!("SvgService::append_text_elements", svg.text_elements = items.len()).entered();
+
         // Find the closing tag of the SVG
         if let Some(last_tag_pos) = content.rfind("</svg>") {
             let (base_content, _) = content.split_at(last_tag_pos);
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use tokio::fs;
+
+/// Directory the SVG handlers read their templates from
+pub const TEMPLATE_DIR: &str = "./static/svg";
+
+/// Templates kept in memory, keyed by the path they were read from
+static TEMPLATES: LazyLock<RwLock<HashMap<PathBuf, Arc<str>>>> = LazyLock::new(Default::default);
+
+/// Reads every `.svg` file of `dir` into memory; returns the number of templates loaded
+pub async fn preload(dir: impl AsRef<Path>) -> std::io::Result<usize> {
+    let dir = dir.as_ref();
+    let mut entries = fs::read_dir(dir).await?;
+    let mut count = 0;
+
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = PathBuf::from(entry.file_name());
+        if file_name.extension().and_then(|e| e.to_str()) != Some("svg") {
+            continue;
+        }
+        let path = dir.join(file_name);
+        let content = fs::read_to_string(&path).await?;
+        store(path, content.into());
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Returns a template, reading it from disk only the first time it is requested
+pub async fn load(path: impl AsRef<Path>) -> std::io::Result<Arc<str>> {
+    let path = path.as_ref();
+    if let Some(template) = TEMPLATES.read().unwrap_or_else(|e| e.into_inner()).get(path) {
+        return Ok(template.clone());
+    }
+
+    let content: Arc<str> = fs::read_to_string(path).await?.into();
+    store(path.to_path_buf(), content.clone());
+    Ok(content)
+}
+
+/// Number of templates currently held in memory
+pub fn loaded() -> usize {
+    TEMPLATES.read().unwrap_or_else(|e| e.into_inner()).len()
+}
+
+fn store(path: PathBuf, content: Arc<str>) {
+    TEMPLATES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(path, content);
+}
@@ -0,0 +1,404 @@
+use crate::services::geometry::{ProfileOutline, ThreadProfile};
+use crate::shared::enums::{Language, Theme, ThreadType, Unit};
+use crate::shared::utils::number::NumberFormatter;
+
+use super::drawing::{LineStyle, SvgDrawing};
+use super::enums::{FontFamily, FontWeight, TextAnchor};
+use super::models::{SvgText, SvgTextOptions};
+use super::svg_service::SvgService;
+use super::text_options::TextOptionsGenerator;
+
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 800.0;
+/// Area of the basic profile on the left
+const PROFILE_LEFT: f64 = 60.0;
+const PROFILE_RIGHT: f64 = 440.0;
+const PROFILE_TOP: f64 = 190.0;
+const PROFILE_BOTTOM: f64 = 720.0;
+/// Zero lines of the three diameters on the right
+const ZERO_LINE_START: f64 = 520.0;
+const ZERO_LINE_END: f64 = 1160.0;
+const BAND_CENTERS: [f64; 3] = [255.0, 452.0, 649.0];
+const FIRST_COLUMN: f64 = 700.0;
+const COLUMN_WIDTH: f64 = 110.0;
+const COLUMN_STEP: f64 = 230.0;
+/// Largest deviation on the drawing, in pixels from the zero line
+const MAX_DEVIATION_PX: f64 = 65.0;
+/// Extent of a zone that is not limited on one side
+const OPEN_ZONE_PX: f64 = 80.0;
+
+/// Tolerance zone of one diameter, deviations in millimetres.
+/// `None` means the diameter is not limited on that side.
+#[derive(Debug, Clone, Copy)]
+pub struct DiameterZone {
+    pub basic: f64,
+    pub es: Option<f64>,
+    pub ei: Option<f64>,
+}
+
+impl DiameterZone {
+    /// Zone from the limits of size of a diameter
+    pub fn from_limits(basic: f64, max: Option<f64>, min: Option<f64>) -> Self {
+        Self {
+            basic,
+            es: max.map(|max| max - basic),
+            ei: min.map(|min| min - basic),
+        }
+    }
+}
+
+/// Tolerance zones of the major, pitch and minor diameters of one thread
+#[derive(Debug, Clone)]
+pub struct ToleranceZones {
+    pub thread_type: ThreadType,
+    pub class: String,
+    pub major: DiameterZone,
+    pub pitch: DiameterZone,
+    pub minor: DiameterZone,
+}
+
+/// Material condition of a thread within its tolerance zones
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaterialCondition {
+    /// Largest external or smallest internal thread
+    Maximum,
+    /// Smallest external or largest internal thread
+    Minimum,
+    /// Middle of the tolerance zones
+    Middle,
+}
+
+impl ToleranceZones {
+    fn diameters(&self) -> [DiameterZone; 3] {
+        [self.major, self.pitch, self.minor]
+    }
+
+    /// Profile made to the limits of size at the given material condition.
+    /// A diameter that is not limited on that side keeps the deviation of the pitch diameter.
+    pub fn material_profile(&self, profile: &ThreadProfile, condition: MaterialCondition) -> ThreadProfile {
+        let upper = match (condition, profile.thread_type) {
+            (MaterialCondition::Maximum, ThreadType::Male) | (MaterialCondition::Minimum, ThreadType::Female) => true,
+            (MaterialCondition::Maximum, ThreadType::Female) | (MaterialCondition::Minimum, ThreadType::Male) => false,
+            (MaterialCondition::Middle, _) => {
+                let max = self.material_profile(profile, MaterialCondition::Maximum);
+                let min = self.material_profile(profile, MaterialCondition::Minimum);
+                return profile.with_diameters(
+                    (max.major_diameter + min.major_diameter) / 2.0,
+                    (max.pitch_diameter + min.pitch_diameter) / 2.0,
+                    (max.minor_diameter + min.minor_diameter) / 2.0,
+                );
+            }
+        };
+
+        let deviation = |zone: &DiameterZone| if upper { zone.es } else { zone.ei };
+        let pitch_deviation = deviation(&self.pitch).unwrap_or(0.0);
+        let limit = |zone: &DiameterZone, basic: f64| {
+            deviation(zone)
+                .map(|d| zone.basic + d)
+                .unwrap_or(basic + pitch_deviation)
+        };
+
+        profile.with_diameters(
+            limit(&self.major, profile.major_diameter),
+            limit(&self.pitch, profile.pitch_diameter),
+            limit(&self.minor, profile.minor_diameter),
+        )
+    }
+}
+
+/// Parameters of a tolerance zone diagram
+#[derive(Debug, Clone)]
+pub struct ToleranceDrawingOptions {
+    pub title: String,
+    pub theme: Theme,
+    pub language: Language,
+    pub units: Unit,
+    pub precision: Option<usize>,
+}
+
+/// Draws tolerance zones of a thread (or a male/female pair) against the basic profile and zero lines
+pub struct ToleranceRenderer<'a> {
+    service: &'a SvgService,
+}
+
+impl<'a> ToleranceRenderer<'a> {
+    pub fn new(service: &'a SvgService) -> Self {
+        Self { service }
+    }
+
+    /// `profile` is the basic profile of the thread; `zones` are drawn left to right,
+    /// conventionally the internal thread first for a pair.
+    pub fn render(
+        &self,
+        profile: &ThreadProfile,
+        zones: &[ToleranceZones],
+        options: &ToleranceDrawingOptions,
+    ) -> String {
+        let mut drawing = SvgDrawing::new(self.service, options.theme, WIDTH, HEIGHT);
+
+        let levels = self.draw_basic_profile(&mut drawing, profile);
+        let deviation_scale = Self::deviation_scale(zones);
+
+        for (index, (&level_y, &band_y)) in levels.iter().zip(BAND_CENTERS.iter()).enumerate() {
+            drawing.line(
+                PROFILE_RIGHT,
+                level_y,
+                PROFILE_RIGHT + 30.0,
+                level_y,
+                LineStyle::Reference,
+            );
+            drawing.line(PROFILE_RIGHT + 30.0, level_y, ZERO_LINE_START, band_y, LineStyle::Thin);
+            drawing.line(ZERO_LINE_START, band_y, ZERO_LINE_END, band_y, LineStyle::Reference);
+
+            let symbols = zones
+                .iter()
+                .map(|zone| Self::symbols(profile, zone.thread_type)[index])
+                .collect::<Vec<_>>()
+                .join(", ");
+            drawing.text(
+                SvgText::new(ZERO_LINE_START + 10.0, band_y - 12.0, symbols),
+                self.text_options(26.0, TextAnchor::Start),
+            );
+            drawing.text(
+                SvgText::new(ZERO_LINE_START + 150.0, band_y - 8.0, "+"),
+                self.text_options(20.0, TextAnchor::Middle),
+            );
+            drawing.text(
+                SvgText::new(ZERO_LINE_START + 150.0, band_y + 22.0, "−"),
+                self.text_options(20.0, TextAnchor::Middle),
+            );
+
+            for (column, zone) in zones.iter().enumerate() {
+                let x = FIRST_COLUMN + column as f64 * COLUMN_STEP;
+                self.draw_zone(
+                    &mut drawing,
+                    x,
+                    band_y,
+                    zone.diameters()[index],
+                    zone.thread_type,
+                    deviation_scale,
+                    options,
+                );
+            }
+        }
+
+        self.draw_captions(&mut drawing, zones, options);
+        drawing.finish()
+    }
+
+    fn text_options(&self, size: f64, anchor: TextAnchor) -> SvgTextOptions {
+        self.service
+            .create_custom_text_options(size, 0.0, anchor, FontWeight::Normal, FontFamily::Arial)
+    }
+
+    fn symbols(profile: &ThreadProfile, thread_type: ThreadType) -> [&'static str; 3] {
+        if profile.thread_type == thread_type {
+            profile.diameter_symbols()
+        } else {
+            profile.mating().diameter_symbols()
+        }
+    }
+
+    /// Pixels per millimetre of deviation, shared by all zones so they can be compared
+    fn deviation_scale(zones: &[ToleranceZones]) -> f64 {
+        let largest = zones
+            .iter()
+            .flat_map(|zone| zone.diameters())
+            .flat_map(|diameter| [diameter.es, diameter.ei])
+            .flatten()
+            .fold(0.0_f64, |acc, value| acc.max(value.abs()));
+        if largest > 0.0 {
+            MAX_DEVIATION_PX / largest
+        } else {
+            MAX_DEVIATION_PX
+        }
+    }
+
+    /// Basic profile with its mating outline; returns the canvas y of the major, pitch and minor diameters
+    fn draw_basic_profile(&self, drawing: &mut SvgDrawing, profile: &ThreadProfile) -> [f64; 3] {
+        const PITCHES: usize = 2;
+        let mating = profile.mating();
+        let (male, female) = match profile.thread_type {
+            ThreadType::Male => (*profile, mating),
+            ThreadType::Female => (mating, *profile),
+        };
+
+        let depth = profile.thread_depth();
+        let top = male.crest_level.max(female.root_level) + 0.3 * depth;
+        let bottom = male.root_level.min(female.crest_level) - 0.3 * depth;
+        let length = PITCHES as f64 * profile.pitch;
+        let scale = ((PROFILE_RIGHT - PROFILE_LEFT) / length).min((PROFILE_BOTTOM - PROFILE_TOP) / (top - bottom));
+        let origin_x = PROFILE_LEFT + ((PROFILE_RIGHT - PROFILE_LEFT) - length * scale) / 2.0;
+        let origin_y = PROFILE_TOP + ((PROFILE_BOTTOM - PROFILE_TOP) - (top - bottom) * scale) / 2.0;
+        let to_canvas = |outline: ProfileOutline| -> Vec<(f64, f64)> {
+            outline
+                .points(5.0)
+                .into_iter()
+                .map(|point| (origin_x + point.x * scale, origin_y + (top - point.y) * scale))
+                .collect()
+        };
+
+        let mut material = to_canvas(ProfileOutline::build(&male, PITCHES));
+        let bottom_y = origin_y + (top - bottom) * scale;
+        material.push((origin_x + length * scale, bottom_y));
+        material.push((origin_x, bottom_y));
+        let color = drawing.material_color();
+        drawing.filled_polygon(&material, color, 0.18);
+
+        drawing.polyline(&to_canvas(ProfileOutline::build(&female, PITCHES)), LineStyle::Dashed);
+        drawing.polyline(&to_canvas(ProfileOutline::build(&male, PITCHES)), LineStyle::Outline);
+
+        let level_y = |diameter: f64| origin_y + (top - diameter / 2.0) * scale;
+        let levels = [
+            level_y(profile.nominal_diameter()),
+            level_y(profile.pitch_diameter),
+            level_y(female.minor_diameter),
+        ];
+        for &y in &levels {
+            drawing.line(origin_x - 15.0, y, PROFILE_RIGHT, y, LineStyle::Reference);
+        }
+        levels
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_zone(
+        &self,
+        drawing: &mut SvgDrawing,
+        x: f64,
+        zero_y: f64,
+        zone: DiameterZone,
+        thread_type: ThreadType,
+        scale: f64,
+        options: &ToleranceDrawingOptions,
+    ) {
+        let basic_label = format!(
+            "ø{}",
+            NumberFormatter::convert_and_round_to_string(
+                zone.basic,
+                &Unit::Mm,
+                &options.units,
+                options.precision,
+                false
+            )
+        );
+        drawing.text(
+            SvgText::new(x + COLUMN_WIDTH / 2.0, zero_y + OPEN_ZONE_PX + 18.0, basic_label),
+            self.text_options(16.0, TextAnchor::Middle),
+        );
+
+        if zone.es.is_none() && zone.ei.is_none() {
+            drawing.text(
+                SvgText::new(x + COLUMN_WIDTH / 2.0, zero_y - 10.0, "—"),
+                self.text_options(22.0, TextAnchor::Middle),
+            );
+            return;
+        }
+
+        let top = zone.es.map_or(zero_y - OPEN_ZONE_PX, |es| zero_y - es * scale);
+        let bottom = zone.ei.map_or(zero_y + OPEN_ZONE_PX, |ei| zero_y - ei * scale);
+        let right = x + COLUMN_WIDTH;
+        let opacity = match thread_type {
+            ThreadType::Male => 0.4,
+            ThreadType::Female => 0.15,
+        };
+        let color = drawing.material_color();
+        drawing.filled_polygon(&[(x, top), (right, top), (right, bottom), (x, bottom)], color, opacity);
+        drawing.line(x, top, x, bottom, LineStyle::Thin);
+        drawing.line(right, top, right, bottom, LineStyle::Thin);
+
+        let center = x + COLUMN_WIDTH / 2.0;
+        match zone.es {
+            Some(_) => drawing.line(x, top, right, top, LineStyle::Outline),
+            // Open side: the diameter is not limited
+            None => drawing.arrowhead(center, top - 6.0, -std::f64::consts::FRAC_PI_2),
+        }
+        match zone.ei {
+            Some(_) => drawing.line(x, bottom, right, bottom, LineStyle::Outline),
+            None => drawing.arrowhead(center, bottom + 6.0, std::f64::consts::FRAC_PI_2),
+        }
+
+        // Upper-case symbols for internal threads, as in ISO 286
+        let (es_symbol, ei_symbol) = match thread_type {
+            ThreadType::Male => ("es", "ei"),
+            ThreadType::Female => ("ES", "EI"),
+        };
+        // Keep labels of narrow zones apart
+        let gap = (bottom - top).max(0.0);
+        let shift = ((24.0 - gap) / 2.0).max(0.0);
+        if let Some(es) = zone.es {
+            drawing.text(
+                SvgText::new(
+                    right + 8.0,
+                    top + 6.0 - shift,
+                    format!("{} = {}", es_symbol, Self::deviation(es, options)),
+                ),
+                self.text_options(18.0, TextAnchor::Start),
+            );
+        }
+        if let Some(ei) = zone.ei {
+            drawing.text(
+                SvgText::new(
+                    right + 8.0,
+                    bottom + 6.0 + shift,
+                    format!("{} = {}", ei_symbol, Self::deviation(ei, options)),
+                ),
+                self.text_options(18.0, TextAnchor::Start),
+            );
+        }
+    }
+
+    /// Signed deviation in the requested units, "0" for the zero line
+    fn deviation(value: f64, options: &ToleranceDrawingOptions) -> String {
+        let rounded = NumberFormatter::convert_and_round(value, &Unit::Mm, &options.units, options.precision);
+        if rounded == 0.0 {
+            "0".to_string()
+        } else {
+            NumberFormatter::convert_and_round_to_string(value, &Unit::Mm, &options.units, options.precision, true)
+        }
+    }
+
+    /// Title, column headers and legend
+    fn draw_captions(&self, drawing: &mut SvgDrawing, zones: &[ToleranceZones], options: &ToleranceDrawingOptions) {
+        let (external, internal, basic_profile, zero_line) = match options.language {
+            Language::Ru => (
+                "Наружная",
+                "Внутренняя",
+                "Основной профиль",
+                "Нулевая линия — номинальный размер",
+            ),
+            Language::En => ("External", "Internal", "Basic profile", "Zero line — basic size"),
+        };
+
+        drawing.text(
+            SvgText::new(40.0, 60.0, options.title.clone()),
+            self.service.text_option_40_0_start_bold(),
+        );
+
+        for (column, zone) in zones.iter().enumerate() {
+            let kind = match zone.thread_type {
+                ThreadType::Male => external,
+                ThreadType::Female => internal,
+            };
+            let x = FIRST_COLUMN + column as f64 * COLUMN_STEP + COLUMN_WIDTH / 2.0;
+            drawing.text(
+                SvgText::new(x, 120.0, kind),
+                self.text_options(22.0, TextAnchor::Middle),
+            );
+            drawing.text(
+                SvgText::new(x, 152.0, zone.class.clone()),
+                self.service.text_option_40_0_center_bold().with(|o| o.font_size = 28.0),
+            );
+        }
+
+        drawing.text(
+            SvgText::new((PROFILE_LEFT + PROFILE_RIGHT) / 2.0, 120.0, basic_profile),
+            self.text_options(22.0, TextAnchor::Middle),
+        );
+        let y = HEIGHT - 35.0;
+        drawing.line(40.0, y - 7.0, 110.0, y - 7.0, LineStyle::Reference);
+        drawing.text(
+            SvgText::new(125.0, y, zero_line),
+            self.text_options(20.0, TextAnchor::Start),
+        );
+    }
+}
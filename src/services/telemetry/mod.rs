@@ -0,0 +1,165 @@
+//! Spans of requests, SQL queries and SVG rendering. They are ordinary `tracing`
+//! spans of this crate; `tracing-opentelemetry` turns them into OpenTelemetry spans
+//! and the SDK exports them over OTLP/HTTP when `[tracing]` is enabled. A future
+//! spawned from a handler keeps its parent only when it is `instrument`ed with
+//! `tracing::Span::current()`
+
+use axum::http::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{Span as _, SpanKind, TraceContextExt, Tracer as _, TracerProvider as _};
+use opentelemetry::{global, Context, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{BatchConfigBuilder, BatchSpanProcessor, Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use std::time::{Duration, SystemTime};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+use crate::services::metrics::{QueryMetricsLayer, SQLX_QUERY_TARGET};
+use crate::shared::setting::models::app_config::TracingConfig;
+use crate::{log_error, log_info, log_warn};
+
+/// Instrumentation scope of the spans
+const SCOPE_NAME: &str = "thread-api";
+
+/// Only the spans of this crate are exported, not those of sqlx, hyper or tower
+fn is_own_span(target: &str) -> bool {
+    target == env!("CARGO_CRATE_NAME") || target.starts_with(concat!(env!("CARGO_CRATE_NAME"), "::"))
+}
+
+/// Tracer provider of the exporter; dropping it without `shutdown` loses the queued spans
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    /// Sends the spans still queued and stops the export thread
+    pub async fn shutdown(self) {
+        let Some(provider) = self.provider else {
+            return;
+        };
+        // The exporter thread posts the last batch with a blocking client
+        match tokio::task::spawn_blocking(move || provider.shutdown()).await {
+            Ok(Ok(())) => log_info!("Tracing stopped"),
+            Ok(Err(e)) => log_error!("Tracing exporter failed: {}", e),
+            Err(e) => log_error!("Tracing exporter failed: {}", e),
+        }
+    }
+}
+
+/// Installs the global `tracing` subscriber: query metrics always, and the OTLP
+/// export of spans when `[tracing]` is enabled
+pub fn install(config: &TracingConfig) -> Telemetry {
+    let provider = if config.enabled {
+        match tracer_provider(config) {
+            Ok(provider) => Some(provider),
+            Err(e) => {
+                log_warn!("Tracing is disabled: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let spans = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer(SCOPE_NAME))
+            .with_filter(filter_fn(|metadata| is_own_span(metadata.target())))
+    });
+    let queries = QueryMetricsLayer::new().with_filter(filter_fn(|metadata| metadata.target() == SQLX_QUERY_TARGET));
+    let subscriber = tracing_subscriber::registry().with(queries).with(spans);
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        log_warn!("Database query metrics and tracing are disabled: {}", e);
+    }
+
+    if let Some(provider) = &provider {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+        log_info!(
+            "Tracing started: OTLP to {}, sample ratio {}",
+            config.endpoint,
+            config.sample_ratio
+        );
+    }
+    Telemetry { provider }
+}
+
+fn tracer_provider(config: &TracingConfig) -> Result<SdkTracerProvider, String> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&config.endpoint)
+        .with_timeout(Duration::from_millis(config.timeout_ms))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let batches = BatchConfigBuilder::default()
+        .with_max_queue_size(config.queue_capacity.max(1))
+        .with_max_export_batch_size(config.batch_size.max(1))
+        .with_scheduled_delay(Duration::from_millis(config.flush_interval_ms))
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_span_processor(BatchSpanProcessor::builder(exporter).with_batch_config(batches).build())
+        // Traces with `traceparent` follow the decision of the client
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio.clamp(0.0, 1.0),
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build())
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+/// Makes `span` continue the trace of the `traceparent` a client or a proxy sent
+pub fn continue_trace(span: &tracing::Span, headers: &HeaderMap) {
+    let parent = global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    if parent.span().span_context().is_valid() {
+        // Fails only when tracing is off, and then there is no trace to continue
+        let _ = span.set_parent(parent);
+    }
+}
+
+/// Marks the operation of `span` as failed; the span needs an empty `otel.status_code` field
+pub fn set_error(span: &tracing::Span, message: impl std::fmt::Display) {
+    span.record("otel.status_code", "error");
+    span.record("otel.status_description", message.to_string());
+}
+
+/// A statement sqlx has finished, as a client span ending now. It is a child of the
+/// span the statement ran in: the PostgreSQL driver runs on the task of the request,
+/// SQLite statements run on a worker thread without one and are not recorded
+pub fn record_query(operation: &str, summary: &str, elapsed: Duration) {
+    let parent = Context::current();
+    if !parent.span().span_context().is_sampled() {
+        return;
+    }
+    let end = SystemTime::now();
+    let tracer = global::tracer(SCOPE_NAME);
+    let mut span = tracer
+        .span_builder(operation.to_uppercase())
+        .with_kind(SpanKind::Client)
+        .with_start_time(end.checked_sub(elapsed).unwrap_or(end))
+        .with_attributes([
+            KeyValue::new("db.operation.name", operation.to_uppercase()),
+            KeyValue::new("db.query.summary", summary.to_string()),
+        ])
+        .start_with_context(&tracer, &parent);
+    span.end_with_timestamp(end);
+}
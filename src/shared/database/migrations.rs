@@ -1,4 +1,4 @@
-use crate::{log_error, log_info};
+use crate::{log_debug, log_error, log_info};
 use sqlx::{
     migrate::{MigrateError, Migrator},
     Executor, PgPool,
@@ -51,16 +51,14 @@ pub async fn run_migrations(pool: &PgPool) -> Result<(), MigrateError> {
     }
 }
 
-// Функция для быстрой проверки новых миграций без перезапуска
-pub async fn check_pending_migrations(pool: &PgPool) -> Result<(), MigrateError> {
-    log_info!("Checking for pending migrations...");
+/// Миграции из `./migrations`, ещё не применённые к базе: версия и описание.
+/// Вызывается и пробой готовности, поэтому пишет в лог только найденные
+pub async fn check_pending_migrations(pool: &PgPool) -> Result<Vec<(i64, String)>, MigrateError> {
+    log_debug!("Checking for pending migrations...");
 
     let migrations_path = Path::new("./migrations");
     let migrator = Migrator::new(migrations_path).await?;
 
-    // Получаем список всех миграций
-    let all_migrations = migrator.iter().collect::<Vec<_>>();
-
     // Получаем примененные миграции
     let applied: Vec<(i64,)> = sqlx::query_as("SELECT version FROM _sqlx_migrations ORDER BY version")
         .fetch_all(pool)
@@ -69,19 +67,20 @@ pub async fn check_pending_migrations(pool: &PgPool) -> Result<(), MigrateError>
 
     let applied_versions: std::collections::HashSet<i64> = applied.into_iter().map(|(v,)| v).collect();
 
-    let pending: Vec<_> = all_migrations
+    let pending: Vec<(i64, String)> = migrator
         .iter()
         .filter(|m| !applied_versions.contains(&m.version))
+        .map(|m| (m.version, m.description.to_string()))
         .collect();
 
     if pending.is_empty() {
-        log_info!("✅ No pending migrations");
+        log_debug!("No pending migrations");
     } else {
         log_info!("📋 Found {} pending migration(s):", pending.len());
-        for migration in pending {
-            log_info!("  - {} : {}", migration.version, migration.description);
+        for (version, description) in &pending {
+            log_info!("  - {} : {}", version, description);
         }
     }
 
-    Ok(())
+    Ok(pending)
 }
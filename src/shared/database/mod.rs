@@ -1,3 +1,6 @@
 pub mod connection;
 pub mod migrations;
+pub mod repositories;
 pub mod service;
+pub mod sqlite;
+pub mod version;
@@ -0,0 +1,494 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+use sqlx::{Postgres, QueryBuilder, Sqlite};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use crate::features::error_reports::models::ErrorReport;
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::enums::{Language, ThreadStandard};
+use crate::shared::error::AppError;
+
+/// One lookup of a thread designation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThreadUsage {
+    pub designation: String,
+    pub standard: ThreadStandard,
+    pub language: Language,
+    /// UTC day of the request
+    pub day: NaiveDate,
+}
+
+impl ThreadUsage {
+    /// Lookup made now
+    pub fn new(designation: String, standard: ThreadStandard, language: Language) -> Self {
+        Self {
+            designation,
+            standard,
+            language,
+            day: chrono::Utc::now().date_naive(),
+        }
+    }
+}
+
+/// Days, both ends included, and optional filters of `daily_usage`
+#[derive(Debug, Clone)]
+pub struct UsageFilter {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub standard: Option<ThreadStandard>,
+    pub language: Option<Language>,
+    pub designation: Option<String>,
+}
+
+impl UsageFilter {
+    fn matches(&self, designation: &str, standard: ThreadStandard, language: Language, day: NaiveDate) -> bool {
+        (self.from..=self.to).contains(&day)
+            && self.standard.is_none_or(|wanted| wanted == standard)
+            && self.language.is_none_or(|wanted| wanted == language)
+            && self.designation.as_deref().is_none_or(|wanted| wanted == designation)
+    }
+}
+
+/// Lookups of one designation in one standard and language during one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyUsage {
+    pub designation: String,
+    pub standard: ThreadStandard,
+    pub language: Language,
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+type DailyUsageRow = (String, String, String, NaiveDate, i64);
+
+impl DailyUsage {
+    fn from_row((designation, standard, language, day, count): DailyUsageRow) -> Result<Self, AppError> {
+        Ok(Self {
+            designation,
+            standard: ThreadStandard::from_str(&standard)
+                .map_err(|_| AppError::DatabaseError(format!("Unknown thread standard '{}'", standard)))?,
+            language: language_from_code(&language)?,
+            day,
+            count,
+        })
+    }
+}
+
+/// Filters of `error_reports`; the newest `limit` matching reports are returned
+#[derive(Debug, Clone)]
+pub struct ErrorReportFilter {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub app_version: Option<String>,
+    /// Part of the message, case-insensitive
+    pub message: Option<String>,
+    pub resolved: Option<bool>,
+    pub limit: usize,
+}
+
+impl ErrorReportFilter {
+    fn matches(&self, report: &ErrorReport) -> bool {
+        let timestamp = report.timestamp;
+        self.from.is_none_or(|from| timestamp.is_some_and(|at| at >= from))
+            && self.to.is_none_or(|to| timestamp.is_some_and(|at| at <= to))
+            && self
+                .app_version
+                .as_deref()
+                .is_none_or(|wanted| report.app_version() == Some(wanted))
+            && self
+                .message
+                .as_deref()
+                .is_none_or(|part| report.message().to_lowercase().contains(&part.to_lowercase()))
+            && self
+                .resolved
+                .is_none_or(|resolved| report.resolved_at.is_some() == resolved)
+    }
+}
+
+/// Rows of one `INSERT`, well under the bind parameter limits of PostgreSQL and SQLite
+const ROWS_PER_INSERT: usize = 1000;
+
+/// Stored language code, the same as in the `language` query parameter
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Ru => "ru",
+        Language::En => "en",
+    }
+}
+
+fn language_from_code(code: &str) -> Result<Language, AppError> {
+    match code {
+        "ru" => Ok(Language::Ru),
+        "en" => Ok(Language::En),
+        other => Err(AppError::DatabaseError(format!("Unknown language '{}'", other))),
+    }
+}
+
+/// Usage counters of thread designations and client error reports
+#[async_trait]
+pub trait TraitAnalyticsRepository {
+    /// Adds one lookup to the counter of the designation, standard, language and day
+    async fn add_or_increment_thread(&self, usage: &ThreadUsage) -> Result<(), AppError> {
+        self.add_usage(&[(usage.clone(), 1)]).await
+    }
+    /// Adds `count` lookups to every counter; each key appears at most once
+    async fn add_usage(&self, batch: &[(ThreadUsage, i64)]) -> Result<(), AppError>;
+    /// Usage count of every designation seen so far
+    async fn usage_counts(&self) -> Result<Vec<(String, i64)>, AppError>;
+    /// Daily counters matching the filter
+    async fn daily_usage(&self, filter: &UsageFilter) -> Result<Vec<DailyUsage>, AppError>;
+    /// Stores a client error report and returns its id
+    async fn insert_error_report(&self, json_data: Value, client_ip: &str) -> Result<i32, AppError>;
+    /// Error reports matching the filter, newest first
+    async fn error_reports(&self, filter: &ErrorReportFilter) -> Result<Vec<ErrorReport>, AppError>;
+    /// Marks reports as resolved or reopens them; returns the number of changed reports
+    async fn set_error_reports_resolved(&self, ids: &[i32], resolved: bool) -> Result<u64, AppError>;
+}
+
+pub struct PgAnalyticsRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgAnalyticsRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitAnalyticsRepository for PgAnalyticsRepository {
+    async fn add_usage(&self, batch: &[(ThreadUsage, i64)]) -> Result<(), AppError> {
+        for rows in batch.chunks(ROWS_PER_INSERT) {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO analytics.thread_usage (designation, standard, language, day, usage_count) ",
+            );
+            query.push_values(rows, |mut row, (usage, count)| {
+                row.push_bind(&usage.designation)
+                    .push_bind(usage.standard.as_ref())
+                    .push_bind(language_code(usage.language))
+                    .push_bind(usage.day)
+                    .push_bind(*count);
+            });
+            query.push(
+                "
+ON CONFLICT (designation, standard, language, day) DO UPDATE
+SET usage_count = analytics.thread_usage.usage_count + EXCLUDED.usage_count, last_used_at = now()",
+            );
+            query.build().execute(self.connection.pool()).await?;
+        }
+        Ok(())
+    }
+
+    /// Adds the running totals of `analytics.popular_threads`, kept from before the
+    /// daily counters, while that table exists; the reports only read `thread_usage`
+    async fn usage_counts(&self) -> Result<Vec<(String, i64)>, AppError> {
+        let pool = self.connection.pool();
+        let legacy: bool = sqlx::query_scalar("SELECT to_regclass('analytics.popular_threads') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+        let query = if legacy {
+            "
+SELECT designation, SUM(usage_count)::bigint
+FROM (
+    SELECT designation, usage_count::bigint AS usage_count FROM analytics.thread_usage
+    UNION ALL
+    SELECT designation, usage_count::bigint AS usage_count FROM analytics.popular_threads
+) AS usage
+GROUP BY designation
+"
+        } else {
+            "SELECT designation, SUM(usage_count)::bigint FROM analytics.thread_usage GROUP BY designation"
+        };
+        let rows = sqlx::query_as::<_, (String, i64)>(query).fetch_all(pool).await?;
+        Ok(rows)
+    }
+
+    async fn daily_usage(&self, filter: &UsageFilter) -> Result<Vec<DailyUsage>, AppError> {
+        let rows = sqlx::query_as::<_, DailyUsageRow>(
+            "
+SELECT designation, standard, language, day, usage_count
+FROM analytics.thread_usage
+WHERE day BETWEEN $1 AND $2
+  AND ($3::text IS NULL OR standard = $3)
+  AND ($4::text IS NULL OR language = $4)
+  AND ($5::text IS NULL OR designation = $5)
+",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.standard.map(|standard| standard.as_ref().to_string()))
+        .bind(filter.language.map(language_code))
+        .bind(filter.designation.as_deref())
+        .fetch_all(self.connection.pool())
+        .await?;
+        rows.into_iter().map(DailyUsage::from_row).collect()
+    }
+
+    async fn insert_error_report(&self, json_data: Value, client_ip: &str) -> Result<i32, AppError> {
+        let id = sqlx::query_scalar::<_, i32>(
+            "INSERT INTO analytics.error_reports (json_data, client_ip) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(json_data)
+        .bind(client_ip)
+        .fetch_one(self.connection.pool())
+        .await?;
+        Ok(id)
+    }
+
+    async fn error_reports(&self, filter: &ErrorReportFilter) -> Result<Vec<ErrorReport>, AppError> {
+        let reports = sqlx::query_as::<_, ErrorReport>(
+            "
+SELECT id, timestamp, json_data, client_ip, resolved_at
+FROM analytics.error_reports
+WHERE ($1::timestamptz IS NULL OR timestamp >= $1)
+  AND ($2::timestamptz IS NULL OR timestamp <= $2)
+  AND ($3::text IS NULL OR json_data->>'app_version' = $3)
+  AND ($4::text IS NULL OR strpos(lower(json_data->>'message'), lower($4)) > 0)
+  AND ($5::bool IS NULL OR (resolved_at IS NOT NULL) = $5)
+ORDER BY id DESC
+LIMIT $6
+",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.app_version.as_deref())
+        .bind(filter.message.as_deref())
+        .bind(filter.resolved)
+        .bind(filter.limit as i64)
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(reports)
+    }
+
+    async fn set_error_reports_resolved(&self, ids: &[i32], resolved: bool) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "
+UPDATE analytics.error_reports
+SET resolved_at = CASE WHEN $2 THEN now() ELSE NULL END
+WHERE id = ANY($1) AND (resolved_at IS NOT NULL) <> $2
+",
+        )
+        .bind(ids)
+        .bind(resolved)
+        .execute(self.connection.pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+type UsageKey = (String, ThreadStandard, Language, NaiveDate);
+
+/// Keeps analytics in process memory; everything is lost on restart
+#[derive(Default)]
+pub struct MemoryAnalyticsRepository {
+    usage: Mutex<HashMap<UsageKey, i64>>,
+    error_reports: Mutex<Vec<ErrorReport>>,
+}
+
+impl MemoryAnalyticsRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn poisoned<T>(_: T) -> AppError {
+        AppError::InternalError {
+            message: "analytics storage lock poisoned".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TraitAnalyticsRepository for MemoryAnalyticsRepository {
+    async fn add_usage(&self, batch: &[(ThreadUsage, i64)]) -> Result<(), AppError> {
+        let mut counters = self.usage.lock().map_err(Self::poisoned)?;
+        for (usage, count) in batch {
+            let key = (usage.designation.clone(), usage.standard, usage.language, usage.day);
+            *counters.entry(key).or_default() += count;
+        }
+        Ok(())
+    }
+
+    async fn usage_counts(&self) -> Result<Vec<(String, i64)>, AppError> {
+        let counters = self.usage.lock().map_err(Self::poisoned)?;
+        let mut totals: HashMap<String, i64> = HashMap::new();
+        for ((designation, ..), count) in counters.iter() {
+            *totals.entry(designation.clone()).or_default() += count;
+        }
+        Ok(totals.into_iter().collect())
+    }
+
+    async fn daily_usage(&self, filter: &UsageFilter) -> Result<Vec<DailyUsage>, AppError> {
+        let counters = self.usage.lock().map_err(Self::poisoned)?;
+        Ok(counters
+            .iter()
+            .filter(|((designation, standard, language, day), _)| {
+                filter.matches(designation, *standard, *language, *day)
+            })
+            .map(|((designation, standard, language, day), count)| DailyUsage {
+                designation: designation.clone(),
+                standard: *standard,
+                language: *language,
+                day: *day,
+                count: *count,
+            })
+            .collect())
+    }
+
+    async fn insert_error_report(&self, json_data: Value, client_ip: &str) -> Result<i32, AppError> {
+        let mut reports = self.error_reports.lock().map_err(Self::poisoned)?;
+        let id = reports.len() as i32 + 1;
+        reports.push(ErrorReport {
+            id,
+            timestamp: Some(chrono::Utc::now()),
+            json_data,
+            client_ip: client_ip.to_string(),
+            resolved_at: None,
+        });
+        Ok(id)
+    }
+
+    async fn error_reports(&self, filter: &ErrorReportFilter) -> Result<Vec<ErrorReport>, AppError> {
+        let reports = self.error_reports.lock().map_err(Self::poisoned)?;
+        Ok(reports
+            .iter()
+            .rev()
+            .filter(|report| filter.matches(report))
+            .take(filter.limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_error_reports_resolved(&self, ids: &[i32], resolved: bool) -> Result<u64, AppError> {
+        let mut reports = self.error_reports.lock().map_err(Self::poisoned)?;
+        let mut updated = 0;
+        for report in reports.iter_mut() {
+            if ids.contains(&report.id) && report.resolved_at.is_some() != resolved {
+                report.resolved_at = resolved.then(chrono::Utc::now);
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+}
+
+pub struct SqliteAnalyticsRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqliteAnalyticsRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitAnalyticsRepository for SqliteAnalyticsRepository {
+    async fn add_usage(&self, batch: &[(ThreadUsage, i64)]) -> Result<(), AppError> {
+        for rows in batch.chunks(ROWS_PER_INSERT) {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO analytics_thread_usage (designation, standard, language, day, usage_count) ",
+            );
+            query.push_values(rows, |mut row, (usage, count)| {
+                row.push_bind(&usage.designation)
+                    .push_bind(usage.standard.as_ref())
+                    .push_bind(language_code(usage.language))
+                    .push_bind(usage.day)
+                    .push_bind(*count);
+            });
+            query.push(
+                "
+ON CONFLICT (designation, standard, language, day) DO UPDATE
+SET usage_count = usage_count + excluded.usage_count, last_used_at = CURRENT_TIMESTAMP",
+            );
+            query.build().execute(self.connection.pool()).await?;
+        }
+        Ok(())
+    }
+
+    async fn usage_counts(&self) -> Result<Vec<(String, i64)>, AppError> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT designation, SUM(usage_count) FROM analytics_thread_usage GROUP BY designation",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    async fn daily_usage(&self, filter: &UsageFilter) -> Result<Vec<DailyUsage>, AppError> {
+        let rows = sqlx::query_as::<_, DailyUsageRow>(
+            "
+SELECT designation, standard, language, day, usage_count
+FROM analytics_thread_usage
+WHERE day BETWEEN ?1 AND ?2
+  AND (?3 IS NULL OR standard = ?3)
+  AND (?4 IS NULL OR language = ?4)
+  AND (?5 IS NULL OR designation = ?5)
+",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.standard.map(|standard| standard.as_ref().to_string()))
+        .bind(filter.language.map(language_code))
+        .bind(filter.designation.as_deref())
+        .fetch_all(self.connection.pool())
+        .await?;
+        rows.into_iter().map(DailyUsage::from_row).collect()
+    }
+
+    async fn insert_error_report(&self, json_data: Value, client_ip: &str) -> Result<i32, AppError> {
+        let result = sqlx::query("INSERT INTO analytics_error_reports (json_data, client_ip) VALUES (?, ?)")
+            .bind(json_data.to_string())
+            .bind(client_ip)
+            .execute(self.connection.pool())
+            .await?;
+        Ok(result.last_insert_rowid() as i32)
+    }
+
+    async fn error_reports(&self, filter: &ErrorReportFilter) -> Result<Vec<ErrorReport>, AppError> {
+        // `datetime()` reads both the stored "YYYY-MM-DD HH:MM:SS" and the bound RFC 3339
+        let reports = sqlx::query_as::<_, ErrorReport>(
+            "
+SELECT id, timestamp, json_data, client_ip, resolved_at
+FROM analytics_error_reports
+WHERE (?1 IS NULL OR datetime(timestamp) >= datetime(?1))
+  AND (?2 IS NULL OR datetime(timestamp) <= datetime(?2))
+  AND (?3 IS NULL OR json_extract(json_data, '$.app_version') = ?3)
+  AND (?4 IS NULL OR instr(lower(json_extract(json_data, '$.message')), lower(?4)) > 0)
+  AND (?5 IS NULL OR (resolved_at IS NOT NULL) = ?5)
+ORDER BY id DESC
+LIMIT ?6
+",
+        )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.app_version.as_deref())
+        .bind(filter.message.as_deref())
+        .bind(filter.resolved)
+        .bind(filter.limit as i64)
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(reports)
+    }
+
+    async fn set_error_reports_resolved(&self, ids: &[i32], resolved: bool) -> Result<u64, AppError> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut query = QueryBuilder::<Sqlite>::new("UPDATE analytics_error_reports SET resolved_at = ");
+        query.push(if resolved { "CURRENT_TIMESTAMP" } else { "NULL" });
+        query.push(" WHERE (resolved_at IS NOT NULL) <> ");
+        query.push_bind(resolved);
+        query.push(" AND id IN (");
+        let mut separated = query.separated(", ");
+        for id in ids {
+            separated.push_bind(*id);
+        }
+        query.push(")");
+        let result = query.build().execute(self.connection.pool()).await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,412 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use strum_macros::{AsRefStr, Display, EnumString};
+use utoipa::ToSchema;
+
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::error::AppError;
+
+/// Group of routes a key may call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, EnumString, AsRefStr, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ApiScope {
+    /// Reference data: diameters, tolerances, info, search and batch
+    Read,
+    /// Drawings and exports: SVG, PNG, PDF, DXF and STL
+    Svg,
+    /// Admin routes; also allows every other scope
+    Admin,
+}
+
+/// Issued key; the secret itself is never stored, only its SHA-256
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKey {
+    pub id: i64,
+    /// Partner or application the key was issued to
+    pub name: String,
+    /// First characters of the secret, to tell keys apart
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    /// Requests per UTC day, unlimited when absent
+    pub daily_quota: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&ApiScope::Admin)
+    }
+}
+
+/// Key to store: `key_hash` and `prefix` of a freshly generated secret
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    pub name: String,
+    pub key_hash: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    pub daily_quota: Option<i64>,
+}
+
+/// Requests of one key on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema, sqlx::FromRow)]
+pub struct ApiKeyUsage {
+    pub key_id: i64,
+    pub day: NaiveDate,
+    pub requests: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ApiKeyRow {
+    id: i64,
+    name: String,
+    key_prefix: String,
+    scopes: String,
+    daily_quota: Option<i64>,
+    created_at: DateTime<Utc>,
+    rotated_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKeyRow> for ApiKey {
+    fn from(row: ApiKeyRow) -> Self {
+        ApiKey {
+            id: row.id,
+            name: row.name,
+            prefix: row.key_prefix,
+            scopes: parse_scopes(&row.scopes),
+            daily_quota: row.daily_quota,
+            created_at: row.created_at,
+            rotated_at: row.rotated_at,
+            revoked_at: row.revoked_at,
+        }
+    }
+}
+
+/// Scopes are stored as "read,svg" in both databases
+fn join_scopes(scopes: &[ApiScope]) -> String {
+    scopes.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(",")
+}
+
+fn parse_scopes(scopes: &str) -> Vec<ApiScope> {
+    scopes
+        .split(',')
+        .filter_map(|scope| ApiScope::from_str(scope.trim()).ok())
+        .collect()
+}
+
+const KEY_COLUMNS: &str = "id, name, key_prefix, scopes, daily_quota, created_at, rotated_at, revoked_at";
+
+#[async_trait]
+pub trait TraitApiKeyRepository {
+    async fn create_api_key(&self, key: &NewApiKey) -> Result<ApiKey, AppError>;
+    /// Key with this hash unless it was revoked
+    async fn active_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError>;
+    /// Every key including revoked ones, oldest first
+    async fn api_keys(&self) -> Result<Vec<ApiKey>, AppError>;
+    /// Replaces the secret of an active key; `None` when there is no such key
+    async fn rotate_api_key(&self, id: i64, key_hash: &str, prefix: &str) -> Result<Option<ApiKey>, AppError>;
+    /// `None` when there is no such key; a revoked key is returned as is
+    async fn revoke_api_key(&self, id: i64) -> Result<Option<ApiKey>, AppError>;
+    /// Counts `requests` requests of the key and returns the requests of that day so far
+    async fn count_api_key_requests(&self, key_id: i64, day: NaiveDate, requests: i64) -> Result<i64, AppError>;
+    /// Requests of all keys in the days `from..=to`
+    async fn api_key_usage(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<ApiKeyUsage>, AppError>;
+}
+
+pub struct PgApiKeyRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgApiKeyRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitApiKeyRepository for PgApiKeyRepository {
+    async fn create_api_key(&self, key: &NewApiKey) -> Result<ApiKey, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "INSERT INTO auth.api_keys (name, key_prefix, key_hash, scopes, daily_quota) VALUES ($1, $2, $3, $4, $5) \
+             RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(join_scopes(&key.scopes))
+        .bind(key.daily_quota)
+        .fetch_one(self.connection.pool())
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn active_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "SELECT {} FROM auth.api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+            KEY_COLUMNS
+        ))
+        .bind(key_hash)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn api_keys(&self) -> Result<Vec<ApiKey>, AppError> {
+        let rows = sqlx::query_as::<_, ApiKeyRow>(&format!("SELECT {} FROM auth.api_keys ORDER BY id", KEY_COLUMNS))
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows.into_iter().map(ApiKey::from).collect())
+    }
+
+    async fn rotate_api_key(&self, id: i64, key_hash: &str, prefix: &str) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "UPDATE auth.api_keys SET key_hash = $2, key_prefix = $3, rotated_at = now() \
+             WHERE id = $1 AND revoked_at IS NULL RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(key_hash)
+        .bind(prefix)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn revoke_api_key(&self, id: i64) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "UPDATE auth.api_keys SET revoked_at = COALESCE(revoked_at, now()) WHERE id = $1 RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn count_api_key_requests(&self, key_id: i64, day: NaiveDate, requests: i64) -> Result<i64, AppError> {
+        let requests = sqlx::query_scalar::<_, i64>(
+            "
+INSERT INTO auth.api_key_usage (key_id, day, requests) VALUES ($1, $2, $3)
+ON CONFLICT (key_id, day) DO UPDATE SET requests = auth.api_key_usage.requests + EXCLUDED.requests
+RETURNING requests
+",
+        )
+        .bind(key_id)
+        .bind(day)
+        .bind(requests)
+        .fetch_one(self.connection.pool())
+        .await?;
+        Ok(requests)
+    }
+
+    async fn api_key_usage(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<ApiKeyUsage>, AppError> {
+        let rows = sqlx::query_as::<_, ApiKeyUsage>(
+            "SELECT key_id, day, requests FROM auth.api_key_usage WHERE day BETWEEN $1 AND $2 ORDER BY key_id, day",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+}
+
+/// Keeps keys in process memory; they are lost on restart
+#[derive(Default)]
+pub struct MemoryApiKeyRepository {
+    keys: Mutex<Vec<(String, ApiKey)>>,
+    usage: Mutex<HashMap<(i64, NaiveDate), i64>>,
+}
+
+impl MemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn poisoned<T>(_: T) -> AppError {
+        AppError::InternalError {
+            message: "API key storage lock poisoned".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TraitApiKeyRepository for MemoryApiKeyRepository {
+    async fn create_api_key(&self, key: &NewApiKey) -> Result<ApiKey, AppError> {
+        let mut keys = self.keys.lock().map_err(Self::poisoned)?;
+        let stored = ApiKey {
+            id: keys.len() as i64 + 1,
+            name: key.name.clone(),
+            prefix: key.prefix.clone(),
+            scopes: key.scopes.clone(),
+            daily_quota: key.daily_quota,
+            created_at: Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+        };
+        keys.push((key.key_hash.clone(), stored.clone()));
+        Ok(stored)
+    }
+
+    async fn active_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let keys = self.keys.lock().map_err(Self::poisoned)?;
+        Ok(keys
+            .iter()
+            .find(|(hash, key)| hash == key_hash && key.revoked_at.is_none())
+            .map(|(_, key)| key.clone()))
+    }
+
+    async fn api_keys(&self) -> Result<Vec<ApiKey>, AppError> {
+        let keys = self.keys.lock().map_err(Self::poisoned)?;
+        Ok(keys.iter().map(|(_, key)| key.clone()).collect())
+    }
+
+    async fn rotate_api_key(&self, id: i64, key_hash: &str, prefix: &str) -> Result<Option<ApiKey>, AppError> {
+        let mut keys = self.keys.lock().map_err(Self::poisoned)?;
+        let Some((hash, key)) = keys
+            .iter_mut()
+            .find(|(_, key)| key.id == id && key.revoked_at.is_none())
+        else {
+            return Ok(None);
+        };
+        *hash = key_hash.to_string();
+        key.prefix = prefix.to_string();
+        key.rotated_at = Some(Utc::now());
+        Ok(Some(key.clone()))
+    }
+
+    async fn revoke_api_key(&self, id: i64) -> Result<Option<ApiKey>, AppError> {
+        let mut keys = self.keys.lock().map_err(Self::poisoned)?;
+        Ok(keys.iter_mut().find(|(_, key)| key.id == id).map(|(_, key)| {
+            key.revoked_at.get_or_insert_with(Utc::now);
+            key.clone()
+        }))
+    }
+
+    async fn count_api_key_requests(&self, key_id: i64, day: NaiveDate, requests: i64) -> Result<i64, AppError> {
+        let mut usage = self.usage.lock().map_err(Self::poisoned)?;
+        let total = usage.entry((key_id, day)).or_default();
+        *total += requests;
+        Ok(*total)
+    }
+
+    async fn api_key_usage(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<ApiKeyUsage>, AppError> {
+        let usage = self.usage.lock().map_err(Self::poisoned)?;
+        let mut rows: Vec<ApiKeyUsage> = usage
+            .iter()
+            .filter(|((_, day), _)| (from..=to).contains(day))
+            .map(|(&(key_id, day), &requests)| ApiKeyUsage { key_id, day, requests })
+            .collect();
+        rows.sort_by_key(|row| (row.key_id, row.day));
+        Ok(rows)
+    }
+}
+
+pub struct SqliteApiKeyRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqliteApiKeyRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitApiKeyRepository for SqliteApiKeyRepository {
+    async fn create_api_key(&self, key: &NewApiKey) -> Result<ApiKey, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "INSERT INTO auth_api_keys (name, key_prefix, key_hash, scopes, daily_quota) VALUES (?, ?, ?, ?, ?) \
+             RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(join_scopes(&key.scopes))
+        .bind(key.daily_quota)
+        .fetch_one(self.connection.pool())
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn active_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "SELECT {} FROM auth_api_keys WHERE key_hash = ? AND revoked_at IS NULL",
+            KEY_COLUMNS
+        ))
+        .bind(key_hash)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn api_keys(&self) -> Result<Vec<ApiKey>, AppError> {
+        let rows = sqlx::query_as::<_, ApiKeyRow>(&format!("SELECT {} FROM auth_api_keys ORDER BY id", KEY_COLUMNS))
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows.into_iter().map(ApiKey::from).collect())
+    }
+
+    async fn rotate_api_key(&self, id: i64, key_hash: &str, prefix: &str) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "UPDATE auth_api_keys SET key_hash = ?2, key_prefix = ?3, rotated_at = CURRENT_TIMESTAMP \
+             WHERE id = ?1 AND revoked_at IS NULL RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(key_hash)
+        .bind(prefix)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn revoke_api_key(&self, id: i64) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "UPDATE auth_api_keys SET revoked_at = COALESCE(revoked_at, CURRENT_TIMESTAMP) WHERE id = ? RETURNING {}",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.connection.pool())
+        .await?;
+        Ok(row.map(ApiKey::from))
+    }
+
+    async fn count_api_key_requests(&self, key_id: i64, day: NaiveDate, requests: i64) -> Result<i64, AppError> {
+        let requests = sqlx::query_scalar::<_, i64>(
+            "
+INSERT INTO auth_api_key_usage (key_id, day, requests) VALUES (?, ?, ?)
+ON CONFLICT (key_id, day) DO UPDATE SET requests = auth_api_key_usage.requests + excluded.requests
+RETURNING requests
+",
+        )
+        .bind(key_id)
+        .bind(day)
+        .bind(requests)
+        .fetch_one(self.connection.pool())
+        .await?;
+        Ok(requests)
+    }
+
+    async fn api_key_usage(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<ApiKeyUsage>, AppError> {
+        let rows = sqlx::query_as::<_, ApiKeyUsage>(
+            "SELECT key_id, day, requests FROM auth_api_key_usage WHERE day BETWEEN ? AND ? ORDER BY key_id, day",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::features::metric::common::{FundamentalDeviationRow, MetricThread, ToleranceGradeRow};
+use crate::log_info;
+use crate::shared::database::version::DatasetVersion;
+use crate::shared::error::AppError;
+use crate::shared::utils::db::JsonRow;
+
+use super::{imperial::ImperialThreadRow, pipe::PipeThreadRow};
+
+/// Reference tables bundled with the application, one JSON array per table
+/// in `<data_dir>/<schema>/<table>.json`
+#[derive(Debug, Default, Clone)]
+pub struct MemoryDataset {
+    pub metric_main: Vec<MetricThread>,
+    pub metric_tolerance_grades: Vec<ToleranceGradeRow>,
+    pub metric_fundamental_deviations: Vec<FundamentalDeviationRow>,
+    pub imperial_main: Vec<ImperialThreadRow>,
+    pub trapezoidal_main: Vec<JsonRow>,
+    pub trapezoidal_basic_dimensions: Vec<JsonRow>,
+    pub pipe_main: Vec<PipeThreadRow>,
+    /// Digest of the files the tables were read from; `None` for datasets built in code
+    pub version: Option<DatasetVersion>,
+}
+
+impl MemoryDataset {
+    /// Fails when a file is missing, malformed or has no rows: a standard without
+    /// rows would answer 404 to every request instead of refusing to start
+    pub fn load(data_dir: &Path) -> Result<Self, AppError> {
+        log_info!("Loading reference data from {}", data_dir.display());
+
+        let mut files = TableFiles::new(data_dir);
+        let mut dataset = Self {
+            metric_main: files.load("metric", "main")?,
+            metric_tolerance_grades: files.load("metric", "tolerance_grades")?,
+            metric_fundamental_deviations: files.load("metric", "fundamental_deviations")?,
+            imperial_main: files.load("imperial", "main")?,
+            trapezoidal_main: files.load("trapezoidal", "main")?,
+            trapezoidal_basic_dimensions: files.load("trapezoidal", "basic_dimensions")?,
+            pipe_main: files.load("pipe", "main")?,
+            version: None,
+        };
+        dataset.version = Some(files.version());
+
+        let rows = dataset.row_counts();
+        let empty: Vec<&str> = rows
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(table, _)| table.as_str())
+            .collect();
+        if !empty.is_empty() {
+            return Err(AppError::ConfigurationError {
+                message: format!("no rows in {} under {}", empty.join(", "), data_dir.display()),
+            });
+        }
+
+        log_info!(
+            "Reference data loaded: metric {} rows, imperial {} rows, trapezoidal {} rows, pipe {} rows",
+            dataset.metric_main.len(),
+            dataset.imperial_main.len(),
+            dataset.trapezoidal_main.len(),
+            dataset.pipe_main.len()
+        );
+        Ok(dataset)
+    }
+
+    /// Rows per table, keyed like the PostgreSQL tables ("imperial.main")
+    pub fn row_counts(&self) -> BTreeMap<String, usize> {
+        [
+            ("metric.main", self.metric_main.len()),
+            ("metric.tolerance_grades", self.metric_tolerance_grades.len()),
+            (
+                "metric.fundamental_deviations",
+                self.metric_fundamental_deviations.len(),
+            ),
+            ("imperial.main", self.imperial_main.len()),
+            ("trapezoidal.main", self.trapezoidal_main.len()),
+            ("trapezoidal.basic_dimensions", self.trapezoidal_basic_dimensions.len()),
+            ("pipe.main", self.pipe_main.len()),
+        ]
+        .into_iter()
+        .map(|(table, count)| (table.to_string(), count))
+        .collect()
+    }
+}
+
+pub fn table_path(data_dir: &Path, schema: &str, table: &str) -> PathBuf {
+    data_dir.join(schema).join(format!("{}.json", table))
+}
+
+/// Reads the tables and digests exactly the bytes they were parsed from, so the
+/// ETags stay tied to the served data even if the files change on disk later
+struct TableFiles<'a> {
+    data_dir: &'a Path,
+    hasher: Sha256,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> TableFiles<'a> {
+    fn new(data_dir: &'a Path) -> Self {
+        Self {
+            data_dir,
+            hasher: Sha256::new(),
+            modified_at: None,
+        }
+    }
+
+    fn load<T: DeserializeOwned>(&mut self, schema: &str, table: &str) -> Result<Vec<T>, AppError> {
+        let path = table_path(self.data_dir, schema, table);
+        let fs_error = |e: std::io::Error| AppError::FileSystemError(format!("{}: {}", path.display(), e));
+        let content = std::fs::read_to_string(&path).map_err(fs_error)?;
+        let modified = DateTime::<Utc>::from(std::fs::metadata(&path).and_then(|m| m.modified()).map_err(fs_error)?);
+
+        self.hasher.update(format!("{}/{}", schema, table).as_bytes());
+        self.hasher.update(content.as_bytes());
+        self.modified_at = Some(self.modified_at.map_or(modified, |latest| latest.max(modified)));
+
+        serde_json::from_str(&content).map_err(|e| AppError::ConfigurationError {
+            message: format!("invalid reference table {}: {}", path.display(), e),
+        })
+    }
+
+    fn version(self) -> DatasetVersion {
+        DatasetVersion::from_digest(self.hasher, self.modified_at)
+    }
+}
@@ -0,0 +1,255 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::sync::Arc;
+
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::enums::ThreadType;
+use crate::shared::error::AppError;
+
+/// Row of `imperial.main`
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct ImperialThreadRow {
+    pub id: i64,
+    pub diameter: String,
+    pub diameter_2: f64, // Used as 'd'
+    pub tpi: f64,
+    pub series_designation: String,
+    pub class_m: String,
+    pub class_f: String,
+    pub allowance: f64,
+    pub major_diam_max_m: f64,
+    pub major_diam_min_m: f64,
+    pub major_diam_min2_m: Option<f64>,
+    pub pitch_diameter_max_m: f64,
+    pub pitch_diameter_min_m: f64,
+    pub pitch_diameter_tolerance_m: f64,
+    pub unr_minor_diameter_max_m: f64,
+    pub minor_diameter_min_f: f64,
+    pub minor_diameter_max_f: f64,
+    pub pitch_diameter_min_f: f64,
+    pub pitch_diameter_max_f: f64,
+    pub pitch_diameter_tolerance_f: f64,
+    pub major_diameter_min_f: f64,
+}
+
+/// One diameter/TPI combination, as returned by `imperial.get_unique_diameters()`
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct ImperialDiameterRow {
+    pub id: i64,
+    pub diameter: String,
+    pub diameter_2: f64,
+    pub tpi: f64,
+    pub series_designation: String,
+}
+
+/// Class pair of one thread, as returned by `imperial.get_diameter_tolerance_data()`
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct ImperialClassRow {
+    pub id: i64,
+    pub diameter: String,
+    pub diameter_2: f64,
+    pub tpi: f64,
+    pub class_f: String,
+    pub class_m: String,
+}
+
+/// Unified inch screw threads (ASME B1.1)
+#[async_trait]
+pub trait TraitImperialRepository {
+    /// Thread by diameter, TPI and the class of the given side (`class_m` or `class_f`)
+    async fn find_thread(
+        &self,
+        diameter: &str,
+        tpi: f64,
+        thread_type: ThreadType,
+        class: &str,
+    ) -> Result<Option<ImperialThreadRow>, AppError>;
+    async fn unique_diameters(&self) -> Result<Vec<ImperialDiameterRow>, AppError>;
+    async fn diameter_classes(&self, diameter: &str, tpi: f64) -> Result<Vec<ImperialClassRow>, AppError>;
+}
+
+pub struct PgImperialRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgImperialRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitImperialRepository for PgImperialRepository {
+    async fn find_thread(
+        &self,
+        diameter: &str,
+        tpi: f64,
+        thread_type: ThreadType,
+        class: &str,
+    ) -> Result<Option<ImperialThreadRow>, AppError> {
+        let query = match thread_type {
+            ThreadType::Male => "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_m = $3",
+            ThreadType::Female => "SELECT * FROM imperial.main WHERE diameter = $1 AND tpi = $2 AND class_f = $3",
+        };
+        let row = sqlx::query_as::<_, ImperialThreadRow>(query)
+            .bind(diameter)
+            .bind(tpi)
+            .bind(class)
+            .fetch_optional(self.connection.pool())
+            .await?;
+        Ok(row)
+    }
+
+    async fn unique_diameters(&self) -> Result<Vec<ImperialDiameterRow>, AppError> {
+        let rows = sqlx::query_as::<_, ImperialDiameterRow>("SELECT * FROM imperial.get_unique_diameters()")
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows)
+    }
+
+    async fn diameter_classes(&self, diameter: &str, tpi: f64) -> Result<Vec<ImperialClassRow>, AppError> {
+        let rows = sqlx::query_as::<_, ImperialClassRow>("SELECT * FROM imperial.get_diameter_tolerance_data($1, $2)")
+            .bind(diameter)
+            .bind(tpi)
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows)
+    }
+}
+
+pub struct MemoryImperialRepository {
+    rows: Vec<ImperialThreadRow>,
+}
+
+impl MemoryImperialRepository {
+    pub fn new(mut rows: Vec<ImperialThreadRow>) -> Self {
+        rows.sort_by_key(|row| row.id);
+        Self { rows }
+    }
+
+    fn same_thread(row: &ImperialThreadRow, diameter: &str, tpi: f64) -> bool {
+        row.diameter == diameter && (row.tpi - tpi).abs() < 1e-9
+    }
+}
+
+#[async_trait]
+impl TraitImperialRepository for MemoryImperialRepository {
+    async fn find_thread(
+        &self,
+        diameter: &str,
+        tpi: f64,
+        thread_type: ThreadType,
+        class: &str,
+    ) -> Result<Option<ImperialThreadRow>, AppError> {
+        Ok(self
+            .rows
+            .iter()
+            .find(|row| {
+                let row_class = match thread_type {
+                    ThreadType::Male => &row.class_m,
+                    ThreadType::Female => &row.class_f,
+                };
+                Self::same_thread(row, diameter, tpi) && row_class == class
+            })
+            .cloned())
+    }
+
+    /// First row of every diameter/TPI pair, from the smallest diameter up
+    async fn unique_diameters(&self) -> Result<Vec<ImperialDiameterRow>, AppError> {
+        let mut unique: Vec<ImperialDiameterRow> = Vec::new();
+        for row in &self.rows {
+            if unique
+                .iter()
+                .any(|seen| seen.diameter == row.diameter && (seen.tpi - row.tpi).abs() < 1e-9)
+            {
+                continue;
+            }
+            unique.push(ImperialDiameterRow {
+                id: row.id,
+                diameter: row.diameter.clone(),
+                diameter_2: row.diameter_2,
+                tpi: row.tpi,
+                series_designation: row.series_designation.clone(),
+            });
+        }
+        unique.sort_by(|a, b| a.diameter_2.total_cmp(&b.diameter_2).then(a.tpi.total_cmp(&b.tpi)));
+        Ok(unique)
+    }
+
+    async fn diameter_classes(&self, diameter: &str, tpi: f64) -> Result<Vec<ImperialClassRow>, AppError> {
+        Ok(self
+            .rows
+            .iter()
+            .filter(|row| Self::same_thread(row, diameter, tpi))
+            .map(|row| ImperialClassRow {
+                id: row.id,
+                diameter: row.diameter.clone(),
+                diameter_2: row.diameter_2,
+                tpi: row.tpi,
+                class_f: row.class_f.clone(),
+                class_m: row.class_m.clone(),
+            })
+            .collect())
+    }
+}
+
+pub struct SqliteImperialRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqliteImperialRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitImperialRepository for SqliteImperialRepository {
+    async fn find_thread(
+        &self,
+        diameter: &str,
+        tpi: f64,
+        thread_type: ThreadType,
+        class: &str,
+    ) -> Result<Option<ImperialThreadRow>, AppError> {
+        let query = match thread_type {
+            ThreadType::Male => "SELECT * FROM imperial_main WHERE diameter = ? AND tpi = ? AND class_m = ?",
+            ThreadType::Female => "SELECT * FROM imperial_main WHERE diameter = ? AND tpi = ? AND class_f = ?",
+        };
+        let row = sqlx::query_as::<_, ImperialThreadRow>(query)
+            .bind(diameter)
+            .bind(tpi)
+            .bind(class)
+            .fetch_optional(self.connection.pool())
+            .await?;
+        Ok(row)
+    }
+
+    /// Same rows as `imperial.get_unique_diameters()`: the first row of every diameter/TPI pair
+    async fn unique_diameters(&self) -> Result<Vec<ImperialDiameterRow>, AppError> {
+        let rows = sqlx::query_as::<_, ImperialDiameterRow>(
+            "
+SELECT id, diameter, diameter_2, tpi, series_designation
+FROM imperial_main
+WHERE id IN (SELECT MIN(id) FROM imperial_main GROUP BY diameter, tpi)
+ORDER BY diameter_2, tpi;
+",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    async fn diameter_classes(&self, diameter: &str, tpi: f64) -> Result<Vec<ImperialClassRow>, AppError> {
+        let rows = sqlx::query_as::<_, ImperialClassRow>(
+            "SELECT id, diameter, diameter_2, tpi, class_f, class_m FROM imperial_main WHERE diameter = ? AND tpi = ? ORDER BY id",
+        )
+        .bind(diameter)
+        .bind(tpi)
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+}
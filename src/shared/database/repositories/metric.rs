@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::features::metric::common::{
+    type_pitch_description, FundamentalDeviationRow, MetricThread, MetricThreadCalculator, ToleranceGradeRow,
+    EXTERNAL_CLASSES, INTERNAL_CLASSES,
+};
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::enums::ThreadType;
+use crate::shared::error::AppError;
+use crate::shared::utils::db::{fetch_json_rows, fetch_sqlite_json_rows, JsonRow};
+
+/// Arguments of `metric.get_info`
+#[derive(Debug, Clone)]
+pub struct MetricInfoQuery {
+    pub diameter: f64,
+    pub pitch: f64,
+    pub type_: String,
+    pub tolerance: String,
+    pub language: String,
+    pub units: String,
+}
+
+/// ISO metric threads. The PostgreSQL schema exposes them only through stored
+/// functions, so rows come back as untyped JSON objects.
+#[async_trait]
+pub trait TraitMetricRepository {
+    async fn get_info(&self, query: &MetricInfoQuery) -> Result<Vec<JsonRow>, AppError>;
+    async fn get_pitch(&self, diameter: f64, language: &str) -> Result<Vec<JsonRow>, AppError>;
+    async fn get_tolerance(&self, id: i32, type_: &str) -> Result<Vec<JsonRow>, AppError>;
+    async fn get_diameters(&self, order: &str) -> Result<Vec<JsonRow>, AppError>;
+    /// Every size in one query: by diameter, then in the order of `get_pitch`
+    async fn get_all_pitches(&self) -> Result<Vec<JsonRow>, AppError>;
+}
+
+pub struct PgMetricRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgMetricRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitMetricRepository for PgMetricRepository {
+    async fn get_info(&self, query: &MetricInfoQuery) -> Result<Vec<JsonRow>, AppError> {
+        let rows = fetch_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM metric.get_info($1, $2, $3, $4, $5, $6)",
+            |q| {
+                q.bind(query.diameter)
+                    .bind(query.pitch)
+                    .bind(query.type_.clone())
+                    .bind(query.tolerance.clone())
+                    .bind(query.language.clone())
+                    .bind(query.units.clone())
+            },
+        )
+        .await?;
+        Ok(rows)
+    }
+
+    async fn get_pitch(&self, diameter: f64, language: &str) -> Result<Vec<JsonRow>, AppError> {
+        let language = language.to_string();
+        let rows = fetch_json_rows(self.connection.pool(), "SELECT * FROM metric.get_pitch($1, $2)", |q| {
+            q.bind(diameter).bind(language)
+        })
+        .await?;
+        Ok(rows)
+    }
+
+    async fn get_tolerance(&self, id: i32, type_: &str) -> Result<Vec<JsonRow>, AppError> {
+        let type_ = type_.to_string();
+        let rows = fetch_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM metric.get_tolerance($1, $2)",
+            |q| q.bind(id).bind(type_),
+        )
+        .await?;
+        Ok(rows)
+    }
+
+    async fn get_diameters(&self, order: &str) -> Result<Vec<JsonRow>, AppError> {
+        let order = order.to_string();
+        let rows = fetch_json_rows(self.connection.pool(), "SELECT * FROM metric.get_diameters($1)", |q| {
+            q.bind(order)
+        })
+        .await?;
+        Ok(rows)
+    }
+
+    async fn get_all_pitches(&self) -> Result<Vec<JsonRow>, AppError> {
+        let rows = fetch_json_rows(
+            self.connection.pool(),
+            "SELECT id, diameter::float8 AS diameter, pitch::float8 AS pitch, type_pitch::int4 AS type_pitch \
+             FROM metric.main ORDER BY diameter, type_pitch, pitch DESC",
+            |q| q,
+        )
+        .await?;
+        Ok(rows)
+    }
+}
+
+/// The `metric` stored functions over the bundled `metric/main.json` (ISO 261);
+/// tolerances come from the ISO 965-1 formulas of the calculator
+pub struct MemoryMetricRepository {
+    threads: Vec<MetricThread>,
+    grades: Vec<ToleranceGradeRow>,
+    deviations: Vec<FundamentalDeviationRow>,
+}
+
+impl MemoryMetricRepository {
+    pub fn new(
+        threads: Vec<MetricThread>,
+        grades: Vec<ToleranceGradeRow>,
+        deviations: Vec<FundamentalDeviationRow>,
+    ) -> Self {
+        Self {
+            threads,
+            grades,
+            deviations,
+        }
+    }
+
+    fn find(&self, diameter: f64, pitch: f64) -> Option<&MetricThread> {
+        self.threads
+            .iter()
+            .find(|thread| same(thread.diameter, diameter) && same(thread.pitch, pitch))
+    }
+}
+
+#[async_trait]
+impl TraitMetricRepository for MemoryMetricRepository {
+    async fn get_info(&self, query: &MetricInfoQuery) -> Result<Vec<JsonRow>, AppError> {
+        let thread_type = parse_type(&query.type_)?;
+        match self.find(query.diameter, query.pitch) {
+            Some(thread) => Ok(vec![info_row(
+                thread,
+                thread_type,
+                query,
+                &self.grades,
+                &self.deviations,
+            )?]),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_pitch(&self, diameter: f64, language: &str) -> Result<Vec<JsonRow>, AppError> {
+        let mut threads: Vec<&MetricThread> = self
+            .threads
+            .iter()
+            .filter(|thread| same(thread.diameter, diameter))
+            .collect();
+        threads.sort_by(|a, b| a.type_pitch.cmp(&b.type_pitch).then(b.pitch.total_cmp(&a.pitch)));
+        pitch_rows(threads, language)
+    }
+
+    async fn get_tolerance(&self, id: i32, type_: &str) -> Result<Vec<JsonRow>, AppError> {
+        let exists = self.threads.iter().any(|thread| thread.id == id);
+        Ok(if exists { tolerance_rows(id, type_) } else { Vec::new() })
+    }
+
+    async fn get_diameters(&self, order: &str) -> Result<Vec<JsonRow>, AppError> {
+        let mut diameters: Vec<f64> = self.threads.iter().map(|thread| thread.diameter).collect();
+        diameters.sort_by(f64::total_cmp);
+        diameters.dedup_by(|a, b| same(*a, *b));
+        if order.eq_ignore_ascii_case("desc") {
+            diameters.reverse();
+        }
+        Ok(diameters
+            .into_iter()
+            .map(|diameter| {
+                let mut row = JsonRow::new();
+                row.insert("diameter".to_string(), Value::from(diameter));
+                row
+            })
+            .collect())
+    }
+
+    async fn get_all_pitches(&self) -> Result<Vec<JsonRow>, AppError> {
+        let mut threads: Vec<&MetricThread> = self.threads.iter().collect();
+        threads.sort_by(|a, b| {
+            a.diameter
+                .total_cmp(&b.diameter)
+                .then(a.type_pitch.cmp(&b.type_pitch))
+                .then(b.pitch.total_cmp(&a.pitch))
+        });
+        pitch_rows(threads, "en")
+    }
+}
+
+/// The `metric` stored functions computed in Rust over `metric_main` and the
+/// tabulated ISO 965-1 values of the SQLite database
+pub struct SqliteMetricRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqliteMetricRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitMetricRepository for SqliteMetricRepository {
+    async fn get_info(&self, query: &MetricInfoQuery) -> Result<Vec<JsonRow>, AppError> {
+        let thread_type = parse_type(&query.type_)?;
+        let pool = self.connection.pool();
+
+        let thread = sqlx::query_as::<_, MetricThread>(
+            "SELECT id, diameter, pitch, type_pitch FROM metric_main WHERE abs(diameter - ?) < 1e-9 AND abs(pitch - ?) < 1e-9",
+        )
+        .bind(query.diameter)
+        .bind(query.pitch)
+        .fetch_optional(pool)
+        .await?;
+        let Some(thread) = thread else {
+            return Ok(Vec::new());
+        };
+
+        let grades = sqlx::query_as::<_, ToleranceGradeRow>(
+            "SELECT kind, grade, pitch, diameter_min, diameter_max, value FROM metric_tolerance_grades WHERE abs(pitch - ?) < 1e-9",
+        )
+        .bind(thread.pitch)
+        .fetch_all(pool)
+        .await?;
+        let deviations = sqlx::query_as::<_, FundamentalDeviationRow>(
+            "SELECT position, pitch, value FROM metric_fundamental_deviations WHERE abs(pitch - ?) < 1e-9",
+        )
+        .bind(thread.pitch)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(vec![info_row(&thread, thread_type, query, &grades, &deviations)?])
+    }
+
+    async fn get_pitch(&self, diameter: f64, language: &str) -> Result<Vec<JsonRow>, AppError> {
+        let threads = sqlx::query_as::<_, MetricThread>(
+            "SELECT id, diameter, pitch, type_pitch FROM metric_main WHERE abs(diameter - ?) < 1e-9 ORDER BY type_pitch, pitch DESC",
+        )
+        .bind(diameter)
+        .fetch_all(self.connection.pool())
+        .await?;
+
+        pitch_rows(threads.iter(), language)
+    }
+
+    /// Preferred tolerance classes of ISO 965-1 for the thread `id`
+    async fn get_tolerance(&self, id: i32, type_: &str) -> Result<Vec<JsonRow>, AppError> {
+        let exists = sqlx::query("SELECT 1 FROM metric_main WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self.connection.pool())
+            .await?
+            .is_some();
+        Ok(if exists { tolerance_rows(id, type_) } else { Vec::new() })
+    }
+
+    async fn get_diameters(&self, order: &str) -> Result<Vec<JsonRow>, AppError> {
+        let query = if order.eq_ignore_ascii_case("desc") {
+            "SELECT DISTINCT diameter FROM metric_main ORDER BY diameter DESC"
+        } else {
+            "SELECT DISTINCT diameter FROM metric_main ORDER BY diameter ASC"
+        };
+        let rows = fetch_sqlite_json_rows(self.connection.pool(), query, |q| q).await?;
+        Ok(rows)
+    }
+
+    async fn get_all_pitches(&self) -> Result<Vec<JsonRow>, AppError> {
+        let threads = sqlx::query_as::<_, MetricThread>(
+            "SELECT id, diameter, pitch, type_pitch FROM metric_main ORDER BY diameter, type_pitch, pitch DESC",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+
+        pitch_rows(threads.iter(), "en")
+    }
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn parse_type(type_: &str) -> Result<ThreadType, AppError> {
+    ThreadType::from_str(&type_.to_ascii_lowercase())
+        .map_err(|_| AppError::InvalidThreadParams(format!("unknown thread type '{}'", type_)))
+}
+
+fn to_row<T: Serialize>(value: &T) -> Result<JsonRow, AppError> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(mut row)) => {
+            row.retain(|_, v| !v.is_null());
+            Ok(row)
+        }
+        Ok(_) => Err(AppError::InternalError {
+            message: "row is not a JSON object".to_string(),
+        }),
+        Err(e) => Err(AppError::InternalError { message: e.to_string() }),
+    }
+}
+
+/// Row of `metric.get_info`
+fn info_row(
+    thread: &MetricThread,
+    thread_type: ThreadType,
+    query: &MetricInfoQuery,
+    grades: &[ToleranceGradeRow],
+    deviations: &[FundamentalDeviationRow],
+) -> Result<JsonRow, AppError> {
+    let info = MetricThreadCalculator::new(grades, deviations).info(
+        thread,
+        thread_type,
+        &query.tolerance,
+        &query.language,
+        &query.units,
+    )?;
+    to_row(&info)
+}
+
+/// Rows of `metric.get_pitch`, in the order given
+fn pitch_rows<'a>(
+    threads: impl IntoIterator<Item = &'a MetricThread>,
+    language: &str,
+) -> Result<Vec<JsonRow>, AppError> {
+    threads
+        .into_iter()
+        .map(|thread| {
+            let mut row = to_row(thread)?;
+            row.insert(
+                "type_pitch_description".to_string(),
+                Value::from(type_pitch_description(thread.type_pitch, language)),
+            );
+            Ok(row)
+        })
+        .collect()
+}
+
+/// Preferred tolerance classes of ISO 965-1, rows of `metric.get_tolerance`
+fn tolerance_rows(id: i32, type_: &str) -> Vec<JsonRow> {
+    let classes: &[&str] = match type_ {
+        "m" | "male" => &EXTERNAL_CLASSES,
+        _ => &INTERNAL_CLASSES,
+    };
+    classes
+        .iter()
+        .map(|class| {
+            let mut row = JsonRow::new();
+            row.insert("id".to_string(), Value::from(id));
+            row.insert("tolerance".to_string(), Value::from(*class));
+            row
+        })
+        .collect()
+}
@@ -0,0 +1,33 @@
+pub mod analytics;
+pub mod api_keys;
+pub mod dataset;
+pub mod imperial;
+pub mod metric;
+pub mod pipe;
+pub mod trapezoidal;
+
+pub use analytics::{
+    DailyUsage, ErrorReportFilter, MemoryAnalyticsRepository, PgAnalyticsRepository, SqliteAnalyticsRepository,
+    ThreadUsage, TraitAnalyticsRepository, UsageFilter,
+};
+pub use api_keys::{
+    ApiKey, ApiKeyUsage, ApiScope, MemoryApiKeyRepository, NewApiKey, PgApiKeyRepository, SqliteApiKeyRepository,
+    TraitApiKeyRepository,
+};
+pub use dataset::MemoryDataset;
+pub use imperial::{MemoryImperialRepository, PgImperialRepository, SqliteImperialRepository, TraitImperialRepository};
+pub use metric::{
+    MemoryMetricRepository, MetricInfoQuery, PgMetricRepository, SqliteMetricRepository, TraitMetricRepository,
+};
+pub use pipe::{MemoryPipeRepository, PgPipeRepository, SqlitePipeRepository, TraitPipeRepository};
+pub use trapezoidal::{
+    MemoryTrapezoidalRepository, PgTrapezoidalRepository, SqliteTrapezoidalRepository, TraitTrapezoidalRepository,
+};
+
+use crate::shared::error::AppError;
+
+/// Turns a missing row into `ThreadDataNotFound` with the lookup parameters:
+/// `.and_then(required(|| json!({ "id": id })))`
+pub fn required<T>(params: impl FnOnce() -> serde_json::Value) -> impl FnOnce(Option<T>) -> Result<T, AppError> {
+    move |row| row.ok_or_else(|| AppError::thread_not_found(params()))
+}
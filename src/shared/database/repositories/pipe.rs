@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::sync::Arc;
+
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::error::AppError;
+
+/// Row of `pipe.main`
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct PipeThreadRow {
+    pub id: i32,
+    pub designation: f64,
+    pub designation_2: String,
+    pub thread_pitch: f64,
+    pub thread_per: i32,
+    pub class_name: Option<String>,
+    // External thread dimensions
+    pub ex_major_dia_max: Option<f64>,
+    pub ex_major_dia_min: Option<f64>,
+    pub ex_pitch_diam_max: Option<f64>,
+    pub ex_pitch_diam_min: Option<f64>,
+    pub ex_minor_dia_max: Option<f64>,
+    // Internal thread dimensions
+    pub in_minor_dia_min: Option<f64>,
+    pub in_minor_dia_max: Option<f64>,
+    pub in_pitch_diam_min: Option<f64>,
+    pub in_pitch_diam_max: Option<f64>,
+    pub in_major_dia_min: Option<f64>,
+    pub in_tap_drill: Option<f64>,
+}
+
+/// Pipe threads (ISO 228-1)
+#[async_trait]
+pub trait TraitPipeRepository {
+    /// All threads ordered by designation, with only the columns the size list needs
+    async fn list(&self) -> Result<Vec<PipeThreadRow>, AppError>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<PipeThreadRow>, AppError>;
+}
+
+pub struct PgPipeRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgPipeRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+const QUERY_PIPE_LIST: &str = "
+SELECT
+    id,
+    designation,
+    designation_2,
+    thread_pitch,
+    thread_per,
+    class_name,
+    ex_major_dia_max,
+    NULL::double precision as ex_major_dia_min,
+    NULL::double precision as ex_pitch_diam_max,
+    NULL::double precision as ex_pitch_diam_min,
+    NULL::double precision as ex_minor_dia_max,
+    NULL::double precision as in_minor_dia_min,
+    NULL::double precision as in_minor_dia_max,
+    NULL::double precision as in_pitch_diam_min,
+    NULL::double precision as in_pitch_diam_max,
+    in_major_dia_min,
+    NULL::double precision as in_tap_drill
+FROM pipe.main
+ORDER BY designation ASC;
+";
+
+const QUERY_PIPE_BY_ID: &str = "
+SELECT id,
+       designation,
+       designation_2,
+       thread_pitch,
+       thread_per,
+       class_name,
+       ex_major_dia_max,
+       ex_major_dia_min,
+       ex_pitch_diam_max,
+       ex_pitch_diam_min,
+       ex_minor_dia_max,
+       in_minor_dia_min,
+       in_minor_dia_max,
+       in_pitch_diam_min,
+       in_pitch_diam_max,
+       in_major_dia_min,
+       in_tap_drill
+FROM pipe.main
+where id = $1;
+";
+
+#[async_trait]
+impl TraitPipeRepository for PgPipeRepository {
+    async fn list(&self) -> Result<Vec<PipeThreadRow>, AppError> {
+        let rows = sqlx::query_as::<_, PipeThreadRow>(QUERY_PIPE_LIST)
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<PipeThreadRow>, AppError> {
+        let row = sqlx::query_as::<_, PipeThreadRow>(QUERY_PIPE_BY_ID)
+            .bind(id)
+            .fetch_optional(self.connection.pool())
+            .await?;
+        Ok(row)
+    }
+}
+
+pub struct MemoryPipeRepository {
+    rows: Vec<PipeThreadRow>,
+}
+
+impl MemoryPipeRepository {
+    pub fn new(rows: Vec<PipeThreadRow>) -> Self {
+        Self { rows }
+    }
+}
+
+#[async_trait]
+impl TraitPipeRepository for MemoryPipeRepository {
+    async fn list(&self) -> Result<Vec<PipeThreadRow>, AppError> {
+        let mut rows: Vec<PipeThreadRow> = self
+            .rows
+            .iter()
+            .map(|row| PipeThreadRow {
+                ex_major_dia_min: None,
+                ex_pitch_diam_max: None,
+                ex_pitch_diam_min: None,
+                ex_minor_dia_max: None,
+                in_minor_dia_min: None,
+                in_minor_dia_max: None,
+                in_pitch_diam_min: None,
+                in_pitch_diam_max: None,
+                in_tap_drill: None,
+                ..row.clone()
+            })
+            .collect();
+        rows.sort_by(|a, b| a.designation.total_cmp(&b.designation));
+        Ok(rows)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<PipeThreadRow>, AppError> {
+        Ok(self.rows.iter().find(|row| row.id == id).cloned())
+    }
+}
+
+pub struct SqlitePipeRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqlitePipeRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitPipeRepository for SqlitePipeRepository {
+    async fn list(&self) -> Result<Vec<PipeThreadRow>, AppError> {
+        let query = QUERY_PIPE_LIST
+            .replace("NULL::double precision", "NULL")
+            .replace("pipe.main", "pipe_main");
+        let rows = sqlx::query_as::<_, PipeThreadRow>(&query)
+            .fetch_all(self.connection.pool())
+            .await?;
+        Ok(rows)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<PipeThreadRow>, AppError> {
+        let query = QUERY_PIPE_BY_ID.replace("pipe.main", "pipe_main").replace("$1", "?");
+        let row = sqlx::query_as::<_, PipeThreadRow>(&query)
+            .bind(id)
+            .fetch_optional(self.connection.pool())
+            .await?;
+        Ok(row)
+    }
+}
@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+use std::sync::Arc;
+
+use crate::shared::database::connection::PostgresConnection;
+use crate::shared::database::sqlite::SqliteConnection;
+use crate::shared::error::AppError;
+use crate::shared::utils::db::{fetch_json_rows, fetch_sqlite_json_rows, JsonRow};
+
+/// Diameter/pitch combination of `trapezoidal.main`
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct TrapezoidalDiameterRow {
+    pub diameter: i32,
+    pub pitch: f64,
+}
+
+/// Trapezoidal threads (ISO 2904). `trapezoidal.main` keeps one column per
+/// deviation and tolerance class (`es_d2_7e_m`, ...), so its rows are untyped.
+#[async_trait]
+pub trait TraitTrapezoidalRepository {
+    async fn find_thread(&self, diameter: i32, pitch: f64) -> Result<Option<JsonRow>, AppError>;
+    async fn find_basic_dimensions(&self, pitch: f64) -> Result<Option<JsonRow>, AppError>;
+    /// Distinct diameter/pitch pairs ordered by diameter and pitch
+    async fn diameters(&self) -> Result<Vec<TrapezoidalDiameterRow>, AppError>;
+    /// Sorted names of the `es_d2_<class>_<m|f>` columns, one per tolerance class
+    async fn tolerance_columns(&self) -> Result<Vec<String>, AppError>;
+}
+
+pub struct PgTrapezoidalRepository {
+    connection: Arc<PostgresConnection>,
+}
+
+impl PgTrapezoidalRepository {
+    pub fn new(connection: Arc<PostgresConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitTrapezoidalRepository for PgTrapezoidalRepository {
+    async fn find_thread(&self, diameter: i32, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        let rows = fetch_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM trapezoidal.main WHERE diameter = $1::integer AND pitch = $2::double precision",
+            |q| q.bind(diameter).bind(pitch),
+        )
+        .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    async fn find_basic_dimensions(&self, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        let rows = fetch_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM trapezoidal.basic_dimensions WHERE p = $1::double precision",
+            |q| q.bind(pitch),
+        )
+        .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    async fn diameters(&self) -> Result<Vec<TrapezoidalDiameterRow>, AppError> {
+        let rows = sqlx::query_as::<_, TrapezoidalDiameterRow>(
+            "
+SELECT DISTINCT ON (diameter, pitch)
+    id,
+    diameter,
+    pitch
+
+FROM
+    trapezoidal.main
+ORDER BY diameter, pitch, id;
+",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    async fn tolerance_columns(&self) -> Result<Vec<String>, AppError> {
+        let rows = sqlx::query(
+            r#"
+        SELECT column_name
+        FROM information_schema.columns
+        WHERE table_schema = 'trapezoidal'
+        AND table_name = 'main'
+        AND (column_name LIKE 'es_d2_%_m' OR column_name LIKE 'es_d2_%_f')
+        ORDER BY column_name;
+    "#,
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows.iter().map(|row| row.get::<String, _>(0)).collect())
+    }
+}
+
+pub struct MemoryTrapezoidalRepository {
+    main: Vec<JsonRow>,
+    basic_dimensions: Vec<JsonRow>,
+}
+
+impl MemoryTrapezoidalRepository {
+    pub fn new(mut main: Vec<JsonRow>, basic_dimensions: Vec<JsonRow>) -> Self {
+        main.sort_by_key(|row| row.get("id").and_then(|v| v.as_i64()).unwrap_or_default());
+        Self { main, basic_dimensions }
+    }
+
+    fn number(row: &JsonRow, column: &str) -> Option<f64> {
+        row.get(column).and_then(|v| v.as_f64())
+    }
+
+    fn equals(row: &JsonRow, column: &str, value: f64) -> bool {
+        Self::number(row, column).is_some_and(|v| (v - value).abs() < 1e-9)
+    }
+}
+
+#[async_trait]
+impl TraitTrapezoidalRepository for MemoryTrapezoidalRepository {
+    async fn find_thread(&self, diameter: i32, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        Ok(self
+            .main
+            .iter()
+            .find(|row| Self::equals(row, "diameter", diameter as f64) && Self::equals(row, "pitch", pitch))
+            .cloned())
+    }
+
+    async fn find_basic_dimensions(&self, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        Ok(self
+            .basic_dimensions
+            .iter()
+            .find(|row| Self::equals(row, "p", pitch))
+            .cloned())
+    }
+
+    async fn diameters(&self) -> Result<Vec<TrapezoidalDiameterRow>, AppError> {
+        let mut rows: Vec<TrapezoidalDiameterRow> = self
+            .main
+            .iter()
+            .filter_map(|row| {
+                Some(TrapezoidalDiameterRow {
+                    diameter: Self::number(row, "diameter")? as i32,
+                    pitch: Self::number(row, "pitch")?,
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| a.diameter.cmp(&b.diameter).then(a.pitch.total_cmp(&b.pitch)));
+        rows.dedup_by(|a, b| a.diameter == b.diameter && a.pitch == b.pitch);
+        Ok(rows)
+    }
+
+    async fn tolerance_columns(&self) -> Result<Vec<String>, AppError> {
+        let mut columns: Vec<String> = self
+            .main
+            .iter()
+            .flat_map(|row| row.keys())
+            .filter(|name| name.starts_with("es_d2_") && (name.ends_with("_m") || name.ends_with("_f")))
+            .cloned()
+            .collect();
+        columns.sort();
+        columns.dedup();
+        Ok(columns)
+    }
+}
+
+pub struct SqliteTrapezoidalRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SqliteTrapezoidalRepository {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TraitTrapezoidalRepository for SqliteTrapezoidalRepository {
+    async fn find_thread(&self, diameter: i32, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        let rows = fetch_sqlite_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM trapezoidal_main WHERE diameter = ? AND pitch = ?",
+            |q| q.bind(diameter).bind(pitch),
+        )
+        .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    async fn find_basic_dimensions(&self, pitch: f64) -> Result<Option<JsonRow>, AppError> {
+        let rows = fetch_sqlite_json_rows(
+            self.connection.pool(),
+            "SELECT * FROM trapezoidal_basic_dimensions WHERE p = ?",
+            |q| q.bind(pitch),
+        )
+        .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    async fn diameters(&self) -> Result<Vec<TrapezoidalDiameterRow>, AppError> {
+        let rows = sqlx::query_as::<_, TrapezoidalDiameterRow>(
+            "SELECT DISTINCT diameter, pitch FROM trapezoidal_main ORDER BY diameter, pitch",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    async fn tolerance_columns(&self) -> Result<Vec<String>, AppError> {
+        let rows = sqlx::query(
+            "
+SELECT name
+FROM pragma_table_info('trapezoidal_main')
+WHERE name LIKE 'es_d2_%_m' OR name LIKE 'es_d2_%_f'
+ORDER BY name;
+",
+        )
+        .fetch_all(self.connection.pool())
+        .await?;
+        Ok(rows.iter().map(|row| row.get::<String, _>(0)).collect())
+    }
+}
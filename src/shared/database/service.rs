@@ -1,23 +1,87 @@
-use crate::{log_error, log_info};
+use crate::{log_error, log_info, log_warn};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::shared::{database::connection::PostgresConnection, setting::models::app_setting::AppSettings};
+use crate::shared::{
+    database::{
+        connection::PostgresConnection,
+        repositories::{
+            MemoryAnalyticsRepository, MemoryApiKeyRepository, MemoryDataset, MemoryImperialRepository,
+            MemoryMetricRepository, MemoryPipeRepository, MemoryTrapezoidalRepository, PgAnalyticsRepository,
+            PgApiKeyRepository, PgImperialRepository, PgMetricRepository, PgPipeRepository, PgTrapezoidalRepository,
+            SqliteAnalyticsRepository, SqliteApiKeyRepository, SqliteImperialRepository, SqliteMetricRepository,
+            SqlitePipeRepository, SqliteTrapezoidalRepository, TraitAnalyticsRepository, TraitApiKeyRepository,
+            TraitImperialRepository, TraitMetricRepository, TraitPipeRepository, TraitTrapezoidalRepository,
+        },
+        sqlite::SqliteConnection,
+        version::DatasetVersion,
+    },
+    error::AppError,
+    setting::models::{app_config::DatabaseBackend, app_setting::AppSettings},
+};
 
 pub struct PostgresService {
-    // Connection
-    pub connection: Arc<PostgresConnection>,
-    // Operational repositories (PostgreSQL)
-    // pub repository_health_check: Arc<dyn TraitHealthCheckRepository + Send + Sync>,
-    // pub repository_tinkoff_candles_status: Arc<dyn TraitTinkoffCandlesStatusRepository + Send + Sync>,
-    // Add other PostgreSQL repositories here as needed
-    // Example: pub user_repository: Arc<dyn UserRepository + Send + Sync>,
-    // Example: pub order_repository: Arc<dyn OrderRepository + Send + Sync>,
+    // Connection, absent for the in-memory and SQLite backends
+    pub connection: Option<Arc<PostgresConnection>>,
+    // SQLite file of the sqlite backend
+    pub sqlite: Option<Arc<SqliteConnection>>,
+    // Reference data repositories
+    pub repository_metric: Arc<dyn TraitMetricRepository + Send + Sync>,
+    pub repository_imperial: Arc<dyn TraitImperialRepository + Send + Sync>,
+    pub repository_trapezoidal: Arc<dyn TraitTrapezoidalRepository + Send + Sync>,
+    pub repository_pipe: Arc<dyn TraitPipeRepository + Send + Sync>,
+    // Operational repositories
+    pub repository_analytics: Arc<dyn TraitAnalyticsRepository + Send + Sync>,
+    pub repository_api_keys: Arc<dyn TraitApiKeyRepository + Send + Sync>,
+    // Rows per table of the in-memory backend, for the health checks
+    pub bundled_rows: Option<BTreeMap<String, usize>>,
+    // Version of the data the in-memory backend loaded
+    pub bundled_version: Option<DatasetVersion>,
 }
 
 impl PostgresService {
     pub async fn new(settings: &Arc<AppSettings>) -> Result<Self, Box<dyn std::error::Error>> {
+        match settings.config.database.backend {
+            DatabaseBackend::Postgres => Self::new_postgres(settings).await,
+            DatabaseBackend::Memory => {
+                log_info!("Initializing in-memory database backend");
+                let dataset = MemoryDataset::load(&settings.config.database.data_dir).map_err(|e| {
+                    log_error!("Failed to load reference data: {}", e);
+                    e
+                })?;
+                Ok(Self::from_dataset(dataset))
+            }
+            DatabaseBackend::Sqlite => {
+                log_info!("Initializing SQLite database backend");
+                let connection = SqliteConnection::open(&settings.config.database.sqlite_path)
+                    .await
+                    .map_err(|e| {
+                        log_error!("Failed to open SQLite database: {}", e);
+                        e
+                    })?;
+                Ok(Self::from_sqlite(Arc::new(connection)))
+            }
+        }
+    }
+
+    async fn new_postgres(settings: &Arc<AppSettings>) -> Result<Self, Box<dyn std::error::Error>> {
         log_info!("Initializing PostgreSQL service components");
 
+        let env = &settings.env;
+        for (variable, value) in [
+            ("POSTGRES_HOST", &env.postgres_host),
+            ("POSTGRES_USER", &env.postgres_user),
+            ("POSTGRES_DATABASE", &env.postgres_database),
+        ] {
+            if value.is_empty() {
+                return Err(Box::new(AppError::EnvironmentError {
+                    variable: variable.to_string(),
+                }));
+            }
+        }
+
         // Initialize PostgreSQL connection
         log_info!("Creating PostgreSQL connection");
         let postgres_connection = match PostgresConnection::new(settings.clone()).await {
@@ -30,29 +94,86 @@ impl PostgresService {
 
         // Initialize repositories
         log_info!("Initializing repositories");
-        // let health_check_repository = Arc::new(StructHealthCheckRepository::new(
-        //     postgres_connection.clone(),
-        // )) as Arc<dyn TraitHealthCheckRepository + Send + Sync>;
+        let service = Self {
+            repository_metric: Arc::new(PgMetricRepository::new(postgres_connection.clone())),
+            repository_imperial: Arc::new(PgImperialRepository::new(postgres_connection.clone())),
+            repository_trapezoidal: Arc::new(PgTrapezoidalRepository::new(postgres_connection.clone())),
+            repository_pipe: Arc::new(PgPipeRepository::new(postgres_connection.clone())),
+            repository_analytics: Arc::new(PgAnalyticsRepository::new(postgres_connection.clone())),
+            repository_api_keys: Arc::new(PgApiKeyRepository::new(postgres_connection.clone())),
+            connection: Some(postgres_connection),
+            sqlite: None,
+            bundled_rows: None,
+            bundled_version: None,
+        };
+
+        log_info!("PostgreSQL service initialized successfully");
+        Ok(service)
+    }
 
-        // let tinkoff_candles_status_repository = Arc::new(StructTinkoffCandlesStatusRepository::new(
-        //     postgres_connection.clone(),
-        // )) as Arc<dyn TraitTinkoffCandlesStatusRepository + Send + Sync>;
+    /// Serves the bundled reference tables without a database; analytics and API keys stay in memory
+    pub fn from_dataset(dataset: MemoryDataset) -> Self {
+        Self {
+            connection: None,
+            sqlite: None,
+            bundled_rows: Some(dataset.row_counts()),
+            bundled_version: dataset.version,
+            repository_metric: Arc::new(MemoryMetricRepository::new(
+                dataset.metric_main,
+                dataset.metric_tolerance_grades,
+                dataset.metric_fundamental_deviations,
+            )),
+            repository_imperial: Arc::new(MemoryImperialRepository::new(dataset.imperial_main)),
+            repository_trapezoidal: Arc::new(MemoryTrapezoidalRepository::new(
+                dataset.trapezoidal_main,
+                dataset.trapezoidal_basic_dimensions,
+            )),
+            repository_pipe: Arc::new(MemoryPipeRepository::new(dataset.pipe_main)),
+            repository_analytics: Arc::new(MemoryAnalyticsRepository::new()),
+            repository_api_keys: Arc::new(MemoryApiKeyRepository::new()),
+        }
+    }
 
-        // Initialize any other repositories here
-        // Example:
-        // let user_repository = Arc::new(PgUserRepository::new(
-        //    postgres_connection.clone(),
-        // )) as Arc<dyn UserRepository + Send + Sync>;
+    /// Reference data, analytics and API keys in one SQLite file
+    pub fn from_sqlite(connection: Arc<SqliteConnection>) -> Self {
+        Self {
+            connection: None,
+            repository_metric: Arc::new(SqliteMetricRepository::new(connection.clone())),
+            repository_imperial: Arc::new(SqliteImperialRepository::new(connection.clone())),
+            repository_trapezoidal: Arc::new(SqliteTrapezoidalRepository::new(connection.clone())),
+            repository_pipe: Arc::new(SqlitePipeRepository::new(connection.clone())),
+            repository_analytics: Arc::new(SqliteAnalyticsRepository::new(connection.clone())),
+            repository_api_keys: Arc::new(SqliteApiKeyRepository::new(connection.clone())),
+            sqlite: Some(connection),
+            bundled_rows: None,
+            bundled_version: None,
+        }
+    }
 
-        log_info!("PostgreSQL service initialized successfully");
-        Ok(Self {
-            connection: postgres_connection,
-            // repository_health_check: health_check_repository,
-            // repository_tinkoff_candles_status: tinkoff_candles_status_repository,
-            // Add other repositories here as they are implemented
-            // Example: user_repository,
-        })
+    /// Closes the pools once the background writers have finished; waits up to
+    /// `timeout` per pool for connections still checked out, then gives up on them
+    pub async fn close(&self, timeout: Duration) {
+        if let Some(pool) = self.pool() {
+            match tokio::time::timeout(timeout, pool.close()).await {
+                Ok(()) => log_info!("PostgreSQL pool closed"),
+                Err(_) => log_warn!(
+                    "PostgreSQL pool not closed within {:?}, connections are still in use",
+                    timeout
+                ),
+            }
+        }
+        if let Some(sqlite) = &self.sqlite {
+            match tokio::time::timeout(timeout, sqlite.pool().close()).await {
+                Ok(()) => log_info!("SQLite pool closed"),
+                Err(_) => log_warn!(
+                    "SQLite pool not closed within {:?}, connections are still in use",
+                    timeout
+                ),
+            }
+        }
     }
 
-    // Add any service-level methods here that might coordinate between repositories
+    pub fn pool(&self) -> Option<&PgPool> {
+        self.connection.as_ref().map(|connection| connection.pool())
+    }
 }
@@ -0,0 +1,68 @@
+use crate::{log_debug, log_info};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    SqlitePool,
+};
+use std::path::Path;
+
+use crate::shared::error::AppError;
+
+/// Tables of the SQLite database, mirroring the PostgreSQL schemas
+pub const SCHEMA: &str = include_str!("schema.sql");
+
+/// Columns added to the schema later: `CREATE TABLE IF NOT EXISTS` leaves existing
+/// tables alone, so the missing columns are added separately
+const ADDED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("analytics_error_reports", "resolved_at", "TEXT"),
+    ("metric_tolerance_grades", "id", "INTEGER"),
+    ("metric_fundamental_deviations", "id", "INTEGER"),
+];
+
+/// Connection to an SQLite file for deployments without PostgreSQL
+#[derive(Clone)]
+pub struct SqliteConnection {
+    pool: SqlitePool,
+}
+
+impl SqliteConnection {
+    /// Opens (or creates) the database file and applies the schema
+    pub async fn open(path: &Path) -> Result<Self, AppError> {
+        log_info!("Opening SQLite database {}", path.display());
+        let options = SqliteConnectOptions::new().filename(path).create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await?;
+        Self::with_pool(pool).await
+    }
+
+    /// Uses an existing pool, e.g. `sqlite::memory:` in tests
+    pub async fn with_pool(pool: SqlitePool) -> Result<Self, AppError> {
+        log_debug!("Applying SQLite schema");
+        sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+        for (table, column, definition) in ADDED_COLUMNS {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)")
+                    .bind(table)
+                    .bind(column)
+                    .fetch_one(&pool)
+                    .await?;
+            if !exists {
+                log_info!("Adding column {}.{}", table, column);
+                sqlx::raw_sql(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+                    .execute(&pool)
+                    .await?;
+            }
+        }
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    pub async fn health_check(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+}
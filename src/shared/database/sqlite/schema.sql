@@ -0,0 +1,234 @@
+-- Reference tables of the PostgreSQL schemas, flattened to `<schema>_<table>`.
+-- The metric stored functions are implemented in Rust on top of `metric_main`
+-- and the ISO 965-1 tables.
+
+CREATE TABLE IF NOT EXISTS metric_main (
+    id INTEGER PRIMARY KEY,
+    diameter REAL NOT NULL,
+    pitch REAL NOT NULL,
+    type_pitch INTEGER NOT NULL DEFAULT 0 -- 0 coarse, 1 fine
+);
+
+-- Tabulated tolerances in µm (kind: Td, TD1, Td2, TD2); diameter bounds are 0 for Td and TD1
+CREATE TABLE IF NOT EXISTS metric_tolerance_grades (
+    id INTEGER,
+    kind TEXT NOT NULL,
+    grade INTEGER NOT NULL,
+    pitch REAL NOT NULL,
+    diameter_min REAL NOT NULL DEFAULT 0,
+    diameter_max REAL NOT NULL DEFAULT 0,
+    value INTEGER NOT NULL,
+    PRIMARY KEY (kind, grade, pitch, diameter_min)
+);
+
+-- Tabulated fundamental deviations in µm (position: e, f, g, h, E, F, G, H)
+CREATE TABLE IF NOT EXISTS metric_fundamental_deviations (
+    id INTEGER,
+    position TEXT NOT NULL,
+    pitch REAL NOT NULL,
+    value INTEGER NOT NULL,
+    PRIMARY KEY (position, pitch)
+);
+
+CREATE TABLE IF NOT EXISTS imperial_main (
+    id INTEGER PRIMARY KEY,
+    diameter TEXT NOT NULL,
+    diameter_2 REAL NOT NULL,
+    tpi REAL NOT NULL,
+    series_designation TEXT NOT NULL,
+    class_m TEXT NOT NULL,
+    class_f TEXT NOT NULL,
+    allowance REAL NOT NULL,
+    major_diam_max_m REAL NOT NULL,
+    major_diam_min_m REAL NOT NULL,
+    major_diam_min2_m REAL,
+    pitch_diameter_max_m REAL NOT NULL,
+    pitch_diameter_min_m REAL NOT NULL,
+    pitch_diameter_tolerance_m REAL NOT NULL,
+    unr_minor_diameter_max_m REAL NOT NULL,
+    minor_diameter_min_f REAL NOT NULL,
+    minor_diameter_max_f REAL NOT NULL,
+    pitch_diameter_min_f REAL NOT NULL,
+    pitch_diameter_max_f REAL NOT NULL,
+    pitch_diameter_tolerance_f REAL NOT NULL,
+    major_diameter_min_f REAL NOT NULL
+);
+
+-- Deviations in µm, one column per diameter, tolerance class and side
+CREATE TABLE IF NOT EXISTS trapezoidal_main (
+    id INTEGER PRIMARY KEY,
+    diameter INTEGER NOT NULL,
+    pitch REAL NOT NULL,
+    d1 REAL, d2_d2 REAL, d3 REAL, d4 REAL,
+    es_d_7e_m REAL, ei_d_7e_m REAL, es_d2_7e_m REAL, ei_d2_7e_m REAL,
+    es_d3_7e_m REAL, ei_d3_7e_m REAL, es_d_8e_m REAL, ei_d_8e_m REAL,
+    es_d2_8e_m REAL, ei_d2_8e_m REAL, es_d3_8e_m REAL, ei_d3_8e_m REAL,
+    es_d_8c_m REAL, ei_d_8c_m REAL, es_d2_8c_m REAL, ei_d2_8c_m REAL,
+    es_d3_8c_m REAL, ei_d3_8c_m REAL, es_d_9c_m REAL, ei_d_9c_m REAL,
+    es_d2_9c_m REAL, ei_d2_9c_m REAL, es_d3_9c_m REAL, ei_d3_9c_m REAL,
+    es_d1_7h_f REAL, ei_d1_7h_f REAL, es_d2_7h_f REAL, ei_d2_7h_f REAL,
+    ei_d4_7h_f REAL, es_d1_8h_f REAL, ei_d1_8h_f REAL, es_d2_8h_f REAL,
+    ei_d2_8h_f REAL, ei_d4_8h_f REAL, es_d1_9h_f REAL, ei_d1_9h_f REAL,
+    es_d2_9h_f REAL, ei_d2_9h_f REAL, ei_d4_9h_f REAL
+);
+
+CREATE TABLE IF NOT EXISTS trapezoidal_basic_dimensions (
+    p REAL PRIMARY KEY,
+    a_c REAL NOT NULL,
+    h4_h3 REAL NOT NULL,
+    h1 REAL NOT NULL,
+    r1_max REAL NOT NULL,
+    r2_max REAL NOT NULL,
+    z REAL NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS pipe_main (
+    id INTEGER PRIMARY KEY,
+    designation REAL NOT NULL,
+    designation_2 TEXT NOT NULL,
+    thread_pitch REAL NOT NULL,
+    thread_per INTEGER NOT NULL,
+    class_name TEXT,
+    ex_major_dia_max REAL,
+    ex_major_dia_min REAL,
+    ex_pitch_diam_max REAL,
+    ex_pitch_diam_min REAL,
+    ex_minor_dia_max REAL,
+    in_minor_dia_min REAL,
+    in_minor_dia_max REAL,
+    in_pitch_diam_min REAL,
+    in_pitch_diam_max REAL,
+    in_major_dia_min REAL,
+    in_tap_drill REAL
+);
+
+-- Version of the reference tables, bumped by the triggers below on every change
+CREATE TABLE IF NOT EXISTS reference_data_version (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+INSERT OR IGNORE INTO reference_data_version (id) VALUES (1);
+
+CREATE TRIGGER IF NOT EXISTS metric_main_insert_version AFTER INSERT ON metric_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_main_update_version AFTER UPDATE ON metric_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_main_delete_version AFTER DELETE ON metric_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_tolerance_grades_insert_version AFTER INSERT ON metric_tolerance_grades
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_tolerance_grades_update_version AFTER UPDATE ON metric_tolerance_grades
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_tolerance_grades_delete_version AFTER DELETE ON metric_tolerance_grades
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_fundamental_deviations_insert_version AFTER INSERT ON metric_fundamental_deviations
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_fundamental_deviations_update_version AFTER UPDATE ON metric_fundamental_deviations
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS metric_fundamental_deviations_delete_version AFTER DELETE ON metric_fundamental_deviations
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS imperial_main_insert_version AFTER INSERT ON imperial_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS imperial_main_update_version AFTER UPDATE ON imperial_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS imperial_main_delete_version AFTER DELETE ON imperial_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_main_insert_version AFTER INSERT ON trapezoidal_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_main_update_version AFTER UPDATE ON trapezoidal_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_main_delete_version AFTER DELETE ON trapezoidal_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_basic_dimensions_insert_version AFTER INSERT ON trapezoidal_basic_dimensions
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_basic_dimensions_update_version AFTER UPDATE ON trapezoidal_basic_dimensions
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS trapezoidal_basic_dimensions_delete_version AFTER DELETE ON trapezoidal_basic_dimensions
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS pipe_main_insert_version AFTER INSERT ON pipe_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS pipe_main_update_version AFTER UPDATE ON pipe_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS pipe_main_delete_version AFTER DELETE ON pipe_main
+BEGIN
+    UPDATE reference_data_version SET version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
+END;
+
+CREATE TABLE IF NOT EXISTS analytics_thread_usage (
+    designation TEXT NOT NULL,
+    standard TEXT NOT NULL,
+    language TEXT NOT NULL,
+    day TEXT NOT NULL,
+    usage_count INTEGER NOT NULL DEFAULT 1,
+    last_used_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (designation, standard, language, day)
+);
+CREATE INDEX IF NOT EXISTS analytics_thread_usage_day ON analytics_thread_usage (day);
+
+CREATE TABLE IF NOT EXISTS analytics_error_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    json_data TEXT NOT NULL,
+    client_ip TEXT NOT NULL,
+    resolved_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS auth_api_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    daily_quota INTEGER,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    rotated_at TEXT,
+    revoked_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS auth_api_key_usage (
+    key_id INTEGER NOT NULL REFERENCES auth_api_keys (id) ON DELETE CASCADE,
+    day TEXT NOT NULL,
+    requests INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (key_id, day)
+);
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::shared::{database::service::PostgresService, error::AppError};
+
+/// Version of the reference data the server answers from.
+///
+/// Detected at startup and again every `http_cache.version_refresh_seconds`, so an
+/// import through `thread-api-admin` reaches clients without a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetVersion {
+    pub id: String,
+    pub modified_at: DateTime<Utc>,
+}
+
+impl DatasetVersion {
+    /// Version set explicitly in the configuration
+    pub fn fixed(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            modified_at: Utc::now(),
+        }
+    }
+
+    /// Derives the version from the backend: the `reference_data_version` row that
+    /// triggers on the reference tables bump for PostgreSQL and SQLite, and the digest
+    /// of the files the memory backend loaded at startup. Migrations of other schemas
+    /// (analytics, API keys) do not change it.
+    pub async fn detect(database: &PostgresService) -> Result<Self, AppError> {
+        if let Some(pool) = database.pool() {
+            let (version, updated_at): (i64, DateTime<Utc>) =
+                sqlx::query_as("SELECT version, updated_at FROM public.reference_data_version WHERE id = 1")
+                    .fetch_one(pool)
+                    .await?;
+            Ok(Self::marker("pg", version, updated_at))
+        } else if let Some(sqlite) = &database.sqlite {
+            let (version, updated_at): (i64, DateTime<Utc>) =
+                sqlx::query_as("SELECT version, updated_at FROM reference_data_version WHERE id = 1")
+                    .fetch_one(sqlite.pool())
+                    .await?;
+            Ok(Self::marker("sqlite", version, updated_at))
+        } else {
+            // Datasets built in code have no files to digest and never change
+            Ok(database
+                .bundled_version
+                .clone()
+                .unwrap_or_else(|| Self::fixed("memory")))
+        }
+    }
+
+    /// The time of the last change goes into the id too: a recreated database
+    /// starts counting again and must not repeat the ETags of the old one
+    fn marker(prefix: &str, version: i64, updated_at: DateTime<Utc>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(version.to_be_bytes());
+        hasher.update(updated_at.timestamp_micros().to_be_bytes());
+        Self {
+            id: format!("{}-{}", prefix, short_hex(hasher)),
+            modified_at: updated_at,
+        }
+    }
+
+    /// Digest of the bundled files with the time of the latest change among them
+    pub(crate) fn from_digest(hasher: Sha256, modified_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            id: format!("files-{}", short_hex(hasher)),
+            modified_at: modified_at.unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+fn short_hex(hasher: Sha256) -> String {
+    hasher.finalize()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
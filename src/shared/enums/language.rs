@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::{AsRefStr, Display, EnumString};
+use utoipa::ToSchema;
 
 /// Поддерживаемые языки интерфейса
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Display, EnumString, AsRefStr)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     /// Русский язык
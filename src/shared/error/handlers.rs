@@ -1,33 +1,57 @@
 use axum::{
+    http::{header::RETRY_AFTER, HeaderValue},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
 use serde_json::Value;
+use utoipa::ToSchema;
 
 use super::types::AppError;
+use crate::shared::enums::Language;
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    code: &'static str,
-    message: String,
+/// Тело ответа с ошибкой
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ErrorResponse {
+    /// Машинный код ошибки, например `THREAD_NOT_FOUND`
+    pub code: &'static str,
+    pub message: String,
     #[serde(skip_serializing_if = "is_empty_details")]
-    details: Value,
-    timestamp: String,
+    pub details: Value,
+    pub timestamp: String,
 }
 
 fn is_empty_details(details: &Value) -> bool {
     details.as_object().is_none_or(|obj| obj.is_empty())
 }
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let error_response = ErrorResponse {
+
+impl AppError {
+    /// Конверт `{code, message, details, timestamp}` с сообщением на указанном языке
+    pub fn envelope(&self, language: Language) -> ErrorResponse {
+        ErrorResponse {
             code: self.error_code(),
-            message: self.to_string(),
+            message: self.message(language),
             details: self.details(),
             timestamp: chrono::Utc::now().to_rfc3339(),
-        };
+        }
+    }
 
-        (self.status_code(), Json(error_response)).into_response()
+    /// Ответ с конвертом ошибки и статусом `status_code()`
+    pub fn localized_response(&self, language: Language) -> Response {
+        let mut response = (self.status_code(), Json(self.envelope(language))).into_response();
+        if let AppError::TooManyRequests { retry_after } = self {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(*retry_after));
+        }
+        // Ошибка остается в ответе, чтобы middleware могло перевести сообщение
+        response.extensions_mut().insert(self.clone());
+        response
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        self.localized_response(Language::En)
     }
 }
@@ -2,4 +2,5 @@ pub mod conversions;
 pub mod handlers;
 pub mod types;
 
+pub use handlers::ErrorResponse;
 pub use types::AppError;
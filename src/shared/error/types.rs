@@ -1,21 +1,48 @@
 // src/error/types.rs - Определение типов ошибок
 use axum::http::StatusCode;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::fmt;
 
+use crate::shared::enums::Language;
+
 #[derive(Debug, Clone)]
 pub enum AppError {
     // Критичные системные ошибки
-    ConfigurationError { message: String },
-    EnvironmentError { variable: String },
-    InternalError { message: String },
-    DatabaseConnectionError { message: String },
-    MigrationError { message: String },
+    ConfigurationError {
+        message: String,
+    },
+    EnvironmentError {
+        variable: String,
+    },
+    InternalError {
+        message: String,
+    },
+    DatabaseConnectionError {
+        message: String,
+    },
+    MigrationError {
+        message: String,
+    },
 
     // Прикладные ошибки
     BadRequest(String),
+    /// Нет или неверный токен доступа
+    Unauthorized(String),
+    /// Ключ не даёт доступа к маршруту
+    Forbidden(String),
+    /// Нет записи, кроме справочных таблиц, например API-ключа
+    NotFound(String),
+    /// Тело запроса больше допустимого
+    PayloadTooLarge(String),
+    /// Клиент превысил лимит запросов; повторить можно через `retry_after` секунд
+    TooManyRequests {
+        retry_after: u64,
+    },
     InvalidThreadParams(String),
-    ThreadDataNotFound { diameter: f64, pitch: f64 },
+    /// Нет строки в справочных таблицах; `params` — параметры поиска, например `{"diameter": 20, "pitch": 4}`
+    ThreadDataNotFound {
+        params: Value,
+    },
     SvgTemplateNotFound(String),
     InvalidSvgTemplate(String),
     DatabaseError(String),
@@ -25,39 +52,73 @@ pub enum AppError {
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::En))
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl AppError {
+    /// Резьба не найдена по указанным параметрам поиска
+    pub fn thread_not_found(params: Value) -> Self {
+        AppError::ThreadDataNotFound { params }
+    }
+
+    /// Сообщение для клиента на языке запроса
+    pub fn message(&self, language: Language) -> String {
+        let ru = language == Language::Ru;
         match self {
-            AppError::ConfigurationError { message } => {
-                write!(f, "Ошибка конфигурации: {}", message)
+            AppError::ConfigurationError { message } if ru => format!("Ошибка конфигурации: {}", message),
+            AppError::ConfigurationError { message } => format!("Configuration error: {}", message),
+            AppError::EnvironmentError { variable } if ru => {
+                format!("Переменная окружения '{}' не установлена", variable)
             }
-            AppError::EnvironmentError { variable } => {
-                write!(f, "Переменная окружения '{}' не установлена", variable)
+            AppError::EnvironmentError { variable } => format!("Environment variable '{}' is not set", variable),
+            AppError::InternalError { message } if ru => format!("Внутренняя ошибка: {}", message),
+            AppError::InternalError { message } => format!("Internal error: {}", message),
+            AppError::DatabaseConnectionError { message } if ru => {
+                format!("Ошибка подключения к базе данных: {}", message)
             }
-            AppError::InternalError { message } => {
-                write!(f, "Внутренняя ошибка: {}", message)
+            AppError::DatabaseConnectionError { message } => format!("Database connection error: {}", message),
+            AppError::MigrationError { message } if ru => format!("Ошибка миграции базы данных: {}", message),
+            AppError::MigrationError { message } => format!("Database migration error: {}", message),
+            AppError::BadRequest(msg) if ru => format!("Некорректный запрос: {}", msg),
+            AppError::BadRequest(msg) => format!("Bad request: {}", msg),
+            AppError::Unauthorized(msg) if ru => format!("Доступ запрещён: {}", msg),
+            AppError::Unauthorized(msg) => format!("Unauthorized: {}", msg),
+            AppError::Forbidden(msg) if ru => format!("Недостаточно прав: {}", msg),
+            AppError::Forbidden(msg) => format!("Forbidden: {}", msg),
+            AppError::NotFound(msg) if ru => format!("Не найдено: {}", msg),
+            AppError::NotFound(msg) => format!("Not found: {}", msg),
+            AppError::PayloadTooLarge(msg) if ru => format!("Слишком большой запрос: {}", msg),
+            AppError::PayloadTooLarge(msg) => format!("Payload too large: {}", msg),
+            AppError::TooManyRequests { retry_after } if ru => {
+                format!("Слишком много запросов, повторите через {} с", retry_after)
             }
-            AppError::DatabaseConnectionError { message } => {
-                write!(f, "Ошибка подключения к базе данных: {}", message)
+            AppError::TooManyRequests { retry_after } => {
+                format!("Too many requests, retry in {} s", retry_after)
             }
-            AppError::MigrationError { message } => {
-                write!(f, "Ошибка миграции базы данных: {}", message)
+            AppError::InvalidThreadParams(msg) if ru => format!("Некорректные параметры резьбы: {}", msg),
+            AppError::InvalidThreadParams(msg) => format!("Invalid thread parameters: {}", msg),
+            AppError::ThreadDataNotFound { params } if ru => {
+                format!("Резьба не найдена: {}", describe_params(params, true))
             }
-            AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
-            AppError::InvalidThreadParams(msg) => write!(f, "Invalid thread parameters: {}", msg),
-            AppError::ThreadDataNotFound { diameter, pitch } => {
-                write!(f, "Thread data not found for diameter {} and pitch {}", diameter, pitch)
+            AppError::ThreadDataNotFound { params } => {
+                format!("Thread data not found for {}", describe_params(params, false))
             }
-            AppError::SvgTemplateNotFound(path) => write!(f, "SVG template not found: {}", path),
-            AppError::InvalidSvgTemplate(msg) => write!(f, "Invalid SVG template format: {}", msg),
-            AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            AppError::FileSystemError(msg) => write!(f, "File system error: {}", msg),
-            AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            AppError::SvgTemplateNotFound(path) if ru => format!("SVG-шаблон не найден: {}", path),
+            AppError::SvgTemplateNotFound(path) => format!("SVG template not found: {}", path),
+            AppError::InvalidSvgTemplate(msg) if ru => format!("Некорректный SVG-шаблон: {}", msg),
+            AppError::InvalidSvgTemplate(msg) => format!("Invalid SVG template format: {}", msg),
+            AppError::DatabaseError(msg) if ru => format!("Ошибка базы данных: {}", msg),
+            AppError::DatabaseError(msg) => format!("Database error: {}", msg),
+            AppError::FileSystemError(msg) if ru => format!("Ошибка файловой системы: {}", msg),
+            AppError::FileSystemError(msg) => format!("File system error: {}", msg),
+            AppError::ValidationError(msg) if ru => format!("Ошибка проверки: {}", msg),
+            AppError::ValidationError(msg) => format!("Validation error: {}", msg),
         }
     }
-}
-
-impl std::error::Error for AppError {}
 
-impl AppError {
     pub fn error_code(&self) -> &'static str {
         match self {
             AppError::ConfigurationError { .. }
@@ -66,6 +127,11 @@ impl AppError {
             | AppError::DatabaseConnectionError { .. }
             | AppError::MigrationError { .. } => "SYSTEM_ERROR",
             AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            AppError::TooManyRequests { .. } => "TOO_MANY_REQUESTS",
             AppError::InvalidThreadParams(_) => "INVALID_THREAD_PARAMS",
             AppError::ThreadDataNotFound { .. } => "THREAD_NOT_FOUND",
             AppError::SvgTemplateNotFound(_) => "SVG_TEMPLATE_NOT_FOUND",
@@ -89,7 +155,13 @@ impl AppError {
             AppError::BadRequest(_) | AppError::InvalidThreadParams(_) | AppError::ValidationError(_) => {
                 StatusCode::BAD_REQUEST
             }
-            AppError::ThreadDataNotFound { .. } | AppError::SvgTemplateNotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AppError::ThreadDataNotFound { .. } | AppError::SvgTemplateNotFound(_) | AppError::NotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
         }
     }
 
@@ -101,9 +173,9 @@ impl AppError {
             AppError::DatabaseConnectionError { message } => json!({
                 "message": message
             }),
-            AppError::ThreadDataNotFound { diameter, pitch } => json!({
-                "diameter": diameter,
-                "pitch": pitch
+            AppError::ThreadDataNotFound { params } => params.clone(),
+            AppError::TooManyRequests { retry_after } => json!({
+                "retry_after": retry_after
             }),
             AppError::BadRequest(msg)
             | AppError::InvalidThreadParams(msg)
@@ -118,3 +190,27 @@ impl AppError {
         }
     }
 }
+
+/// "diameter 20 and pitch 4" из `{"diameter": 20, "pitch": 4}`
+fn describe_params(params: &Value, ru: bool) -> String {
+    let parts: Vec<String> = params
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| match value {
+            Value::String(text) => format!("{} {}", name, text),
+            other => format!("{} {}", name, other),
+        })
+        .collect();
+
+    let (and, nothing) = if ru {
+        (" и ", "указанные параметры")
+    } else {
+        (" and ", "the given parameters")
+    };
+    match parts.split_last() {
+        None => nothing.to_string(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{}{}{}", rest.join(", "), and, last),
+    }
+}
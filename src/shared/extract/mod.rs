@@ -0,0 +1,20 @@
+//! Extractors that reject with the `AppError` envelope instead of axum's plain text
+
+use axum::extract::{FromRequest, FromRequestParts};
+
+use crate::shared::error::AppError;
+
+/// `axum::extract::Query` answering malformed query strings with `BAD_REQUEST`
+#[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(AppError))]
+pub struct Query<T>(pub T);
+
+/// `axum::extract::Path` answering malformed path segments with `BAD_REQUEST`
+#[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(AppError))]
+pub struct Path<T>(pub T);
+
+/// `axum::Json` answering malformed bodies with `BAD_REQUEST`
+#[derive(Debug, Clone, Copy, Default, FromRequest)]
+#[from_request(via(axum::Json), rejection(AppError))]
+pub struct Json<T>(pub T);
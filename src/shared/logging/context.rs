@@ -0,0 +1,19 @@
+//! Контекст запроса для строк лога
+
+use std::future::Future;
+
+tokio::task_local! {
+    /// `X-Request-Id` запроса, который обрабатывает текущая задача
+    static REQUEST_ID: String;
+}
+
+/// Выполняет `future` так, что все строки лога внутри получают `request_id`:
+/// middleware, обработчик и запросы к базе работают в одной задаче
+pub async fn with_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// `X-Request-Id` текущего запроса; `None` вне запроса, например в фоновых задачах
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
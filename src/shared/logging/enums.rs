@@ -35,3 +35,22 @@ impl From<&str> for LogLevel {
         }
     }
 }
+
+/// Формат строк лога
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Цветные строки для терминала
+    #[default]
+    Text,
+    /// Один JSON-объект на строку для сборщика логов
+    Json,
+}
+
+impl From<&str> for LogFormat {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Text,
+        }
+    }
+}
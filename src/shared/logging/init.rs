@@ -1,5 +1,7 @@
-use crate::logging::enums::LogLevel;
+use crate::logging::context::current_request_id;
+use crate::logging::enums::{LogFormat, LogLevel};
 use crate::logging::structs::LogConfig;
+use chrono::{SecondsFormat, Utc};
 use std::io::{self, Write};
 use std::sync::{Mutex, OnceLock};
 
@@ -23,24 +25,51 @@ impl AppLogger {
             return;
         }
 
-        // ANSI коды цветов
-        let (color_code, reset_code) = match level {
-            LogLevel::Error => ("\x1b[31m", "\x1b[0m"), // Красный
-            LogLevel::Warn => ("\x1b[33m", "\x1b[0m"),  // Желтый
-            LogLevel::Info => ("\x1b[32m", "\x1b[0m"),  // Зеленый
-            LogLevel::Debug => ("\x1b[36m", "\x1b[0m"), // Голубой
+        let request_id = current_request_id();
+        let line = match config.format {
+            LogFormat::Text => Self::text_line(level, message, request_id.as_deref()),
+            LogFormat::Json => Self::json_line(level, message, request_id.as_deref()),
         };
 
         // Выводим в stderr для ошибок, в stdout для остального
         match level {
             LogLevel::Error => {
-                let _ = writeln!(io::stderr(), "{}[{}]{} {}", color_code, level, reset_code, message);
+                let _ = writeln!(io::stderr(), "{}", line);
             }
             _ => {
-                let _ = writeln!(io::stdout(), "{}[{}]{} {}", color_code, level, reset_code, message);
+                let _ = writeln!(io::stdout(), "{}", line);
             }
         }
     }
+
+    /// Цветная строка: `[INFO] [<request id>] message`
+    fn text_line(level: LogLevel, message: &str, request_id: Option<&str>) -> String {
+        // ANSI коды цветов
+        let (color_code, reset_code) = match level {
+            LogLevel::Error => ("\x1b[31m", "\x1b[0m"), // Красный
+            LogLevel::Warn => ("\x1b[33m", "\x1b[0m"),  // Желтый
+            LogLevel::Info => ("\x1b[32m", "\x1b[0m"),  // Зеленый
+            LogLevel::Debug => ("\x1b[36m", "\x1b[0m"), // Голубой
+        };
+
+        match request_id {
+            Some(id) => format!("{}[{}]{} [{}] {}", color_code, level, reset_code, id, message),
+            None => format!("{}[{}]{} {}", color_code, level, reset_code, message),
+        }
+    }
+
+    /// Одна строка JSON: `timestamp`, `level`, `message` и `request_id` внутри запроса
+    pub fn json_line(level: LogLevel, message: &str, request_id: Option<&str>) -> String {
+        let mut line = serde_json::json!({
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            "level": level.to_string(),
+            "message": message,
+        });
+        if let Some(id) = request_id {
+            line["request_id"] = id.into();
+        }
+        line.to_string()
+    }
 }
 
 /// Инициализация логирования
@@ -1,3 +1,4 @@
+pub mod context;
 pub mod enums;
 pub mod init;
 pub mod macros;
@@ -1,10 +1,21 @@
 //! Структуры для модуля логирования
 
-use crate::logging::enums::LogLevel;
+use crate::logging::enums::{LogFormat, LogLevel};
 
 /// Простая конфигурация логирования
 #[derive(Debug, Clone)]
 pub struct LogConfig {
     /// Уровень логирования
     pub level: LogLevel,
+    /// Формат вывода: текст или JSON
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            format: LogFormat::Text,
+        }
+    }
 }
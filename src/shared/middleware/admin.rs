@@ -0,0 +1,62 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, Response},
+    middleware::Next,
+    response::IntoResponse,
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use super::ApiClient;
+use crate::log_warn;
+use crate::shared::database::repositories::ApiScope;
+use crate::shared::error::AppError;
+
+/// Token of the admin routes, from the `ADMIN_TOKEN` environment variable
+#[derive(Clone)]
+pub struct AdminToken(Option<Arc<str>>);
+
+impl AdminToken {
+    /// An empty token closes the admin routes
+    pub fn new(token: &str) -> Self {
+        let token = token.trim();
+        if token.is_empty() {
+            log_warn!("ADMIN_TOKEN is not set, admin routes will answer 401");
+            return Self(None);
+        }
+        Self(Some(Arc::from(token)))
+    }
+
+    /// Compares digests, so the time taken does not depend on the matching prefix
+    fn accepts(&self, presented: &str) -> bool {
+        self.0
+            .as_deref()
+            .is_some_and(|token| Sha256::digest(token.as_bytes()) == Sha256::digest(presented.as_bytes()))
+    }
+}
+
+/// Lets the request through only with `Authorization: Bearer <ADMIN_TOKEN>`
+/// or an API key with the `admin` scope, admitted by `authenticate`
+pub async fn require_admin(State(token): State<AdminToken>, request: Request, next: Next) -> Response<Body> {
+    let admin_key = request
+        .extensions()
+        .get::<ApiClient>()
+        .is_some_and(|client| client.key.allows(ApiScope::Admin));
+    if admin_key {
+        return next.run(request).await;
+    }
+
+    let presented = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match presented {
+        Some(presented) if token.accepts(presented) => next.run(request).await,
+        Some(_) => AppError::Unauthorized("invalid admin token".to_string()).into_response(),
+        None => AppError::Unauthorized("admin token required".to_string()).into_response(),
+    }
+}
@@ -0,0 +1,196 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{Response, Uri},
+    middleware::Next,
+    response::IntoResponse,
+};
+use chrono::{Days, Utc};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::log_warn;
+use crate::shared::database::repositories::{ApiKey, ApiScope, TraitApiKeyRepository};
+use crate::shared::error::AppError;
+
+/// Header with the key; `?api_key=` is accepted where headers cannot be set
+pub const API_KEY_HEADER: &str = "x-api-key";
+const API_KEY_PARAM: &str = "api_key";
+const KEY_PREFIX: &str = "tk_";
+/// Characters of the secret kept in listings
+const SHOWN_CHARS: usize = 10;
+
+/// New secret: `tk_` and 32 random bytes in hex
+pub fn generate_api_key() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}", KEY_PREFIX, hex)
+}
+
+/// What is stored instead of the secret
+pub fn hash_api_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Start of the secret shown in listings, e.g. "tk_3f9a1c0"
+pub fn api_key_prefix(key: &str) -> String {
+    key.chars().take(SHOWN_CHARS).collect()
+}
+
+/// Scope a route needs; `None` for routes open to everyone
+pub fn required_scope(path: &str) -> Option<ApiScope> {
+    if path.starts_with("/admin") || path.starts_with("/v1/analytics") {
+        return Some(ApiScope::Admin);
+    }
+    if !(path.starts_with("/v1/") || path.starts_with("/v2/")) || path.starts_with("/v1/error_reports") {
+        return None;
+    }
+    let last = path.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+    if last.starts_with("svg") || last == "dxf" || last == "stl" {
+        Some(ApiScope::Svg)
+    } else {
+        Some(ApiScope::Read)
+    }
+}
+
+/// Key the request was admitted with, in the request extensions
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    pub key: ApiKey,
+    /// Requests of the key today, this one included; 0 until `charge_quota` counted it
+    pub requests_today: i64,
+}
+
+/// State of `authenticate` and `charge_quota`
+#[derive(Clone)]
+pub struct ApiKeyAuth {
+    repository: Arc<dyn TraitApiKeyRepository + Send + Sync>,
+    /// Without a key only the open routes answer
+    required: bool,
+}
+
+impl ApiKeyAuth {
+    pub fn new(repository: Arc<dyn TraitApiKeyRepository + Send + Sync>, required: bool) -> Self {
+        Self { repository, required }
+    }
+
+    /// Checks the key and its scope; the quota is charged later by `charge_quota`
+    async fn admit(&self, presented: &str, scope: ApiScope) -> Result<ApiClient, AppError> {
+        let key = self
+            .repository
+            .active_api_key(&hash_api_key(presented))
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("invalid or revoked API key".to_string()))?;
+        if !key.allows(scope) {
+            return Err(AppError::Forbidden(format!("API key has no '{}' scope", scope)));
+        }
+        Ok(ApiClient { key, requests_today: 0 })
+    }
+}
+
+/// Counts `requests` requests of the key, e.g. the rows of a batch, and checks
+/// the day's total against its quota; returns the total
+pub async fn charge_api_key(
+    repository: &(dyn TraitApiKeyRepository + Send + Sync),
+    key: &ApiKey,
+    requests: i64,
+) -> Result<i64, AppError> {
+    let today = Utc::now().date_naive();
+    let requests_today = repository.count_api_key_requests(key.id, today, requests).await?;
+    if let Some(quota) = key.daily_quota.filter(|quota| requests_today > *quota) {
+        if requests_today - requests <= quota {
+            log_warn!("API key {} ({}) reached its daily quota of {}", key.id, key.name, quota);
+        }
+        return Err(AppError::TooManyRequests {
+            retry_after: seconds_until_tomorrow(),
+        });
+    }
+    Ok(requests_today)
+}
+
+/// The quota is reset at UTC midnight
+fn seconds_until_tomorrow() -> u64 {
+    let now = Utc::now();
+    let tomorrow = now
+        .date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc());
+    tomorrow.map_or(0, |midnight| (midnight - now).num_seconds().max(1) as u64)
+}
+
+/// Path and query for logs, with the value of `?api_key=` masked
+pub fn redact_api_key(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.path().to_string();
+    };
+    let query: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((API_KEY_PARAM, _)) => format!("{}=[redacted]", API_KEY_PARAM),
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", uri.path(), query.join("&"))
+}
+
+fn presented_key(request: &Request) -> Option<String> {
+    let from_header = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string());
+    from_header.filter(|key| !key.is_empty()).or_else(|| {
+        request.uri().query().and_then(|query| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(name, _)| *name == API_KEY_PARAM)
+                .map(|(_, value)| value.to_string())
+                .filter(|key| !key.is_empty())
+        })
+    })
+}
+
+/// Admits requests by API key and puts `ApiClient` into the extensions.
+/// Admin routes without a key are left to `require_admin` and the admin token.
+/// Nothing is counted here: `rate_limit` and then `charge_quota` run inside it
+pub async fn authenticate(State(auth): State<ApiKeyAuth>, mut request: Request, next: Next) -> Response<Body> {
+    let Some(scope) = required_scope(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let Some(presented) = presented_key(&request) else {
+        if auth.required && scope != ApiScope::Admin {
+            return AppError::Unauthorized("API key required".to_string()).into_response();
+        }
+        return next.run(request).await;
+    };
+
+    match auth.admit(&presented, scope).await {
+        Ok(client) => {
+            request.extensions_mut().insert(client);
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Counts the request against the daily quota of its key. Runs inside `rate_limit`,
+/// so a request rejected there with 429 does not use up the quota
+pub async fn charge_quota(State(auth): State<ApiKeyAuth>, mut request: Request, next: Next) -> Response<Body> {
+    let Some(client) = request.extensions_mut().get_mut::<ApiClient>() else {
+        return next.run(request).await;
+    };
+    match charge_api_key(auth.repository.as_ref(), &client.key, 1).await {
+        Ok(requests_today) => {
+            client.requests_today = requests_today;
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,170 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{
+        header::{CACHE_CONTROL, VARY},
+        HeaderMap, HeaderValue, Method, Response, StatusCode,
+    },
+    middleware::Next,
+    response::IntoResponse,
+};
+use headers::{ETag, HeaderMapExt, IfModifiedSince, IfNoneMatch, LastModified};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::log_warn;
+use crate::shared::database::version::DatasetVersion;
+use crate::shared::setting::models::app_config::HttpCacheConfig;
+
+/// Validators and caching policy shared by every reference data route
+pub struct ConditionalGet {
+    enabled: bool,
+    version: RwLock<DatasetVersion>,
+    cache_control: HeaderValue,
+}
+
+impl ConditionalGet {
+    /// With `require_api_key` the responses are never `public`: a shared cache
+    /// would hand them to callers without a key, past authentication and quotas
+    pub fn new(config: &HttpCacheConfig, require_api_key: bool, version: DatasetVersion) -> Self {
+        let directives = if require_api_key {
+            private_cache_control(&config.cache_control)
+        } else {
+            config.cache_control.clone()
+        };
+        let cache_control = HeaderValue::from_str(&directives).unwrap_or_else(|_| {
+            log_warn!(
+                "Invalid http_cache.cache_control '{}', using no-cache",
+                config.cache_control
+            );
+            HeaderValue::from_static("no-cache")
+        });
+        Self {
+            enabled: config.enabled,
+            version: RwLock::new(version),
+            cache_control,
+        }
+    }
+
+    pub fn version(&self) -> DatasetVersion {
+        self.version.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replaces the version unless its id is unchanged; returns whether it was replaced
+    pub fn set_version(&self, version: DatasetVersion) -> bool {
+        let mut current = self.version.write().unwrap_or_else(|e| e.into_inner());
+        if current.id == version.id {
+            return false;
+        }
+        *current = version;
+        true
+    }
+
+    /// Strong ETag of a GET request: the application and dataset versions plus
+    /// the path and the query parameters in sorted order
+    pub fn etag(&self, path: &str, query: Option<&str>) -> ETag {
+        let mut params: Vec<&str> = query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .collect();
+        params.sort_unstable();
+
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update([0]);
+        hasher.update(self.version().id.as_bytes());
+        hasher.update([0]);
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(params.join("&").as_bytes());
+        let digest: String = hasher.finalize()[..16].iter().map(|b| format!("{:02x}", b)).collect();
+
+        format!("\"{}\"", digest).parse().expect("hex digest is a valid ETag")
+    }
+
+    fn last_modified(&self) -> SystemTime {
+        self.version().modified_at.into()
+    }
+
+    fn insert_validators(&self, headers: &mut HeaderMap, etag: ETag) {
+        headers.typed_insert(etag);
+        headers.typed_insert(LastModified::from(self.last_modified()));
+        if !headers.contains_key(CACHE_CONTROL) {
+            headers.insert(CACHE_CONTROL, self.cache_control.clone());
+        }
+        headers.append(VARY, HeaderValue::from_static("X-API-Key"));
+    }
+
+    /// `If-None-Match` takes precedence over `If-Modified-Since` (RFC 9110, 13.2.2)
+    fn is_fresh(&self, headers: &HeaderMap, etag: &ETag) -> bool {
+        if let Some(if_none_match) = headers.typed_get::<IfNoneMatch>() {
+            return !if_none_match.precondition_passes(etag);
+        }
+        headers
+            .typed_get::<IfModifiedSince>()
+            .is_some_and(|since| !since.is_modified(self.last_modified()))
+    }
+}
+
+/// Replaces `public` with `private` and adds `private` to a storable policy
+/// that names neither, e.g. "max-age=60" becomes "private, max-age=60"
+fn private_cache_control(cache_control: &str) -> String {
+    let mut directives: Vec<&str> = cache_control
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| {
+            if directive.eq_ignore_ascii_case("public") {
+                "private"
+            } else {
+                directive
+            }
+        })
+        .collect();
+    let restricted = directives
+        .iter()
+        .any(|directive| directive.eq_ignore_ascii_case("private") || directive.eq_ignore_ascii_case("no-store"));
+    if !restricted {
+        directives.insert(0, "private");
+    }
+    directives.join(", ")
+}
+
+/// Adds ETag, Last-Modified, Cache-Control and `Vary: X-API-Key` to successful GET responses and
+/// answers 304 Not Modified without running the handler when the client copy is current
+pub async fn conditional_get(
+    State(policy): State<Arc<ConditionalGet>>,
+    request: Request,
+    next: Next,
+) -> Response<Body> {
+    if !policy.enabled || request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let etag = policy.etag(request.uri().path(), request.uri().query());
+    if policy.is_fresh(request.headers(), &etag) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        policy.insert_validators(response.headers_mut(), etag);
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::OK {
+        policy.insert_validators(response.headers_mut(), etag);
+    }
+    response
+}
+
+/// Forbids caching of responses without their own policy:
+/// service routes such as `/health` and errors of the reference routes
+pub async fn no_store(request: Request, next: Next) -> Response<Body> {
+    let mut response = next.run(request).await;
+    if !response.headers().contains_key(CACHE_CONTROL) {
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    }
+    response
+}
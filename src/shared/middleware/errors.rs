@@ -0,0 +1,49 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::CONTENT_LENGTH, Response},
+    middleware::Next,
+};
+
+use crate::shared::enums::Language;
+use crate::shared::error::AppError;
+use crate::shared::utils::language::LanguageUtils;
+
+/// Renders `AppError` responses in the language of the request: the `language`
+/// query parameter, then `Accept-Language`, English by default
+pub async fn localize_errors(request: Request, next: Next) -> Response<Body> {
+    let language = request_language(&request);
+    let response = next.run(request).await;
+    if language == Language::En {
+        return response;
+    }
+    let Some(error) = response.extensions().get::<AppError>().cloned() else {
+        return response;
+    };
+
+    // Status and headers (CORS, Cache-Control) stay, only the message changes
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    let (_, body) = error.localized_response(language).into_parts();
+    Response::from_parts(parts, body)
+}
+
+fn request_language(request: &Request) -> Language {
+    let from_query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == "language")
+            .map(|(_, value)| value.to_string())
+    });
+
+    let language = match from_query {
+        Some(code) => code.as_str().into(),
+        None => LanguageUtils::extract_from_headers(request.headers()),
+    };
+    if language.is_russian() {
+        Language::Ru
+    } else {
+        Language::En
+    }
+}
@@ -0,0 +1,82 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{MatchedPath, Query, Request},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        HeaderValue, Response,
+    },
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use std::time::Instant;
+
+use crate::log_error;
+use crate::services::metrics::Metrics;
+use crate::services::svg::export::{ExportFormat, ExportParams, SvgExporter};
+use crate::shared::error::AppError;
+
+/// Converts SVG responses to PNG or PDF according to the `format` query parameter
+pub async fn svg_export(request: Request, next: Next) -> Response<Body> {
+    let params = match Query::<ExportParams>::try_from_uri(request.uri()) {
+        Ok(Query(params)) => params,
+        Err(rejection) => return AppError::ValidationError(rejection.body_text()).into_response(),
+    };
+
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let is_svg = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("image/svg+xml"));
+    if !response.status().is_success() || !is_svg {
+        return response;
+    }
+    Metrics::global().observe_render(&route, ExportFormat::Svg.extension(), started.elapsed());
+    if params.format == ExportFormat::Svg {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let svg = match to_bytes(body, usize::MAX)
+        .await
+        .map(|bytes| String::from_utf8(bytes.to_vec()))
+    {
+        Ok(Ok(svg)) => svg,
+        Ok(Err(e)) => return AppError::InvalidSvgTemplate(e.to_string()).into_response(),
+        Err(e) => return AppError::InternalError { message: e.to_string() }.into_response(),
+    };
+
+    // Rendering is CPU-bound, keep it off the async workers
+    let format = params.format;
+    let started = Instant::now();
+    let converted = tokio::task::spawn_blocking(move || SvgExporter::global().export(&svg, &params)).await;
+    let content = match converted {
+        Ok(Ok(content)) => {
+            Metrics::global().observe_render(&route, format.extension(), started.elapsed());
+            content
+        }
+        Ok(Err(e)) => return e.into_response(),
+        Err(e) => {
+            log_error!("SVG export task failed: {}", e);
+            return AppError::InternalError {
+                message: "SVG export failed".to_string(),
+            }
+            .into_response();
+        }
+    };
+
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+    if let Ok(disposition) = HeaderValue::from_str(&format!("inline; filename=\"thread.{}\"", format.extension())) {
+        parts.headers.insert(CONTENT_DISPOSITION, disposition);
+    }
+    Response::from_parts(parts, Body::from(content))
+}
@@ -1,59 +1,7 @@
-use crate::shared::utils::http;
-use crate::{log_debug, log_info};
-use axum::http::Request;
+use axum::http::HeaderName;
 use tower_http::cors::{Any, CorsLayer};
 
-/// Создаёт и настраивает middleware для логирования HTTP-запросов с использованием собственной системы логирования
-pub fn create_trace<B>() -> tower::util::MapRequestLayer<impl Fn(Request<B>) -> Request<B> + Clone> {
-    tower::util::MapRequestLayer::new(|request: Request<B>| {
-        // Пропускаем логирование для запроса GET /test
-        if request.uri().path() == "/test" {
-            return request;
-        }
-
-        // Извлечение данных о запросе
-        let method = request.method().clone();
-        let uri = request.uri().clone();
-        let version = request.version();
-        let client_ip = http::get_client_ip(&request);
-
-        // Извлечение заголовков
-        let user_agent = request
-            .headers()
-            .get("user-agent")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("unknown");
-
-        let referer = request
-            .headers()
-            .get("referer")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("unknown");
-
-        let accept_language = request
-            .headers()
-            .get("accept-language")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("unknown");
-
-        // Сокращенное логирование на уровне INFO
-        log_info!("{} {} - {}", method, uri, client_ip);
-
-        // Подробное логирование на уровне DEBUG
-        log_debug!(
-            "Request details: {} {} {:?} - IP: {}, User-Agent: {}, Referer: {}, Accept-Language: {}",
-            method,
-            uri,
-            version,
-            client_ip,
-            user_agent,
-            referer,
-            accept_language
-        );
-
-        request
-    })
-}
+use super::REQUEST_ID_HEADER;
 
 pub fn create_cors() -> CorsLayer {
     // Настройка CORS
@@ -61,4 +9,5 @@ pub fn create_cors() -> CorsLayer {
         .allow_origin(Any) // Разрешить любые источники.
         .allow_methods(Any) // Разрешить любые HTTP-методы
         .allow_headers(Any) // Разрешить любые заголовки
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)]) // Идентификатор запроса для обращений в поддержку
 }
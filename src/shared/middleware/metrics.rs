@@ -0,0 +1,32 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::Response,
+    middleware::Next,
+};
+use std::time::Instant;
+
+use crate::services::metrics::Metrics;
+use crate::shared::error::AppError;
+
+/// Counts requests and their latency per route template and status, and
+/// error responses per `AppError::error_code`
+pub async fn track_metrics(request: Request, next: Next) -> Response<Body> {
+    // Route templates keep the label set small: "/admin/api-keys/{id}", not every id
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str)
+        .to_string();
+    let method = request.method().to_string();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    let metrics = Metrics::global();
+    metrics.observe_request(&method, &route, response.status().as_u16(), started.elapsed());
+    if let Some(error) = response.extensions().get::<AppError>() {
+        metrics.count_error(error.error_code());
+    }
+    response
+}
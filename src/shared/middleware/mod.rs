@@ -1,4 +1,22 @@
+mod admin;
+mod api_key;
+mod conditional;
+mod errors;
+mod export;
 mod layer;
+mod metrics;
+mod rate_limit;
+mod trace;
 
+pub use admin::{require_admin, AdminToken};
+pub use api_key::{
+    api_key_prefix, authenticate, charge_api_key, charge_quota, generate_api_key, hash_api_key, redact_api_key,
+    required_scope, ApiClient, ApiKeyAuth, API_KEY_HEADER,
+};
+pub use conditional::{conditional_get, no_store, ConditionalGet};
+pub use errors::localize_errors;
+pub use export::svg_export;
 pub use layer::create_cors;
-pub use layer::create_trace;
+pub use metrics::track_metrics;
+pub use rate_limit::{client_ip, rate_limit, resolve_client_ip, ClientIp, RateLimitBucket};
+pub use trace::{request_id, trace_requests, REQUEST_ID_HEADER};
@@ -0,0 +1,108 @@
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request, State},
+    http::Response,
+    middleware::Next,
+    response::IntoResponse,
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::ApiClient;
+use crate::log_warn;
+use crate::services::rate_limit::{RateLimitPolicy, RateLimits, TrustedProxies};
+use crate::shared::error::AppError;
+
+/// Address of the client resolved by `resolve_client_ip`, in the request extensions
+#[derive(Debug, Clone)]
+pub struct ClientIp(pub String);
+
+/// Resolves the client address once, before the logs, the limits and the handlers read it.
+/// `X-Forwarded-For` counts only when the peer is one of `rate_limit.trusted_proxies`
+pub async fn resolve_client_ip(
+    State(proxies): State<Arc<TrustedProxies>>,
+    mut request: Request,
+    next: Next,
+) -> Response<Body> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(peer)| *peer);
+    let client_ip = proxies.client_ip(request.headers(), peer);
+    request.extensions_mut().insert(ClientIp(client_ip));
+    next.run(request).await
+}
+
+/// Address resolved by `resolve_client_ip`; without it the peer address, never the headers
+pub fn client_ip(request: &Request) -> String {
+    if let Some(ClientIp(client_ip)) = request.extensions().get::<ClientIp>() {
+        return client_ip.clone();
+    }
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or_else(|| "unknown".to_string(), |ConnectInfo(peer)| peer.ip().to_string())
+}
+
+/// Bucket the request was admitted from, in the request extensions, for handlers
+/// whose request stands for several lookups such as the rows of a batch
+#[derive(Clone)]
+pub struct RateLimitBucket {
+    limits: Arc<RateLimits>,
+    path: String,
+    api_key_id: Option<i64>,
+    client_ip: String,
+}
+
+impl RateLimitBucket {
+    /// Takes `tokens` more tokens, answering 429 like the middleware when they run out
+    pub fn charge(&self, tokens: u32) -> Result<(), AppError> {
+        let Some(policy) = self.limits.policy_for(&self.path) else {
+            return Ok(());
+        };
+        policy
+            .take(self.api_key_id, &self.client_ip, tokens)
+            .map_err(|wait| rejected(policy, self.api_key_id, &self.client_ip, &self.path, wait))
+    }
+}
+
+fn rejected(
+    policy: &RateLimitPolicy,
+    api_key_id: Option<i64>,
+    client_ip: &str,
+    path: &str,
+    wait: Duration,
+) -> AppError {
+    log_warn!(
+        "Rate limit '{}' exceeded by {} on {}",
+        policy.name(),
+        api_key_id.map_or_else(|| client_ip.to_string(), |id| format!("API key {}", id)),
+        path
+    );
+    AppError::TooManyRequests {
+        retry_after: wait.as_secs_f64().ceil().max(1.0) as u64,
+    }
+}
+
+/// Answers 429 with `Retry-After` once the client runs out of tokens of the route's policy.
+/// Runs after `authenticate`: requests with an API key are counted per key, the rest per IP
+pub async fn rate_limit(State(limits): State<Arc<RateLimits>>, mut request: Request, next: Next) -> Response<Body> {
+    let path = request.uri().path().to_string();
+    let Some(policy) = limits.policy_for(&path) else {
+        return next.run(request).await;
+    };
+
+    let api_key_id = request.extensions().get::<ApiClient>().map(|client| client.key.id);
+    let client_ip = client_ip(&request);
+    if let Err(wait) = policy.check(api_key_id, &client_ip) {
+        return rejected(policy, api_key_id, &client_ip, &path, wait).into_response();
+    }
+    request.extensions_mut().insert(RateLimitBucket {
+        limits: limits.clone(),
+        path,
+        api_key_id,
+        client_ip,
+    });
+    next.run(request).await
+}
@@ -0,0 +1,133 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{HeaderValue, Response},
+    middleware::Next,
+};
+use std::time::Instant;
+use tracing::{field, Instrument};
+
+use super::api_key::redact_api_key;
+use super::rate_limit::client_ip;
+use crate::logging::context::with_request_id;
+use crate::services::telemetry;
+use crate::{log_debug, log_info, log_warn};
+
+/// Header with the request ID, taken from the client or a proxy and returned in the response
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// ID from the request if it is fit for the logs, a new one otherwise
+pub fn request_id<B>(request: &axum::http::Request<B>) -> String {
+    request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        })
+        .map_or_else(|| format!("{:032x}", rand::random::<u128>()), str::to_string)
+}
+
+/// Logs the request and its completion with status and duration. Every log line of
+/// the handler, database queries included, gets the `X-Request-Id`, and with tracing
+/// enabled the request is the root span of the SQL and SVG spans
+pub async fn trace_requests(request: Request, next: Next) -> Response<Body> {
+    let id = request_id(&request);
+    let header = HeaderValue::from_str(&id).ok();
+    let span = server_span(&request, &id);
+
+    let mut response = with_request_id(id, log_request(request, next).instrument(span.clone())).await;
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        telemetry::set_error(&span, status);
+    }
+    if let Some(header) = header {
+        response.headers_mut().insert(REQUEST_ID_HEADER, header);
+    }
+    response
+}
+
+/// The request span continues the trace of `traceparent` when the client sent one
+fn server_span(request: &Request, request_id: &str) -> tracing::Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path(), MatchedPath::as_str);
+    let name = format!("{} {}", request.method(), route);
+
+    let span = tracing::info_span!(
+        "request",
+        otel.name = name.as_str(),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        otel.status_description = field::Empty,
+        http.request.method = request.method().as_str(),
+        http.route = route,
+        url.path = request.uri().path(),
+        client.address = client_ip(request).as_str(),
+        http.request.id = request_id,
+        http.response.status_code = field::Empty,
+    );
+    telemetry::continue_trace(&span, request.headers());
+    span
+}
+
+async fn log_request(request: Request, next: Next) -> Response<Body> {
+    // Skip logging for GET /test
+    if request.uri().path() == "/test" {
+        return next.run(request).await;
+    }
+
+    // Request data; the key of `?api_key=` stays out of the log
+    let method = request.method().clone();
+    let uri = redact_api_key(request.uri());
+    let version = request.version();
+    let client_ip = client_ip(&request);
+
+    // Headers; the closure must not live across an `await`
+    let (user_agent, referer, accept_language) = {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("unknown")
+                .to_string()
+        };
+        (header("user-agent"), header("referer"), header("accept-language"))
+    };
+
+    // Short log line at INFO
+    log_info!("{} {} - {}", method, uri, client_ip);
+
+    // Detailed log line at DEBUG
+    log_debug!(
+        "Request details: {} {} {:?} - IP: {}, User-Agent: {}, Referer: {}, Accept-Language: {}",
+        method,
+        uri,
+        version,
+        client_ip,
+        user_agent,
+        referer,
+        accept_language
+    );
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let status = response.status();
+    if status.is_server_error() {
+        log_warn!("{} {} - {} in {:.1} ms", method, uri, status.as_u16(), elapsed_ms);
+    } else {
+        log_info!("{} {} - {} in {:.1} ms", method, uri, status.as_u16(), elapsed_ms);
+    }
+    response
+}
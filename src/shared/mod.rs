@@ -1,6 +1,7 @@
 pub mod database;
 pub mod enums;
 pub mod error;
+pub mod extract;
 pub mod logging;
 pub mod middleware;
 pub mod setting;
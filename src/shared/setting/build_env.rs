@@ -8,10 +8,14 @@ impl AppEnv {
         let server_port = get_env_var("SERVER_PORT").parse().expect("PORT must be a number");
         let server_address = get_env_var("SERVER_ADDRESS");
 
-        let postgres_user = get_env_var("POSTGRES_USER");
-        let postgres_password = get_env_var("POSTGRES_PASSWORD");
-        let postgres_host = get_env_var("POSTGRES_HOST");
-        let postgres_database = get_env_var("POSTGRES_DATABASE");
+        // Not needed with `database.backend = "memory"`, checked when connecting to PostgreSQL
+        let postgres_user = get_optional_env_var("POSTGRES_USER");
+        let postgres_password = get_optional_env_var("POSTGRES_PASSWORD");
+        let postgres_host = get_optional_env_var("POSTGRES_HOST");
+        let postgres_database = get_optional_env_var("POSTGRES_DATABASE");
+
+        // Without a token the admin routes answer 401
+        let admin_token = get_optional_env_var("ADMIN_TOKEN");
 
         AppEnv {
             env,
@@ -21,6 +25,7 @@ impl AppEnv {
             postgres_user,
             postgres_password,
             postgres_database,
+            admin_token,
         }
     }
 }
@@ -34,3 +39,7 @@ impl Default for AppEnv {
 fn get_env_var(name: &str) -> String {
     env::var(name).unwrap_or_else(|_| panic!("ENV -> {} is not set", name))
 }
+
+fn get_optional_env_var(name: &str) -> String {
+    env::var(name).unwrap_or_default()
+}
@@ -1,9 +1,28 @@
 use serde::Deserialize;
+use std::path::PathBuf;
 
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
     pub logging: LogConfig,
     pub postgres: PostgresConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub http_cache: HttpCacheConfig,
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+    #[serde(default)]
+    pub error_reports: ErrorReportsConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub tracing: TracingConfig,
+    #[serde(default)]
+    pub shutdown: ShutdownConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,3 +39,217 @@ pub struct PostgresConfig {
     pub max_lifetime: u64,
     pub idle_timeout: u64,
 }
+
+/// Source of the reference data
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackend {
+    /// The `metric`, `imperial`, `trapezoidal`, `pipe` and `analytics` schemas in PostgreSQL
+    #[default]
+    Postgres,
+    /// Bundled tables from the JSON files of `data_dir`, without an external database
+    Memory,
+    /// A single SQLite file (`sqlite_path`) with the same tables and the analytics
+    Sqlite,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub backend: DatabaseBackend,
+    pub data_dir: PathBuf,
+    pub sqlite_path: PathBuf,
+    /// Check the integrity of the reference tables on startup
+    pub check_on_startup: bool,
+    /// Do not start the server when the check finds errors
+    pub fail_on_check_errors: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            backend: DatabaseBackend::Postgres,
+            data_dir: PathBuf::from("./data"),
+            sqlite_path: PathBuf::from("./data/thread-api.sqlite"),
+            check_on_startup: false,
+            fail_on_check_errors: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Cache the responses of `/v2/imperial/info`, `/v1/trapezoidal/svg-dimensions` and `/v1/metric/svg`
+    pub enabled: bool,
+    /// Most responses kept in memory, the least recently used are evicted
+    pub capacity: usize,
+    /// Lifetime of a response in seconds
+    pub ttl_seconds: u64,
+    /// Load the SVG templates into memory on startup
+    pub preload_templates: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capacity: 1000,
+            ttl_seconds: 3600,
+            preload_templates: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HttpCacheConfig {
+    /// Send ETag/Last-Modified and answer conditional GET requests with 304
+    pub enabled: bool,
+    /// `Cache-Control` of the reference data and drawings; with
+    /// `auth.require_api_key` `public` is replaced by `private`
+    pub cache_control: String,
+    /// Reference data version; detected from the database on startup by default
+    pub dataset_version: Option<String>,
+    /// Seconds between version re-detections; 0 - on startup only
+    pub version_refresh_seconds: u64,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cache_control: "private, max-age=3600".to_string(),
+            dataset_version: None,
+            version_refresh_seconds: 60,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AnalyticsConfig {
+    /// Usages queued for the background writer; new ones are dropped when it is full
+    pub channel_capacity: usize,
+    /// Write the pending counters as soon as there are this many distinct keys
+    pub batch_size: usize,
+    /// Interval between writes of the pending counters in milliseconds
+    pub flush_interval_ms: u64,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            channel_capacity: 10_000,
+            batch_size: 500,
+            flush_interval_ms: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ErrorReportsConfig {
+    /// Largest body of `POST /v1/error_reports/` in bytes
+    pub max_body_bytes: usize,
+    /// Reports per minute from one IP; as many are accepted in a row
+    pub per_ip_per_minute: u32,
+}
+
+impl Default for ErrorReportsConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 16 * 1024,
+            per_ip_per_minute: 10,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Require an API key on the reference and SVG routes; otherwise a key is only checked when sent
+    pub require_api_key: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Limit the request rate by the rules of `policies`
+    pub enabled: bool,
+    /// Route rules; the rule with the longest matching path prefix applies
+    pub policies: Vec<RateLimitPolicyConfig>,
+    /// Addresses and networks of reverse proxies, e.g. "172.16.0.0/12": `X-Forwarded-For`
+    /// is read only on connections from them; otherwise the client is the peer address
+    pub trusted_proxies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitPolicyConfig {
+    /// Name of the rule in the statistics
+    pub name: String,
+    /// Path prefixes, e.g. "/v1/metric/svg"
+    pub paths: Vec<String>,
+    /// Requests per minute from one IP without an API key
+    pub per_ip_per_minute: u32,
+    /// Requests per minute with one API key; the same as per IP by default
+    #[serde(default)]
+    pub per_key_per_minute: Option<u32>,
+    /// Requests in a row without waiting; the per-minute limit by default
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TracingConfig {
+    /// Send the spans of requests, SQL queries and SVG rendering to an OTLP collector
+    pub enabled: bool,
+    /// OTLP/HTTP endpoint of the spans, `http://` or `https://`, e.g. a collector on the node
+    pub endpoint: String,
+    /// `service.name` of the span resource
+    pub service_name: String,
+    /// Share of new traces that are recorded; traces with `traceparent` follow the client
+    pub sample_ratio: f64,
+    /// Spans queued for export; new ones are dropped when it is full
+    pub queue_capacity: usize,
+    /// Export as soon as this many spans are queued
+    pub batch_size: usize,
+    /// Interval between exports of the queued spans in milliseconds
+    pub flush_interval_ms: u64,
+    /// Timeout of a collector response in milliseconds
+    pub timeout_ms: u64,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://127.0.0.1:4318/v1/traces".to_string(),
+            service_name: "thread-api".to_string(),
+            sample_ratio: 1.0,
+            queue_capacity: 4096,
+            batch_size: 512,
+            flush_interval_ms: 5000,
+            timeout_ms: 3000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ShutdownConfig {
+    /// Seconds between the signal and closing the listener: readiness is already 503, the balancer drops the pod
+    pub readiness_delay_seconds: u64,
+    /// Seconds for the running requests to finish; the remaining connections are cut
+    pub drain_timeout_seconds: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            readiness_delay_seconds: 5,
+            drain_timeout_seconds: 20,
+        }
+    }
+}
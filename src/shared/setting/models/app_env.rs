@@ -12,6 +12,9 @@ pub struct AppEnv {
     pub postgres_user: String,
     pub postgres_password: String,
     pub postgres_database: String,
+
+    /// Bearer token of the admin routes; empty keeps them closed
+    pub admin_token: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Copy)]
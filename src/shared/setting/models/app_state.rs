@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::{
+    analytics::pipeline::AnalyticsRecorder,
     features::{
         // Health feature
         health::{
@@ -8,12 +10,25 @@ use crate::{
             service::{HealthService, HealthServiceImpl},
         },
     },
-    shared::{database::service::PostgresService, setting::models::app_setting::AppSettings},
+    log_info, log_warn,
+    services::{cache::ResponseCache, rate_limit::RateLimits, search::SearchIndex, shutdown::Drain},
+    shared::{
+        database::{service::PostgresService, version::DatasetVersion},
+        middleware::ConditionalGet,
+        setting::models::app_setting::AppSettings,
+    },
 };
 
 pub struct AppState {
     pub settings: Arc<AppSettings>,
     pub postgres_service: Arc<PostgresService>,
+    pub response_cache: Arc<ResponseCache>,
+    pub conditional_get: Arc<ConditionalGet>,
+    pub search_index: Arc<SearchIndex>,
+    pub analytics: AnalyticsRecorder,
+    pub rate_limits: Arc<RateLimits>,
+    /// Set on SIGTERM/SIGINT before the listener closes
+    pub drain: Drain,
 
     // Health feature dependencies
     pub health_handler: Arc<dyn HealthHandler>,
@@ -21,20 +36,96 @@ pub struct AppState {
 }
 
 impl AppState {
-    pub async fn new(settings: Arc<AppSettings>, postgres_service: Arc<PostgresService>) -> Self {
-        // Получаем pool из postgres_service
-        let pool = postgres_service.connection.pool().clone();
+    pub async fn new(
+        settings: Arc<AppSettings>,
+        postgres_service: Arc<PostgresService>,
+        analytics: AnalyticsRecorder,
+    ) -> Self {
+        // Pool of postgres_service, absent with the bundled data
+        let pool = postgres_service.pool().cloned();
+        let response_cache = Arc::new(ResponseCache::new(&settings.config.cache));
+        let dataset_version = Self::dataset_version(&settings, &postgres_service).await;
+        let conditional_get = Arc::new(ConditionalGet::new(
+            &settings.config.http_cache,
+            settings.config.auth.require_api_key,
+            dataset_version,
+        ));
+        let rate_limits = Arc::new(RateLimits::new(&settings.config.rate_limit));
+        let drain = Drain::new();
 
         // Создаем зависимости для health feature
-        let health_service: Arc<dyn HealthService> = Arc::new(HealthServiceImpl::new(settings.clone(), pool.clone()));
+        let health_service: Arc<dyn HealthService> = Arc::new(
+            HealthServiceImpl::new(settings.clone(), pool)
+                .with_sqlite(postgres_service.sqlite.clone())
+                .with_bundled_rows(postgres_service.bundled_rows.clone())
+                .with_cache(response_cache.clone())
+                .with_analytics(analytics.clone())
+                .with_rate_limits(rate_limits.clone())
+                .with_drain(drain.clone()),
+        );
         let health_handler: Arc<dyn HealthHandler> = Arc::new(HealthHandlerV1::new(health_service.clone()));
 
         Self {
             settings,
             postgres_service,
+            response_cache,
+            conditional_get,
+            search_index: Arc::new(SearchIndex::new()),
+            analytics,
+            rate_limits,
+            drain,
 
             health_handler,
             health_service,
         }
     }
+
+    /// Reference data version of the ETags: from the configuration or detected from the database
+    async fn dataset_version(settings: &AppSettings, postgres_service: &PostgresService) -> DatasetVersion {
+        let version = match &settings.config.http_cache.dataset_version {
+            Some(id) => DatasetVersion::fixed(id.clone()),
+            None => DatasetVersion::detect(postgres_service).await.unwrap_or_else(|e| {
+                log_warn!("Failed to detect reference data version: {}", e);
+                DatasetVersion::fixed(format!("startup-{}", chrono::Utc::now().timestamp()))
+            }),
+        };
+        log_info!("Reference data version: {} ({})", version.id, version.modified_at);
+        version
+    }
+
+    /// Re-detects the reference data version in the background. A new version
+    /// changes the ETags, drops the cached responses computed from the old data
+    /// and rebuilds the search index.
+    pub fn watch_dataset_version(&self) {
+        let config = &self.settings.config.http_cache;
+        if config.dataset_version.is_some() || config.version_refresh_seconds == 0 {
+            return;
+        }
+
+        let period = Duration::from_secs(config.version_refresh_seconds);
+        let database = self.postgres_service.clone();
+        let conditional_get = self.conditional_get.clone();
+        let response_cache = self.response_cache.clone();
+        let search_index = self.search_index.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                match DatasetVersion::detect(&database).await {
+                    Ok(version) => {
+                        let id = version.id.clone();
+                        if conditional_get.set_version(version) {
+                            response_cache.clear();
+                            log_info!("Reference data version changed to {}, response cache cleared", id);
+                            if let Err(e) = search_index.rebuild(&database).await {
+                                log_warn!("Failed to rebuild the search index: {}", e);
+                            }
+                        }
+                    }
+                    Err(e) => log_warn!("Failed to detect reference data version: {}", e),
+                }
+            }
+        });
+    }
 }
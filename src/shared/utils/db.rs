@@ -1,34 +1,34 @@
+use crate::shared::error::AppError;
 use crate::{log_error, log_info};
-use axum::{http::StatusCode, Json};
-use serde_json::Value;
-use sqlx::{postgres::PgArguments, Column, PgPool, Row};
+use axum::Json;
+use serde_json::{json, Map, Value};
+use sqlx::{
+    postgres::{PgArguments, PgRow},
+    sqlite::{SqliteArguments, SqliteRow},
+    Column, PgPool, Row, SqlitePool,
+};
 
-/// Тип ответа для функции `execute_query`.
+/// Строка результата запроса в виде JSON-объекта (столбец → значение)
+pub type JsonRow = Map<String, Value>;
+
+/// Тип ответа для функции `build_json_response`.
 pub enum ResponseType {
     Single,
     Multiple,
 }
 
-/// Выполняет SQL-запрос с заданными параметрами и возвращает результат в виде JSON с учетом опционального precision.
+/// Выполняет SQL-запрос с заданными параметрами и возвращает строки в виде JSON-объектов без округления.
 ///
 /// # Аргументы
 ///
 /// * `pool` - Пул соединений с базой данных.
 /// * `query` - SQL-запрос с плейсхолдерами ($1, $2, ...).
 /// * `binds` - Функция для привязки параметров к запросу.
-/// * `response_type` - Тип ответа: Single для одиночного объекта или Multiple для массива.
-/// * `precision` - Опциональная точность форматирования числовых значений.
 ///
 /// # Возвращает
 ///
-/// * `Result<Json<Value>, (StatusCode, String)>` - Результат выполнения запроса или ошибка.
-pub async fn execute_query<F>(
-    pool: &PgPool,
-    query: &str,
-    binds: F,
-    response_type: ResponseType,
-    precision: Option<usize>,
-) -> Result<Json<Value>, (StatusCode, String)>
+/// * `Result<Vec<JsonRow>, sqlx::Error>` - Строки результата или ошибка базы данных.
+pub async fn fetch_json_rows<F>(pool: &PgPool, query: &str, binds: F) -> Result<Vec<JsonRow>, sqlx::Error>
 where
     F: FnOnce(
         sqlx::query::Query<'_, sqlx::Postgres, PgArguments>,
@@ -39,46 +39,112 @@ where
     let sql_query = binds(sqlx::query(query));
 
     // Выполнение запроса
-    let rows = match sql_query.fetch_all(pool).await {
-        Ok(r) => r,
-        Err(e) => {
-            log_error!("Error executing query: {}", e);
-            return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Ошибка базы данных: {}", e)));
-        }
-    };
+    let rows = sql_query.fetch_all(pool).await.map_err(|e| {
+        log_error!("Error executing query: {}", e);
+        e
+    })?;
 
     log_info!("Query executed successfully, received {} rows", rows.len());
 
-    // Преобразование строк в JSON с учетом precision
-    let results: Vec<Value> = rows.iter().map(|row| row_to_json(row, precision)).collect();
+    Ok(rows.iter().map(row_to_json).collect())
+}
+
+/// То же, что `fetch_json_rows`, для базы SQLite (плейсхолдеры `?`).
+pub async fn fetch_sqlite_json_rows<'q, F>(
+    pool: &SqlitePool,
+    query: &'q str,
+    binds: F,
+) -> Result<Vec<JsonRow>, sqlx::Error>
+where
+    F: FnOnce(
+        sqlx::query::Query<'q, sqlx::Sqlite, SqliteArguments<'q>>,
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, SqliteArguments<'q>>,
+{
+    log_info!("Executing query: {}", query);
+    let rows = binds(sqlx::query(query)).fetch_all(pool).await.map_err(|e| {
+        log_error!("Error executing query: {}", e);
+        e
+    })?;
+
+    log_info!("Query executed successfully, received {} rows", rows.len());
+
+    Ok(rows.iter().map(sqlite_row_to_json).collect())
+}
+
+/// Формирует JSON-ответ из строк с учетом типа ответа и опционального precision.
+///
+/// # Аргументы
+///
+/// * `rows` - Строки, полученные из репозитория.
+/// * `response_type` - Тип ответа: Single для одиночного объекта или Multiple для массива.
+/// * `precision` - Опциональная точность форматирования числовых значений.
+///
+/// # Возвращает
+///
+/// * `Result<Json<Value>, AppError>` - Ответ или ошибка; пустой результат для
+///   одиночного объекта — `ThreadDataNotFound`.
+pub fn build_json_response(
+    rows: Vec<JsonRow>,
+    response_type: ResponseType,
+    precision: Option<usize>,
+) -> Result<Json<Value>, AppError> {
+    let mut results: Vec<Value> = rows
+        .into_iter()
+        .map(|row| Value::Object(format_row(row, precision)))
+        .collect();
 
     match response_type {
-        ResponseType::Single => {
-            if results.len() == 1 {
-                Ok(Json(results[0].clone()))
-            } else {
-                Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Ожидался один результат, но получено {}", results.len()),
-                ))
-            }
-        }
+        ResponseType::Single => match results.len() {
+            1 => Ok(Json(results.remove(0))),
+            0 => Err(AppError::thread_not_found(json!({}))),
+            count => Err(AppError::InternalError {
+                message: format!("expected one row, got {}", count),
+            }),
+        },
         ResponseType::Multiple => Ok(Json(Value::Array(results))),
     }
 }
 
-/// Преобразует одну строку SQL-запроса в JSON-объект с учетом опционального precision.
+/// Округляет числовые значения строки: целые числа выводятся без дробной части,
+/// остальные — с заданной точностью.
+pub fn format_row(row: JsonRow, precision: Option<usize>) -> JsonRow {
+    row.into_iter()
+        .map(|(name, value)| {
+            let value = match value.as_f64() {
+                Some(v) if value.is_f64() => format_number(v, precision),
+                _ => value,
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+fn format_number(v: f64, precision: Option<usize>) -> Value {
+    // Проверяем, является ли число целым
+    if v.fract().abs() < 1e-10 {
+        // Преобразуем в i64, если возможно
+        Value::from(v as i64)
+    } else if let Some(precision) = precision {
+        // Форматируем число с заданной точностью и преобразуем обратно в число с плавающей точкой
+        let formatted = format!("{:.*}", precision, v);
+        Value::from(formatted.parse::<f64>().unwrap_or(v))
+    } else {
+        // Выводим как есть
+        Value::from(v)
+    }
+}
+
+/// Преобразует одну строку SQL-запроса в JSON-объект.
 ///
 /// # Аргументы
 ///
 /// * `row` - Строка результата SQL-запроса.
-/// * `precision` - Опциональная точность форматирования числовых значений.
 ///
 /// # Возвращает
 ///
-/// * `Value` - JSON-объект, представляющий строку.
-fn row_to_json(row: &sqlx::postgres::PgRow, precision: Option<usize>) -> Value {
-    let mut map = serde_json::Map::new();
+/// * `JsonRow` - JSON-объект, представляющий строку. Столбцы со значением NULL пропускаются.
+pub fn row_to_json(row: &PgRow) -> JsonRow {
+    let mut map = JsonRow::new();
     for column in row.columns() {
         let column_name = column.name();
         let value = if let Ok(val) = row.try_get::<Option<i32>, _>(column_name) {
@@ -86,22 +152,7 @@ fn row_to_json(row: &sqlx::postgres::PgRow, precision: Option<usize>) -> Value {
         } else if let Ok(val) = row.try_get::<Option<i64>, _>(column_name) {
             val.map(Value::from)
         } else if let Ok(val) = row.try_get::<Option<f64>, _>(column_name) {
-            val.map(|v| {
-                // Проверяем, является ли число целым
-                if v.fract().abs() < 1e-10 {
-                    // Преобразуем в i64, если возможно
-                    let int_val = v as i64;
-                    Value::from(int_val)
-                } else if let Some(precision) = precision {
-                    // Форматируем число с заданной точностью
-                    let formatted = format!("{:.*}", precision, v);
-                    // Преобразуем обратно в число с плавающей точкой
-                    Value::from(formatted.parse::<f64>().unwrap_or(v))
-                } else {
-                    // Выводим как есть
-                    Value::from(v)
-                }
-            })
+            val.map(Value::from)
         } else if let Ok(val) = row.try_get::<Option<bool>, _>(column_name) {
             val.map(Value::from)
         } else if let Ok(val) = row.try_get::<Option<String>, _>(column_name) {
@@ -122,5 +173,32 @@ fn row_to_json(row: &sqlx::postgres::PgRow, precision: Option<usize>) -> Value {
             map.insert(column_name.to_string(), v);
         }
     }
-    Value::Object(map)
+    map
+}
+
+/// Преобразует строку SQLite в JSON-объект. Столбцы со значением NULL пропускаются.
+pub fn sqlite_row_to_json(row: &SqliteRow) -> JsonRow {
+    let mut map = JsonRow::new();
+    for column in row.columns() {
+        let column_name = column.name();
+        let value = if let Ok(val) = row.try_get::<Option<i64>, _>(column_name) {
+            val.map(Value::from)
+        } else if let Ok(val) = row.try_get::<Option<f64>, _>(column_name) {
+            val.map(Value::from)
+        } else if let Ok(val) = row.try_get::<Option<String>, _>(column_name) {
+            val.map(Value::from)
+        } else {
+            log_error!(
+                "Failed to process column '{}', data type: '{:?}'. The data type may not be supported.",
+                column_name,
+                column.type_info()
+            );
+            None
+        };
+
+        if let Some(v) = value {
+            map.insert(column_name.to_string(), v);
+        }
+    }
+    map
 }
@@ -16,6 +16,7 @@ impl NumberFormatter {
     /// # Примеры
     ///
     /// ```
+    /// # use thread_api::shared::utils::number::NumberFormatter;
     /// let rounded = NumberFormatter::round(3.14159, 2);
     /// assert_eq!(rounded, 3.14);
     /// ```
@@ -36,16 +37,17 @@ impl NumberFormatter {
     /// # Примеры
     ///
     /// ```
+    /// # use thread_api::shared::{enums::Unit, utils::number::NumberFormatter};
     /// // Конвертация из микронов в миллиметры
-    /// let mm = NumberFormatter::convert_and_round(1000.0, &Units::Micron, &Units::Mm, Some(3));
+    /// let mm = NumberFormatter::convert_and_round(1000.0, &Unit::Micron, &Unit::Mm, Some(3));
     /// assert_eq!(mm, 1.000);
     ///
     /// // Конвертация из микронов в дюймы
-    /// let inches = NumberFormatter::convert_and_round(25400.0, &Units::Micron, &Units::Inch, Some(3));
+    /// let inches = NumberFormatter::convert_and_round(25400.0, &Unit::Micron, &Unit::Inch, Some(3));
     /// assert_eq!(inches, 1.000);
     ///
     /// // Конвертация из дюймов в микроны
-    /// let microns = NumberFormatter::convert_and_round(1.0, &Units::Inch, &Units::Micron, Some(1));
+    /// let microns = NumberFormatter::convert_and_round(1.0, &Unit::Inch, &Unit::Micron, Some(1));
     /// assert_eq!(microns, 25400.0);
     /// ```
     pub fn convert_and_round(value: f64, from_units: &Unit, to_units: &Unit, precision: Option<usize>) -> f64 {
@@ -1,2 +1,54 @@
+pub mod test_analytics_pipeline;
+pub mod test_analytics_report;
+pub mod test_api_keys;
+pub mod test_batch_info;
+pub mod test_conditional_get;
+pub mod test_dxf;
+pub mod test_error_envelope;
+pub mod test_error_reports;
+pub mod test_geometry;
+pub mod test_health;
 pub mod test_imperial_1;
 pub mod test_imperial_2;
+pub mod test_integrity;
+pub mod test_memory_repository;
+pub mod test_metrics;
+pub mod test_openapi;
+pub mod test_rate_limit;
+pub mod test_reference_data;
+pub mod test_request_id;
+pub mod test_response_cache;
+pub mod test_search;
+pub mod test_sqlite_repository;
+pub mod test_svg_export;
+pub mod test_telemetry;
+pub mod test_thread_mesh;
+pub mod test_tolerance_zones;
+
+#[cfg(test)]
+use crate::shared::database::repositories::MemoryDataset;
+#[cfg(test)]
+use crate::shared::logging::{enums::LogLevel, init::init_logging, structs::LogConfig};
+
+/// Quiet logger for tests. The logger is process-wide, another test may already have set it
+#[cfg(test)]
+pub fn init_test_logger() {
+    let _ = init_logging(LogConfig {
+        level: LogLevel::Error,
+        ..LogConfig::default()
+    });
+}
+
+/// Memory backend data with M10 (coarse 1.5 and fine 1.25) and M12 in `metric.main`
+#[cfg(test)]
+pub fn metric_dataset() -> MemoryDataset {
+    MemoryDataset {
+        metric_main: serde_json::from_value(serde_json::json!([
+            {"id": 1, "diameter": 10.0, "pitch": 1.5, "type_pitch": 0},
+            {"id": 2, "diameter": 10.0, "pitch": 1.25, "type_pitch": 1},
+            {"id": 3, "diameter": 12.0, "pitch": 1.75, "type_pitch": 0}
+        ]))
+        .unwrap(),
+        ..MemoryDataset::default()
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod run {
+    use std::sync::Arc;
+
+    use crate::analytics::pipeline::AnalyticsPipeline;
+    use crate::shared::database::repositories::{
+        MemoryAnalyticsRepository, ThreadUsage, TraitAnalyticsRepository, UsageFilter,
+    };
+    use crate::shared::enums::{Language, ThreadStandard};
+    use crate::shared::setting::models::app_config::AnalyticsConfig;
+    use crate::test::init_test_logger;
+
+    #[tokio::test]
+    async fn full_queue_drops_and_shutdown_flushes_the_rest() {
+        init_test_logger();
+        let repository = Arc::new(MemoryAnalyticsRepository::new());
+        let config = AnalyticsConfig {
+            channel_capacity: 3,
+            batch_size: 100,
+            flush_interval_ms: 60_000,
+        };
+        let (recorder, pipeline) = AnalyticsPipeline::start(repository.clone(), &config);
+
+        // The single-threaded test runtime does not run the worker before the first await
+        let usage = ThreadUsage::new("M10-6g".to_string(), ThreadStandard::Metric, Language::En);
+        for _ in 0..5 {
+            recorder.record(usage.clone());
+        }
+        let stats = recorder.stats();
+        assert_eq!((stats.recorded, stats.dropped, stats.queued), (3, 2, 3));
+
+        pipeline.shutdown().await;
+        let stats = recorder.stats();
+        assert_eq!((stats.flushed, stats.batches, stats.queued), (3, 1, 0));
+
+        // Queued lookups of one key arrive as a single counter
+        let filter = UsageFilter {
+            from: usage.day,
+            to: usage.day,
+            standard: None,
+            language: None,
+            designation: None,
+        };
+        let rows = repository.daily_usage(&filter).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].count, 3);
+
+        recorder.record(usage);
+        assert_eq!(recorder.stats().dropped, 3);
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod run {
+    use chrono::NaiveDate;
+
+    use crate::analytics::report::{bucket_count, buckets, popular, timeseries, Period, UsagePoint};
+    use crate::shared::database::repositories::{
+        DailyUsage, MemoryAnalyticsRepository, ThreadUsage, TraitAnalyticsRepository, UsageFilter,
+    };
+    use crate::shared::enums::{Language, ThreadStandard};
+
+    fn day(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    fn usage(designation: &str, standard: ThreadStandard, language: Language, on: &str) -> ThreadUsage {
+        ThreadUsage {
+            designation: designation.to_string(),
+            standard,
+            language,
+            day: day(on),
+        }
+    }
+
+    async fn rows(repository: &MemoryAnalyticsRepository, filter: UsageFilter) -> Vec<DailyUsage> {
+        repository.daily_usage(&filter).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn counts_by_day_standard_and_language() {
+        let repository = MemoryAnalyticsRepository::new();
+        for record in [
+            usage("M10-6g", ThreadStandard::Metric, Language::En, "2026-10-05"),
+            usage("M10-6g", ThreadStandard::Metric, Language::En, "2026-10-05"),
+            usage("M10-6g", ThreadStandard::Metric, Language::Ru, "2026-10-12"),
+            usage("Tr 20 x 4-7e", ThreadStandard::Trapezoidal, Language::En, "2026-10-13"),
+            usage("G 1/2 - 14", ThreadStandard::Pipe, Language::En, "2026-09-30"),
+        ] {
+            repository.add_or_increment_thread(&record).await.unwrap();
+        }
+
+        let october = UsageFilter {
+            from: day("2026-10-01"),
+            to: day("2026-10-31"),
+            standard: None,
+            language: None,
+            designation: None,
+        };
+        let top = popular(&rows(&repository, october.clone()).await, 10);
+        let top: Vec<(&str, i64)> = top.iter().map(|t| (t.designation.as_str(), t.count)).collect();
+        assert_eq!(top, [("M10-6g", 3), ("Tr 20 x 4-7e", 1)]);
+
+        let russian = UsageFilter {
+            language: Some(Language::Ru),
+            ..october.clone()
+        };
+        assert_eq!(popular(&rows(&repository, russian).await, 10)[0].count, 1);
+
+        // 2026-10-05 and 2026-10-12 are Mondays
+        let weekly = timeseries(
+            &rows(&repository, october).await,
+            Period::Week,
+            day("2026-10-05"),
+            day("2026-10-20"),
+        );
+        let expected = [("2026-10-05", 2), ("2026-10-12", 2), ("2026-10-19", 0)].map(|(start, count)| UsagePoint {
+            start: day(start),
+            count,
+        });
+        assert_eq!(weekly, expected);
+
+        let all_time: i64 = repository
+            .usage_counts()
+            .await
+            .unwrap()
+            .iter()
+            .map(|(_, count)| count)
+            .sum();
+        assert_eq!(all_time, 5);
+    }
+
+    #[test]
+    fn periods_start_on_monday_and_first_of_month() {
+        assert_eq!(Period::Week.start(day("2026-10-18")), day("2026-10-12"));
+        assert_eq!(Period::Month.start(day("2026-10-18")), day("2026-10-01"));
+        assert_eq!(Period::Month.default_from(day("2026-10-18")), day("2025-11-01"));
+        assert_eq!(
+            Period::Week.trailing(day("2026-10-18")),
+            (day("2026-10-12"), day("2026-10-18"))
+        );
+    }
+
+    #[test]
+    fn bucket_count_matches_buckets() {
+        for (from, to) in [
+            ("2026-10-18", "2026-10-18"),
+            ("2026-09-30", "2026-10-19"),
+            ("2025-12-29", "2026-03-01"),
+        ] {
+            for period in [Period::Day, Period::Week, Period::Month] {
+                let expected = buckets(period, day(from), day(to)).len() as u64;
+                assert_eq!(
+                    bucket_count(period, day(from), day(to)),
+                    expected,
+                    "{:?} {}..{}",
+                    period,
+                    from,
+                    to
+                );
+            }
+        }
+        // Counted, not enumerated: a range back to the backfilled totals stays cheap
+        assert_eq!(bucket_count(Period::Day, day("1970-01-01"), day("1970-12-31")), 365);
+        assert_eq!(bucket_count(Period::Month, day("1970-01-01"), day("2026-10-19")), 682);
+        assert_eq!(bucket_count(Period::Day, day("2026-10-19"), day("2026-10-18")), 0);
+    }
+}
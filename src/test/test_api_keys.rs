@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::services::rate_limit::RateLimits;
+    use crate::shared::database::repositories::{ApiScope, MemoryApiKeyRepository, NewApiKey, TraitApiKeyRepository};
+    use crate::shared::middleware::{
+        api_key_prefix, authenticate, charge_quota, generate_api_key, hash_api_key, rate_limit, redact_api_key,
+        required_scope, ApiKeyAuth,
+    };
+    use crate::shared::setting::models::app_config::RateLimitConfig;
+    use crate::test::init_test_logger;
+
+    async fn issue(repository: &MemoryApiKeyRepository, scopes: Vec<ApiScope>, daily_quota: Option<i64>) -> String {
+        let secret = generate_api_key();
+        repository
+            .create_api_key(&NewApiKey {
+                name: "partner".to_string(),
+                key_hash: hash_api_key(&secret),
+                prefix: api_key_prefix(&secret),
+                scopes,
+                daily_quota,
+            })
+            .await
+            .unwrap();
+        secret
+    }
+
+    fn app(repository: Arc<MemoryApiKeyRepository>, required: bool) -> Router {
+        init_test_logger();
+        Router::new()
+            .route("/v1/pipe/info", get(|| async { "info" }))
+            .route("/v1/metric/svg", get(|| async { "svg" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                ApiKeyAuth::new(repository.clone(), required),
+                charge_quota,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                ApiKeyAuth::new(repository, required),
+                authenticate,
+            ))
+    }
+
+    async fn status(app: &Router, uri: &str, key: Option<&str>) -> StatusCode {
+        let mut request = Request::get(uri);
+        if let Some(key) = key {
+            request = request.header("x-api-key", key);
+        }
+        let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        response.status()
+    }
+
+    #[test]
+    fn scopes_follow_paths() {
+        assert_eq!(required_scope("/v1/pipe/info"), Some(ApiScope::Read));
+        assert_eq!(required_scope("/v1/imperial/svg-dimensions"), Some(ApiScope::Svg));
+        assert_eq!(required_scope("/v1/profile/dxf"), Some(ApiScope::Svg));
+        assert_eq!(required_scope("/admin/api-keys/3/rotate"), Some(ApiScope::Admin));
+        assert_eq!(required_scope("/admin/data-check"), Some(ApiScope::Admin));
+        assert_eq!(required_scope("/v1/analytics/popular"), Some(ApiScope::Admin));
+        assert_eq!(required_scope("/v1/error_reports/"), None);
+        assert_eq!(required_scope("/health"), None);
+    }
+
+    #[test]
+    fn query_keys_are_redacted_for_logs() {
+        let uri: axum::http::Uri = "/v1/metric/svg?diameter=10&api_key=tk_secret&pitch=1.5"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redact_api_key(&uri),
+            "/v1/metric/svg?diameter=10&api_key=[redacted]&pitch=1.5"
+        );
+        assert_eq!(redact_api_key(&"/health".parse().unwrap()), "/health");
+    }
+
+    #[tokio::test]
+    async fn keys_are_checked_for_scope_and_quota() {
+        let repository = Arc::new(MemoryApiKeyRepository::new());
+        let reader = issue(&repository, vec![ApiScope::Read], Some(2)).await;
+        let app = self::app(repository.clone(), true);
+
+        assert_eq!(status(&app, "/health", None).await, StatusCode::OK);
+        assert_eq!(status(&app, "/v1/pipe/info", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(&app, "/v1/pipe/info", Some("tk_wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, "/v1/metric/svg", Some(&reader)).await,
+            StatusCode::FORBIDDEN
+        );
+        let query = format!("/v1/pipe/info?api_key={}", reader);
+        assert_eq!(status(&app, &query, None).await, StatusCode::OK);
+        // The forbidden request is not counted, the third allowed one is over the quota
+        assert_eq!(status(&app, "/v1/pipe/info", Some(&reader)).await, StatusCode::OK);
+        assert_eq!(
+            status(&app, "/v1/pipe/info", Some(&reader)).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        repository.revoke_api_key(1).await.unwrap();
+        assert_eq!(
+            status(&app, "/v1/pipe/info", Some(&reader)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        // Anonymous requests pass while keys are optional
+        let optional = self::app(repository, false);
+        assert_eq!(status(&optional, "/v1/pipe/info", None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_keep_the_quota() {
+        let repository = Arc::new(MemoryApiKeyRepository::new());
+        let reader = issue(&repository, vec![ApiScope::Read], Some(5)).await;
+        let config: RateLimitConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[policies]]
+name = "reference"
+paths = ["/v1/"]
+per_ip_per_minute = 1
+"#,
+        )
+        .unwrap();
+        let auth = ApiKeyAuth::new(repository.clone(), true);
+        let app = Router::new()
+            .route("/v1/pipe/info", get(|| async { "info" }))
+            .layer(axum::middleware::from_fn_with_state(auth.clone(), charge_quota))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(RateLimits::new(&config)),
+                rate_limit,
+            ))
+            .layer(axum::middleware::from_fn_with_state(auth, authenticate));
+        init_test_logger();
+
+        assert_eq!(status(&app, "/v1/pipe/info", Some(&reader)).await, StatusCode::OK);
+        assert_eq!(
+            status(&app, "/v1/pipe/info", Some(&reader)).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        let today = chrono::Utc::now().date_naive();
+        assert_eq!(repository.count_api_key_requests(1, today, 0).await.unwrap(), 1);
+    }
+}
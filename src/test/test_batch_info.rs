@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Request, StatusCode},
+        routing::post,
+        Extension, Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::analytics::pipeline::AnalyticsPipeline;
+    use crate::features::batch::handler::handle;
+    use crate::services::rate_limit::RateLimits;
+    use crate::shared::database::repositories::{ApiScope, NewApiKey};
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::middleware::{
+        api_key_prefix, authenticate, charge_quota, generate_api_key, hash_api_key, rate_limit, ApiKeyAuth,
+    };
+    use crate::shared::setting::models::app_config::{AnalyticsConfig, RateLimitConfig};
+    use crate::test::{init_test_logger, metric_dataset};
+
+    fn app_with(database: Arc<PostgresService>) -> Router {
+        init_test_logger();
+        let (analytics, _) =
+            AnalyticsPipeline::start(database.repository_analytics.clone(), &AnalyticsConfig::default());
+        Router::new()
+            .route("/v1/batch/info", post(handle))
+            .layer(axum::middleware::from_fn_with_state(
+                ApiKeyAuth::new(database.repository_api_keys.clone(), false),
+                charge_quota,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                ApiKeyAuth::new(database.repository_api_keys.clone(), false),
+                authenticate,
+            ))
+            .layer(Extension(database))
+            .layer(Extension(analytics))
+    }
+
+    fn app() -> Router {
+        app_with(Arc::new(PostgresService::from_dataset(metric_dataset())))
+    }
+
+    fn batch_request(body: &Value, key: Option<&str>) -> Request<Body> {
+        let mut request = Request::post("/v1/batch/info").header(header::CONTENT_TYPE, "application/json");
+        if let Some(key) = key {
+            request = request.header("x-api-key", key);
+        }
+        request.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn send(body: Value) -> (StatusCode, Value) {
+        let response = app().oneshot(batch_request(&body, None)).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn rows_keep_order_and_own_status() {
+        let metric = json!({
+            "standard": "metric", "diameter": 10, "pitch": 1.5, "type": "male",
+            "tolerance": "6g", "language": "en", "units": "mm"
+        });
+        let mut missing = metric.clone();
+        missing["diameter"] = json!(12);
+        missing["language"] = json!("ru");
+
+        let (status, body) = send(json!([metric, missing, {"standard": "acme"}])).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let rows = body.as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["status"], 200);
+        assert_eq!(rows[0]["data"]["designation"], "M10-6g");
+        assert_eq!(rows[1]["index"], 1);
+        assert_eq!(rows[1]["status"], 404);
+        assert_eq!(rows[1]["error"]["code"], "THREAD_NOT_FOUND");
+        assert!(rows[1]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Резьба не найдена"));
+        assert_eq!(rows[2]["status"], 400);
+    }
+
+    #[tokio::test]
+    async fn every_row_counts_against_the_quota() {
+        let database = Arc::new(PostgresService::from_dataset(metric_dataset()));
+        let secret = generate_api_key();
+        database
+            .repository_api_keys
+            .create_api_key(&NewApiKey {
+                name: "bom-import".to_string(),
+                key_hash: hash_api_key(&secret),
+                prefix: api_key_prefix(&secret),
+                scopes: vec![ApiScope::Read],
+                daily_quota: Some(3),
+            })
+            .await
+            .unwrap();
+        let app = app_with(database);
+        let row = json!({"standard": "metric", "diameter": 10, "pitch": 1.5, "type": "male", "tolerance": "6g"});
+
+        let three_rows = json!([row, row, row]);
+        let response = app
+            .clone()
+            .oneshot(batch_request(&three_rows, Some(&secret)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let one_row = json!([row]);
+        let response = app.oneshot(batch_request(&one_row, Some(&secret))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn every_row_takes_a_rate_limit_token() {
+        let config: RateLimitConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[policies]]
+name = "batch"
+paths = ["/v1/batch/"]
+per_ip_per_minute = 4
+"#,
+        )
+        .unwrap();
+        let app = app().layer(axum::middleware::from_fn_with_state(
+            Arc::new(RateLimits::new(&config)),
+            rate_limit,
+        ));
+        let row = json!({"standard": "metric", "diameter": 10, "pitch": 1.5, "type": "male", "tolerance": "6g"});
+
+        let three_rows = json!([row, row, row]);
+        let response = app.clone().oneshot(batch_request(&three_rows, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.oneshot(batch_request(&three_rows, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn body_must_be_an_array() {
+        let (status, body) = send(json!({"standard": "metric"})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::shared::database::repositories::MemoryDataset;
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::database::sqlite::SqliteConnection;
+    use crate::shared::database::version::DatasetVersion;
+    use crate::shared::middleware::{conditional_get, ConditionalGet};
+    use crate::shared::setting::models::app_config::HttpCacheConfig;
+    use crate::test::{init_test_logger, metric_dataset};
+
+    fn policy(version: &str) -> Arc<ConditionalGet> {
+        keyed_policy(version, false)
+    }
+
+    fn keyed_policy(version: &str, require_api_key: bool) -> Arc<ConditionalGet> {
+        let config = HttpCacheConfig {
+            cache_control: "public, max-age=60".to_string(),
+            ..HttpCacheConfig::default()
+        };
+        Arc::new(ConditionalGet::new(
+            &config,
+            require_api_key,
+            DatasetVersion::fixed(version),
+        ))
+    }
+
+    fn app(policy: Arc<ConditionalGet>) -> Router {
+        Router::new()
+            .route("/v1/pipe/diameters", get(|| async { "[]" }))
+            .route_layer(axum::middleware::from_fn_with_state(policy, conditional_get))
+    }
+
+    #[test]
+    fn etag_depends_on_params_and_version() {
+        let v1 = policy("v1");
+        let same = v1.etag("/v1/metric/svg", Some("pitch=1.5&diameter=10"));
+
+        assert_eq!(same, v1.etag("/v1/metric/svg", Some("diameter=10&pitch=1.5")));
+        assert_ne!(same, v1.etag("/v1/metric/svg", Some("diameter=12&pitch=1.5")));
+        assert_ne!(same, policy("v2").etag("/v1/metric/svg", Some("diameter=10&pitch=1.5")));
+
+        // An import seen by the version refresh changes the ETags in place
+        assert!(!v1.set_version(DatasetVersion::fixed("v1")));
+        assert!(v1.set_version(DatasetVersion::fixed("v2")));
+        assert_ne!(same, v1.etag("/v1/metric/svg", Some("diameter=10&pitch=1.5")));
+    }
+
+    #[tokio::test]
+    async fn sqlite_version_follows_reference_rows() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let connection = Arc::new(SqliteConnection::with_pool(pool).await.unwrap());
+        let database = PostgresService::from_sqlite(connection.clone());
+
+        let empty = DatasetVersion::detect(&database).await.unwrap();
+        assert!(empty.id.starts_with("sqlite-"));
+        sqlx::raw_sql("INSERT INTO pipe_main (id, designation, designation_2, thread_pitch, thread_per, ex_major_dia_max, ex_major_dia_min) VALUES (1, 0.5, '1/2', 1.814, 14, 20.955, 20.587);")
+            .execute(connection.pool())
+            .await
+            .unwrap();
+        let imported = DatasetVersion::detect(&database).await.unwrap();
+        assert_ne!(empty.id, imported.id);
+    }
+
+    #[tokio::test]
+    async fn memory_version_is_the_digest_of_the_loaded_files() {
+        init_test_logger();
+        let dataset = MemoryDataset::load(Path::new("./data")).unwrap();
+        let loaded = dataset.version.clone().unwrap();
+        assert!(loaded.id.starts_with("files-"));
+
+        let database = PostgresService::from_dataset(dataset);
+        assert_eq!(DatasetVersion::detect(&database).await.unwrap(), loaded);
+        let built = PostgresService::from_dataset(metric_dataset());
+        assert_eq!(DatasetVersion::detect(&built).await.unwrap().id, "memory");
+    }
+
+    #[tokio::test]
+    async fn matching_if_none_match_returns_304() {
+        let app = app(policy("v1"));
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/v1/pipe/diameters").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=60");
+        assert_eq!(response.headers()[header::VARY], "X-API-Key");
+        assert!(response.headers().contains_key(header::LAST_MODIFIED));
+        let etag = response.headers()[header::ETAG].clone();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/v1/pipe/diameters")
+                    .header(header::IF_NONE_MATCH, etag.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag);
+
+        let response = app
+            .oneshot(
+                Request::get("/v1/pipe/diameters")
+                    .header(header::IF_NONE_MATCH, "\"stale\"")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn required_api_key_keeps_responses_private() {
+        let response = app(keyed_policy("v1", true))
+            .oneshot(Request::get("/v1/pipe/diameters").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "private, max-age=60");
+        assert_eq!(response.headers()[header::VARY], "X-API-Key");
+    }
+}
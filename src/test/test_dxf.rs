@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod run {
+    use crate::services::dxf::profile_exporter::{ProfileDxfExporter, ProfileDxfOptions};
+    use crate::services::geometry::ThreadProfile;
+    use crate::services::svg::tolerance_renderer::{DiameterZone, ToleranceZones};
+    use crate::shared::enums::ThreadType;
+
+    fn options() -> ProfileDxfOptions {
+        ProfileDxfOptions {
+            title: "M10x1.5-6g".to_string(),
+            precision: 3,
+            pitches: 3,
+        }
+    }
+
+    fn layer_entities(dxf: &str, layer: &str) -> usize {
+        let lines: Vec<&str> = dxf.lines().map(str::trim).collect();
+        lines
+            .windows(2)
+            .filter(|pair| pair[0] == "8" && pair[1] == layer)
+            .count()
+    }
+
+    #[test]
+    fn test_with_basic_diameters_keeps_profile() {
+        for profile in [
+            ThreadProfile::metric(ThreadType::Male, 10.0, 1.5),
+            ThreadProfile::trapezoidal(ThreadType::Female, 20.0, 4.0),
+            ThreadProfile::pipe(ThreadType::Male, 20.955, 1.814),
+        ] {
+            let same = profile.with_diameters(profile.major_diameter, profile.pitch_diameter, profile.minor_diameter);
+            assert!((same.crest_truncation - profile.crest_truncation).abs() < 1e-6);
+            assert!((same.root_truncation - profile.root_truncation).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_export_layers() {
+        let profile = ThreadProfile::metric(ThreadType::Male, 10.0, 1.5);
+        let basic = ProfileDxfExporter::export(&profile, None, &options());
+        assert!(basic.contains("AC1009"));
+        assert!(basic.trim_end().ends_with("EOF"));
+        assert!(layer_entities(&basic, "BASIC_PROFILE") > 0);
+        assert_eq!(layer_entities(&basic, "LIMIT_MAX_MATERIAL"), 0);
+
+        let zones = ToleranceZones {
+            thread_type: ThreadType::Male,
+            class: "6g".to_string(),
+            major: DiameterZone::from_limits(10.0, Some(9.968), Some(9.732)),
+            pitch: DiameterZone::from_limits(9.026, Some(8.994), Some(8.862)),
+            minor: DiameterZone::from_limits(8.160, Some(8.128), None),
+        };
+        let limits = ProfileDxfExporter::export(&profile, Some(&zones), &options());
+        assert!(layer_entities(&limits, "LIMIT_MAX_MATERIAL") > 0);
+        assert!(layer_entities(&limits, "LIMIT_MIN_MATERIAL") > 0);
+        assert!(limits.contains("d2 = %%c9.026 (8.994 / 8.862)"));
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use crate::shared::error::AppError;
+    use crate::shared::extract::Query;
+    use crate::shared::middleware::localize_errors;
+
+    #[derive(Deserialize)]
+    struct Params {
+        diameter: i32,
+        pitch: f64,
+    }
+
+    async fn find(Query(params): Query<Params>) -> Result<&'static str, AppError> {
+        Err(AppError::thread_not_found(
+            json!({ "diameter": params.diameter, "pitch": params.pitch }),
+        ))
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/v1/trapezoidal/info", get(find))
+            .layer(axum::middleware::from_fn(localize_errors))
+    }
+
+    async fn send(request: Request<Body>) -> (StatusCode, Value) {
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn query_rejection_uses_envelope() {
+        let request = Request::get("/v1/trapezoidal/info?diameter=abc&pitch=4")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(request).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+        assert!(body["message"].as_str().unwrap().contains("diameter"));
+        assert!(body["timestamp"].is_string());
+    }
+
+    #[tokio::test]
+    async fn not_found_is_localized_by_request_language() {
+        let (status, body) = send(
+            Request::get("/v1/trapezoidal/info?diameter=20&pitch=4")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "THREAD_NOT_FOUND");
+        assert_eq!(body["details"], json!({ "diameter": 20, "pitch": 4.0 }));
+        assert!(body["message"].as_str().unwrap().starts_with("Thread data not found"));
+
+        let (status, body) = send(
+            Request::get("/v1/trapezoidal/info?diameter=20&pitch=4")
+                .header(header::ACCEPT_LANGUAGE, "ru-RU,ru;q=0.9")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "THREAD_NOT_FOUND");
+        assert!(body["message"].as_str().unwrap().starts_with("Резьба не найдена"));
+    }
+}
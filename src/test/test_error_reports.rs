@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::Body,
+        extract::ConnectInfo,
+        http::{header, Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use serde_json::json;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tower::ServiceExt;
+
+    use crate::features::error_reports::handlers::{create_error_report, group_reports, ErrorReportsState};
+    use crate::features::error_reports::models::{ErrorReport, ErrorReportItem, ErrorReportPayload};
+    use crate::services::rate_limit::{KeyedRateLimiter, TrustedProxies};
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::middleware::resolve_client_ip;
+    use crate::test::{init_test_logger, metric_dataset};
+
+    fn item(id: i32, json_data: serde_json::Value) -> ErrorReportItem {
+        let report = ErrorReport {
+            id,
+            timestamp: Some(chrono::Utc::now()),
+            json_data,
+            client_ip: "10.0.0.1".to_string(),
+            resolved_at: None,
+        };
+        ErrorReportItem {
+            fingerprint: report.fingerprint(),
+            report,
+        }
+    }
+
+    #[test]
+    fn payload_schema() {
+        let payload: ErrorReportPayload = serde_json::from_value(json!({"message": "boom"})).unwrap();
+        assert!(payload.validate().is_ok());
+        assert!(serde_json::from_value::<ErrorReportPayload>(json!({"message": "boom", "extra": 1})).is_err());
+
+        let payload: ErrorReportPayload = serde_json::from_value(json!({"message": "  "})).unwrap();
+        assert!(payload.validate().is_err());
+        let payload: ErrorReportPayload = serde_json::from_value(json!({"message": "boom", "context": [1]})).unwrap();
+        assert!(payload.validate().is_err());
+    }
+
+    #[test]
+    fn duplicates_share_fingerprint() {
+        let items = [
+            item(1, json!({"message": "Timeout after 3012 ms", "app_version": "2.0"})),
+            item(2, json!({"message": "timeout after  2870 ms", "app_version": "2.1"})),
+            item(
+                3,
+                json!({"message": "Timeout after 10 ms", "stack_trace": "at load (main.js:10)"}),
+            ),
+        ];
+        assert_eq!(items[0].fingerprint, items[1].fingerprint);
+        assert_ne!(items[0].fingerprint, items[2].fingerprint);
+
+        let groups = group_reports(&items);
+        assert_eq!(groups.len(), 2);
+        let group = groups.iter().find(|group| group.count == 2).unwrap();
+        assert_eq!(group.latest_id, 2);
+        assert_eq!(group.open, 2);
+        assert_eq!(group.app_versions, ["2.0", "2.1"]);
+    }
+
+    #[test]
+    fn rate_limit_per_key() {
+        let limiter = KeyedRateLimiter::per_minute(2);
+        let now = Instant::now();
+        assert!(limiter.check_at("10.0.0.1", now).is_ok());
+        assert!(limiter.check_at("10.0.0.1", now).is_ok());
+        let wait = limiter.check_at("10.0.0.1", now).unwrap_err();
+        assert_eq!(wait.as_secs_f64().ceil(), 30.0);
+        assert!(limiter.check_at("10.0.0.2", now).is_ok());
+        assert!(limiter.check_at("10.0.0.1", now + Duration::from_secs(30)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn forged_forwarded_for_shares_the_proxy_bucket() {
+        init_test_logger();
+        let proxies = TrustedProxies::new(&["172.16.0.0/12".to_string()]);
+        let app = Router::new()
+            .route("/v1/error_reports/", post(create_error_report))
+            .with_state(ErrorReportsState {
+                database: Arc::new(PostgresService::from_dataset(metric_dataset())),
+                limiter: Arc::new(KeyedRateLimiter::per_minute(1)),
+            })
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(proxies),
+                resolve_client_ip,
+            ));
+        let report = |peer: &str, forwarded: &str| {
+            let peer: SocketAddr = peer.parse().unwrap();
+            Request::post("/v1/error_reports/")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-forwarded-for", forwarded)
+                .extension(ConnectInfo(peer))
+                .body(Body::from(json!({"message": "boom"}).to_string()))
+                .unwrap()
+        };
+
+        let proxy = "172.18.0.5:41000";
+        let response = app
+            .clone()
+            .oneshot(report(proxy, "1.1.1.1, 203.0.113.7"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app
+            .clone()
+            .oneshot(report(proxy, "2.2.2.2, 203.0.113.7"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // A client connecting directly cannot pick its bucket with the header
+        let direct = "198.51.100.9:50000";
+        let response = app.clone().oneshot(report(direct, "3.3.3.3")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.oneshot(report(direct, "4.4.4.4")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod run {
+    use crate::services::geometry::{ProfileOutline, ThreadProfile};
+    use crate::shared::enums::ThreadType;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "Expected {}, but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_metric_profile_m10() {
+        let male = ThreadProfile::metric(ThreadType::Male, 10.0, 1.5);
+        assert_close(male.pitch_diameter, 9.026);
+        assert_close(male.minor_diameter, 8.160);
+        assert_close(male.crest_flat(), 1.5 / 8.0);
+
+        let female = male.mating();
+        assert_close(female.minor_diameter, 8.376);
+        assert_close(female.crest_flat(), 1.5 / 4.0);
+        assert_close(female.root_flat(), 1.5 / 8.0);
+    }
+
+    #[test]
+    fn test_trapezoidal_profile_tr20x4() {
+        let male = ThreadProfile::trapezoidal(ThreadType::Male, 20.0, 4.0);
+        assert_close(male.minor_diameter, 15.5);
+        assert_close(male.crest_flat(), 0.366 * 4.0);
+        assert_close(male.root_flat(), 0.366 * 4.0 - 0.536 * 0.25);
+
+        let female = male.mating();
+        assert_close(female.major_diameter, 20.5);
+        assert_close(female.minor_diameter, 16.0);
+        assert_close(female.nominal_diameter(), 20.0);
+    }
+
+    #[test]
+    fn test_outline_is_continuous() {
+        for profile in [
+            ThreadProfile::metric(ThreadType::Male, 10.0, 1.5),
+            ThreadProfile::unified(ThreadType::Female, 0.5, 13.0),
+            ThreadProfile::trapezoidal(ThreadType::Female, 20.0, 4.0),
+            ThreadProfile::pipe(ThreadType::Male, 20.955, 1.814),
+        ] {
+            let outline = ProfileOutline::build(&profile, 3);
+            for pair in outline.segments.windows(2) {
+                let gap = (pair[0].end().x - pair[1].start().x).hypot(pair[0].end().y - pair[1].start().y);
+                assert!(gap < 1e-9, "Gap of {} in {:?} outline", gap, profile.standard);
+            }
+
+            let points = outline.points(5.0);
+            assert_close(points.first().unwrap().x, 0.0);
+            assert_close(points.last().unwrap().x, 3.0 * profile.pitch);
+            let (low, high) = (
+                profile.crest_level.min(profile.root_level),
+                profile.crest_level.max(profile.root_level),
+            );
+            assert!(points.iter().all(|p| p.y >= low - 1e-6 && p.y <= high + 1e-6));
+        }
+    }
+}
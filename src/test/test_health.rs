@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod run {
+    use axum::http::StatusCode;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use crate::features::health::entity::{ComponentHealth, Health, HealthStatus};
+    use crate::features::health::handler::{HealthHandler, HealthHandlerV1};
+    use crate::features::health::service::{HealthService, HealthServiceImpl};
+    use crate::services::shutdown::Drain;
+    use crate::shared::database::repositories::MemoryDataset;
+    use crate::shared::database::sqlite::SqliteConnection;
+    use crate::shared::setting::models::{
+        app_config::AppConfig,
+        app_env::{AppEnv, Env},
+        app_setting::AppSettings,
+    };
+    use crate::test::init_test_logger;
+
+    fn settings() -> Arc<AppSettings> {
+        init_test_logger();
+        Arc::new(AppSettings {
+            config: AppConfig::new(&Env::Local),
+            env: AppEnv {
+                env: Env::Local,
+                server_port: 0,
+                server_address: "127.0.0.1".to_string(),
+                postgres_host: String::new(),
+                postgres_user: String::new(),
+                postgres_password: String::new(),
+                postgres_database: String::new(),
+                admin_token: String::new(),
+            },
+        })
+    }
+
+    async fn sqlite(seed: &str) -> Arc<SqliteConnection> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let connection = SqliteConnection::with_pool(pool).await.unwrap();
+        sqlx::raw_sql(seed).execute(connection.pool()).await.unwrap();
+        Arc::new(connection)
+    }
+
+    fn component<'a>(health: &'a Health, name: &str) -> &'a ComponentHealth {
+        health.components.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn bundled_data_with_all_templates_is_ready() {
+        let service = HealthServiceImpl::new(settings(), None).with_template_dir("./static/svg");
+        let health = service.readiness().await;
+
+        assert_eq!(health.status, HealthStatus::Healthy);
+        let names: Vec<&str> = health.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["database", "svg_templates"]);
+        assert!(service.liveness().await.components.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_standards_and_missing_templates_degrade() {
+        let dir = std::env::temp_dir().join(format!("thread_api_health_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy(
+            "./static/svg/metric-thread-male-light.svg",
+            dir.join("metric-thread-male-light.svg"),
+        )
+        .unwrap();
+        let database =
+            sqlite("INSERT INTO metric_main (id, diameter, pitch, type_pitch) VALUES (1, 10, 1.5, 0);").await;
+
+        let service = HealthServiceImpl::new(settings(), None)
+            .with_sqlite(Some(database))
+            .with_template_dir(&dir);
+        let health = service.readiness().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(health.status, HealthStatus::Degraded);
+        let reference_data = component(&health, "reference_data");
+        assert_eq!(reference_data.status, HealthStatus::Degraded);
+        let counts = reference_data.details.as_ref().unwrap();
+        assert_eq!(counts["metric"], 1);
+        assert_eq!(counts["pipe"], 0);
+        let templates = component(&health, "svg_templates");
+        assert_eq!(templates.status, HealthStatus::Degraded);
+        assert_eq!(templates.details.as_ref().unwrap().as_array().unwrap().len(), 11);
+    }
+
+    #[tokio::test]
+    async fn bundled_rows_are_counted_per_standard() {
+        let dataset = MemoryDataset {
+            pipe_main: Vec::new(),
+            ..MemoryDataset::load(Path::new("./data")).unwrap()
+        };
+        let service = HealthServiceImpl::new(settings(), None)
+            .with_bundled_rows(Some(dataset.row_counts()))
+            .with_template_dir("./static/svg");
+        let health = service.readiness().await;
+
+        let reference_data = component(&health, "reference_data");
+        assert_eq!(reference_data.status, HealthStatus::Degraded);
+        assert_eq!(reference_data.message.as_deref(), Some("No rows for pipe"));
+        let counts = reference_data.details.as_ref().unwrap();
+        assert_eq!(counts["imperial"], dataset.imperial_main.len());
+        assert_eq!(counts["trapezoidal"], dataset.trapezoidal_main.len());
+    }
+
+    #[tokio::test]
+    async fn unhealthy_dependencies_fail_readiness_but_not_liveness() {
+        let service = HealthServiceImpl::new(settings(), None)
+            .with_sqlite(Some(sqlite("").await))
+            .with_template_dir("./static/no-such-dir");
+        let handler = HealthHandlerV1::new(Arc::new(service));
+
+        assert_eq!(handler.get_readiness().await.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(handler.get_health().await.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(handler.get_liveness().await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn draining_fails_readiness_but_not_liveness() {
+        let drain = Drain::new();
+        let service = HealthServiceImpl::new(settings(), None)
+            .with_template_dir("./static/svg")
+            .with_drain(drain.clone());
+        let handler = HealthHandlerV1::new(Arc::new(service));
+        assert_eq!(handler.get_readiness().await.status(), StatusCode::OK);
+
+        drain.begin();
+        assert_eq!(handler.get_readiness().await.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(handler.get_liveness().await.status(), StatusCode::OK);
+    }
+}
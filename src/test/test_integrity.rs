@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod run {
+    use serde_json::json;
+
+    use crate::services::reference_data::integrity::{check_tables, IntegrityCheck, Severity, TableData};
+    use crate::services::reference_data::{Column, ColumnKind, ReferenceTable};
+    use crate::shared::utils::db::JsonRow;
+
+    fn object(value: serde_json::Value) -> JsonRow {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn columns(names: &[&str]) -> Vec<Column> {
+        names
+            .iter()
+            .map(|name| Column {
+                name: name.to_string(),
+                kind: ColumnKind::Real,
+                nullable: true,
+            })
+            .collect()
+    }
+
+    fn trapezoidal(rows: Vec<JsonRow>) -> Vec<TableData> {
+        vec![
+            TableData {
+                table: ReferenceTable::TrapezoidalMain,
+                columns: columns(&[
+                    "id",
+                    "diameter",
+                    "pitch",
+                    "d1",
+                    "d2_d2",
+                    "d3",
+                    "d4",
+                    "es_d_7e_m",
+                    "ei_d_7e_m",
+                    "es_d2_7e_m",
+                    "ei_d2_7e_m",
+                    "es_d3_7e_m",
+                    "ei_d3_7e_m",
+                ]),
+                rows,
+            },
+            TableData {
+                table: ReferenceTable::TrapezoidalBasicDimensions,
+                columns: columns(&["p", "a_c", "h4_h3", "h1", "r1_max", "r2_max", "z"]),
+                rows: vec![object(json!({
+                    "p": 4.0, "a_c": 0.25, "h4_h3": 2.25, "h1": 2.0, "r1_max": 0.125, "r2_max": 0.25, "z": 1.0
+                }))],
+            },
+        ]
+    }
+
+    fn tr20x4(id: i64) -> JsonRow {
+        object(json!({
+            "id": id, "diameter": 20, "pitch": 4.0, "d1": 16.0, "d2_d2": 18.0, "d3": 15.5, "d4": 20.5,
+            "es_d_7e_m": 0, "ei_d_7e_m": -375, "es_d2_7e_m": -95, "ei_d2_7e_m": -450,
+            "es_d3_7e_m": 0, "ei_d3_7e_m": -539
+        }))
+    }
+
+    #[test]
+    fn test_consistent_rows_pass() {
+        let report = check_tables(&trapezoidal(vec![tr20x4(1)]));
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+        assert_eq!(report.rows["trapezoidal.main"], 1);
+    }
+
+    #[test]
+    fn test_findings_per_row() {
+        let mut missing = tr20x4(2);
+        missing.remove("es_d2_7e_m");
+        let mut inverted = tr20x4(3);
+        inverted.insert("diameter".to_string(), json!(24));
+        inverted.insert("es_d3_7e_m".to_string(), json!(-600));
+        inverted.insert("d2_d2".to_string(), json!(18.2));
+
+        let report = check_tables(&trapezoidal(vec![tr20x4(1), missing, inverted]));
+        let find = |row: &str, check: IntegrityCheck| {
+            report
+                .findings
+                .iter()
+                .find(|f| f.row == row && f.check == check)
+                .unwrap_or_else(|| panic!("no {:?} finding for {}: {:?}", check, row, report.findings))
+        };
+
+        let null = find("id=2", IntegrityCheck::MissingTolerance);
+        assert_eq!(null.column.as_deref(), Some("es_d2_7e_m"));
+        assert_eq!(null.severity, Severity::Error);
+        assert_eq!(
+            find("id=2", IntegrityCheck::Duplicate).message,
+            "same diameter, pitch as id=1"
+        );
+
+        assert_eq!(
+            find("id=3", IntegrityCheck::Deviation).column.as_deref(),
+            Some("es_d3_7e_m")
+        );
+        assert_eq!(find("id=3", IntegrityCheck::Limits).column.as_deref(), Some("diameter"));
+        assert_eq!(find("id=3", IntegrityCheck::Formula).severity, Severity::Warning);
+        assert!(!report.is_ok());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod run {
+    use serde_json::json;
+    use std::path::Path;
+
+    use crate::features::trapezoidal::common::db::ThreadDataService;
+    use crate::shared::database::repositories::imperial::ImperialThreadRow;
+    use crate::shared::database::repositories::{MemoryDataset, MetricInfoQuery};
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::enums::ThreadType;
+    use crate::shared::error::AppError;
+    use crate::shared::utils::db::JsonRow;
+    use crate::test::{init_test_logger, metric_dataset};
+
+    fn object(value: serde_json::Value) -> JsonRow {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn imperial_row(id: i64, diameter: &str, tpi: f64, class_m: &str, class_f: &str) -> ImperialThreadRow {
+        serde_json::from_value(json!({
+            "id": id, "diameter": diameter, "diameter_2": 0.25, "tpi": tpi, "series_designation": "UNC",
+            "class_m": class_m, "class_f": class_f, "allowance": 0.0011,
+            "major_diam_max_m": 0.2489, "major_diam_min_m": 0.2408, "major_diam_min2_m": null,
+            "pitch_diameter_max_m": 0.2164, "pitch_diameter_min_m": 0.2127, "pitch_diameter_tolerance_m": 0.0037,
+            "unr_minor_diameter_max_m": 0.1876, "minor_diameter_min_f": 0.196, "minor_diameter_max_f": 0.207,
+            "pitch_diameter_min_f": 0.2175, "pitch_diameter_max_f": 0.2224, "pitch_diameter_tolerance_f": 0.0049,
+            "major_diameter_min_f": 0.25
+        }))
+        .unwrap()
+    }
+
+    fn dataset() -> MemoryDataset {
+        MemoryDataset {
+            imperial_main: vec![
+                imperial_row(2, "1/4", 20.0, "2A", "2B"),
+                imperial_row(1, "1/4", 20.0, "3A", "3B"),
+            ],
+            trapezoidal_main: vec![object(json!({
+                "id": 1, "diameter": 20, "pitch": 4.0, "d1": 16.0, "d2_d2": 18.0, "d3": 15.5, "d4": 20.5,
+                "es_d_7e_m": 0, "ei_d_7e_m": -375, "es_d2_7e_m": -95, "ei_d2_7e_m": -450,
+                "es_d3_7e_m": 0, "ei_d3_7e_m": -539, "es_d2_7h_f": 355, "ei_d2_7h_f": 0
+            }))],
+            trapezoidal_basic_dimensions: vec![object(json!({
+                "p": 4.0, "a_c": 0.25, "h4_h3": 2.25, "h1": 2.0, "r1_max": 0.125, "r2_max": 0.25, "z": 1.0
+            }))],
+            ..metric_dataset()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bundled_data_loads() {
+        init_test_logger();
+        let dataset = MemoryDataset::load(Path::new("./data")).unwrap();
+        assert!(dataset.metric_main.len() > 300);
+        assert!(dataset.metric_tolerance_grades.len() > 700);
+        assert!(dataset.imperial_main.len() > 150);
+        assert!(dataset.trapezoidal_main.len() > 150);
+        assert_eq!(dataset.trapezoidal_basic_dimensions.len(), 22);
+        assert!(dataset.pipe_main.len() > 70);
+        let database = PostgresService::from_dataset(dataset);
+        assert!(database.pool().is_none());
+        assert!(MemoryDataset::load(Path::new("./missing-data")).is_err());
+    }
+
+    #[test]
+    fn test_empty_bundled_table_is_rejected() {
+        init_test_logger();
+        let dir = std::env::temp_dir().join(format!("thread_api_dataset_{}", std::process::id()));
+        for schema in ["metric", "imperial", "trapezoidal", "pipe"] {
+            std::fs::create_dir_all(dir.join(schema)).unwrap();
+            for file in std::fs::read_dir(Path::new("./data").join(schema)).unwrap() {
+                let file = file.unwrap().path();
+                std::fs::copy(&file, dir.join(schema).join(file.file_name().unwrap())).unwrap();
+            }
+        }
+        std::fs::write(dir.join("pipe").join("main.json"), "[]").unwrap();
+
+        let result = MemoryDataset::load(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let Err(AppError::ConfigurationError { message }) = result else {
+            panic!("an empty table must not load");
+        };
+        assert!(message.contains("pipe.main"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_memory_repositories() {
+        init_test_logger();
+        let database = PostgresService::from_dataset(dataset());
+
+        // Limits are computed from the ISO 261 series, not looked up
+        let query = MetricInfoQuery {
+            diameter: 10.0,
+            pitch: 1.5,
+            type_: "male".to_string(),
+            tolerance: "6g".to_string(),
+            language: "en".to_string(),
+            units: "mm".to_string(),
+        };
+        let metric = &database.repository_metric;
+        let info = metric.get_info(&query).await.unwrap();
+        assert_eq!(info[0]["designation"], "M10-6g");
+        assert!((info[0]["major_diam_max"].as_f64().unwrap() - 9.968).abs() < 1e-9);
+        // Without the tables the formulas give the printed TD2 = 180 µm, in micrometres here
+        let female = MetricInfoQuery {
+            type_: "female".to_string(),
+            tolerance: "6H".to_string(),
+            units: "micron".to_string(),
+            ..query.clone()
+        };
+        let info = metric.get_info(&female).await.unwrap();
+        assert!((info[0]["pitch_diam_max"].as_f64().unwrap() - 9206.0).abs() < 1.0);
+        let other = MetricInfoQuery {
+            diameter: 11.0,
+            ..query
+        };
+        assert!(metric.get_info(&other).await.unwrap().is_empty());
+        let pitches = metric.get_pitch(10.0, "en").await.unwrap();
+        assert_eq!(pitches.len(), 2);
+        assert_eq!(pitches[0]["type_pitch_description"], "Coarse pitch");
+        assert_eq!(metric.get_diameters("desc").await.unwrap()[0]["diameter"], 12.0);
+        assert_eq!(metric.get_tolerance(2, "male").await.unwrap().len(), 6);
+
+        let imperial = &database.repository_imperial;
+        let diameters = imperial.unique_diameters().await.unwrap();
+        assert_eq!(diameters.len(), 1);
+        assert_eq!(diameters[0].id, 1);
+        assert_eq!(imperial.diameter_classes("1/4", 20.0).await.unwrap().len(), 2);
+        let thread = imperial
+            .find_thread("1/4", 20.0, ThreadType::Female, "2B")
+            .await
+            .unwrap();
+        assert_eq!(thread.map(|row| row.id), Some(2));
+        assert!(imperial
+            .find_thread("1/4", 20.0, ThreadType::Male, "2B")
+            .await
+            .unwrap()
+            .is_none());
+
+        let trapezoidal = database.repository_trapezoidal.clone();
+        assert_eq!(
+            trapezoidal.tolerance_columns().await.unwrap(),
+            vec!["es_d2_7e_m".to_string(), "es_d2_7h_f".to_string()]
+        );
+        let data = ThreadDataService::new(trapezoidal.clone())
+            .fetch_thread_data(20, 4.0, ThreadType::Male, "7e")
+            .await
+            .unwrap();
+        assert_eq!(data.basic_diameters.d2, 18.0);
+        assert!((data.tolerances.ei_d2 + 0.45).abs() < 1e-12);
+        let missing = ThreadDataService::new(trapezoidal)
+            .fetch_thread_data(24, 5.0, ThreadType::Male, "7e")
+            .await;
+        assert!(matches!(missing, Err(AppError::ThreadDataNotFound { .. })));
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod run {
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    use crate::services::metrics::{Metrics, PrometheusText};
+    use crate::shared::error::AppError;
+    use crate::shared::middleware::track_metrics;
+
+    fn render(metrics: &Metrics) -> String {
+        let mut text = PrometheusText::new();
+        metrics.render(&mut text);
+        text.into_string()
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let metrics = Metrics::default();
+        metrics.observe_request("GET", "/v1/pipe/info", 200, Duration::from_millis(3));
+        metrics.observe_request("GET", "/v1/pipe/info", 200, Duration::from_millis(40));
+        metrics.observe_request("GET", "/v1/pipe/info", 200, Duration::from_secs(20));
+        let text = render(&metrics);
+
+        let labels = r#"method="GET",route="/v1/pipe/info",status="200""#;
+        assert!(text.contains("# TYPE http_request_duration_seconds histogram"));
+        assert!(text.contains(&format!("http_requests_total{{{}}} 3", labels)));
+        assert!(text.contains(&format!(
+            r#"http_request_duration_seconds_bucket{{{},le="0.005"}} 1"#,
+            labels
+        )));
+        assert!(text.contains(&format!(
+            r#"http_request_duration_seconds_bucket{{{},le="0.05"}} 2"#,
+            labels
+        )));
+        assert!(text.contains(&format!(
+            r#"http_request_duration_seconds_bucket{{{},le="10"}} 2"#,
+            labels
+        )));
+        assert!(text.contains(&format!(
+            r#"http_request_duration_seconds_bucket{{{},le="+Inf"}} 3"#,
+            labels
+        )));
+        assert!(text.contains(&format!("http_request_duration_seconds_count{{{}}} 3", labels)));
+    }
+
+    #[tokio::test]
+    async fn middleware_labels_route_templates_and_error_codes() {
+        let app = Router::new()
+            .route("/metrics-test/{id}", get(|| async { "ok" }))
+            .route(
+                "/metrics-test/{id}/missing",
+                get(|| async { Err::<(), _>(AppError::NotFound("no such key".to_string())) }),
+            )
+            .layer(axum::middleware::from_fn(track_metrics));
+
+        for uri in ["/metrics-test/1", "/metrics-test/2", "/metrics-test/3/missing"] {
+            app.clone()
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+        let text = render(Metrics::global());
+
+        assert!(text.contains(r#"http_requests_total{method="GET",route="/metrics-test/{id}",status="200"} 2"#));
+        assert!(text.contains(r#"route="/metrics-test/{id}/missing",status="404"} 1"#));
+        assert!(text.contains(r#"app_errors_total{code="NOT_FOUND"}"#));
+        assert!(!text.contains("/metrics-test/1"));
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod run {
+    use serde_json::Value;
+    use utoipa::OpenApi;
+
+    use crate::features::openapi::ApiDoc;
+
+    fn document() -> Value {
+        serde_json::to_value(ApiDoc::openapi()).unwrap()
+    }
+
+    #[test]
+    fn documents_every_route() {
+        let doc = document();
+        assert_eq!(doc["openapi"], "3.1.0");
+
+        let paths = doc["paths"].as_object().unwrap();
+        for path in [
+            "/v1/metric/svg",
+            "/v2/imperial/info",
+            "/v1/batch/info",
+            "/v1/analytics/timeseries",
+            "/admin/error-reports/groups",
+            "/v1/trapezoidal/svg-dimensions",
+            "/health",
+        ] {
+            assert!(paths.contains_key(path), "{} is missing", path);
+        }
+        assert_eq!(paths.len(), 41);
+    }
+
+    #[test]
+    fn lists_enum_values() {
+        let doc = document();
+        let schemas = &doc["components"]["schemas"];
+
+        assert_eq!(schemas["ThreadType"]["enum"], serde_json::json!(["male", "female"]));
+        assert_eq!(schemas["Language"]["enum"], serde_json::json!(["ru", "en"]));
+        assert_eq!(
+            schemas["ThreadStandard"]["enum"],
+            serde_json::json!(["metric", "trapezoidal", "imperial", "pipe"])
+        );
+
+        let params = doc["paths"]["/v1/trapezoidal/svg-dimensions"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        let type_param = params.iter().find(|p| p["name"] == "type").unwrap();
+        assert_eq!(type_param["in"], "query");
+        assert_eq!(type_param["schema"]["$ref"], "#/components/schemas/ThreadType");
+    }
+}
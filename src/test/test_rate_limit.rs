@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod run {
+    use axum::http::HeaderMap;
+    use std::net::SocketAddr;
+    use std::time::Instant;
+
+    use crate::services::rate_limit::{KeyedRateLimiter, RateLimits, TrustedProxies};
+    use crate::shared::setting::models::app_config::RateLimitConfig;
+
+    fn limits() -> RateLimits {
+        let config: RateLimitConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[policies]]
+name = "drawings"
+paths = ["/v1/metric/svg"]
+per_ip_per_minute = 2
+per_key_per_minute = 3
+
+[[policies]]
+name = "reference"
+paths = ["/v1/"]
+per_ip_per_minute = 100
+"#,
+        )
+        .unwrap();
+        RateLimits::new(&config)
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let limits = limits();
+        assert_eq!(limits.policy_for("/v1/metric/svg").unwrap().name(), "drawings");
+        assert_eq!(limits.policy_for("/v1/metric/info").unwrap().name(), "reference");
+        assert!(limits.policy_for("/health").is_none());
+    }
+
+    #[test]
+    fn keys_and_ips_have_own_buckets() {
+        let limits = limits();
+        let drawings = limits.policy_for("/v1/metric/svg").unwrap();
+        assert!(drawings.check(None, "10.0.0.1").is_ok());
+        assert!(drawings.check(None, "10.0.0.1").is_ok());
+        let wait = drawings.check(None, "10.0.0.1").unwrap_err();
+        assert_eq!(wait.as_secs_f64().ceil(), 30.0);
+        assert!(drawings.check(None, "10.0.0.2").is_ok());
+        // A key behind the same IP is counted on its own
+        for _ in 0..3 {
+            assert!(drawings.check(Some(7), "10.0.0.1").is_ok());
+        }
+        assert!(drawings.check(Some(7), "10.0.0.1").is_err());
+
+        let stats = &limits.stats()[0];
+        assert_eq!((stats.allowed, stats.limited), (6, 2));
+        assert_eq!((stats.tracked_ips, stats.tracked_keys), (2, 1));
+    }
+
+    #[test]
+    fn client_ip_is_the_entry_added_by_the_proxy() {
+        let proxies = TrustedProxies::new(&["172.16.0.0/12".to_string(), "10.0.0.7".to_string(), "bad".to_string()]);
+        let peer: SocketAddr = "172.18.0.5:41000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(proxies.client_ip(&headers, Some(peer)), "172.18.0.5");
+        assert_eq!(proxies.client_ip(&headers, None), "unknown");
+
+        // The client may send its own X-Forwarded-For, the proxy appends the real address
+        headers.insert("x-forwarded-for", "1.2.3.4, 203.0.113.7".parse().unwrap());
+        assert_eq!(proxies.client_ip(&headers, Some(peer)), "203.0.113.7");
+        headers.append("x-forwarded-for", "198.51.100.1".parse().unwrap());
+        assert_eq!(proxies.client_ip(&headers, Some(peer)), "198.51.100.1");
+        // Trusted proxies of a chain are skipped
+        headers.append("x-forwarded-for", "10.0.0.7".parse().unwrap());
+        assert_eq!(proxies.client_ip(&headers, Some(peer)), "198.51.100.1");
+
+        // The header of a peer that is not a trusted proxy is ignored
+        let direct: SocketAddr = "203.0.113.50:50000".parse().unwrap();
+        assert_eq!(proxies.client_ip(&headers, Some(direct)), "203.0.113.50");
+        let mapped: SocketAddr = "[::ffff:172.18.0.5]:41000".parse().unwrap();
+        assert_eq!(proxies.client_ip(&headers, Some(mapped)), "198.51.100.1");
+    }
+
+    #[test]
+    fn batches_take_a_token_per_row() {
+        let limiter = KeyedRateLimiter::per_minute(10);
+        let now = Instant::now();
+        assert!(limiter.take_at("10.0.0.1", 8, now).is_ok());
+        let wait = limiter.take_at("10.0.0.1", 3, now).unwrap_err();
+        assert_eq!(wait.as_secs_f64().round(), 6.0);
+
+        // A batch larger than the bucket waits for a full one and leaves it in debt
+        assert!(limiter.take_at("10.0.0.2", 25, now).is_ok());
+        let wait = limiter.check_at("10.0.0.2", now).unwrap_err();
+        assert_eq!(wait.as_secs_f64().round(), 96.0);
+    }
+
+    #[test]
+    fn stalest_keys_are_evicted() {
+        let limiter = KeyedRateLimiter::per_minute(1);
+        let now = Instant::now();
+        assert!(limiter.check_at("busy", now).is_ok());
+        for i in 0..10_000 {
+            let _ = limiter.check_at(&format!("10.0.{}.{}", i / 256, i % 256), now);
+            if i % 1000 == 0 {
+                // A throttled client that keeps coming back is never forgotten
+                assert!(limiter.check_at("busy", now).is_err());
+            }
+        }
+        assert_eq!(limiter.tracked(), 10_000);
+        assert!(limiter.check_at("busy", now).is_err());
+        // The first one-off client was the stalest and got a fresh bucket
+        assert!(limiter.check_at("10.0.0.0", now).is_ok());
+    }
+}
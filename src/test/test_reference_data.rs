@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod run {
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use crate::services::reference_data::{
+        migration::render_migration, validate_rows, ReferenceStore, ReferenceTable, TableDiff,
+    };
+    use crate::shared::database::sqlite::SqliteConnection;
+    use crate::shared::utils::db::JsonRow;
+    use crate::test::init_test_logger;
+
+    fn object(value: serde_json::Value) -> JsonRow {
+        value.as_object().cloned().unwrap()
+    }
+
+    async fn store() -> ReferenceStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let connection = SqliteConnection::with_pool(pool).await.unwrap();
+        sqlx::raw_sql(
+            "
+INSERT INTO trapezoidal_basic_dimensions (p, a_c, h4_h3, h1, r1_max, r2_max, z)
+VALUES (2, 0.25, 1.25, 1, 0.125, 0.25, 0.5), (3, 0.25, 1.75, 1.5, 0.125, 0.25, 0.75);
+",
+        )
+        .execute(connection.pool())
+        .await
+        .unwrap();
+        ReferenceStore::Sqlite(connection.pool().clone())
+    }
+
+    #[tokio::test]
+    async fn test_validation_findings() {
+        init_test_logger();
+        let store = store().await;
+        let table = ReferenceTable::TrapezoidalBasicDimensions;
+        let columns = store.columns(table).await.unwrap();
+
+        // CSV fields arrive as strings
+        let rows = vec![
+            object(
+                json!({"p": "2", "a_c": "0.25", "h4_h3": "1.25", "h1": "1", "r1_max": "0.125", "r2_max": "0.25", "z": "0.5"}),
+            ),
+            object(json!({"p": "2", "a_c": "x", "h4_h3": "1.25", "h1": "1", "r1_max": "0.125", "r2_max": "0.25"})),
+            object(
+                json!({"p": "4", "a_c": "0.5", "h4_h3": "2.25", "h1": "2", "r1_max": "0.25", "r2_max": "0.5", "z": "1", "extra": "1"}),
+            ),
+        ];
+        let (typed, findings) = validate_rows(table, &columns, rows);
+        assert_eq!(typed[0]["p"], json!(2.0));
+
+        let messages: Vec<String> = findings.iter().map(|finding| finding.to_string()).collect();
+        assert_eq!(findings.len(), 4, "{:?}", messages);
+        assert!(messages.contains(&"row 2, column 'a_c': expected a number, got \"x\"".to_string()));
+        assert!(messages.contains(&"row 2, column 'z': value is required".to_string()));
+        assert!(messages.contains(&"row 2, column 'p': duplicate key 2".to_string()));
+        assert!(messages.contains(&"row 3, column 'extra': unknown column of trapezoidal.basic_dimensions".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_import_diff_and_migration() {
+        init_test_logger();
+        let store = store().await;
+        let table = ReferenceTable::TrapezoidalBasicDimensions;
+        let columns = store.columns(table).await.unwrap();
+        let current = store.rows(table).await.unwrap();
+
+        // Change p = 2, drop p = 3, add p = 4
+        let mut edited = current.clone();
+        edited[0].insert("z".to_string(), json!(0.55));
+        edited.remove(1);
+        edited.push(object(json!({
+            "p": 4.0, "a_c": 0.5, "h4_h3": 2.25, "h1": 2.0, "r1_max": 0.25, "r2_max": 0.5, "z": 1.0
+        })));
+
+        let diff = TableDiff::between(table, &current, &edited);
+        assert_eq!((diff.inserted.len(), diff.updated.len(), diff.deleted.len()), (1, 1, 1));
+        assert_eq!(diff.updated[0].columns, vec!["z".to_string()]);
+        assert!(diff.preview(table).contains("    z: 0.5 -> 0.55"));
+
+        let sql = render_migration(table, &columns, &diff, "basic_dimensions.csv");
+        assert!(sql.contains("DELETE FROM trapezoidal.basic_dimensions WHERE p = 3.0;"));
+        assert!(sql.contains("UPDATE trapezoidal.basic_dimensions SET z = 0.55 WHERE p = 2.0;"));
+        assert!(sql.contains("WHERE NOT EXISTS (SELECT 1 FROM trapezoidal.basic_dimensions WHERE p = 4.0);"));
+
+        store.apply(table, &columns, &diff).await.unwrap();
+        let stored = store.rows(table).await.unwrap();
+        assert!(TableDiff::between(table, &stored, &edited).is_empty());
+    }
+}
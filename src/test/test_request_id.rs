@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod run {
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    use crate::logging::context::current_request_id;
+    use crate::logging::enums::LogLevel;
+    use crate::logging::init::AppLogger;
+    use crate::shared::middleware::{request_id, trace_requests, REQUEST_ID_HEADER};
+    use crate::test::init_test_logger;
+
+    fn app() -> Router {
+        init_test_logger();
+        Router::new()
+            .route("/v1/echo", get(|| async { current_request_id().unwrap_or_default() }))
+            .layer(axum::middleware::from_fn(trace_requests))
+    }
+
+    #[test]
+    fn client_request_id_is_kept_only_when_safe() {
+        let with = |id: &str| Request::get("/").header(REQUEST_ID_HEADER, id).body(()).unwrap();
+
+        assert_eq!(request_id(&with("checkout-42.retry:1")), "checkout-42.retry:1");
+        assert_eq!(request_id(&with("a\"b")).len(), 32);
+        assert_eq!(request_id(&with(&"x".repeat(200))).len(), 32);
+        assert_ne!(
+            request_id(&Request::get("/").body(()).unwrap()),
+            request_id(&Request::get("/").body(()).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn request_id_reaches_handler_and_response() {
+        let request = Request::get("/v1/echo")
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::empty());
+        let response = app().oneshot(request.unwrap()).await.unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-1");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"req-1");
+
+        let response = app()
+            .oneshot(Request::get("/v1/echo").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let generated = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, generated);
+    }
+
+    #[test]
+    fn json_lines_carry_the_request_id() {
+        let line: serde_json::Value =
+            serde_json::from_str(&AppLogger::json_line(LogLevel::Warn, "slow \"query\"", Some("req-1"))).unwrap();
+
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["message"], "slow \"query\"");
+        assert_eq!(line["request_id"], "req-1");
+        assert!(line["timestamp"].as_str().unwrap().ends_with('Z'));
+
+        let line: serde_json::Value =
+            serde_json::from_str(&AppLogger::json_line(LogLevel::Info, "started", None)).unwrap();
+        assert!(line.get("request_id").is_none());
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod run {
+    use std::time::Duration;
+
+    use crate::features::metric::v1::svg::params::SvgParams;
+    use crate::services::cache::{CacheKey, LruCache};
+
+    #[test]
+    fn evicts_least_recently_used_entry() {
+        let cache = LruCache::new(2, Duration::from_secs(60));
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        // "a" becomes the most recently used, so "b" goes first
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.insert("c", 3);
+
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+    }
+
+    #[test]
+    fn expired_entries_are_misses() {
+        let cache = LruCache::new(4, Duration::ZERO);
+        cache.insert("a", 1);
+
+        assert_eq!(cache.get(&"a"), None);
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().expirations, 1);
+
+        let disabled = LruCache::new(0, Duration::from_secs(60));
+        disabled.insert("a", 1);
+        assert_eq!(disabled.get(&"a"), None);
+    }
+
+    #[test]
+    fn cache_key_ignores_parameter_order() {
+        let first = CacheKey::new(
+            "/v1/metric/svg",
+            [
+                ("pitch", Some("1.5".to_string())),
+                ("diameter", Some(" 10".to_string())),
+            ],
+        );
+        let second = CacheKey::new(
+            "/v1/metric/svg",
+            [
+                ("diameter", Some("10".to_string())),
+                ("precision", None),
+                ("pitch", Some("1.5".to_string())),
+            ],
+        );
+
+        assert_eq!(first, second);
+        assert_eq!(first.as_str(), "/v1/metric/svg?diameter=10&pitch=1.5");
+    }
+
+    #[test]
+    fn svg_cache_key_ignores_enum_case() {
+        let params = |type_: &str, theme: &str, units: &str| SvgParams {
+            type_: type_.to_string(),
+            theme: theme.to_string(),
+            tolerance: "6g".to_string(),
+            diameter: 10.0,
+            pitch: 1.5,
+            language: "en".to_string(),
+            units: units.to_string(),
+            precision: 3,
+            show_dimensions: true,
+        };
+
+        assert_eq!(
+            params("Male", "LIGHT", "MM").cache_key(),
+            params("male", "light", "mm").cache_key()
+        );
+        assert_ne!(
+            params("male", "light", "mm").cache_key(),
+            params("female", "light", "mm").cache_key()
+        );
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod run {
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::services::search::SearchIndex;
+    use crate::shared::database::repositories::{MemoryDataset, ThreadUsage};
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::database::sqlite::SqliteConnection;
+    use crate::shared::enums::{Language, ThreadStandard};
+    use crate::shared::utils::db::JsonRow;
+    use crate::test::{init_test_logger, metric_dataset};
+
+    fn database() -> Arc<PostgresService> {
+        init_test_logger();
+        let object = |value: serde_json::Value| -> JsonRow { value.as_object().cloned().unwrap() };
+
+        let dataset = MemoryDataset {
+            trapezoidal_main: vec![
+                object(json!({"id": 1, "diameter": 20, "pitch": 4.0})),
+                object(json!({"id": 2, "diameter": 24, "pitch": 5.0})),
+            ],
+            ..metric_dataset()
+        };
+        Arc::new(PostgresService::from_dataset(dataset))
+    }
+
+    fn designations(hits: &[crate::services::search::SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.entry.designation.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn prefix_matches_are_ranked_by_popularity() {
+        let database = database();
+        let analytics = &database.repository_analytics;
+        for designation in ["M12-6g", "M12-6H", "M10×1.25-6g"] {
+            let usage = ThreadUsage::new(designation.to_string(), ThreadStandard::Metric, Language::En);
+            analytics.add_or_increment_thread(&usage).await.unwrap();
+        }
+
+        let index = SearchIndex::new();
+        let hits = index.search(&database, "m1", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["M12", "M10×1.25", "M10"]);
+        assert_eq!(hits[0].popularity, 2);
+
+        // The full spelling of a coarse thread is an exact match, fuzzy ones follow
+        let hits = index.search(&database, "M10x1.5", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["M10", "M10×1.25"]);
+
+        // Totals are reused between keystrokes and recounted once stale
+        let usage = ThreadUsage::new("M10-6g".to_string(), ThreadStandard::Metric, Language::En);
+        analytics.add_or_increment_thread(&usage).await.unwrap();
+        let hits = index.search(&database, "M10", None, 10).await.unwrap();
+        assert_eq!(hits[0].popularity, 0);
+        let recounting = SearchIndex::with_refresh(Duration::ZERO);
+        let hits = recounting.search(&database, "M10", None, 10).await.unwrap();
+        assert_eq!(hits[0].popularity, 1);
+    }
+
+    #[tokio::test]
+    async fn filters_by_standard_and_limit() {
+        let database = database();
+        let index = SearchIndex::new();
+
+        let hits = index.search(&database, "Tr 2", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["Tr 20 x 4", "Tr 24 x 5"]);
+
+        let hits = index
+            .search(&database, "2", Some(ThreadStandard::Trapezoidal), 1)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(index.search(&database, "zz", None, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rebuild_picks_up_imported_rows() {
+        init_test_logger();
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let connection = Arc::new(SqliteConnection::with_pool(pool).await.unwrap());
+        let database = PostgresService::from_sqlite(connection.clone());
+        let insert = |id: i32, diameter: f64, pitch: f64, type_pitch: i32| {
+            sqlx::query("INSERT INTO metric_main (id, diameter, pitch, type_pitch) VALUES (?, ?, ?, ?)")
+                .bind(id)
+                .bind(diameter)
+                .bind(pitch)
+                .bind(type_pitch)
+                .execute(connection.pool())
+        };
+        insert(1, 10.0, 1.5, 0).await.unwrap();
+        insert(2, 10.0, 1.25, 1).await.unwrap();
+
+        let index = SearchIndex::new();
+        let hits = index.search(&database, "M", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["M10", "M10×1.25"]);
+
+        insert(3, 12.0, 1.75, 0).await.unwrap();
+        let hits = index.search(&database, "M12", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["M10×1.25"]);
+        index.rebuild(&database).await.unwrap();
+        let hits = index.search(&database, "M12", None, 10).await.unwrap();
+        assert_eq!(designations(&hits), vec!["M12", "M10×1.25"]);
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod run {
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Arc;
+
+    use crate::shared::database::repositories::{ErrorReportFilter, MetricInfoQuery, ThreadUsage, UsageFilter};
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::database::sqlite::SqliteConnection;
+    use crate::shared::enums::{Language, ThreadStandard, ThreadType};
+    use crate::test::init_test_logger;
+
+    async fn database() -> PostgresService {
+        // Every connection to `sqlite::memory:` opens its own database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let connection = SqliteConnection::with_pool(pool).await.unwrap();
+        sqlx::raw_sql(
+            "
+INSERT INTO metric_main (id, diameter, pitch, type_pitch) VALUES (1, 10, 1.5, 0), (2, 10, 1.25, 1), (3, 12, 1.75, 0);
+INSERT INTO metric_tolerance_grades (kind, grade, pitch, diameter_min, diameter_max, value)
+VALUES ('TD2', 6, 1.5, 5.6, 11.2, 180);
+INSERT INTO trapezoidal_main (id, diameter, pitch, d1, d2_d2, d3, d4, es_d2_7e_m, ei_d2_7e_m, es_d2_7h_f, ei_d2_7h_f)
+VALUES (1, 20, 4, 16, 18, 15.5, 20.5, -95, -450, 355, 0);
+INSERT INTO pipe_main (id, designation, designation_2, thread_pitch, thread_per, ex_major_dia_max, ex_major_dia_min)
+VALUES (1, 0.5, '1/2', 1.814, 14, 20.955, 20.587);
+",
+        )
+        .execute(connection.pool())
+        .await
+        .unwrap();
+        PostgresService::from_sqlite(Arc::new(connection))
+    }
+
+    fn info_query(type_: &str, tolerance: &str) -> MetricInfoQuery {
+        MetricInfoQuery {
+            diameter: 10.0,
+            pitch: 1.5,
+            type_: type_.to_string(),
+            tolerance: tolerance.to_string(),
+            language: "en".to_string(),
+            units: "mm".to_string(),
+        }
+    }
+
+    fn number(row: &crate::shared::utils::db::JsonRow, column: &str) -> f64 {
+        row.get(column).and_then(|v| v.as_f64()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_metric_functions() {
+        init_test_logger();
+        let database = database().await;
+        let metric = &database.repository_metric;
+
+        // ISO 965-1: M10 6g, es = -32 µm, Td2 = 132 µm, Td = 236 µm
+        let male = metric.get_info(&info_query("male", "6g")).await.unwrap();
+        assert_eq!(male.len(), 1);
+        assert_eq!(male[0]["designation"], "M10-6g");
+        assert!((number(&male[0], "major_diam_max") - 9.968).abs() < 1e-9);
+        assert!((number(&male[0], "major_diam_min") - 9.732).abs() < 1e-9);
+        assert!((number(&male[0], "pitch_diam_max") - 8.994).abs() < 1e-3);
+        assert!((number(&male[0], "pitch_diam_min") - 8.862).abs() < 1e-3);
+
+        // The tabulated TD2 wins over the formula
+        let female = metric.get_info(&info_query("female", "6H")).await.unwrap();
+        assert!((number(&female[0], "pitch_diam_max") - 9.206).abs() < 1e-3);
+        assert!(female[0].get("minor_diam_max_d3").is_none());
+        // ISO 965-1 leaves D max and the minor diameter d1 of a bolt unspecified
+        assert!(female[0].get("major_diam_max").is_none());
+        assert!((number(&female[0], "minor_diam_max") - 8.676).abs() < 1e-3);
+        assert!(male[0].get("minor_diam_max").is_none());
+        assert!((number(&male[0], "minor_diam_max_d3") - 8.128).abs() < 1e-3);
+
+        assert!(metric.get_info(&info_query("male", "6H")).await.is_err());
+        let mut missing = info_query("male", "6g");
+        missing.pitch = 1.0;
+        assert!(metric.get_info(&missing).await.unwrap().is_empty());
+
+        let pitches = metric.get_pitch(10.0, "en").await.unwrap();
+        assert_eq!(pitches.len(), 2);
+        assert_eq!(pitches[0]["type_pitch_description"], "Coarse pitch");
+
+        let diameters = metric.get_diameters("desc").await.unwrap();
+        assert_eq!(diameters[0]["diameter"].as_f64(), Some(12.0));
+        assert_eq!(metric.get_tolerance(1, "m").await.unwrap().len(), 6);
+        assert!(metric.get_tolerance(9, "f").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_reference_tables() {
+        init_test_logger();
+        let database = database().await;
+
+        let trapezoidal = &database.repository_trapezoidal;
+        assert_eq!(
+            trapezoidal.tolerance_columns().await.unwrap().len(),
+            7,
+            "one es_d2 column per tolerance class"
+        );
+        let thread = trapezoidal.find_thread(20, 4.0).await.unwrap().unwrap();
+        assert_eq!(thread["es_d2_7e_m"].as_f64(), Some(-95.0));
+        assert!(!thread.contains_key("es_d2_8e_m"));
+
+        let pipe = database.repository_pipe.list().await.unwrap();
+        assert_eq!(pipe[0].designation_2, "1/2");
+        assert!(pipe[0].ex_major_dia_min.is_none());
+        let pipe = database.repository_pipe.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(pipe.ex_major_dia_min, Some(20.587));
+
+        assert!(database
+            .repository_imperial
+            .find_thread("1/4", 20.0, ThreadType::Male, "2A")
+            .await
+            .unwrap()
+            .is_none());
+
+        let analytics = &database.repository_analytics;
+        let usage = ThreadUsage::new("M10-6g".to_string(), ThreadStandard::Metric, Language::En);
+        analytics.add_or_increment_thread(&usage).await.unwrap();
+        analytics.add_or_increment_thread(&usage).await.unwrap();
+        let filter = UsageFilter {
+            from: usage.day,
+            to: usage.day,
+            standard: Some(ThreadStandard::Metric),
+            language: None,
+            designation: None,
+        };
+        let rows = analytics.daily_usage(&filter).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].count, 2);
+        assert_eq!(rows[0].language, Language::En);
+        let id = analytics
+            .insert_error_report(serde_json::json!({"message": "boom"}), "127.0.0.1")
+            .await
+            .unwrap();
+        assert_eq!(id, 1);
+        analytics
+            .insert_error_report(
+                serde_json::json!({"message": "Timeout", "app_version": "2.1"}),
+                "127.0.0.1",
+            )
+            .await
+            .unwrap();
+
+        let filter = ErrorReportFilter {
+            from: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+            to: None,
+            app_version: Some("2.1".to_string()),
+            message: Some("timeout".to_string()),
+            resolved: Some(false),
+            limit: 10,
+        };
+        let reports = analytics.error_reports(&filter).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].id, 2);
+        assert!(reports[0].timestamp.is_some());
+
+        assert_eq!(analytics.set_error_reports_resolved(&[1, 2], true).await.unwrap(), 2);
+        assert_eq!(analytics.set_error_reports_resolved(&[2], true).await.unwrap(), 0);
+        assert!(analytics.error_reports(&filter).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod run {
+    use crate::services::svg::export::{ExportFormat, ExportParams, PageSize, SvgExporter};
+
+    const SVG: &str = r#"<svg width="200" height="100" viewBox="0 0 200 100" xmlns="http://www.w3.org/2000/svg"><line x1="0" y1="50" x2="200" y2="50" stroke="black"/><text x="10" y="40" font-family="Arial" font-size="20">M10×1.5</text></svg>"#;
+
+    fn png_size(png: &[u8]) -> (u32, u32) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let read = |offset: usize| u32::from_be_bytes(png[offset..offset + 4].try_into().unwrap());
+        (read(16), read(20))
+    }
+
+    #[test]
+    fn test_png_scaling() {
+        let exporter = SvgExporter::global();
+        let params = |dpi, width| ExportParams {
+            format: ExportFormat::Png,
+            dpi,
+            width,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            png_size(&exporter.export(SVG, &params(None, None)).unwrap()),
+            (200, 100)
+        );
+        assert_eq!(
+            png_size(&exporter.export(SVG, &params(Some(192.0), None)).unwrap()),
+            (400, 200)
+        );
+        assert_eq!(
+            png_size(&exporter.export(SVG, &params(None, Some(100))).unwrap()),
+            (100, 50)
+        );
+        assert!(exporter.export(SVG, &params(Some(0.0), None)).is_err());
+    }
+
+    #[test]
+    fn test_pdf_page() {
+        let params = ExportParams {
+            format: ExportFormat::Pdf,
+            page: PageSize::A4,
+            ..Default::default()
+        };
+        let pdf = SvgExporter::global().export(SVG, &params).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
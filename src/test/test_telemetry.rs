@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod run {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::post,
+        Extension, Router,
+    };
+    use opentelemetry::global;
+    use opentelemetry::trace::{SpanKind, TracerProvider as _};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use crate::analytics::pipeline::AnalyticsPipeline;
+    use crate::features::batch::handler::handle;
+    use crate::services::telemetry;
+    use crate::shared::database::service::PostgresService;
+    use crate::shared::middleware::trace_requests;
+    use crate::shared::setting::models::app_config::AnalyticsConfig;
+    use crate::test::{init_test_logger, metric_dataset};
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    /// Spans of the current thread go to memory instead of a collector
+    fn record_spans() -> (
+        SdkTracerProvider,
+        InMemorySpanExporter,
+        tracing::subscriber::DefaultGuard,
+    ) {
+        init_test_logger();
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        (provider, exporter, tracing::subscriber::set_default(subscriber))
+    }
+
+    fn named<'a>(spans: &'a [SpanData], name: &str) -> Vec<&'a SpanData> {
+        spans.iter().filter(|span| span.name == name).collect()
+    }
+
+    // The current-thread runtime keeps the spawned lookups on the thread of the subscriber
+    #[tokio::test]
+    async fn batch_rows_are_children_of_the_request_span() {
+        let (provider, exporter, _guard) = record_spans();
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let database = Arc::new(PostgresService::from_dataset(metric_dataset()));
+        let (analytics, _) =
+            AnalyticsPipeline::start(database.repository_analytics.clone(), &AnalyticsConfig::default());
+        let app = Router::new()
+            .route("/v1/batch/info", post(handle))
+            .layer(axum::middleware::from_fn(trace_requests))
+            .layer(Extension(database))
+            .layer(Extension(analytics));
+
+        let row = json!({
+            "standard": "metric", "diameter": 10, "pitch": 1.5, "type": "male",
+            "tolerance": "6g", "language": "en", "units": "mm"
+        });
+        let request = Request::post("/v1/batch/info")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("traceparent", TRACEPARENT)
+            .body(Body::from(json!([row, row]).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        provider.force_flush().unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let request = named(&spans, "POST /v1/batch/info");
+        assert_eq!(request.len(), 1);
+        let request = request[0];
+        assert_eq!(request.span_kind, SpanKind::Server);
+        assert_eq!(
+            request.span_context.trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+        assert_eq!(request.parent_span_id.to_string(), "b7ad6b7169203331");
+        assert!(request.attributes.iter().any(
+            |attribute| attribute.key.as_str() == "http.response.status_code" && attribute.value.as_str() == "200"
+        ));
+
+        let rows = named(&spans, "batch::row");
+        assert_eq!(rows.len(), 2);
+        for row in rows {
+            assert_eq!(row.span_context.trace_id(), request.span_context.trace_id());
+            assert_eq!(row.parent_span_id, request.span_context.span_id());
+        }
+    }
+
+    #[test]
+    fn statements_are_client_spans_of_the_current_span() {
+        let (provider, exporter, _guard) = record_spans();
+        // `record_query` starts its span on the global tracer
+        global::set_tracer_provider(provider.clone());
+
+        tracing::info_span!("lookup").in_scope(|| {
+            telemetry::record_query("select", "SELECT metric.main", Duration::from_millis(2));
+        });
+        telemetry::record_query("select", "SELECT outside a span", Duration::from_millis(2));
+        provider.force_flush().unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let lookup = named(&spans, "lookup")[0];
+        let statements = named(&spans, "SELECT");
+        assert_eq!(statements.len(), 1);
+        let statement = statements[0];
+        assert_eq!(statement.span_kind, SpanKind::Client);
+        assert_eq!(statement.parent_span_id, lookup.span_context.span_id());
+        assert!(statement.end_time.duration_since(statement.start_time).unwrap() >= Duration::from_millis(2));
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod run {
+    use crate::services::geometry::ThreadProfile;
+    use crate::services::mesh::{Hand, StlEncoding, StlWriter, ThreadMesh, ThreadMeshOptions};
+    use crate::shared::enums::ThreadType;
+
+    fn options(hand: Hand, starts: usize) -> ThreadMeshOptions {
+        ThreadMeshOptions {
+            hand,
+            starts,
+            length: 10.0,
+            segments: 48,
+        }
+    }
+
+    #[test]
+    fn test_mesh_is_closed() {
+        let profile = ThreadProfile::metric(ThreadType::Male, 10.0, 1.5);
+        for (hand, starts) in [(Hand::Right, 1), (Hand::Left, 1), (Hand::Right, 3)] {
+            let mesh = ThreadMesh::build(&profile, &options(hand, starts));
+            assert!(mesh.is_watertight(), "{:?} {} starts", hand, starts);
+            // The estimate guards the STL route, it must not undercount
+            assert!(mesh.triangles.len() <= ThreadMesh::surface_facets(&profile, &options(hand, starts)));
+
+            // Between the cylinders on the minor and the major diameter
+            let volume = mesh.volume();
+            let cylinder = |d: f64| std::f64::consts::PI * d * d / 4.0 * 10.0;
+            assert!(volume > cylinder(profile.minor_diameter) && volume < cylinder(profile.major_diameter));
+        }
+    }
+
+    #[test]
+    fn test_binary_stl_size() {
+        let profile = ThreadProfile::trapezoidal(ThreadType::Female, 20.0, 4.0);
+        let mesh = ThreadMesh::build(&profile, &options(Hand::Right, 1));
+        let stl = StlWriter::write(&mesh, "Tr20x4", StlEncoding::Binary);
+        assert_eq!(stl.len(), 84 + 50 * mesh.triangles.len());
+        assert_eq!(
+            u32::from_le_bytes(stl[80..84].try_into().unwrap()) as usize,
+            mesh.triangles.len()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod run {
+    use crate::services::geometry::ThreadProfile;
+    use crate::services::svg::tolerance_renderer::{
+        DiameterZone, ToleranceDrawingOptions, ToleranceRenderer, ToleranceZones,
+    };
+    use crate::services::svg::SvgService;
+    use crate::shared::enums::{Language, Theme, ThreadType, Unit};
+
+    fn m10_6g() -> ToleranceZones {
+        ToleranceZones {
+            thread_type: ThreadType::Male,
+            class: "6g".to_string(),
+            major: DiameterZone::from_limits(10.0, Some(9.968), Some(9.732)),
+            pitch: DiameterZone::from_limits(9.026, Some(8.994), Some(8.862)),
+            minor: DiameterZone::from_limits(8.160, Some(8.128), None),
+        }
+    }
+
+    #[test]
+    fn test_zone_from_limits() {
+        let zone = m10_6g().pitch;
+        assert!((zone.es.unwrap() + 0.032).abs() < 1e-9);
+        assert!((zone.ei.unwrap() + 0.164).abs() < 1e-9);
+        assert!(m10_6g().minor.ei.is_none());
+    }
+
+    #[test]
+    fn test_render_fit_labels() {
+        let female = ToleranceZones {
+            thread_type: ThreadType::Female,
+            class: "6H".to_string(),
+            major: DiameterZone::from_limits(10.0, None, Some(10.0)),
+            pitch: DiameterZone::from_limits(9.026, Some(9.206), Some(9.026)),
+            minor: DiameterZone::from_limits(8.376, Some(8.676), Some(8.376)),
+        };
+        let options = ToleranceDrawingOptions {
+            title: "M10×1.5-6H/6g".to_string(),
+            theme: Theme::Light,
+            language: Language::En,
+            units: Unit::Micron,
+            precision: Some(0),
+        };
+        let svg_service = SvgService::new("./static/svg");
+        let profile = ThreadProfile::metric(ThreadType::Female, 10.0, 1.5);
+        let svg = ToleranceRenderer::new(&svg_service).render(&profile, &[female, m10_6g()], &options);
+
+        assert!(svg.contains(">es = -32</text>"));
+        assert!(svg.contains(">EI = 0</text>"));
+        assert!(svg.contains(">ES = +180</text>"));
+    }
+}